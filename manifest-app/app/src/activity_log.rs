@@ -0,0 +1,145 @@
+//! In-app activity feed.
+//!
+//! Saves, loads and other background work used to report only to stderr.
+//! The activity log keeps the most recent of those messages in memory and
+//! shows them as a timestamped, read-only list below the terminal. Messages
+//! are still written to stderr as well.
+
+use std::collections::VecDeque;
+
+use chrono::{DateTime, Local};
+use gpui::{
+    Context, InteractiveElement, IntoElement, ParentElement, Render, Rgba,
+    StatefulInteractiveElement, Styled, Window, div, px, rgba,
+};
+use terminal::mappings::colors::TerminalColors;
+
+/// Number of entries kept before the oldest are dropped.
+const MAX_ENTRIES: usize = 500;
+
+/// How important an activity entry is, which picks its color.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    fn color(self) -> Rgba {
+        match self {
+            Self::Info => TerminalColors::foreground(),
+            Self::Warning => TerminalColors::ansi_yellow(),
+            Self::Error => TerminalColors::ansi_red(),
+        }
+    }
+}
+
+/// A single logged event.
+#[derive(Clone, Debug)]
+pub struct ActivityEntry {
+    pub at: DateTime<Local>,
+    pub severity: Severity,
+    pub message: String,
+}
+
+/// Ring buffer of recent activity, rendered newest last.
+#[derive(Default)]
+pub struct ActivityLog {
+    entries: VecDeque<ActivityEntry>,
+}
+
+impl ActivityLog {
+    /// Record `message` and echo it to stderr.
+    pub fn push(&mut self, severity: Severity, message: impl Into<String>, cx: &mut Context<Self>) {
+        let message = message.into();
+        eprintln!("{}", message);
+        self.record(severity, message);
+        cx.notify();
+    }
+
+    fn record(&mut self, severity: Severity, message: String) {
+        if self.entries.len() == MAX_ENTRIES {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(ActivityEntry {
+            at: Local::now(),
+            severity,
+            message,
+        });
+    }
+
+    fn render_entry(entry: &ActivityEntry) -> impl IntoElement {
+        div()
+            .flex()
+            .gap(px(8.0))
+            .child(
+                div()
+                    .flex_none()
+                    .text_color(TerminalColors::ansi_bright_black())
+                    .child(entry.at.format("%H:%M:%S").to_string()),
+            )
+            .child(
+                div()
+                    .text_color(entry.severity.color())
+                    .child(entry.message.clone()),
+            )
+    }
+}
+
+impl Render for ActivityLog {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .id("activity-log")
+            .size_full()
+            .flex()
+            .flex_col()
+            .bg(TerminalColors::background())
+            .child(
+                // Header
+                div()
+                    .h(px(24.0))
+                    .px(px(12.0))
+                    .flex()
+                    .flex_none()
+                    .items_center()
+                    .border_b_1()
+                    .border_color(rgba(0x2d333aff))
+                    .font_family("IBM Plex Sans")
+                    .text_color(TerminalColors::ansi_bright_black())
+                    .text_size(px(11.0))
+                    .child("ACTIVITY"),
+            )
+            .child(
+                div()
+                    .id("activity-entries")
+                    .flex_1()
+                    .overflow_y_scroll()
+                    .px(px(12.0))
+                    .py(px(4.0))
+                    .font_family("Bitstream Vera Sans Mono")
+                    .text_size(px(12.0))
+                    .children(self.entries.iter().map(Self::render_entry)),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drops_oldest_entries_past_capacity() {
+        let mut log = ActivityLog::default();
+        for i in 0..MAX_ENTRIES + 3 {
+            log.record(Severity::Info, format!("entry {}", i));
+        }
+
+        assert_eq!(log.entries.len(), MAX_ENTRIES);
+        assert_eq!(log.entries.front().unwrap().message, "entry 3");
+        assert_eq!(
+            log.entries.back().unwrap().message,
+            format!("entry {}", MAX_ENTRIES + 2)
+        );
+    }
+}
@@ -1,5 +1,6 @@
 use anyhow::{Context, Result};
 use dirs::config_dir;
+use manifest_client::FeatureState;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
@@ -17,7 +18,60 @@ pub struct AppConfig {
     pub feature_panel_width: Option<f32>,
     /// Ratio of the editor height in the vertical editor/terminal split (0.0 to 1.0).
     /// Default is 0.6 (60% editor, 40% terminal).
+    #[serde(default = "default_editor_split_ratio")]
     pub editor_split_ratio: f32,
+    /// Debug setting: directory to log raw PTY traffic for each terminal tab.
+    /// Logging is off when unset.
+    #[serde(default)]
+    pub pty_log_dir: Option<PathBuf>,
+    /// Characters that end a word when double-clicking in the terminal.
+    /// Uses the terminal's default separators when unset.
+    #[serde(default)]
+    pub word_separators: Option<String>,
+    /// Draw bold terminal text in ANSI colors 0-7 with the bright variants.
+    #[serde(default = "default_bold_is_bright")]
+    pub bold_is_bright: bool,
+    /// Command for "Open in Editor", e.g. `code --wait`. GUI editors need
+    /// their wait flag. Uses `$VISUAL` or `$EDITOR` when unset.
+    #[serde(default)]
+    pub external_editor: Option<String>,
+    /// Spaces Tab indents feature details by.
+    #[serde(default = "default_indent_width")]
+    pub indent_width: usize,
+    /// Terminal color theme: `pigs-in-space` (the default), `solarized-dark`
+    /// or `solarized-light`.
+    #[serde(default)]
+    pub terminal_theme: Option<String>,
+    /// Let terminal programs set the clipboard with OSC 52 escape sequences.
+    #[serde(default)]
+    pub terminal_clipboard_writes: bool,
+    /// Send Option+key in the terminal as ESC followed by the key (macOS).
+    /// Off by default, so Option types the characters on the keyboard layout.
+    #[serde(default)]
+    pub terminal_option_as_meta: bool,
+    /// Terminal font size, saved when it is zoomed. Uses the terminal's
+    /// default when unset.
+    #[serde(default)]
+    pub terminal_font_size: Option<f32>,
+    /// Feature states hidden from the sidebar tree. Deprecated by default.
+    #[serde(default = "default_hidden_feature_states")]
+    pub hidden_feature_states: Vec<FeatureState>,
+}
+
+fn default_bold_is_bright() -> bool {
+    true
+}
+
+fn default_indent_width() -> usize {
+    feature_editor::DEFAULT_INDENT_WIDTH
+}
+
+fn default_editor_split_ratio() -> f32 {
+    0.6
+}
+
+fn default_hidden_feature_states() -> Vec<FeatureState> {
+    vec![FeatureState::Deprecated]
 }
 
 impl Default for AppConfig {
@@ -26,7 +80,17 @@ impl Default for AppConfig {
             window_width: Some(1200.0),
             window_height: Some(800.0),
             feature_panel_width: Some(250.0),
-            editor_split_ratio: 0.6,
+            editor_split_ratio: default_editor_split_ratio(),
+            pty_log_dir: None,
+            word_separators: None,
+            bold_is_bright: default_bold_is_bright(),
+            external_editor: None,
+            indent_width: default_indent_width(),
+            terminal_theme: None,
+            terminal_clipboard_writes: false,
+            terminal_option_as_meta: false,
+            terminal_font_size: None,
+            hidden_feature_states: default_hidden_feature_states(),
         }
     }
 }
@@ -44,6 +108,15 @@ impl AppConfig {
         }
     }
 
+    /// The editor split ratio, or the default if the saved one is unusable.
+    pub fn editor_ratio(&self) -> f32 {
+        if self.editor_split_ratio.is_finite() {
+            self.editor_split_ratio.clamp(0.1, 0.9)
+        } else {
+            default_editor_split_ratio()
+        }
+    }
+
     fn try_load() -> Result<Self> {
         let config_path = get_config_path()?;
         if !config_path.exists() {
@@ -80,3 +153,29 @@ fn get_config_path() -> Result<PathBuf> {
     path.push(CONFIG_FILE);
     Ok(path)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_fields_use_defaults() {
+        let config: AppConfig = serde_json::from_str(r#"{"feature_panel_width": 320.0}"#).unwrap();
+        assert_eq!(config.feature_panel_width, Some(320.0));
+        assert_eq!(config.editor_split_ratio, 0.6);
+        assert!(config.bold_is_bright);
+        assert!(!config.terminal_option_as_meta);
+        assert_eq!(config.indent_width, 2);
+        assert_eq!(config.terminal_font_size, None);
+        assert_eq!(config.hidden_feature_states, vec![FeatureState::Deprecated]);
+    }
+
+    #[test]
+    fn unusable_split_ratios_are_replaced() {
+        let mut config = AppConfig::default();
+        config.editor_split_ratio = 1.5;
+        assert_eq!(config.editor_ratio(), 0.9);
+        config.editor_split_ratio = f32::NAN;
+        assert_eq!(config.editor_ratio(), 0.6);
+    }
+}
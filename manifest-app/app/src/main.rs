@@ -3,36 +3,57 @@
 //! A GPUI application with a feature explorer panel, feature editor, and terminal.
 
 mod active_context;
+mod activity_log;
 mod config;
 mod context_file;
+mod settings_view;
+mod status_bar;
 
 use active_context::ActiveFeatureContext;
+use activity_log::{ActivityLog, Severity};
 use config::AppConfig;
+use settings_view::{Event as SettingsEvent, SettingsView};
+use status_bar::{Connection, StatusBar};
 
 use feature_editor::{Event as EditorEvent, FeatureEditor};
 use feature_panel::{
-    DEFAULT_PANEL_WIDTH, Event as PanelEvent, FeaturePanel, MAX_PANEL_WIDTH, MIN_PANEL_WIDTH,
+    DEFAULT_PANEL_WIDTH, Event as PanelEvent, FeaturePanel, FeatureProgress, MAX_PANEL_WIDTH,
+    MIN_PANEL_WIDTH, TrashedFeature,
 };
+use futures::StreamExt;
+use futures::channel::mpsc;
 use gpui::{
-    App, Application, Bounds, Context, Entity, Focusable, Hsla, KeyBinding, Menu, MenuItem,
-    ParentElement, PathPromptOptions, Render, Styled, TitlebarOptions, Window, WindowBounds,
-    WindowOptions, actions, div, point, prelude::*, px, size,
+    App, Application, Bounds, ClipboardItem, Context, Entity, FocusHandle, Focusable, Hsla,
+    KeyBinding, Menu, MenuItem, ParentElement, PathPromptOptions, PromptLevel, Render, Styled,
+    Task, TitlebarOptions, Window, WindowBounds, WindowOptions, actions, div, point, prelude::*,
+    px, size,
 };
 use gpui_component::Root;
 use gpui_component::highlighter::{HighlightTheme, HighlightThemeStyle};
-use gpui_component::resizable::{h_resizable, resizable_panel, v_resizable};
+use gpui_component::resizable::{
+    ResizablePanelEvent, ResizableState, h_resizable, resizable_panel, v_resizable,
+};
 use gpui_component::theme::{Theme, ThemeMode};
+use keymap::{GLOBAL_CONTEXT, Keymap};
 use manifest_core::db::Database;
+use manifest_core::models::{ChangeEvent, CreateFeatureInput, UpdateFeatureInput, UpdateTaskInput};
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 use std::sync::Arc;
-use terminal::mappings::colors::TerminalColors;
-use terminal_view::TerminalView;
+use std::time::Duration;
+use terminal::mappings::colors::{TerminalColors, TerminalTheme};
+use terminal_view::{Event as TerminalViewEvent, TerminalView};
 use uuid::Uuid;
 
 /// Convert manifest_core types to manifest_client types for feature_panel compatibility.
 mod convert {
+    use feature_editor::LinkedFeature;
+    use feature_panel::{FeatureProgress, TrashedFeature};
     use manifest_client::{Feature, FeatureState};
-    use manifest_core::models::{FeatureState as CoreState, FeatureTreeNode};
+    use manifest_core::models::{
+        DeletedFeature, Feature as CoreFeature, FeatureState as CoreState, FeatureTreeNode,
+        TaskCounts,
+    };
 
     fn convert_state(state: CoreState) -> FeatureState {
         match state {
@@ -62,9 +83,62 @@ mod convert {
                 .collect(),
         }
     }
+
+    pub fn feature_to_link(feature: CoreFeature) -> LinkedFeature {
+        LinkedFeature {
+            id: feature.id,
+            title: feature.title,
+            state: convert_state(feature.state),
+        }
+    }
+
+    pub fn deleted_to_trashed(deleted: DeletedFeature) -> TrashedFeature {
+        TrashedFeature {
+            id: deleted.feature.id,
+            title: deleted.feature.title,
+            deleted_at: deleted
+                .deleted_at
+                .with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string(),
+        }
+    }
+
+    pub fn task_counts_to_progress(counts: TaskCounts) -> FeatureProgress {
+        FeatureProgress {
+            completed: counts.completed,
+            total: counts.total,
+            running: counts.running > 0,
+        }
+    }
 }
 
-actions!(app, [Quit, Open, OpenRecent, Save]);
+/// How often the feature tree's session progress badges are refreshed while
+/// the server's change stream is unavailable.
+const PROGRESS_POLL_INTERVAL: Duration = Duration::from_secs(3);
+
+/// How often the server's health endpoint is checked.
+const HEALTH_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// How long pane sizes must stay put before they are saved, so dragging a
+/// divider writes the config once rather than on every frame.
+const LAYOUT_SAVE_DELAY: Duration = Duration::from_millis(500);
+
+actions!(
+    app,
+    [
+        Quit,
+        Open,
+        OpenRecent,
+        Save,
+        ToggleActivityLog,
+        FocusSidebar,
+        FocusEditor,
+        FocusTerminal,
+        ToggleZoom,
+        OpenSettings
+    ]
+);
 
 /// Load embedded fonts into the text system.
 fn load_embedded_fonts(cx: &App) {
@@ -228,6 +302,8 @@ fn apply_pigs_in_space_theme(cx: &mut App) {
     // editor.background: #21262c, editor.foreground: #a0b0c1
     // editor.active_line.background: #2c3137
     // editor.line_number: #424b55, editor.active_line_number: #a0b0c1
+    // Token colors come from the default dark theme, so fenced code blocks in
+    // the details preview are highlighted by their language.
     theme.highlight_theme = Arc::new(HighlightTheme {
         name: "Pigs in Space".into(),
         appearance: ThemeMode::Dark,
@@ -262,7 +338,7 @@ fn apply_pigs_in_space_theme(cx: &mut App) {
                 l: 0.66,
                 a: 1.0,
             }), // #a0b0c1
-            ..Default::default()
+            ..HighlightTheme::default_dark().style.clone()
         },
     });
 }
@@ -272,7 +348,11 @@ fn set_menus(cx: &mut App) {
     cx.set_menus(vec![
         Menu {
             name: "Manifest".into(),
-            items: vec![MenuItem::action("Quit Manifest", Quit)],
+            items: vec![
+                MenuItem::action("Settings...", OpenSettings),
+                MenuItem::separator(),
+                MenuItem::action("Quit Manifest", Quit),
+            ],
         },
         Menu {
             name: "File".into(),
@@ -283,54 +363,213 @@ fn set_menus(cx: &mut App) {
                 MenuItem::action("Save", Save),
             ],
         },
+        Menu {
+            name: "View".into(),
+            items: vec![MenuItem::action("Activity Log", ToggleActivityLog)],
+        },
     ]);
 }
 
 /// Result of fetching features: features, the directory name to display, and the project path.
 struct FetchResult {
+    project_id: Uuid,
     features: Vec<manifest_client::Feature>,
+    /// Tags for each feature in the project.
+    tags: HashMap<Uuid, Vec<String>>,
+    /// Features waiting on an unimplemented dependency.
+    blocked: HashSet<Uuid>,
+    /// Deleted features of the project, for the trash view.
+    trash: Vec<TrashedFeature>,
     directory_name: Option<String>,
     /// The project directory path (for writing per-project context).
     project_path: Option<PathBuf>,
 }
 
+/// A pane that can be zoomed to fill the window.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ZoomedPane {
+    Editor,
+    Terminal,
+}
+
+/// What was waiting on the unsaved-changes prompt, done once the editor's
+/// changes are saved or discarded.
+#[derive(Clone, Copy)]
+enum AfterUnsavedPrompt {
+    OpenFeature(Uuid),
+    CloseWindow,
+    Quit,
+}
+
 /// Root application view with feature panel, editor, and terminal.
 pub struct ManifestApp {
     feature_panel: Entity<FeaturePanel>,
     feature_editor: Entity<FeatureEditor>,
     terminal_view: Entity<TerminalView>,
+    activity_log: Entity<ActivityLog>,
+    status_bar: Entity<StatusBar>,
+    /// Whether the activity log is shown below the terminal.
+    show_activity_log: bool,
+    /// Pane temporarily filling the window, hiding the split layout.
+    zoomed: Option<ZoomedPane>,
+    /// Settings overlay, while open.
+    settings: Option<Entity<SettingsView>>,
+    /// Sizes of the sidebar | editor+terminal split.
+    main_layout: Entity<ResizableState>,
+    /// Sizes of the editor | terminal split.
+    editor_terminal_layout: Entity<ResizableState>,
+    /// Pending save of the pane sizes, replaced on every resize.
+    layout_save: Option<Task<()>>,
     config: AppConfig,
     current_project_path: Option<PathBuf>,
+    /// Project whose features are shown, for refreshing session progress.
+    current_project_id: Option<Uuid>,
+    /// Action to finish once the editor's save, chosen in the
+    /// unsaved-changes prompt, succeeds.
+    after_save: Option<AfterUnsavedPrompt>,
 }
 
 impl ManifestApp {
     pub fn new(config: AppConfig, window: &mut Window, cx: &mut Context<Self>) -> Self {
         let feature_panel = cx.new(|cx| {
-            let mut panel = FeaturePanel::new(cx);
+            let mut panel = FeaturePanel::new(window, cx);
             if let Some(width) = config.feature_panel_width {
                 panel.set_width(width, cx);
             }
+            panel.set_hidden_states(config.hidden_feature_states.clone(), cx);
             panel
         });
-        let feature_editor = cx.new(|cx| FeatureEditor::new(cx));
-        let terminal_view = cx.new(|cx| TerminalView::new(window, cx));
+        let feature_editor = cx.new(|cx| {
+            let mut editor = FeatureEditor::new(cx);
+            editor.set_editor_command(config.external_editor.clone());
+            editor.set_indent_width(config.indent_width);
+            editor
+        });
+        let terminal_view = cx.new(|cx| {
+            let mut view = TerminalView::new(window, cx);
+            view.set_pty_log_dir(config.pty_log_dir.clone(), cx);
+            if let Some(separators) = config.word_separators.clone() {
+                view.set_word_separators(separators, cx);
+            }
+            view.set_bold_is_bright(config.bold_is_bright, cx);
+            view.set_allow_clipboard_writes(config.terminal_clipboard_writes, cx);
+            view.set_option_as_meta(config.terminal_option_as_meta, cx);
+            if let Some(font_size) = config.terminal_font_size {
+                view.set_font_size(font_size, cx);
+            }
+            view
+        });
+        let activity_log = cx.new(|_cx| ActivityLog::default());
+        let status_bar = cx.new(|_cx| StatusBar::default());
+        let main_layout = cx.new(|_cx| ResizableState::default());
+        let editor_terminal_layout = cx.new(|_cx| ResizableState::default());
+
+        // Remember pane sizes across launches
+        for layout in [&main_layout, &editor_terminal_layout] {
+            cx.subscribe(layout, |this, _layout, event: &ResizablePanelEvent, cx| {
+                if let ResizablePanelEvent::Resized = event {
+                    this.schedule_layout_save(cx);
+                }
+            })
+            .detach();
+        }
 
-        // Subscribe to feature panel selection events
-        cx.subscribe(&feature_panel, |this, _panel, event: &PanelEvent, cx| {
-            let PanelEvent::FeatureSelected(feature_id) = event;
-            this.on_feature_selected(*feature_id, cx);
-        })
+        cx.subscribe(
+            &terminal_view,
+            |this, _view, event: &TerminalViewEvent, cx| {
+                if let TerminalViewEvent::FontSizeChanged(font_size) = event {
+                    this.save_terminal_font_size(*font_size, cx);
+                }
+            },
+        )
+        .detach();
+
+        if let Some(name) = &config.terminal_theme {
+            match TerminalTheme::named(name) {
+                Some(theme) => terminal_view.update(cx, |view, cx| view.set_theme(theme, cx)),
+                None => activity_log.update(cx, |log, cx| {
+                    let message = format!(
+                        "Unknown terminal theme '{}', expected one of: {}",
+                        name,
+                        TerminalTheme::NAMES.join(", ")
+                    );
+                    log.push(Severity::Warning, message, cx);
+                }),
+            }
+        }
+
+        // Subscribe to feature panel events
+        cx.subscribe_in(
+            &feature_panel,
+            window,
+            |this, _panel, event: &PanelEvent, window, cx| match event {
+                PanelEvent::FeatureSelected(feature_id) => {
+                    this.open_feature(*feature_id, window, cx)
+                }
+                PanelEvent::RestoreFeature(feature_id) => this.restore_feature(*feature_id, cx),
+                PanelEvent::CreateFeature { parent_id } => this.create_feature(*parent_id, cx),
+                PanelEvent::DeleteFeature(feature_id) => {
+                    this.confirm_delete_feature(*feature_id, window, cx)
+                }
+                PanelEvent::RenameFeature { feature_id, title } => {
+                    this.rename_feature(*feature_id, title.clone(), cx)
+                }
+                PanelEvent::HiddenStatesChanged(states) => {
+                    this.config.hidden_feature_states = states.clone();
+                    if let Err(e) = this.config.save() {
+                        let message = format!("Failed to save sidebar filter: {}", e);
+                        this.log(Severity::Error, message, cx);
+                    }
+                }
+            },
+        )
         .detach();
 
         // Subscribe to editor events
-        cx.subscribe(
+        cx.subscribe_in(
             &feature_editor,
-            |_this, _editor, event: &EditorEvent, _cx| match event {
+            window,
+            |this, _editor, event: &EditorEvent, window, cx| match event {
                 EditorEvent::FeatureSaved(id) => {
-                    eprintln!("Feature {} saved", id);
+                    this.log(Severity::Info, format!("Feature {} saved", id), cx);
+                    if let Some(then) = this.after_save.take() {
+                        this.finish_after_prompt(then, window, cx);
+                    }
                 }
                 EditorEvent::SaveFailed(id, err) => {
-                    eprintln!("Failed to save feature {}: {}", id, err);
+                    this.after_save = None;
+                    let message = format!("Failed to save feature {}: {}", id, err);
+                    this.log(Severity::Error, message, cx);
+                }
+                EditorEvent::SaveConflict(id) => {
+                    this.after_save = None;
+                    let message = format!("Feature {} changed elsewhere and was not saved", id);
+                    this.log(Severity::Warning, message, cx);
+                }
+                EditorEvent::Reloaded(_) => {
+                    this.after_save = None;
+                }
+                EditorEvent::ExternalEditFailed(id, err) => {
+                    let message = format!("Failed to edit feature {} externally: {}", id, err);
+                    this.log(Severity::Error, message, cx);
+                }
+                EditorEvent::OpenWorktree {
+                    task_id,
+                    title,
+                    path,
+                } => {
+                    this.open_task_terminal(*task_id, title.clone(), path.clone(), window, cx);
+                }
+                EditorEvent::OpenFeature(id) => {
+                    this.open_feature(*id, window, cx);
+                }
+                EditorEvent::CopyAsMarkdown(id) => {
+                    this.copy_feature_as_markdown(*id, cx);
+                }
+                EditorEvent::TagsChanged(id, tags) => {
+                    this.feature_panel.update(cx, |panel, cx| {
+                        panel.set_feature_tags(*id, tags.clone(), cx);
+                    });
                 }
             },
         )
@@ -342,6 +581,7 @@ impl ManifestApp {
 
         // Fetch features in background
         let feature_panel_clone = feature_panel.clone();
+        let activity = activity_log.clone();
         let background_executor = cx.background_executor().clone();
         cx.spawn(async move |this, cx| {
             let result = background_executor
@@ -350,23 +590,40 @@ impl ManifestApp {
 
             match result {
                 Ok(FetchResult {
+                    project_id,
                     features,
+                    tags,
+                    blocked,
+                    trash,
                     directory_name,
                     project_path,
                 }) => {
-                    eprintln!("Loaded {} features", features.len());
+                    cx.update_entity(&activity, |log, cx| {
+                        let message = format!("Loaded {} features", features.len());
+                        log.push(Severity::Info, message, cx);
+                    });
                     // Save project path for context file writing
                     if let Some(this) = this.upgrade() {
-                        cx.update_entity(&this, |app, _cx| {
+                        cx.update_entity(&this, |app, cx| {
                             app.current_project_path = project_path;
+                            app.current_project_id = Some(project_id);
+                            app.status_bar.update(cx, |bar, cx| {
+                                bar.set_project(directory_name.clone(), &features, cx);
+                            });
                         });
                     }
                     cx.update_entity(&feature_panel_clone, |panel, cx| {
+                        panel.set_tags(tags, cx);
+                        panel.set_blocked(blocked, cx);
+                        panel.set_trash(trash, cx);
                         panel.set_features(features, directory_name, cx);
                     });
                 }
                 Err(e) => {
-                    eprintln!("Failed to load features: {}", e);
+                    cx.update_entity(&activity, |log, cx| {
+                        let message = format!("Failed to load features: {}", e);
+                        log.push(Severity::Error, message, cx);
+                    });
                     cx.update_entity(&feature_panel_clone, |panel, cx| {
                         panel.set_error(e, cx);
                     });
@@ -375,18 +632,363 @@ impl ManifestApp {
         })
         .detach();
 
+        // Refresh session progress badges when the server reports a feature
+        // or task change in the open project
+        let (refresh_tx, mut refresh_rx) = mpsc::unbounded();
+        std::thread::spawn(move || Self::watch_changes(refresh_tx));
+        let feature_panel_clone = feature_panel.clone();
+        let activity = activity_log.clone();
+        let background_executor = cx.background_executor().clone();
+        cx.spawn(async move |this, cx| {
+            while let Some(changed) = refresh_rx.next().await {
+                // One write can send an event per feature; refresh once for all
+                let mut changed: Vec<Option<Uuid>> = vec![changed];
+                while let Ok(more) = refresh_rx.try_recv() {
+                    changed.push(more);
+                }
+                let Some(app) = this.upgrade() else {
+                    break;
+                };
+                let project_id = cx.update_entity(&app, |app, _cx| app.current_project_id);
+                drop(app);
+                let Some(project_id) = project_id else {
+                    continue;
+                };
+                if !changed.iter().any(|c| c.is_none_or(|id| id == project_id)) {
+                    continue;
+                }
+
+                let result = background_executor
+                    .spawn(async move { Self::fetch_progress(project_id) })
+                    .await;
+                match result {
+                    Ok(progress) => {
+                        cx.update_entity(&feature_panel_clone, |panel, cx| {
+                            panel.set_progress(progress, cx);
+                        });
+                    }
+                    Err(e) => {
+                        cx.update_entity(&activity, |log, cx| {
+                            let message = format!("Failed to load session progress: {}", e);
+                            log.push(Severity::Error, message, cx);
+                        });
+                    }
+                }
+
+                // Features unblock as their dependencies are implemented
+                let blocked = background_executor
+                    .spawn(async move {
+                        Database::open_default().map(|db| Self::fetch_blocked(&db, project_id))
+                    })
+                    .await;
+                if let Ok(blocked) = blocked {
+                    cx.update_entity(&feature_panel_clone, |panel, cx| {
+                        panel.set_blocked(blocked, cx);
+                    });
+                }
+            }
+        })
+        .detach();
+
+        // Ping the server so the status bar shows whether saves can reach it
+        let background_executor = cx.background_executor().clone();
+        cx.spawn(async move |this, cx| {
+            let client = manifest_client::ManifestClient::localhost();
+            loop {
+                let Some(app) = this.upgrade() else {
+                    break;
+                };
+                let status_bar = cx.update_entity(&app, |app, _cx| app.status_bar.clone());
+                drop(app);
+
+                let client = client.clone();
+                let connection = background_executor
+                    .spawn(async move {
+                        match client.health() {
+                            Ok(()) => Connection::Connected,
+                            Err(_) => Connection::Disconnected,
+                        }
+                    })
+                    .await;
+                cx.update_entity(&status_bar, |bar, cx| {
+                    bar.set_connection(connection, cx);
+                });
+                background_executor.timer(HEALTH_POLL_INTERVAL).await;
+            }
+        })
+        .detach();
+
         Self {
             feature_panel,
             feature_editor,
             terminal_view,
+            activity_log,
+            status_bar,
+            show_activity_log: false,
+            zoomed: None,
+            settings: None,
+            main_layout,
+            editor_terminal_layout,
+            layout_save: None,
             config,
             current_project_path: None,
+            current_project_id: None,
+            after_save: None,
+        }
+    }
+
+    /// Add an entry to the activity log.
+    fn log(&self, severity: Severity, message: String, cx: &mut Context<Self>) {
+        self.activity_log
+            .update(cx, |log, cx| log.push(severity, message, cx));
+    }
+
+    /// Show or hide the activity log.
+    fn toggle_activity_log(
+        &mut self,
+        _: &ToggleActivityLog,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.show_activity_log = !self.show_activity_log;
+        cx.notify();
+    }
+
+    fn focus_sidebar(&mut self, _: &FocusSidebar, window: &mut Window, cx: &mut Context<Self>) {
+        self.feature_panel.focus_handle(cx).focus(window, cx);
+    }
+
+    fn focus_editor(&mut self, _: &FocusEditor, window: &mut Window, cx: &mut Context<Self>) {
+        self.feature_editor
+            .update(cx, |editor, cx| editor.focus(window, cx));
+    }
+
+    fn focus_terminal(&mut self, _: &FocusTerminal, window: &mut Window, cx: &mut Context<Self>) {
+        self.terminal_view.focus_handle(cx).focus(window, cx);
+    }
+
+    /// Show the settings overlay.
+    fn open_settings(&mut self, _: &OpenSettings, window: &mut Window, cx: &mut Context<Self>) {
+        if self.settings.is_some() {
+            return;
+        }
+        let config = self.config.clone();
+        let settings = cx.new(|cx| SettingsView::new(config, window, cx));
+        cx.subscribe_in(
+            &settings,
+            window,
+            |this, _settings, event: &SettingsEvent, window, cx| match event {
+                SettingsEvent::Changed(config) => this.apply_config(config.clone(), cx),
+                SettingsEvent::Dismissed => {
+                    this.settings = None;
+                    this.terminal_view.focus_handle(cx).focus(window, cx);
+                    cx.notify();
+                }
+            },
+        )
+        .detach();
+        settings.update(cx, |settings, cx| settings.focus(window, cx));
+        self.settings = Some(settings);
+        cx.notify();
+    }
+
+    /// Save the pane sizes once they have stopped changing.
+    fn schedule_layout_save(&mut self, cx: &mut Context<Self>) {
+        let background_executor = cx.background_executor().clone();
+        self.layout_save = Some(cx.spawn(async move |this, cx| {
+            background_executor.timer(LAYOUT_SAVE_DELAY).await;
+            this.update(cx, |app, cx| app.save_layout(cx)).ok();
+        }));
+    }
+
+    /// Copy the current pane sizes into the config and save it.
+    fn save_layout(&mut self, cx: &mut Context<Self>) {
+        self.layout_save = None;
+        if let Some(width) = self.main_layout.read(cx).sizes().first() {
+            self.config.feature_panel_width = Some(f32::from(*width));
+        }
+        if let [editor, terminal, ..] = self.editor_terminal_layout.read(cx).sizes().as_slice() {
+            let total = *editor + *terminal;
+            if total > px(0.0) {
+                self.config.editor_split_ratio = *editor / total;
+            }
+        }
+        if let Err(e) = self.config.save() {
+            self.log(
+                Severity::Error,
+                format!("Failed to save pane sizes: {}", e),
+                cx,
+            );
+        }
+    }
+
+    /// Remember the terminal's zoom for the next launch.
+    fn save_terminal_font_size(&mut self, font_size: f32, cx: &mut Context<Self>) {
+        self.config.terminal_font_size = Some(font_size);
+        if let Err(e) = self.config.save() {
+            let message = format!("Failed to save terminal font size: {}", e);
+            self.log(Severity::Error, message, cx);
+        }
+    }
+
+    /// Apply settings edited in the overlay and save them.
+    fn apply_config(&mut self, mut config: AppConfig, cx: &mut Context<Self>) {
+        // The overlay edits a copy taken when it opened; keep sizes resized,
+        // the terminal zoomed and states hidden since
+        config.feature_panel_width = self.config.feature_panel_width;
+        config.editor_split_ratio = self.config.editor_split_ratio;
+        config.terminal_font_size = self.config.terminal_font_size;
+        config.hidden_feature_states = self.config.hidden_feature_states.clone();
+        // Changing the log directory restarts every tab's log, so only do it when needed
+        let log_dir_changed = self.config.pty_log_dir != config.pty_log_dir;
+        self.terminal_view.update(cx, |view, cx| {
+            let separators = config
+                .word_separators
+                .clone()
+                .unwrap_or_else(|| terminal::DEFAULT_WORD_SEPARATORS.to_string());
+            view.set_word_separators(separators, cx);
+            view.set_bold_is_bright(config.bold_is_bright, cx);
+            view.set_allow_clipboard_writes(config.terminal_clipboard_writes, cx);
+            view.set_option_as_meta(config.terminal_option_as_meta, cx);
+            if log_dir_changed {
+                view.set_pty_log_dir(config.pty_log_dir.clone(), cx);
+            }
+        });
+        self.feature_editor.update(cx, |editor, _cx| {
+            editor.set_editor_command(config.external_editor.clone());
+            editor.set_indent_width(config.indent_width);
+        });
+
+        if let Err(e) = config.save() {
+            self.log(
+                Severity::Error,
+                format!("Failed to save settings: {}", e),
+                cx,
+            );
+        }
+        self.config = config;
+    }
+
+    /// Zoom the focused editor or terminal to fill the window, or restore the split.
+    fn toggle_zoom(&mut self, _: &ToggleZoom, window: &mut Window, cx: &mut Context<Self>) {
+        let terminal_focused = self
+            .terminal_view
+            .focus_handle(cx)
+            .contains_focused(window, cx);
+        let editor_focused = self
+            .feature_editor
+            .focus_handle(cx)
+            .contains_focused(window, cx);
+        self.zoomed = match self.zoomed {
+            Some(_) => None,
+            None if terminal_focused => Some(ZoomedPane::Terminal),
+            None if editor_focused => Some(ZoomedPane::Editor),
+            None => None,
+        };
+        cx.notify();
+    }
+
+    /// Wrap a pane in a border that lights up while focus is inside it.
+    fn focus_ring(
+        content: impl IntoElement,
+        handle: &FocusHandle,
+        window: &Window,
+        cx: &App,
+    ) -> impl IntoElement {
+        let color = if handle.contains_focused(window, cx) {
+            Hsla::from(TerminalColors::ansi_blue()).opacity(0.4)
+        } else {
+            gpui::transparent_black()
+        };
+        div()
+            .size_full()
+            .border_1()
+            .border_color(color)
+            .child(content)
+    }
+
+    /// Open a feature the user picked, first asking what to do with unsaved
+    /// changes to the one in the editor.
+    fn open_feature(&mut self, feature_id: Uuid, window: &mut Window, cx: &mut Context<Self>) {
+        let editor = self.feature_editor.read(cx);
+        if editor.is_dirty() && editor.feature_id() != Some(feature_id) {
+            self.prompt_unsaved(AfterUnsavedPrompt::OpenFeature(feature_id), window, cx);
+        } else {
+            self.on_feature_selected(feature_id, cx);
+        }
+    }
+
+    /// Ask whether to save the editor's unsaved changes before `then`.
+    ///
+    /// Save finishes `then` once the save succeeds, so a failed save or a
+    /// conflict keeps the edits on screen. Discard goes ahead without saving,
+    /// and Cancel (or Escape) leaves everything as it was.
+    fn prompt_unsaved(
+        &mut self,
+        then: AfterUnsavedPrompt,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let answer = window.prompt(
+            PromptLevel::Warning,
+            "Save changes to this feature?",
+            Some("Your changes to its title and details are lost if you don't save them."),
+            &["Save", "Discard", "Cancel"],
+            cx,
+        );
+        cx.spawn_in(window, async move |this, cx| {
+            let answer = answer.await;
+            this.update_in(cx, |app, window, cx| match answer {
+                Ok(0) => {
+                    app.after_save = Some(then);
+                    app.feature_editor
+                        .update(cx, |editor, cx| editor.save_current(window, cx));
+                }
+                Ok(1) => app.finish_after_prompt(then, window, cx),
+                _ => {}
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    fn finish_after_prompt(
+        &mut self,
+        then: AfterUnsavedPrompt,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        match then {
+            AfterUnsavedPrompt::OpenFeature(feature_id) => self.on_feature_selected(feature_id, cx),
+            AfterUnsavedPrompt::CloseWindow => window.remove_window(),
+            AfterUnsavedPrompt::Quit => cx.quit(),
+        }
+    }
+
+    /// Whether the window may close now; with unsaved changes it stays open
+    /// and asks first.
+    fn should_close_window(&mut self, window: &mut Window, cx: &mut Context<Self>) -> bool {
+        if !self.feature_editor.read(cx).is_dirty() {
+            return true;
+        }
+        self.prompt_unsaved(AfterUnsavedPrompt::CloseWindow, window, cx);
+        false
+    }
+
+    /// Quit, asking first about unsaved changes. Without any, the global
+    /// handler quits.
+    fn quit(&mut self, _: &Quit, window: &mut Window, cx: &mut Context<Self>) {
+        if self.feature_editor.read(cx).is_dirty() {
+            self.prompt_unsaved(AfterUnsavedPrompt::Quit, window, cx);
+        } else {
+            cx.propagate();
         }
     }
 
     /// Handle feature selection from the panel.
     fn on_feature_selected(&self, feature_id: Uuid, cx: &mut Context<Self>) {
         let editor_clone = self.feature_editor.clone();
+        let activity = self.activity_log.clone();
         let project_path = self.current_project_path.clone();
         let background_executor = cx.background_executor().clone();
 
@@ -394,12 +996,19 @@ impl ManifestApp {
             let result = background_executor
                 .spawn(async move {
                     let db = Database::open_default()?;
-                    db.get_feature(feature_id)
+                    let Some(feature) = db.get_feature(feature_id)? else {
+                        return Ok(None);
+                    };
+                    let tags = db.get_feature_tags(feature.id)?;
+                    let known_tags = db.get_project_tags(feature.project_id, None)?;
+                    let blocked_by = db.get_dependencies(feature.id)?;
+                    let blocks = db.get_dependents(feature.id)?;
+                    Ok::<_, anyhow::Error>(Some((feature, tags, known_tags, blocked_by, blocks)))
                 })
                 .await;
 
             match result {
-                Ok(Some(feature)) => {
+                Ok(Some((feature, tags, known_tags, blocked_by, blocks))) => {
                     // Write to context file for MCP server (per-project)
                     if let Some(ref path) = project_path {
                         if let Err(e) = context_file::write_context(
@@ -408,7 +1017,10 @@ impl ManifestApp {
                             &feature.title,
                             feature.details.as_deref(),
                         ) {
-                            eprintln!("Failed to write context file: {}", e);
+                            cx.update_entity(&activity, |log, cx| {
+                                let message = format!("Failed to write context file: {}", e);
+                                log.push(Severity::Warning, message, cx);
+                            });
                         }
                     }
 
@@ -426,20 +1038,420 @@ impl ManifestApp {
 
                     // Update editor - use update_entity which works without window handle
                     cx.update_entity(&editor_clone, |editor, cx| {
-                        editor.load_feature(feature.id, feature.title, feature.details, cx);
+                        editor.load_feature(
+                            feature.id,
+                            feature.title,
+                            feature.details,
+                            feature.updated_at.to_rfc3339(),
+                            cx,
+                        );
+                        editor.set_tags(tags, known_tags, cx);
+                        editor.set_dependencies(
+                            blocked_by
+                                .into_iter()
+                                .map(convert::feature_to_link)
+                                .collect(),
+                            blocks.into_iter().map(convert::feature_to_link).collect(),
+                            cx,
+                        );
                     });
                 }
                 Ok(None) => {
-                    eprintln!("Feature not found: {}", feature_id);
+                    cx.update_entity(&activity, |log, cx| {
+                        let message = format!("Feature not found: {}", feature_id);
+                        log.push(Severity::Warning, message, cx);
+                    });
                 }
                 Err(e) => {
-                    eprintln!("Failed to load feature: {}", e);
+                    cx.update_entity(&activity, |log, cx| {
+                        let message = format!("Failed to load feature: {}", e);
+                        log.push(Severity::Error, message, cx);
+                    });
                 }
             }
         })
         .detach();
     }
 
+    /// Reload the feature tree after a change made from the app.
+    fn reload_project(&mut self, cx: &mut Context<Self>) {
+        // Without a project directory (the fallback project) the tree is
+        // only reloaded on the next launch
+        if let Some(path) = self.current_project_path.clone() {
+            self.open_project(path, cx);
+        }
+    }
+
+    /// Create a feature and open it in the editor to be named.
+    fn create_feature(&self, parent_id: Option<Uuid>, cx: &mut Context<Self>) {
+        let Some(project_id) = self.current_project_id else {
+            let message = "Open a project before adding features".to_string();
+            self.log(Severity::Warning, message, cx);
+            return;
+        };
+        let activity = self.activity_log.clone();
+        let background_executor = cx.background_executor().clone();
+
+        cx.spawn(async move |this, cx| {
+            let result = background_executor
+                .spawn(async move {
+                    let db = Database::open_default()?;
+                    db.create_feature(
+                        project_id,
+                        CreateFeatureInput {
+                            id: None,
+                            parent_id,
+                            title: "New feature".to_string(),
+                            details: None,
+                            state: None,
+                            priority: None,
+                        },
+                    )
+                })
+                .await;
+
+            match result {
+                Ok(feature) => {
+                    if let Some(this) = this.upgrade() {
+                        cx.update_entity(&this, |app, cx| {
+                            app.reload_project(cx);
+                            app.on_feature_selected(feature.id, cx);
+                        });
+                    }
+                }
+                Err(e) => {
+                    cx.update_entity(&activity, |log, cx| {
+                        let message = format!("Failed to create feature: {}", e);
+                        log.push(Severity::Error, message, cx);
+                    });
+                }
+            }
+        })
+        .detach();
+    }
+
+    /// Open a shell in a task's worktree, saving its output to
+    /// `.manifest/logs/<task_id>.log` in the project and recording that file
+    /// on the task.
+    fn open_task_terminal(
+        &self,
+        task_id: Uuid,
+        title: String,
+        worktree: PathBuf,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let log_path = self
+            .current_project_path
+            .as_deref()
+            .unwrap_or(&worktree)
+            .join(".manifest")
+            .join("logs")
+            .join(format!("{}.log", task_id));
+        let recorded_path = log_path.to_string_lossy().into_owned();
+        let env = HashMap::from([("MANIFEST_TASK_ID".to_string(), task_id.to_string())]);
+        self.terminal_view.update(cx, |view, cx| {
+            view.open_tab_in(worktree, title, Some(log_path), env, window, cx);
+        });
+
+        let background_executor = cx.background_executor().clone();
+        cx.spawn(async move |this, cx| {
+            let result = background_executor
+                .spawn(async move {
+                    let db = Database::open_default()?;
+                    db.update_task(
+                        task_id,
+                        UpdateTaskInput {
+                            status: None,
+                            worktree_path: None,
+                            branch: None,
+                            log_path: Some(recorded_path),
+                        },
+                    )
+                })
+                .await;
+
+            if let (Err(e), Some(this)) = (result, this.upgrade()) {
+                cx.update_entity(&this, |app, cx| {
+                    let message =
+                        format!("Failed to record the log file of task {}: {}", task_id, e);
+                    app.log(Severity::Warning, message, cx);
+                });
+            }
+        })
+        .detach();
+    }
+
+    /// Save a title edited in the sidebar.
+    fn rename_feature(&self, feature_id: Uuid, title: String, cx: &mut Context<Self>) {
+        let background_executor = cx.background_executor().clone();
+
+        cx.spawn(async move |this, cx| {
+            let result = background_executor
+                .spawn(async move {
+                    let db = Database::open_default()?;
+                    db.update_feature(
+                        feature_id,
+                        UpdateFeatureInput {
+                            parent_id: None,
+                            title: Some(title),
+                            details: None,
+                            desired_details: None,
+                            state: None,
+                            priority: None,
+                            expected_updated_at: None,
+                        },
+                    )
+                })
+                .await;
+
+            let Some(this) = this.upgrade() else {
+                return;
+            };
+            match result {
+                // The editor would otherwise save over the rename with its
+                // stale version; reload it unless it has unsaved changes
+                Ok(Some(_)) => cx.update_entity(&this, |app, cx| {
+                    let editor = app.feature_editor.read(cx);
+                    if editor.feature_id() == Some(feature_id) && !editor.is_dirty() {
+                        app.on_feature_selected(feature_id, cx);
+                    }
+                }),
+                // Put the old title back in the tree
+                Ok(None) => cx.update_entity(&this, |app, cx| {
+                    let message = format!("Feature not found: {}", feature_id);
+                    app.log(Severity::Warning, message, cx);
+                    app.reload_project(cx);
+                }),
+                Err(e) => cx.update_entity(&this, |app, cx| {
+                    let message = format!("Failed to rename feature: {}", e);
+                    app.log(Severity::Error, message, cx);
+                    app.reload_project(cx);
+                }),
+            }
+        })
+        .detach();
+    }
+
+    /// Ask before moving a feature and its children to the trash.
+    fn confirm_delete_feature(
+        &self,
+        feature_id: Uuid,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let answer = window.prompt(
+            PromptLevel::Warning,
+            "Delete this feature?",
+            Some("It moves to the trash with its children, and can be restored from there."),
+            &["Delete", "Cancel"],
+            cx,
+        );
+        cx.spawn(async move |this, cx| {
+            if !matches!(answer.await, Ok(0)) {
+                return;
+            }
+            if let Some(this) = this.upgrade() {
+                cx.update_entity(&this, |app, cx| app.delete_feature(feature_id, cx));
+            }
+        })
+        .detach();
+    }
+
+    fn delete_feature(&self, feature_id: Uuid, cx: &mut Context<Self>) {
+        let activity = self.activity_log.clone();
+        let background_executor = cx.background_executor().clone();
+
+        cx.spawn(async move |this, cx| {
+            let result = background_executor
+                .spawn(async move {
+                    let db = Database::open_default()?;
+                    db.delete_feature(feature_id)
+                })
+                .await;
+
+            match result {
+                Ok(deleted) => {
+                    cx.update_entity(&activity, |log, cx| {
+                        let (severity, message) = if deleted {
+                            (Severity::Info, "Moved feature to the trash".to_string())
+                        } else {
+                            (
+                                Severity::Warning,
+                                format!("Feature not found: {}", feature_id),
+                            )
+                        };
+                        log.push(severity, message, cx);
+                    });
+                    if let Some(this) = this.upgrade() {
+                        cx.update_entity(&this, |app, cx| app.reload_project(cx));
+                    }
+                }
+                Err(e) => {
+                    cx.update_entity(&activity, |log, cx| {
+                        let message = format!("Failed to delete feature: {}", e);
+                        log.push(Severity::Error, message, cx);
+                    });
+                }
+            }
+        })
+        .detach();
+    }
+
+    /// Restore a feature from the trash, then reload the project to show it.
+    fn restore_feature(&self, feature_id: Uuid, cx: &mut Context<Self>) {
+        let activity = self.activity_log.clone();
+        let background_executor = cx.background_executor().clone();
+
+        cx.spawn(async move |this, cx| {
+            let result = background_executor
+                .spawn(async move {
+                    let db = Database::open_default()?;
+                    db.restore_feature(feature_id)
+                })
+                .await;
+
+            match result {
+                Ok(Some(feature)) => {
+                    cx.update_entity(&activity, |log, cx| {
+                        let message = format!("Restored '{}'", feature.title);
+                        log.push(Severity::Info, message, cx);
+                    });
+                    if let Some(this) = this.upgrade() {
+                        cx.update_entity(&this, |app, cx| app.reload_project(cx));
+                    }
+                }
+                Ok(None) => {
+                    cx.update_entity(&activity, |log, cx| {
+                        let message = format!("Feature not found: {}", feature_id);
+                        log.push(Severity::Warning, message, cx);
+                    });
+                }
+                Err(e) => {
+                    cx.update_entity(&activity, |log, cx| {
+                        let message = format!("Failed to restore feature: {}", e);
+                        log.push(Severity::Error, message, cx);
+                    });
+                }
+            }
+        })
+        .detach();
+    }
+
+    /// Copy a feature and its latest session's tasks to the clipboard as Markdown.
+    fn copy_feature_as_markdown(&self, feature_id: Uuid, cx: &mut Context<Self>) {
+        let activity = self.activity_log.clone();
+        let background_executor = cx.background_executor().clone();
+
+        cx.spawn(async move |_this, cx| {
+            let result = background_executor
+                .spawn(async move {
+                    let db = Database::open_default()?;
+                    db.feature_to_markdown(feature_id, true)
+                })
+                .await;
+
+            cx.update_entity(&activity, |log, cx| match result {
+                Ok(Some(markdown)) => {
+                    cx.write_to_clipboard(ClipboardItem::new_string(markdown));
+                    log.push(Severity::Info, "Copied feature as Markdown", cx);
+                }
+                Ok(None) => {
+                    let message = format!("Feature not found: {}", feature_id);
+                    log.push(Severity::Warning, message, cx);
+                }
+                Err(e) => {
+                    let message = format!("Failed to copy feature as Markdown: {}", e);
+                    log.push(Severity::Error, message, cx);
+                }
+            });
+        })
+        .detach();
+    }
+
+    /// Feed `refresh` from the server's change stream (blocking, runs on its
+    /// own thread): `Some(project_id)` for a feature or task change in that
+    /// project, `None` when anything may have changed. While the stream is
+    /// down it reconnects and sends `None` every `PROGRESS_POLL_INTERVAL`, so
+    /// the badges fall back to polling. Returns once the app stops listening.
+    fn watch_changes(refresh: mpsc::UnboundedSender<Option<Uuid>>) {
+        let client = manifest_client::ManifestClient::localhost();
+        while !refresh.is_closed() {
+            if let Ok(events) = client.events() {
+                // Changes made while disconnected went unreported
+                let _ = refresh.unbounded_send(None);
+                for event in events {
+                    let Ok(event) = event else {
+                        break;
+                    };
+                    let changed = match event.event.as_str() {
+                        "change" => match serde_json::from_str(&event.data) {
+                            Ok(
+                                ChangeEvent::FeatureChanged { project_id, .. }
+                                | ChangeEvent::TaskChanged { project_id, .. },
+                            ) => Some(project_id),
+                            _ => continue,
+                        },
+                        // The stream fell behind and dropped changes
+                        "resync" => None,
+                        _ => continue,
+                    };
+                    if refresh.unbounded_send(changed).is_err() {
+                        return;
+                    }
+                }
+            }
+            let _ = refresh.unbounded_send(None);
+            std::thread::sleep(PROGRESS_POLL_INTERVAL);
+        }
+    }
+
+    /// Fetch session progress for a project's features (blocking, runs on background thread).
+    fn fetch_progress(project_id: Uuid) -> Result<HashMap<Uuid, FeatureProgress>, String> {
+        let db = Database::open_default().map_err(|e| format!("Failed to open database: {}", e))?;
+        let counts = db
+            .get_active_task_counts(project_id)
+            .map_err(|e| format!("Failed to fetch task counts: {}", e))?;
+        Ok(counts
+            .into_iter()
+            .map(|(id, counts)| (id, convert::task_counts_to_progress(counts)))
+            .collect())
+    }
+
+    /// Fetch the tags of a project's features. Failures are logged and leave
+    /// the tree untagged rather than failing the feature load.
+    fn fetch_tags(db: &Database, project_id: Uuid) -> HashMap<Uuid, Vec<String>> {
+        db.get_project_feature_tags(project_id).unwrap_or_else(|e| {
+            eprintln!("Failed to fetch tags: {}", e);
+            HashMap::new()
+        })
+    }
+
+    /// Fetch the features blocked by an unimplemented dependency, logging failures.
+    fn fetch_blocked(db: &Database, project_id: Uuid) -> HashSet<Uuid> {
+        match db.get_blocked_feature_ids(project_id) {
+            Ok(ids) => ids.into_iter().collect(),
+            Err(e) => {
+                eprintln!("Failed to fetch blocked features: {}", e);
+                HashSet::new()
+            }
+        }
+    }
+
+    /// Fetch the features in a project's trash, logging failures.
+    fn fetch_trash(db: &Database, project_id: Uuid) -> Vec<TrashedFeature> {
+        match db.get_deleted_features(project_id) {
+            Ok(deleted) => deleted
+                .into_iter()
+                .map(convert::deleted_to_trashed)
+                .collect(),
+            Err(e) => {
+                eprintln!("Failed to fetch deleted features: {}", e);
+                Vec::new()
+            }
+        }
+    }
+
     /// Fetch features for a specific directory path (blocking, runs on background thread).
     fn fetch_features_for_path(path: &str) -> Result<FetchResult, String> {
         let db = Database::open_default().map_err(|e| format!("Failed to open database: {}", e))?;
@@ -471,7 +1483,11 @@ impl ManifestApp {
                         .map(|s| s.to_string());
 
                     return Ok(FetchResult {
+                        project_id: project_with_dirs.project.id,
                         features: converted,
+                        tags: Self::fetch_tags(&db, project_with_dirs.project.id),
+                        blocked: Self::fetch_blocked(&db, project_with_dirs.project.id),
+                        trash: Self::fetch_trash(&db, project_with_dirs.project.id),
                         directory_name,
                         project_path: Some(PathBuf::from(path)),
                     });
@@ -518,7 +1534,11 @@ impl ManifestApp {
                                 .map(|s| s.to_string());
 
                             return Ok(FetchResult {
+                                project_id: project_with_dirs.project.id,
                                 features: converted,
+                                tags: Self::fetch_tags(&db, project_with_dirs.project.id),
+                                blocked: Self::fetch_blocked(&db, project_with_dirs.project.id),
+                                trash: Self::fetch_trash(&db, project_with_dirs.project.id),
                                 directory_name,
                                 project_path: Some(cwd),
                             });
@@ -549,7 +1569,11 @@ impl ManifestApp {
                         .map(convert::tree_node_to_feature)
                         .collect();
                     return Ok(FetchResult {
+                        project_id: project.id,
                         features: converted,
+                        tags: Self::fetch_tags(&db, project.id),
+                        blocked: Self::fetch_blocked(&db, project.id),
+                        trash: Self::fetch_trash(&db, project.id),
                         directory_name: None, // No directory context in fallback
                         project_path: None,   // No project path in fallback
                     });
@@ -570,6 +1594,7 @@ impl ManifestApp {
         self.current_project_path = Some(path);
 
         let feature_panel = self.feature_panel.clone();
+        let activity = self.activity_log.clone();
         let background_executor = cx.background_executor().clone();
 
         cx.spawn(async move |this, cx| {
@@ -579,23 +1604,40 @@ impl ManifestApp {
 
             match result {
                 Ok(FetchResult {
+                    project_id,
                     features,
+                    tags,
+                    blocked,
+                    trash,
                     directory_name,
                     project_path,
                 }) => {
-                    eprintln!("Loaded {} features", features.len());
+                    cx.update_entity(&activity, |log, cx| {
+                        let message = format!("Loaded {} features", features.len());
+                        log.push(Severity::Info, message, cx);
+                    });
                     // Update project path in app state
                     if let Some(this) = this.upgrade() {
-                        cx.update_entity(&this, |app, _cx| {
+                        cx.update_entity(&this, |app, cx| {
                             app.current_project_path = project_path;
+                            app.current_project_id = Some(project_id);
+                            app.status_bar.update(cx, |bar, cx| {
+                                bar.set_project(directory_name.clone(), &features, cx);
+                            });
                         });
                     }
                     cx.update_entity(&feature_panel, |panel, cx| {
+                        panel.set_tags(tags, cx);
+                        panel.set_blocked(blocked, cx);
+                        panel.set_trash(trash, cx);
                         panel.set_features(features, directory_name, cx);
                     });
                 }
                 Err(e) => {
-                    eprintln!("Failed to load features: {}", e);
+                    cx.update_entity(&activity, |log, cx| {
+                        let message = format!("Failed to load features: {}", e);
+                        log.push(Severity::Error, message, cx);
+                    });
                     cx.update_entity(&feature_panel, |panel, cx| {
                         panel.set_error(e, cx);
                     });
@@ -610,29 +1652,103 @@ impl Render for ManifestApp {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let bg_color: gpui::Hsla = TerminalColors::background().into();
 
+        let sidebar_focus = self.feature_panel.focus_handle(cx);
+        let editor_focus = self.feature_editor.focus_handle(cx);
+        let terminal_focus = self.terminal_view.focus_handle(cx);
+        let editor = Self::focus_ring(self.feature_editor.clone(), &editor_focus, window, cx);
+        let terminal = Self::focus_ring(self.terminal_view.clone(), &terminal_focus, window, cx);
+
+        // A zoomed pane is drawn over the split, leaving an empty slot behind.
+        // The split stays laid out (but invisible) so it keeps its sizes.
+        let (editor, terminal, zoomed) = match self.zoomed {
+            Some(ZoomedPane::Editor) => (
+                div().into_any_element(),
+                terminal.into_any_element(),
+                Some(editor.into_any_element()),
+            ),
+            Some(ZoomedPane::Terminal) => (
+                editor.into_any_element(),
+                div().into_any_element(),
+                Some(terminal.into_any_element()),
+            ),
+            None => (editor.into_any_element(), terminal.into_any_element(), None),
+        };
+        let is_zoomed = zoomed.is_some();
+
+        // Vertical split: editor | terminal, with the activity log below when shown
+        // The saved split ratio only sizes the panels until the state has laid them out
+        let editor_height = window.viewport_size().height * self.config.editor_ratio();
+        let editor_terminal = v_resizable("editor-terminal")
+            .with_state(&self.editor_terminal_layout)
+            .child(resizable_panel().size(editor_height).child(editor))
+            .child(resizable_panel().child(terminal));
+        let editor_terminal = if self.show_activity_log {
+            editor_terminal.child(
+                resizable_panel()
+                    .size(px(160.0))
+                    .child(self.activity_log.clone()),
+            )
+        } else {
+            editor_terminal
+        };
+
         div()
             .id("manifest-app")
             .size_full()
+            .relative()
+            .flex()
+            .flex_col()
             .bg(bg_color)
+            .on_action(cx.listener(Self::toggle_activity_log))
+            .on_action(cx.listener(Self::focus_sidebar))
+            .on_action(cx.listener(Self::focus_editor))
+            .on_action(cx.listener(Self::focus_terminal))
+            .on_action(cx.listener(Self::toggle_zoom))
+            .on_action(cx.listener(Self::open_settings))
+            .on_action(cx.listener(Self::quit))
             .child(
-                // Horizontal split: feature panel | editor+terminal
-                h_resizable("main-layout")
-                    .child(
-                        resizable_panel()
-                            .size(px(self
-                                .config
-                                .feature_panel_width
-                                .unwrap_or(DEFAULT_PANEL_WIDTH)))
-                            .size_range(px(MIN_PANEL_WIDTH)..px(MAX_PANEL_WIDTH))
-                            .child(self.feature_panel.clone()),
-                    )
+                div()
+                    .flex_1()
+                    .min_h_0()
+                    .w_full()
+                    .when(is_zoomed, |d| d.invisible())
                     .child(
-                        // Vertical split: editor | terminal
-                        v_resizable("editor-terminal")
-                            .child(resizable_panel().child(self.feature_editor.clone()))
-                            .child(resizable_panel().child(self.terminal_view.clone())),
+                        // Horizontal split: feature panel | editor+terminal
+                        h_resizable("main-layout")
+                            .with_state(&self.main_layout)
+                            .child(
+                                resizable_panel()
+                                    .size(px(self
+                                        .config
+                                        .feature_panel_width
+                                        .unwrap_or(DEFAULT_PANEL_WIDTH)))
+                                    .size_range(px(MIN_PANEL_WIDTH)..px(MAX_PANEL_WIDTH))
+                                    .child(Self::focus_ring(
+                                        self.feature_panel.clone(),
+                                        &sidebar_focus,
+                                        window,
+                                        cx,
+                                    )),
+                            )
+                            .child(editor_terminal),
                     ),
             )
+            .child(self.status_bar.clone())
+            .when_some(zoomed, |d, pane| {
+                d.child(div().absolute().inset_0().child(pane))
+            })
+            .when_some(self.settings.clone(), |d, settings| {
+                d.child(
+                    div()
+                        .absolute()
+                        .inset_0()
+                        .flex()
+                        .items_center()
+                        .justify_center()
+                        .bg(gpui::rgba(0x00000080))
+                        .child(settings),
+                )
+            })
             // Render gpui-component overlay layers (dialogs, sheets, notifications)
             .children(Root::render_dialog_layer(window, cx))
             .children(Root::render_sheet_layer(window, cx))
@@ -659,17 +1775,36 @@ fn main() {
             // Set up application menus
             set_menus(cx);
 
+            // Load key bindings (defaults merged with ~/.manifest/keymap.toml)
+            let keymap = Keymap::load();
+
             // Bind global keys
-            cx.bind_keys([
-                KeyBinding::new("cmd-q", Quit, None),
-                KeyBinding::new("cmd-o", Open, None),
-            ]);
+            let global_bindings = keymap
+                .bindings(GLOBAL_CONTEXT)
+                .filter_map(|(keystroke, action)| match action {
+                    "app::Quit" => Some(KeyBinding::new(keystroke, Quit, None)),
+                    "app::Open" => Some(KeyBinding::new(keystroke, Open, None)),
+                    "app::ToggleActivityLog" => {
+                        Some(KeyBinding::new(keystroke, ToggleActivityLog, None))
+                    }
+                    "app::FocusSidebar" => Some(KeyBinding::new(keystroke, FocusSidebar, None)),
+                    "app::FocusEditor" => Some(KeyBinding::new(keystroke, FocusEditor, None)),
+                    "app::FocusTerminal" => Some(KeyBinding::new(keystroke, FocusTerminal, None)),
+                    "app::ToggleZoom" => Some(KeyBinding::new(keystroke, ToggleZoom, None)),
+                    "app::OpenSettings" => Some(KeyBinding::new(keystroke, OpenSettings, None)),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            cx.bind_keys(global_bindings);
 
             // Register feature editor key bindings
-            feature_editor::register_bindings(cx);
+            feature_editor::register_bindings(&keymap, cx);
 
             // Register feature panel key bindings
-            feature_panel::register_bindings(cx);
+            feature_panel::register_bindings(&keymap, cx);
+
+            // The terminal view looks up its own keys when they arrive
+            cx.set_global(keymap);
 
             let config = AppConfig::load();
             let window_size = size(
@@ -701,6 +1836,12 @@ fn main() {
                 .open_window(window_options, |window, cx| {
                     // Create the ManifestApp view
                     let app_view = cx.new(|cx| ManifestApp::new(config, window, cx));
+                    // Closing the window asks about unsaved feature edits first
+                    let app = app_view.downgrade();
+                    window.on_window_should_close(cx, move |window, cx| {
+                        app.update(cx, |app, cx| app.should_close_window(window, cx))
+                            .unwrap_or(true)
+                    });
                     // Wrap it in Root (required for gpui-component's theme and overlay system)
                     cx.new(|cx| Root::new(app_view, window, cx))
                 })
@@ -0,0 +1,397 @@
+//! Settings overlay.
+//!
+//! Edits the options in [`AppConfig`] that can change while the app runs.
+//! Fields are checked as they are typed: a valid value is emitted straight
+//! away so the app can apply and save it, and an invalid one shows its
+//! error under the field and leaves the previous value in effect.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use gpui::{
+    Context, Entity, EventEmitter, FocusHandle, Focusable, InteractiveElement, IntoElement,
+    KeyDownEvent, ParentElement, Render, Styled, Window, div, prelude::FluentBuilder, px, rgba,
+};
+use gpui_component::Sizable;
+use gpui_component::button::{Button, ButtonRounded, ButtonVariant, ButtonVariants};
+use gpui_component::input::{Input, InputEvent, InputState};
+use terminal::mappings::colors::TerminalColors;
+
+use crate::config::AppConfig;
+
+/// Events emitted by the settings overlay.
+#[derive(Clone, Debug)]
+pub enum Event {
+    /// A setting changed; the config holds every current value.
+    Changed(AppConfig),
+    /// The overlay was closed.
+    Dismissed,
+}
+
+/// A text setting in the overlay.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum Field {
+    WordSeparators,
+    ExternalEditor,
+    PtyLogDir,
+}
+
+/// Settings overlay, editing a copy of the app config.
+pub struct SettingsView {
+    config: AppConfig,
+    word_separators: Entity<InputState>,
+    external_editor: Entity<InputState>,
+    pty_log_dir: Entity<InputState>,
+    /// Why the current text of a field was rejected.
+    errors: HashMap<Field, String>,
+    focus_handle: FocusHandle,
+}
+
+impl SettingsView {
+    pub fn new(config: AppConfig, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let word_separators = Self::text_input(
+            Field::WordSeparators,
+            config.word_separators.clone().unwrap_or_default(),
+            terminal::DEFAULT_WORD_SEPARATORS,
+            window,
+            cx,
+        );
+        let external_editor = Self::text_input(
+            Field::ExternalEditor,
+            config.external_editor.clone().unwrap_or_default(),
+            "$VISUAL or $EDITOR",
+            window,
+            cx,
+        );
+        let pty_log_dir = Self::text_input(
+            Field::PtyLogDir,
+            config
+                .pty_log_dir
+                .as_ref()
+                .map(|dir| dir.display().to_string())
+                .unwrap_or_default(),
+            "Off",
+            window,
+            cx,
+        );
+
+        Self {
+            config,
+            word_separators,
+            external_editor,
+            pty_log_dir,
+            errors: HashMap::new(),
+            focus_handle: cx.focus_handle(),
+        }
+    }
+
+    /// Create a single-line input that applies `field` as it changes.
+    fn text_input(
+        field: Field,
+        value: String,
+        placeholder: &'static str,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Entity<InputState> {
+        let input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .multi_line(false)
+                .placeholder(placeholder)
+                .default_value(value)
+        });
+        cx.subscribe_in(
+            &input,
+            window,
+            move |this, state, event: &InputEvent, _window, cx| {
+                if matches!(event, InputEvent::Change) {
+                    let value = state.read(cx).value().to_string();
+                    this.apply(field, &value, cx);
+                }
+            },
+        )
+        .detach();
+        input
+    }
+
+    /// Validate `value` and, if it is accepted, store and emit it.
+    fn apply(&mut self, field: Field, value: &str, cx: &mut Context<Self>) {
+        let result = match field {
+            Field::WordSeparators => parse_word_separators(value)
+                .map(|separators| self.config.word_separators = separators),
+            Field::ExternalEditor => {
+                parse_editor_command(value).map(|command| self.config.external_editor = command)
+            }
+            Field::PtyLogDir => parse_log_dir(value).map(|dir| self.config.pty_log_dir = dir),
+        };
+        match result {
+            Ok(()) => {
+                self.errors.remove(&field);
+                cx.emit(Event::Changed(self.config.clone()));
+            }
+            Err(error) => {
+                self.errors.insert(field, error);
+            }
+        }
+        cx.notify();
+    }
+
+    /// Flip a boolean setting and emit the result.
+    fn toggle(&mut self, setting: fn(&mut AppConfig) -> &mut bool, cx: &mut Context<Self>) {
+        let value = setting(&mut self.config);
+        *value = !*value;
+        cx.emit(Event::Changed(self.config.clone()));
+        cx.notify();
+    }
+
+    /// Focus the first field.
+    pub fn focus(&self, window: &mut Window, cx: &mut Context<Self>) {
+        self.word_separators.focus_handle(cx).focus(window, cx);
+    }
+
+    fn on_key_down(&mut self, event: &KeyDownEvent, _window: &mut Window, cx: &mut Context<Self>) {
+        if event.keystroke.key == "escape" {
+            cx.emit(Event::Dismissed);
+        }
+    }
+
+    fn render_section(title: &'static str) -> impl IntoElement {
+        div()
+            .pt(px(12.0))
+            .pb(px(4.0))
+            .font_family("IBM Plex Sans")
+            .text_color(rgba(0x78859bff))
+            .text_size(px(11.0))
+            .font_weight(gpui::FontWeight::BOLD)
+            .child(title)
+    }
+
+    /// Render a labelled row with `control` on the right.
+    fn render_row(label: &'static str, control: impl IntoElement) -> gpui::Div {
+        div()
+            .flex()
+            .items_center()
+            .justify_between()
+            .gap(px(12.0))
+            .py(px(4.0))
+            .child(
+                div()
+                    .font_family("IBM Plex Sans")
+                    .text_color(rgba(0xc2d6eaff))
+                    .text_size(px(13.0))
+                    .child(label),
+            )
+            .child(control)
+    }
+
+    /// Render an On/Off button row for a boolean setting.
+    fn render_toggle(
+        id: &'static str,
+        label: &'static str,
+        on: bool,
+        setting: fn(&mut AppConfig) -> &mut bool,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        Self::render_row(
+            label,
+            Button::new(id)
+                .label(if on { "On" } else { "Off" })
+                .xsmall()
+                .rounded(ButtonRounded::Small)
+                .with_variant(if on {
+                    ButtonVariant::Primary
+                } else {
+                    ButtonVariant::Ghost
+                })
+                .font_family("IBM Plex Sans")
+                .on_click(cx.listener(move |this, _, _window, cx| {
+                    this.toggle(setting, cx);
+                })),
+        )
+    }
+
+    fn render_text_field(
+        &self,
+        field: Field,
+        label: &'static str,
+        input: &Entity<InputState>,
+    ) -> impl IntoElement {
+        let error = self.errors.get(&field).cloned();
+        div()
+            .flex()
+            .flex_col()
+            .child(Self::render_row(
+                label,
+                div()
+                    .w(px(240.0))
+                    .px(px(6.0))
+                    .rounded(px(3.0))
+                    .border_1()
+                    .border_color(if error.is_some() {
+                        TerminalColors::ansi_red()
+                    } else {
+                        rgba(0x2d333aff)
+                    })
+                    .child(Input::new(input).appearance(false).w_full()),
+            ))
+            .when_some(error, |d, error| {
+                d.child(
+                    div()
+                        .font_family("IBM Plex Sans")
+                        .text_color(TerminalColors::ansi_red())
+                        .text_size(px(11.0))
+                        .child(error),
+                )
+            })
+    }
+}
+
+impl EventEmitter<Event> for SettingsView {}
+
+impl Render for SettingsView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .id("settings")
+            .track_focus(&self.focus_handle)
+            .on_key_down(cx.listener(Self::on_key_down))
+            .w(px(480.0))
+            .p(px(16.0))
+            .flex()
+            .flex_col()
+            .rounded(px(6.0))
+            .border_1()
+            .border_color(rgba(0x2d333aff))
+            .bg(rgba(0x1d2228ff))
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .child(
+                        div()
+                            .font_family("IBM Plex Sans")
+                            .text_color(rgba(0xc2d6eaff))
+                            .text_size(px(14.0))
+                            .font_weight(gpui::FontWeight::BOLD)
+                            .child("Settings"),
+                    )
+                    .child(
+                        Button::new("settings-done")
+                            .label("Done")
+                            .xsmall()
+                            .rounded(ButtonRounded::Small)
+                            .with_variant(ButtonVariant::Primary)
+                            .font_family("IBM Plex Sans")
+                            .on_click(cx.listener(|_this, _, _window, cx| {
+                                cx.emit(Event::Dismissed);
+                            })),
+                    ),
+            )
+            .child(Self::render_section("TERMINAL"))
+            .child(self.render_text_field(
+                Field::WordSeparators,
+                "Word separators",
+                &self.word_separators,
+            ))
+            .child(Self::render_toggle(
+                "settings-bold-is-bright",
+                "Bold text in bright colors",
+                self.config.bold_is_bright,
+                |config| &mut config.bold_is_bright,
+                cx,
+            ))
+            .child(Self::render_toggle(
+                "settings-clipboard-writes",
+                "Programs can set the clipboard",
+                self.config.terminal_clipboard_writes,
+                |config| &mut config.terminal_clipboard_writes,
+                cx,
+            ))
+            .child(Self::render_toggle(
+                "settings-option-as-meta",
+                "Option key sends Meta",
+                self.config.terminal_option_as_meta,
+                |config| &mut config.terminal_option_as_meta,
+                cx,
+            ))
+            .child(self.render_text_field(Field::PtyLogDir, "PTY log directory", &self.pty_log_dir))
+            .child(Self::render_section("EDITOR"))
+            .child(self.render_text_field(
+                Field::ExternalEditor,
+                "External editor",
+                &self.external_editor,
+            ))
+    }
+}
+
+/// Word separators for double-click selection; blank uses the terminal default.
+fn parse_word_separators(value: &str) -> Result<Option<String>, String> {
+    if value.is_empty() {
+        return Ok(None);
+    }
+    if value.chars().any(char::is_alphanumeric) {
+        return Err("Separators can't include letters or digits".into());
+    }
+    Ok(Some(value.to_string()))
+}
+
+/// External editor command; blank falls back to `$VISUAL`/`$EDITOR`.
+fn parse_editor_command(value: &str) -> Result<Option<String>, String> {
+    let Some(program) = value.split_whitespace().next() else {
+        return Ok(None);
+    };
+    if !program_exists(program) {
+        return Err(format!("'{}' was not found", program));
+    }
+    Ok(Some(value.trim().to_string()))
+}
+
+/// Whether `program` is a file, given as a path or found on `PATH`.
+fn program_exists(program: &str) -> bool {
+    if program.contains(std::path::MAIN_SEPARATOR) {
+        return Path::new(program).is_file();
+    }
+    std::env::var_os("PATH")
+        .is_some_and(|paths| std::env::split_paths(&paths).any(|dir| dir.join(program).is_file()))
+}
+
+/// PTY log directory; blank turns logging off.
+fn parse_log_dir(value: &str) -> Result<Option<PathBuf>, String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(None);
+    }
+    let dir = PathBuf::from(value);
+    if !dir.is_absolute() {
+        return Err("Use an absolute path".into());
+    }
+    Ok(Some(dir))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn word_separators_reject_word_characters() {
+        assert_eq!(parse_word_separators(""), Ok(None));
+        assert_eq!(parse_word_separators(" ,;"), Ok(Some(" ,;".to_string())));
+        assert!(parse_word_separators(" a").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn editor_command_must_name_an_existing_program() {
+        assert_eq!(parse_editor_command("  "), Ok(None));
+        assert_eq!(
+            parse_editor_command(" sh -c true "),
+            Ok(Some("sh -c true".to_string()))
+        );
+        assert!(parse_editor_command("manifest-no-such-editor --wait").is_err());
+    }
+
+    #[test]
+    fn log_dir_must_be_absolute() {
+        assert_eq!(parse_log_dir(""), Ok(None));
+        assert!(parse_log_dir("logs").is_err());
+    }
+}
@@ -0,0 +1,127 @@
+//! Status bar along the bottom of the window.
+//!
+//! Shows which project is loaded, how many features it has, and whether the
+//! Manifest server answers its health check. The app reads features from the
+//! database directly but saves edits through the server, so a stopped server
+//! otherwise only shows up as a failed save.
+
+use gpui::{Context, IntoElement, ParentElement, Render, Rgba, Styled, Window, div, px, rgba};
+use terminal::mappings::colors::TerminalColors;
+
+/// Whether the Manifest server answered the last health check.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Connection {
+    /// No health check has finished yet.
+    #[default]
+    Unknown,
+    Connected,
+    Disconnected,
+}
+
+impl Connection {
+    fn color(self) -> Rgba {
+        match self {
+            Self::Unknown => TerminalColors::ansi_bright_black(),
+            Self::Connected => TerminalColors::ansi_green(),
+            Self::Disconnected => TerminalColors::ansi_red(),
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Unknown => "Connecting",
+            Self::Connected => "Server connected",
+            Self::Disconnected => "Server unreachable",
+        }
+    }
+}
+
+/// One-line summary of the project and server state.
+#[derive(Default)]
+pub struct StatusBar {
+    project_name: Option<String>,
+    feature_count: usize,
+    connection: Connection,
+}
+
+impl StatusBar {
+    /// Show the loaded project and count its features, including nested ones.
+    pub fn set_project(
+        &mut self,
+        name: Option<String>,
+        features: &[manifest_client::Feature],
+        cx: &mut Context<Self>,
+    ) {
+        self.project_name = name;
+        self.feature_count = count_features(features);
+        cx.notify();
+    }
+
+    pub fn set_connection(&mut self, connection: Connection, cx: &mut Context<Self>) {
+        if self.connection != connection {
+            self.connection = connection;
+            cx.notify();
+        }
+    }
+}
+
+impl Render for StatusBar {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        let project = self
+            .project_name
+            .clone()
+            .unwrap_or_else(|| "No project".to_string());
+        let features = match self.feature_count {
+            1 => "1 feature".to_string(),
+            n => format!("{} features", n),
+        };
+
+        div()
+            .h(px(22.0))
+            .w_full()
+            .flex_none()
+            .px(px(12.0))
+            .flex()
+            .items_center()
+            .justify_between()
+            .border_t_1()
+            .border_color(rgba(0x2d333aff))
+            .bg(TerminalColors::background())
+            .font_family("IBM Plex Sans")
+            .text_size(px(11.0))
+            .text_color(TerminalColors::ansi_bright_black())
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap(px(12.0))
+                    .child(
+                        div()
+                            .text_color(TerminalColors::foreground())
+                            .child(project),
+                    )
+                    .child(features),
+            )
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap(px(6.0))
+                    .child(
+                        div()
+                            .w(px(7.0))
+                            .h(px(7.0))
+                            .rounded_full()
+                            .bg(self.connection.color()),
+                    )
+                    .child(self.connection.label()),
+            )
+    }
+}
+
+fn count_features(features: &[manifest_client::Feature]) -> usize {
+    features
+        .iter()
+        .map(|feature| 1 + count_features(&feature.children))
+        .sum()
+}
@@ -0,0 +1,150 @@
+//! Bare URL detection for rendered details.
+//!
+//! The markdown renderer only links URLs written as `[text](url)` or
+//! `<url>`. Specs often paste plain `https://` addresses, so before
+//! rendering those are wrapped in angle brackets to make them autolinks.
+//! Code spans and fenced code blocks are left as written.
+
+/// Wrap bare `http://` and `https://` URLs in `text` as markdown autolinks.
+pub fn autolink(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut fence: Option<&str> = None;
+    for line in text.split_inclusive('\n') {
+        let trimmed = line.trim_start();
+        let marker = ["```", "~~~"]
+            .into_iter()
+            .find(|marker| trimmed.starts_with(marker));
+        match (fence, marker) {
+            (None, Some(marker)) => {
+                fence = Some(marker);
+                out.push_str(line);
+            }
+            (Some(open), Some(marker)) if open == marker => {
+                fence = None;
+                out.push_str(line);
+            }
+            (Some(_), _) => out.push_str(line),
+            (None, None) => autolink_line(line, &mut out),
+        }
+    }
+    out
+}
+
+fn autolink_line(line: &str, out: &mut String) {
+    let mut rest = line;
+    while let Some(c) = rest.chars().next() {
+        // Copy code spans, existing autolinks/HTML and link destinations verbatim.
+        let verbatim = match c {
+            '`' => Some(code_span_len(rest)),
+            '<' if rest[1..].starts_with(|c: char| c.is_ascii_alphabetic() || c == '/') => {
+                rest.find('>').map(|end| end + 1)
+            }
+            ']' if rest.starts_with("](") => rest.find(')').map(|end| end + 1),
+            _ => None,
+        };
+        if let Some(len) = verbatim {
+            out.push_str(&rest[..len]);
+            rest = &rest[len..];
+            continue;
+        }
+
+        if at_word_start(out)
+            && let Some(len) = bare_url_len(rest)
+        {
+            out.push('<');
+            out.push_str(&rest[..len]);
+            out.push('>');
+            rest = &rest[len..];
+            continue;
+        }
+
+        out.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+}
+
+/// Whether a URL may start here, so `[https://...](...)` and the middle of
+/// words are left alone.
+fn at_word_start(out: &str) -> bool {
+    out.chars()
+        .last()
+        .is_none_or(|c| c.is_whitespace() || matches!(c, '(' | '*' | '_'))
+}
+
+/// Length of the code span opening `s`, or of its backtick run if unclosed.
+fn code_span_len(s: &str) -> usize {
+    let ticks = s.len() - s.trim_start_matches('`').len();
+    let mut pos = ticks;
+    while let Some(offset) = s[pos..].find('`') {
+        let start = pos + offset;
+        let run = s[start..].len() - s[start..].trim_start_matches('`').len();
+        if run == ticks {
+            return start + run;
+        }
+        pos = start + run;
+    }
+    ticks
+}
+
+/// Length of the URL starting `s`, without trailing punctuation.
+fn bare_url_len(s: &str) -> Option<usize> {
+    let scheme = ["https://", "http://"]
+        .into_iter()
+        .find(|scheme| s.starts_with(scheme))?;
+    let end = s
+        .find(|c: char| c.is_whitespace() || c == '<')
+        .unwrap_or(s.len());
+    let mut url = &s[..end];
+    loop {
+        let mut trimmed = url.trim_end_matches(['.', ',', ':', ';', '!', '?', '\'', '"', '*', '_']);
+        // Keep a closing paren only if the URL opened one, as in Wikipedia links.
+        if trimmed.ends_with(')') && trimmed.matches(')').count() > trimmed.matches('(').count() {
+            trimmed = &trimmed[..trimmed.len() - 1];
+        }
+        if trimmed.len() == url.len() {
+            break;
+        }
+        url = trimmed;
+    }
+    (url.len() > scheme.len()).then_some(url.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wraps_bare_urls_without_trailing_punctuation() {
+        assert_eq!(
+            autolink("See https://example.com/docs. Or (http://a.io/x), please"),
+            "See <https://example.com/docs>. Or (<http://a.io/x>), please"
+        );
+        assert_eq!(
+            autolink("https://en.wikipedia.org/wiki/Rust_(language)!"),
+            "<https://en.wikipedia.org/wiki/Rust_(language)>!"
+        );
+        assert_eq!(autolink("just https:// here"), "just https:// here");
+    }
+
+    #[test]
+    fn leaves_links_and_code_alone() {
+        let text = "\
+[https://a.io](https://a.io) and <https://b.io>
+Run `curl https://c.io` first
+```
+https://d.io
+```
+";
+        assert_eq!(autolink(text), text);
+    }
+
+    #[test]
+    fn handles_parentheses_and_list_items() {
+        let linked = "- [Rust](https://en.wikipedia.org/wiki/Rust_(language)) docs\n";
+        assert_eq!(autolink(linked), linked);
+        assert_eq!(
+            autolink("- see https://a.io/x_(y)\n* https://b.io\n"),
+            "- see <https://a.io/x_(y)>\n* <https://b.io>\n"
+        );
+    }
+}
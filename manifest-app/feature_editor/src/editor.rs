@@ -1,18 +1,47 @@
+use std::ops::Range;
+use std::path::PathBuf;
+
 use gpui::{
-    actions, div, prelude::*, px, App, Context, Entity, FocusHandle, Focusable, KeyBinding,
-    SharedString, Window,
+    actions, div, prelude::*, px, App, AsyncWindowContext, Context, Entity, EntityInputHandler,
+    FocusHandle, Focusable, KeyBinding, KeyDownEvent, SharedString, WeakEntity, Window,
 };
 use gpui_component::{
     button::{Button, ButtonRounded, ButtonVariant, ButtonVariants},
-    input::{Input, InputEvent, InputState},
+    input::{Input, InputEvent, InputState, Position},
     text::markdown,
     ActiveTheme, Sizable,
 };
-use manifest_client::{ManifestClient, Session, SessionStatus, Task, TaskStatus};
+use keymap::Keymap;
+use manifest_client::{
+    ClientError, Feature, FeatureState, ManifestClient, Session, SessionStatus, Task, TaskStatus,
+};
 use uuid::Uuid;
 
+use crate::autolink::autolink;
+use crate::external_editor::{self, ExternalEditError};
+use crate::find;
+use crate::go_to_line;
+use crate::indent::{self, DEFAULT_INDENT_WIDTH};
+use crate::task_list;
+
 // Define editor actions
-actions!(feature_editor, [Save, Edit, Cancel]);
+actions!(
+    feature_editor,
+    [
+        Save,
+        Edit,
+        Cancel,
+        OpenInExternalEditor,
+        CopyAsMarkdown,
+        Find,
+        FindNext,
+        FindPrevious,
+        ToggleTask,
+        Indent,
+        Outdent,
+        GoToLine
+    ]
+);
 
 /// Events emitted by the FeatureEditor.
 #[derive(Clone, Debug)]
@@ -21,6 +50,32 @@ pub enum Event {
     FeatureSaved(Uuid),
     /// Save failed with error message.
     SaveFailed(Uuid, String),
+    /// Save was refused because the feature changed on the server.
+    SaveConflict(Uuid),
+    /// Local edits were discarded and the feature reloaded from the server.
+    Reloaded(Uuid),
+    /// User asked for a shell in a task's worktree.
+    OpenWorktree {
+        task_id: Uuid,
+        title: String,
+        path: PathBuf,
+    },
+    /// The feature's tags were saved.
+    TagsChanged(Uuid, Vec<String>),
+    /// User clicked a linked feature, e.g. a dependency.
+    OpenFeature(Uuid),
+    /// The external editor could not be run.
+    ExternalEditFailed(Uuid, String),
+    /// User asked to copy the feature to the clipboard as Markdown.
+    CopyAsMarkdown(Uuid),
+}
+
+/// Another feature shown by reference, such as a dependency.
+#[derive(Clone, Debug)]
+pub struct LinkedFeature {
+    pub id: Uuid,
+    pub title: String,
+    pub state: FeatureState,
 }
 
 /// Colors for the editor (Pigs in Space theme).
@@ -115,6 +170,26 @@ mod colors {
         }
     }
 
+    pub fn conflict_background() -> Hsla {
+        // Muted amber banner for save conflicts
+        Hsla {
+            h: 40.0 / 360.0,
+            s: 0.45,
+            l: 0.22,
+            a: 1.0,
+        }
+    }
+
+    pub fn tag_background() -> Rgba {
+        // Matches the feature panel's tag chips (#2d333a)
+        Rgba {
+            r: 0.176,
+            g: 0.200,
+            b: 0.227,
+            a: 1.0,
+        }
+    }
+
     pub fn dirty_indicator() -> Hsla {
         // Yellow/amber for dirty state
         Hsla {
@@ -126,12 +201,34 @@ mod colors {
     }
 }
 
+/// How the details are shown while editing.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum DetailsView {
+    /// Raw markdown input only.
+    #[default]
+    Edit,
+    /// Rendered markdown of the current input.
+    Preview,
+}
+
+/// Find/replace bar over the details input.
+struct FindBar {
+    query: Entity<InputState>,
+    replacement: Entity<InputState>,
+    case_sensitive: bool,
+    /// Matches of the query in the details, refreshed as either changes.
+    matches: Vec<Range<usize>>,
+    /// Index into `matches` of the match the cursor was last moved to.
+    current: Option<usize>,
+}
+
 /// A feature waiting to be opened (set from async context, opened in render).
 #[derive(Clone)]
 struct PendingFeature {
     id: Uuid,
     title: String,
     details: Option<String>,
+    updated_at: String,
 }
 
 /// Single-feature editor view with title, details, and tasks panel.
@@ -146,6 +243,10 @@ pub struct FeatureEditor {
     original_title: SharedString,
     /// Original details for dirty detection.
     original_details: SharedString,
+    /// `updated_at` of the feature as loaded, sent with saves to detect concurrent edits.
+    loaded_version: Option<String>,
+    /// Last save was rejected because the feature changed elsewhere.
+    save_conflict: bool,
     /// Is title dirty?
     title_dirty: bool,
     /// Is details dirty?
@@ -162,6 +263,28 @@ pub struct FeatureEditor {
     client: ManifestClient,
     /// Feature pending to be opened (set from async, opened in render with window access).
     pending_feature: Option<PendingFeature>,
+    /// Tags on the current feature.
+    tags: Vec<String>,
+    /// Tags used elsewhere in the project, offered as completions.
+    known_tags: Vec<String>,
+    /// Input for adding a tag while editing.
+    tag_input: Option<Entity<InputState>>,
+    /// Features the current feature depends on.
+    blocked_by: Vec<LinkedFeature>,
+    /// Features that depend on the current feature.
+    blocks: Vec<LinkedFeature>,
+    /// Configured external editor command; falls back to `$VISUAL`/`$EDITOR`.
+    editor_command: Option<String>,
+    /// An external editor is open on the current feature's details.
+    external_edit_running: bool,
+    /// Edit or preview view of the details while editing.
+    details_view: DetailsView,
+    /// Find/replace bar over the details, while open.
+    find_bar: Option<FindBar>,
+    /// Go-to-line prompt over the details, while open.
+    go_to_line: Option<Entity<InputState>>,
+    /// Spaces Tab inserts in the details, and Shift+Tab removes.
+    indent_width: usize,
 }
 
 impl FeatureEditor {
@@ -173,6 +296,8 @@ impl FeatureEditor {
             details_input: None,
             original_title: "".into(),
             original_details: "".into(),
+            loaded_version: None,
+            save_conflict: false,
             title_dirty: false,
             details_dirty: false,
             is_editing: false,
@@ -181,15 +306,37 @@ impl FeatureEditor {
             focus_handle: cx.focus_handle(),
             client: ManifestClient::localhost(),
             pending_feature: None,
+            tags: Vec::new(),
+            known_tags: Vec::new(),
+            tag_input: None,
+            blocked_by: Vec::new(),
+            blocks: Vec::new(),
+            editor_command: None,
+            external_edit_running: false,
+            details_view: DetailsView::default(),
+            find_bar: None,
+            go_to_line: None,
+            indent_width: DEFAULT_INDENT_WIDTH,
         }
     }
 
+    /// Set the command used to open details in an external editor.
+    pub fn set_editor_command(&mut self, command: Option<String>) {
+        self.editor_command = command;
+    }
+
+    /// Set the number of spaces Tab indents the details by.
+    pub fn set_indent_width(&mut self, width: usize) {
+        self.indent_width = width.max(1);
+    }
+
     /// Queue a feature to be opened (can be called from async context without window).
     pub fn load_feature(
         &mut self,
         feature_id: Uuid,
         title: String,
         details: Option<String>,
+        updated_at: String,
         cx: &mut Context<Self>,
     ) {
         // Queue for opening in render (when we have window access)
@@ -197,16 +344,48 @@ impl FeatureEditor {
             id: feature_id,
             title,
             details,
+            updated_at,
         });
         cx.notify();
     }
 
+    /// Set the current feature's tags and the project's tags to complete from.
+    /// Tags are loaded separately from the feature, so this follows `load_feature`.
+    pub fn set_tags(&mut self, tags: Vec<String>, known_tags: Vec<String>, cx: &mut Context<Self>) {
+        self.tags = tags;
+        self.known_tags = known_tags;
+        cx.notify();
+    }
+
+    /// Move keyboard focus into the editor: the details input while editing,
+    /// otherwise the editor itself so its shortcuts apply.
+    pub fn focus(&self, window: &mut Window, cx: &mut App) {
+        let handle = match &self.details_input {
+            Some(input) if self.is_editing => input.focus_handle(cx),
+            _ => self.focus_handle.clone(),
+        };
+        handle.focus(window, cx);
+    }
+
+    /// Set the current feature's dependencies in both directions.
+    pub fn set_dependencies(
+        &mut self,
+        blocked_by: Vec<LinkedFeature>,
+        blocks: Vec<LinkedFeature>,
+        cx: &mut Context<Self>,
+    ) {
+        self.blocked_by = blocked_by;
+        self.blocks = blocks;
+        cx.notify();
+    }
+
     /// Open a feature for editing.
     fn open_feature(
         &mut self,
         feature_id: Uuid,
         title: String,
         details: Option<String>,
+        updated_at: String,
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
@@ -227,6 +406,28 @@ impl FeatureEditor {
                 .default_value(details_str.clone())
         });
 
+        // Create tag input (single line, Enter adds the tag)
+        let tag_input = cx.new(|cx| {
+            InputState::new(window, cx)
+                .multi_line(false)
+                .placeholder("Add tag")
+        });
+
+        // Subscribe to tag input changes: typing refreshes completions
+        cx.subscribe_in(
+            &tag_input,
+            window,
+            |this, state, event: &InputEvent, window, cx| match event {
+                InputEvent::Change => cx.notify(),
+                InputEvent::PressEnter { .. } => {
+                    let tag = state.read(cx).value().to_string();
+                    this.add_tag(tag, window, cx);
+                }
+                _ => {}
+            },
+        )
+        .detach();
+
         // Subscribe to title input changes
         let title_entity = title_input.clone();
         cx.subscribe_in(
@@ -255,6 +456,7 @@ impl FeatureEditor {
                     if let Some(ref input) = this.details_input {
                         if *input == details_entity {
                             this.update_details_dirty(cx);
+                            this.refresh_find_matches(cx);
                             cx.notify();
                         }
                     }
@@ -266,8 +468,13 @@ impl FeatureEditor {
         self.feature_id = Some(feature_id);
         self.title_input = Some(title_input);
         self.details_input = Some(details_input);
+        self.find_bar = None;
+        self.go_to_line = None;
+        self.tag_input = Some(tag_input);
         self.original_title = title_str.into();
         self.original_details = details_str.into();
+        self.loaded_version = Some(updated_at);
+        self.save_conflict = false;
         self.title_dirty = false;
         self.details_dirty = false;
         self.is_editing = false;
@@ -321,6 +528,11 @@ impl FeatureEditor {
         self.feature_id.is_some()
     }
 
+    /// The loaded feature's ID.
+    pub fn feature_id(&self) -> Option<Uuid> {
+        self.feature_id
+    }
+
     /// Check if content is dirty.
     pub fn is_dirty(&self) -> bool {
         self.title_dirty || self.details_dirty
@@ -343,7 +555,21 @@ impl FeatureEditor {
     }
 
     /// Save the current feature.
+    ///
+    /// The save carries the version the editor loaded, so a concurrent edit
+    /// from an agent or another client is reported as a conflict instead of
+    /// being overwritten.
     pub fn save_current(&mut self, _window: &mut Window, cx: &mut Context<Self>) {
+        let expected_version = self.loaded_version.clone();
+        self.save(expected_version, cx);
+    }
+
+    /// Save the current feature, replacing whatever was stored elsewhere.
+    fn overwrite(&mut self, cx: &mut Context<Self>) {
+        self.save(None, cx);
+    }
+
+    fn save(&mut self, expected_version: Option<String>, cx: &mut Context<Self>) {
         let Some(feature_id) = self.feature_id else {
             return;
         };
@@ -365,25 +591,260 @@ impl FeatureEditor {
             .map(|i| i.read(cx).value().to_string());
 
         let client = self.client.clone();
+        let background = cx.background_executor().clone();
+
+        // Save in background, then reconcile editor state with the result
+        cx.spawn(async move |this, cx| {
+            let result = background
+                .spawn(async move {
+                    client.update_feature_full(
+                        &feature_id,
+                        title,
+                        details,
+                        expected_version.as_deref(),
+                    )
+                })
+                .await;
+
+            if let Some(this) = this.upgrade() {
+                cx.update_entity(&this, |this: &mut FeatureEditor, cx| {
+                    this.finish_save(feature_id, result, cx);
+                });
+            }
+        })
+        .detach();
+    }
+
+    /// Apply the outcome of a background save.
+    fn finish_save(
+        &mut self,
+        feature_id: Uuid,
+        result: Result<Feature, ClientError>,
+        cx: &mut Context<Self>,
+    ) {
+        // Ignore results for a feature the user has since navigated away from
+        if self.feature_id != Some(feature_id) {
+            return;
+        }
+
+        match result {
+            Ok(feature) => {
+                self.original_title = feature.title.into();
+                self.original_details = feature.details.unwrap_or_default().into();
+                self.loaded_version = Some(feature.updated_at);
+                self.save_conflict = false;
+                self.update_title_dirty(cx);
+                self.update_details_dirty(cx);
+                self.is_editing = false;
+                cx.emit(Event::FeatureSaved(feature_id));
+            }
+            Err(ClientError::Conflict(_)) => {
+                // Keep the user's edits; the banner offers reload or overwrite
+                self.save_conflict = true;
+                cx.emit(Event::SaveConflict(feature_id));
+            }
+            Err(e) => {
+                cx.emit(Event::SaveFailed(feature_id, e.to_string()));
+            }
+        }
+        cx.notify();
+    }
+
+    /// Discard local edits and reload the feature from the server.
+    fn reload_feature(&mut self, cx: &mut Context<Self>) {
+        let Some(feature_id) = self.feature_id else {
+            return;
+        };
+        cx.emit(Event::Reloaded(feature_id));
+
+        let client = self.client.clone();
+        let background = cx.background_executor().clone();
+
+        cx.spawn(async move |this, cx| {
+            let result = background
+                .spawn(async move { client.get_feature(&feature_id) })
+                .await;
+
+            if let Ok(Some(feature)) = result {
+                if let Some(this) = this.upgrade() {
+                    cx.update_entity(&this, |this: &mut FeatureEditor, cx| {
+                        this.load_feature(
+                            feature.id,
+                            feature.title,
+                            feature.details,
+                            feature.updated_at,
+                            cx,
+                        );
+                    });
+                }
+            }
+        })
+        .detach();
+    }
 
-        // Update originals optimistically
-        if let Some(ref t) = title {
-            self.original_title = t.clone().into();
+    /// Add a tag to the current feature and clear the tag input.
+    fn add_tag(&mut self, tag: String, window: &mut Window, cx: &mut Context<Self>) {
+        let tag = tag.trim().to_lowercase();
+        if let Some(ref tag_input) = self.tag_input {
+            tag_input.update(cx, |state, cx| state.set_value("", window, cx));
         }
-        if let Some(ref d) = details {
-            self.original_details = d.clone().into();
+        if tag.is_empty() || self.tags.contains(&tag) {
+            return;
         }
-        self.title_dirty = false;
-        self.details_dirty = false;
-        self.is_editing = false;
+        let mut tags = self.tags.clone();
+        tags.push(tag);
+        self.save_tags(tags, cx);
+    }
+
+    /// Remove a tag from the current feature.
+    fn remove_tag(&mut self, tag: &str, cx: &mut Context<Self>) {
+        let tags = self.tags.iter().filter(|t| *t != tag).cloned().collect();
+        self.save_tags(tags, cx);
+    }
+
+    /// Save tags immediately; unlike title and details they have no draft state.
+    fn save_tags(&mut self, tags: Vec<String>, cx: &mut Context<Self>) {
+        let Some(feature_id) = self.feature_id else {
+            return;
+        };
+
+        let client = self.client.clone();
+        let background = cx.background_executor().clone();
+
+        cx.spawn(async move |this, cx| {
+            let result = background
+                .spawn(async move { client.set_feature_tags(&feature_id, &tags) })
+                .await;
+
+            if let Some(this) = this.upgrade() {
+                cx.update_entity(&this, |this: &mut FeatureEditor, cx| {
+                    match result {
+                        Ok(tags) => {
+                            if this.feature_id == Some(feature_id) {
+                                for tag in &tags {
+                                    if !this.known_tags.contains(tag) {
+                                        this.known_tags.push(tag.clone());
+                                    }
+                                }
+                                this.known_tags.sort();
+                                this.tags = tags.clone();
+                            }
+                            cx.emit(Event::TagsChanged(feature_id, tags));
+                        }
+                        Err(e) => cx.emit(Event::SaveFailed(feature_id, e.to_string())),
+                    }
+                    cx.notify();
+                });
+            }
+        })
+        .detach();
+    }
+
+    /// Known tags starting with what has been typed into the tag input,
+    /// excluding tags the feature already has.
+    fn tag_completions(&self, cx: &App) -> Vec<String> {
+        let Some(ref tag_input) = self.tag_input else {
+            return Vec::new();
+        };
+        let prefix = tag_input.read(cx).value().trim().to_lowercase();
+        if prefix.is_empty() {
+            return Vec::new();
+        }
+        self.known_tags
+            .iter()
+            .filter(|tag| tag.starts_with(&prefix) && !self.tags.contains(tag))
+            .take(5)
+            .cloned()
+            .collect()
+    }
+
+    /// Edit the details in the user's editor, then save what comes back.
+    ///
+    /// Starts from the details input, so unsaved edits carry over. If the
+    /// editor exits without changing the file, nothing is saved, and if the
+    /// input was edited meanwhile the result is refused rather than
+    /// overwriting it.
+    fn open_in_external_editor(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(feature_id) = self.feature_id else {
+            return;
+        };
+        if self.external_edit_running {
+            return;
+        }
+        let Some(command) = external_editor::editor_command(self.editor_command.as_deref()) else {
+            cx.emit(Event::ExternalEditFailed(
+                feature_id,
+                ExternalEditError::NoEditor.to_string(),
+            ));
+            return;
+        };
+        let content = self
+            .details_input
+            .as_ref()
+            .map(|i| i.read(cx).value().to_string())
+            .unwrap_or_default();
+
+        self.external_edit_running = true;
         cx.notify();
 
-        // Save in background
-        cx.background_executor()
-            .spawn(async move { client.update_feature_full(&feature_id, title, details) })
-            .detach_and_log_err(cx);
+        let sent = content.clone();
+        let task = cx.background_executor().spawn(async move {
+            external_editor::edit(&command, &feature_id.to_string(), &content)
+        });
+        cx.spawn_in(
+            window,
+            async move |this: WeakEntity<Self>, cx: &mut AsyncWindowContext| {
+                let result = task.await;
+                this.update_in(cx, |this, window, cx| {
+                    this.finish_external_edit(feature_id, &sent, result, window, cx);
+                })
+                .ok();
+            },
+        )
+        .detach();
+    }
+
+    /// Import the outcome of an external edit into the details and save it.
+    /// `sent` is the details the editor was opened on.
+    fn finish_external_edit(
+        &mut self,
+        feature_id: Uuid,
+        sent: &str,
+        result: Result<Option<String>, ExternalEditError>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.external_edit_running = false;
+        cx.notify();
+
+        // Ignore results for a feature the user has since navigated away from
+        if self.feature_id != Some(feature_id) {
+            return;
+        }
+
+        let current = self
+            .details_input
+            .as_ref()
+            .map(|i| i.read(cx).value().to_string())
+            .unwrap_or_default();
+        let result = match result {
+            Ok(Some(_)) if current != sent => Err(ExternalEditError::DetailsChanged),
+            result => result,
+        };
 
-        cx.emit(Event::FeatureSaved(feature_id));
+        match result {
+            Ok(Some(details)) => {
+                if let Some(ref details_input) = self.details_input {
+                    details_input.update(cx, |state, cx| state.set_value(details, window, cx));
+                }
+                self.update_details_dirty(cx);
+                self.is_editing = true;
+                self.save_current(window, cx);
+            }
+            // Quit without saving
+            Ok(None) => {}
+            Err(e) => cx.emit(Event::ExternalEditFailed(feature_id, e.to_string())),
+        }
     }
 
     /// Enter edit mode.
@@ -408,59 +869,427 @@ impl FeatureEditor {
         self.title_dirty = false;
         self.details_dirty = false;
         self.is_editing = false;
+        self.find_bar = None;
+        self.go_to_line = None;
         cx.notify();
     }
 
-    // --- Action handlers ---
+    /// Open the find bar over the details, or close it if it's open.
+    /// Only available while editing, since it searches the details input.
+    fn toggle_find(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.find_bar.take().is_some() {
+            self.focus(window, cx);
+            cx.notify();
+            return;
+        }
+        if !self.is_editing || self.details_input.is_none() {
+            return;
+        }
 
-    fn on_save(&mut self, _: &Save, window: &mut Window, cx: &mut Context<Self>) {
-        self.save_current(window, cx);
+        let query = cx.new(|cx| {
+            InputState::new(window, cx)
+                .multi_line(false)
+                .placeholder("Find")
+        });
+        let replacement = cx.new(|cx| {
+            InputState::new(window, cx)
+                .multi_line(false)
+                .placeholder("Replace")
+        });
+        // Typing searches as you go; Enter moves to the next match
+        cx.subscribe_in(
+            &query,
+            window,
+            |this, _state, event: &InputEvent, window, cx| match event {
+                InputEvent::Change => {
+                    if let Some(bar) = this.find_bar.as_mut() {
+                        bar.current = None;
+                    }
+                    this.refresh_find_matches(cx);
+                    cx.notify();
+                }
+                InputEvent::PressEnter { .. } => this.step_match(true, window, cx),
+                _ => {}
+            },
+        )
+        .detach();
+        query.focus_handle(cx).focus(window, cx);
+
+        self.find_bar = Some(FindBar {
+            query,
+            replacement,
+            case_sensitive: false,
+            matches: Vec::new(),
+            current: None,
+        });
+        cx.notify();
     }
 
-    fn on_edit(&mut self, _: &Edit, _window: &mut Window, cx: &mut Context<Self>) {
-        self.enter_edit_mode(cx);
-    }
+    /// Open the go-to-line prompt over the details, or close it if it's open.
+    /// Like find, it needs the details input, so only works while editing.
+    fn toggle_go_to_line(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.go_to_line.take().is_some() {
+            self.focus(window, cx);
+            cx.notify();
+            return;
+        }
+        if !self.is_editing || self.details_input.is_none() {
+            return;
+        }
 
-    fn on_cancel(&mut self, _: &Cancel, window: &mut Window, cx: &mut Context<Self>) {
-        self.cancel_edit(window, cx);
+        let prompt = cx.new(|cx| {
+            InputState::new(window, cx)
+                .multi_line(false)
+                .placeholder("Line or line:column")
+        });
+        cx.subscribe_in(
+            &prompt,
+            window,
+            |this, _state, event: &InputEvent, window, cx| {
+                if let InputEvent::PressEnter { .. } = event {
+                    this.go_to_typed_line(window, cx);
+                }
+            },
+        )
+        .detach();
+        prompt.focus_handle(cx).focus(window, cx);
+
+        self.go_to_line = Some(prompt);
+        cx.notify();
     }
-}
 
-impl Focusable for FeatureEditor {
-    fn focus_handle(&self, _cx: &App) -> FocusHandle {
-        self.focus_handle.clone()
+    /// Move the details cursor to the line typed into the go-to-line prompt
+    /// and close it. Input that isn't a line number leaves the prompt open.
+    fn go_to_typed_line(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let (Some(prompt), Some(details_input)) = (&self.go_to_line, self.details_input.clone())
+        else {
+            return;
+        };
+        let input = prompt.read(cx).value().to_string();
+        let text = details_input.read(cx).value().to_string();
+        let Some((line, column)) = go_to_line::parse_target(&input, &text) else {
+            return;
+        };
+        // Moving the cursor scrolls the line into view
+        details_input.update(cx, |state, cx| {
+            state.set_cursor_position(Position::new(line, column), window, cx);
+        });
+        self.toggle_go_to_line(window, cx);
     }
-}
 
-impl gpui::EventEmitter<Event> for FeatureEditor {}
+    /// Recompute matches after the query or the details change.
+    fn refresh_find_matches(&mut self, cx: &App) {
+        let (Some(bar), Some(details)) = (self.find_bar.as_mut(), self.details_input.as_ref())
+        else {
+            return;
+        };
+        let text = details.read(cx).value();
+        let query = bar.query.read(cx).value();
+        bar.matches = find::find_matches(&text, &query, bar.case_sensitive);
+        bar.current = bar.current.filter(|&index| index < bar.matches.len());
+    }
 
-impl Render for FeatureEditor {
-    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
-        // Process any pending feature that was queued from async context
-        if let Some(pending) = self.pending_feature.take() {
-            self.open_feature(pending.id, pending.title, pending.details, window, cx);
+    fn toggle_case_sensitive(&mut self, cx: &mut Context<Self>) {
+        if let Some(bar) = self.find_bar.as_mut() {
+            bar.case_sensitive = !bar.case_sensitive;
+            bar.current = None;
         }
+        self.refresh_find_matches(cx);
+        cx.notify();
+    }
 
-        div()
-            .id("feature-editor")
-            .size_full()
-            .flex()
-            .flex_col()
-            .bg(colors::background())
-            .track_focus(&self.focus_handle)
-            .key_context("FeatureEditor")
-            .on_action(cx.listener(Self::on_save))
-            .on_action(cx.listener(Self::on_edit))
-            .on_action(cx.listener(Self::on_cancel))
-            // Top: Feature header (always visible)
-            .child(self.render_feature_header(cx))
-            // Bottom: Content area (tasks + details or empty state)
-            .child(self.render_content_area(cx))
+    /// Move the cursor to the next or previous match, wrapping around.
+    fn step_match(&mut self, forward: bool, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(bar) = self.find_bar.as_mut() else {
+            return;
+        };
+        let count = bar.matches.len();
+        if count == 0 {
+            return;
+        }
+        let index = match (bar.current, forward) {
+            (Some(index), true) => (index + 1) % count,
+            (Some(index), false) => (index + count - 1) % count,
+            (None, true) => 0,
+            (None, false) => count - 1,
+        };
+        bar.current = Some(index);
+        let offset = bar.matches[index].start;
+        self.move_details_cursor(offset, window, cx);
+        cx.notify();
     }
-}
 
-impl FeatureEditor {
-    fn render_feature_header(&self, cx: &mut Context<Self>) -> impl IntoElement {
+    /// Put the details cursor at byte `offset`, which scrolls it into view.
+    fn move_details_cursor(&self, offset: usize, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(ref details_input) = self.details_input {
+            details_input.update(cx, |state, cx| {
+                let (line, column) = find::line_and_column(&state.value(), offset);
+                state.set_cursor_position(Position::new(line, column), window, cx);
+            });
+        }
+    }
+
+    /// Replace the current match (the first, if none is current) and move on
+    /// to the match after it.
+    fn replace_current(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let (Some(bar), Some(details_input)) = (&self.find_bar, self.details_input.clone()) else {
+            return;
+        };
+        let Some(range) = bar.matches.get(bar.current.unwrap_or(0)).cloned() else {
+            return;
+        };
+        let replacement = bar.replacement.read(cx).value().to_string();
+        let mut text = details_input.read(cx).value().to_string();
+        text.replace_range(range.clone(), &replacement);
+        details_input.update(cx, |state, cx| state.set_value(text, window, cx));
+        self.update_details_dirty(cx);
+        self.refresh_find_matches(cx);
+
+        // Later matches shifted by the change in length; pick up at the
+        // first one past the replacement, wrapping to the top
+        let resume_at = range.start + replacement.len();
+        let mut cursor = resume_at;
+        if let Some(bar) = self.find_bar.as_mut() {
+            bar.current = bar
+                .matches
+                .iter()
+                .position(|m| m.start >= resume_at)
+                .or((!bar.matches.is_empty()).then_some(0));
+            if let Some(index) = bar.current {
+                cursor = bar.matches[index].start;
+            }
+        }
+        self.move_details_cursor(cursor, window, cx);
+        cx.notify();
+    }
+
+    /// Replace every match in the details.
+    fn replace_all(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let (Some(bar), Some(details_input)) = (&self.find_bar, self.details_input.clone()) else {
+            return;
+        };
+        let query = bar.query.read(cx).value().to_string();
+        let replacement = bar.replacement.read(cx).value().to_string();
+        let (text, count) = find::replace_all(
+            &details_input.read(cx).value(),
+            &query,
+            &replacement,
+            bar.case_sensitive,
+        );
+        if count == 0 {
+            return;
+        }
+        details_input.update(cx, |state, cx| state.set_value(text, window, cx));
+        self.update_details_dirty(cx);
+        if let Some(bar) = self.find_bar.as_mut() {
+            bar.current = None;
+        }
+        self.refresh_find_matches(cx);
+        cx.notify();
+    }
+
+    /// Tick or untick the task list item on the details cursor's line.
+    fn toggle_task_at_cursor(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if !self.is_editing {
+            return;
+        }
+        let Some(details_input) = self.details_input.clone() else {
+            return;
+        };
+        let (text, position) = {
+            let state = details_input.read(cx);
+            (state.value().to_string(), state.cursor_position())
+        };
+        let Some(text) = task_list::toggle_task(&text, position.line as usize) else {
+            return;
+        };
+        details_input.update(cx, |state, cx| {
+            state.set_value(text, window, cx);
+            state.set_cursor_position(position, window, cx);
+        });
+        self.update_details_dirty(cx);
+        self.refresh_find_matches(cx);
+        cx.notify();
+    }
+
+    /// Indent the details lines under a multi-line selection, or insert an
+    /// indent at the cursor; with `outdent`, remove one level from the lines
+    /// under the cursor or selection. Returns false when the details input
+    /// is not being edited, so the key can do its usual job elsewhere.
+    fn indent_details(
+        &mut self,
+        outdent: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        if !self.is_editing {
+            return false;
+        }
+        let Some(details_input) = self.details_input.clone() else {
+            return false;
+        };
+        if !details_input.focus_handle(cx).is_focused(window) {
+            return false;
+        }
+        let width = self.indent_width;
+        let (text, position, selection) = details_input.update(cx, |state, cx| {
+            let selection = state.selected_text_range(false, window, cx);
+            (
+                state.value().to_string(),
+                state.cursor_position(),
+                selection,
+            )
+        });
+        let (first, last) =
+            indent::selected_lines(&text, selection.map_or(0..0, |selection| selection.range));
+
+        if !outdent && first == last {
+            details_input.update(cx, |state, cx| {
+                state.replace_text_in_range(None, &" ".repeat(width), window, cx);
+            });
+        } else {
+            let reindented = if outdent {
+                indent::dedent_lines(&text, first, last, width)
+            } else {
+                indent::indent_lines(&text, first, last, width)
+            };
+            // Keep the cursor on the same character of its line
+            let line = position.line as usize;
+            let line_len = |text: &str| text.split_inclusive('\n').nth(line).map_or(0, str::len);
+            let shift = line_len(&reindented) as i64 - line_len(&text) as i64;
+            let character = (position.character as i64 + shift).max(0) as u32;
+            details_input.update(cx, |state, cx| {
+                state.set_value(reindented, window, cx);
+                state.set_cursor_position(Position::new(position.line, character), window, cx);
+            });
+        }
+        self.update_details_dirty(cx);
+        self.refresh_find_matches(cx);
+        cx.notify();
+        true
+    }
+
+    // --- Action handlers ---
+
+    fn on_save(&mut self, _: &Save, window: &mut Window, cx: &mut Context<Self>) {
+        self.save_current(window, cx);
+    }
+
+    fn on_edit(&mut self, _: &Edit, _window: &mut Window, cx: &mut Context<Self>) {
+        self.enter_edit_mode(cx);
+    }
+
+    fn on_cancel(&mut self, _: &Cancel, window: &mut Window, cx: &mut Context<Self>) {
+        self.cancel_edit(window, cx);
+    }
+
+    fn on_open_in_external_editor(
+        &mut self,
+        _: &OpenInExternalEditor,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.open_in_external_editor(window, cx);
+    }
+
+    fn on_find(&mut self, _: &Find, window: &mut Window, cx: &mut Context<Self>) {
+        self.toggle_find(window, cx);
+    }
+
+    fn on_find_next(&mut self, _: &FindNext, window: &mut Window, cx: &mut Context<Self>) {
+        self.step_match(true, window, cx);
+    }
+
+    fn on_find_previous(&mut self, _: &FindPrevious, window: &mut Window, cx: &mut Context<Self>) {
+        self.step_match(false, window, cx);
+    }
+
+    fn on_toggle_task(&mut self, _: &ToggleTask, window: &mut Window, cx: &mut Context<Self>) {
+        self.toggle_task_at_cursor(window, cx);
+    }
+
+    fn on_go_to_line(&mut self, _: &GoToLine, window: &mut Window, cx: &mut Context<Self>) {
+        self.toggle_go_to_line(window, cx);
+    }
+
+    fn on_indent(&mut self, _: &Indent, window: &mut Window, cx: &mut Context<Self>) {
+        if !self.indent_details(false, window, cx) {
+            cx.propagate();
+        }
+    }
+
+    fn on_outdent(&mut self, _: &Outdent, window: &mut Window, cx: &mut Context<Self>) {
+        if !self.indent_details(true, window, cx) {
+            cx.propagate();
+        }
+    }
+
+    fn on_copy_as_markdown(
+        &mut self,
+        _: &CopyAsMarkdown,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(feature_id) = self.feature_id {
+            cx.emit(Event::CopyAsMarkdown(feature_id));
+        }
+    }
+}
+
+impl Focusable for FeatureEditor {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl gpui::EventEmitter<Event> for FeatureEditor {}
+
+impl Render for FeatureEditor {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        // Process any pending feature that was queued from async context
+        if let Some(pending) = self.pending_feature.take() {
+            self.open_feature(
+                pending.id,
+                pending.title,
+                pending.details,
+                pending.updated_at,
+                window,
+                cx,
+            );
+        }
+
+        div()
+            .id("feature-editor")
+            .size_full()
+            .flex()
+            .flex_col()
+            .bg(colors::background())
+            .track_focus(&self.focus_handle)
+            .key_context("FeatureEditor")
+            .on_action(cx.listener(Self::on_save))
+            .on_action(cx.listener(Self::on_edit))
+            .on_action(cx.listener(Self::on_cancel))
+            .on_action(cx.listener(Self::on_open_in_external_editor))
+            .on_action(cx.listener(Self::on_copy_as_markdown))
+            .on_action(cx.listener(Self::on_find))
+            .on_action(cx.listener(Self::on_find_next))
+            .on_action(cx.listener(Self::on_find_previous))
+            .on_action(cx.listener(Self::on_toggle_task))
+            .on_action(cx.listener(Self::on_indent))
+            .on_action(cx.listener(Self::on_outdent))
+            .on_action(cx.listener(Self::on_go_to_line))
+            // Top: Feature header (always visible)
+            .child(self.render_feature_header(cx))
+            .when(self.save_conflict, |d| {
+                d.child(self.render_conflict_banner(cx))
+            })
+            // Bottom: Content area (tasks + details or empty state)
+            .child(self.render_content_area(cx))
+    }
+}
+
+impl FeatureEditor {
+    fn render_feature_header(&self, cx: &mut Context<Self>) -> impl IntoElement {
         let is_editing = self.is_editing;
         let is_dirty = self.is_dirty();
         let has_feature = self.has_feature();
@@ -500,6 +1329,9 @@ impl FeatureEditor {
                                 .text_color(colors::dirty_indicator())
                                 .child("• Unsaved"),
                         )
+                    })
+                    .when(has_feature, |d| {
+                        d.children(self.tags.iter().map(|tag| Self::render_tag_chip(tag)))
                     }),
             )
             // Right: Buttons (only show when feature is loaded)
@@ -512,6 +1344,7 @@ impl FeatureEditor {
                         .items_center()
                         .gap(px(6.0))
                         .mr(px(4.0))
+                        .child(self.render_external_editor_button(cx))
                         .child(
                             Button::new("cancel-btn")
                                 .label("Cancel")
@@ -538,7 +1371,23 @@ impl FeatureEditor {
                 } else {
                     // View mode: Edit button
                     div()
+                        .flex()
+                        .flex_row()
+                        .items_center()
+                        .gap(px(6.0))
                         .mr(px(4.0))
+                        .child(
+                            Button::new("copy-markdown-btn")
+                                .label("Copy as Markdown")
+                                .xsmall()
+                                .rounded(ButtonRounded::Small)
+                                .with_variant(ButtonVariant::Ghost)
+                                .font_family("IBM Plex Sans")
+                                .on_click(cx.listener(|this, _, window, cx| {
+                                    this.on_copy_as_markdown(&CopyAsMarkdown, window, cx);
+                                })),
+                        )
+                        .child(self.render_external_editor_button(cx))
                         .child(
                             Button::new("edit-btn")
                                 .label("Edit")
@@ -555,6 +1404,72 @@ impl FeatureEditor {
             })
     }
 
+    fn render_external_editor_button(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let label = if self.external_edit_running {
+            "Editing externally…"
+        } else {
+            "Open in Editor"
+        };
+        Button::new("external-editor-btn")
+            .label(label)
+            .xsmall()
+            .rounded(ButtonRounded::Small)
+            .with_variant(ButtonVariant::Ghost)
+            .font_family("IBM Plex Sans")
+            .on_click(cx.listener(|this, _, window, cx| {
+                this.open_in_external_editor(window, cx);
+            }))
+    }
+
+    fn render_conflict_banner(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .id("conflict-banner")
+            .w_full()
+            .px(px(12.0))
+            .py(px(6.0))
+            .bg(colors::conflict_background())
+            .flex()
+            .flex_row()
+            .items_center()
+            .justify_between()
+            .child(
+                div()
+                    .font_family("IBM Plex Sans")
+                    .text_size(px(12.0))
+                    .text_color(cx.theme().foreground)
+                    .child("This feature changed elsewhere since you opened it."),
+            )
+            .child(
+                div()
+                    .flex()
+                    .flex_row()
+                    .items_center()
+                    .gap(px(6.0))
+                    .child(
+                        Button::new("reload-btn")
+                            .label("Reload")
+                            .xsmall()
+                            .rounded(ButtonRounded::Small)
+                            .with_variant(ButtonVariant::Ghost)
+                            .font_family("IBM Plex Sans")
+                            .on_click(cx.listener(|this, _, _window, cx| {
+                                this.reload_feature(cx);
+                            })),
+                    )
+                    .child(
+                        Button::new("overwrite-btn")
+                            .label("Overwrite")
+                            .xsmall()
+                            .rounded(ButtonRounded::Small)
+                            .with_variant(ButtonVariant::Danger)
+                            .font_family("IBM Plex Sans")
+                            .on_click(cx.listener(|this, _, _window, cx| {
+                                this.overwrite(cx);
+                            })),
+                    ),
+            )
+    }
+
     fn render_content_area(&self, cx: &mut Context<Self>) -> impl IntoElement {
         let has_feature = self.has_feature();
 
@@ -707,7 +1622,26 @@ impl FeatureEditor {
                             .overflow_hidden()
                             .text_ellipsis()
                             .child(task.title.clone()),
-                    ),
+                    )
+                    .when_some(task.worktree_path.clone(), |row, path| {
+                        let task_id = task.id;
+                        let title = task.title.clone();
+                        row.child(
+                            Button::new(("open-worktree", idx))
+                                .label("Terminal")
+                                .xsmall()
+                                .rounded(ButtonRounded::Small)
+                                .with_variant(ButtonVariant::Ghost)
+                                .font_family("IBM Plex Sans")
+                                .on_click(cx.listener(move |_this, _, _window, cx| {
+                                    cx.emit(Event::OpenWorktree {
+                                        task_id,
+                                        title: title.clone(),
+                                        path: PathBuf::from(&path),
+                                    });
+                                })),
+                        )
+                    }),
             )
             // Status label
             .child(
@@ -720,6 +1654,367 @@ impl FeatureEditor {
             )
     }
 
+    /// Render a small tag chip.
+    fn render_tag_chip(tag: &str) -> gpui::Div {
+        div()
+            .px(px(5.0))
+            .rounded(px(3.0))
+            .bg(colors::tag_background())
+            .font_family("IBM Plex Sans")
+            .text_size(px(10.0))
+            .text_color(colors::header_text())
+            .child(tag.to_string())
+    }
+
+    /// Render the tag editor: current tags with remove buttons, the tag
+    /// input, and completions from the project's other tags.
+    fn render_tag_editor(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let completions = self.tag_completions(cx);
+
+        div()
+            .id("tags-section")
+            .flex()
+            .flex_col()
+            .gap(px(6.0))
+            .child(
+                div()
+                    .font_family("IBM Plex Sans")
+                    .text_size(px(11.0))
+                    .font_weight(gpui::FontWeight::MEDIUM)
+                    .text_color(cx.theme().muted_foreground)
+                    .child("TAGS"),
+            )
+            .child(
+                div()
+                    .flex()
+                    .flex_row()
+                    .flex_wrap()
+                    .items_center()
+                    .gap(px(6.0))
+                    .children(self.tags.iter().enumerate().map(|(ix, tag)| {
+                        let tag = tag.clone();
+                        Self::render_tag_chip(&tag)
+                            .id(("tag-chip", ix))
+                            .cursor_pointer()
+                            .child(" ×")
+                            .on_click(cx.listener(move |this, _, _, cx| {
+                                this.remove_tag(&tag, cx);
+                            }))
+                    }))
+                    .children(self.tag_input.as_ref().map(|tag_input| {
+                        div()
+                            .w(px(160.0))
+                            .bg(colors::panel_background())
+                            .rounded(px(4.0))
+                            .px(px(6.0))
+                            .font_family("IBM Plex Mono")
+                            .child(Input::new(tag_input).appearance(false).w_full())
+                    })),
+            )
+            .when(!completions.is_empty(), |d| {
+                d.child(div().flex().flex_row().gap(px(6.0)).children(
+                    completions.into_iter().enumerate().map(|(ix, tag)| {
+                        Self::render_tag_chip(&tag)
+                            .id(("tag-completion", ix))
+                            .cursor_pointer()
+                            .on_click(cx.listener(move |this, _, window, cx| {
+                                this.add_tag(tag.clone(), window, cx);
+                            }))
+                    }),
+                ))
+            })
+    }
+
+    /// Render a labelled list of linked features; clicking one opens it.
+    fn render_linked_features(
+        &self,
+        id: &'static str,
+        label: &'static str,
+        features: &[LinkedFeature],
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        div()
+            .id(id)
+            .flex()
+            .flex_col()
+            .gap(px(6.0))
+            .child(
+                div()
+                    .font_family("IBM Plex Sans")
+                    .text_size(px(11.0))
+                    .font_weight(gpui::FontWeight::MEDIUM)
+                    .text_color(cx.theme().muted_foreground)
+                    .child(label),
+            )
+            .children(features.iter().enumerate().map(|(ix, feature)| {
+                let feature_id = feature.id;
+                let done = matches!(
+                    feature.state,
+                    FeatureState::Implemented | FeatureState::Deprecated
+                );
+                div()
+                    .id((id, ix))
+                    .flex()
+                    .flex_row()
+                    .items_center()
+                    .gap(px(8.0))
+                    .cursor_pointer()
+                    .on_click(cx.listener(move |_this, _, _, cx| {
+                        cx.emit(Event::OpenFeature(feature_id));
+                    }))
+                    .child(div().w(px(8.0)).h(px(8.0)).rounded_full().bg(if done {
+                        colors::task_completed()
+                    } else {
+                        colors::task_pending()
+                    }))
+                    .child(
+                        div()
+                            .font_family("IBM Plex Sans")
+                            .text_size(px(13.0))
+                            .text_color(cx.theme().foreground)
+                            .child(feature.title.clone()),
+                    )
+            }))
+    }
+
+    /// Edit / Preview switch shown above the details while editing.
+    fn render_details_view_toggle(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let option = |id: &'static str, label: &'static str, view: DetailsView| {
+            let variant = if self.details_view == view {
+                ButtonVariant::Primary
+            } else {
+                ButtonVariant::Ghost
+            };
+            Button::new(id)
+                .label(label)
+                .xsmall()
+                .rounded(ButtonRounded::Small)
+                .with_variant(variant)
+                .font_family("IBM Plex Sans")
+                .on_click(cx.listener(move |this, _, _window, cx| {
+                    this.details_view = view;
+                    cx.notify();
+                }))
+        };
+
+        div()
+            .flex()
+            .flex_row()
+            .items_center()
+            .gap(px(2.0))
+            .child(option("details-edit-btn", "Edit", DetailsView::Edit))
+            .child(option(
+                "details-preview-btn",
+                "Preview",
+                DetailsView::Preview,
+            ))
+    }
+
+    /// Details input or its rendered preview, per `details_view`.
+    /// The preview reads the input on every render, so it follows typing.
+    fn render_details_editor(&self, cx: &mut Context<Self>) -> gpui::AnyElement {
+        let Some(ref details_input) = self.details_input else {
+            return div().into_any_element();
+        };
+
+        // Edit mode - IBM Plex Mono with panel background
+        let input = div()
+            .flex_1()
+            .w_full()
+            .bg(colors::panel_background())
+            .rounded(px(4.0))
+            .p(px(8.0))
+            .font_family("IBM Plex Mono")
+            .child(
+                Input::new(details_input)
+                    .appearance(false)
+                    .w_full()
+                    .h_full(),
+            );
+
+        let text: SharedString = details_input.read(cx).value().to_string().into();
+        let preview = div()
+            .id("details-preview")
+            .flex_1()
+            .w_full()
+            .overflow_y_scroll()
+            .border_1()
+            .border_color(colors::header_border())
+            .rounded(px(4.0))
+            .p(px(8.0))
+            .child(if text.is_empty() {
+                div()
+                    .font_family("IBM Plex Sans")
+                    .text_size(px(13.0))
+                    .text_color(cx.theme().muted_foreground)
+                    .child("Nothing to preview")
+                    .into_any_element()
+            } else {
+                markdown(autolink(&text))
+                    .selectable(true)
+                    .into_any_element()
+            });
+
+        let content = match self.details_view {
+            DetailsView::Edit => input.into_any_element(),
+            DetailsView::Preview => preview.into_any_element(),
+        };
+
+        if self.find_bar.is_none() && self.go_to_line.is_none() {
+            return content;
+        }
+        div()
+            .flex_1()
+            .w_full()
+            .flex()
+            .flex_col()
+            .gap(px(6.0))
+            .when_some(self.find_bar.as_ref(), |d, bar| {
+                d.child(self.render_find_bar(bar, cx))
+            })
+            .when_some(self.go_to_line.as_ref(), |d, prompt| {
+                d.child(self.render_go_to_line(prompt, &text, cx))
+            })
+            .child(content)
+            .into_any_element()
+    }
+
+    fn render_go_to_line(
+        &self,
+        prompt: &Entity<InputState>,
+        text: &str,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        div()
+            .flex()
+            .flex_row()
+            .items_center()
+            .gap(px(6.0))
+            // Escape closes the prompt; Enter is handled by its subscription
+            .capture_key_down(cx.listener(|this, event: &KeyDownEvent, window, cx| {
+                if event.keystroke.key == "escape" {
+                    this.toggle_go_to_line(window, cx);
+                    cx.stop_propagation();
+                }
+            }))
+            .child(
+                div()
+                    .w(px(180.0))
+                    .px(px(6.0))
+                    .rounded(px(3.0))
+                    .bg(colors::panel_background())
+                    .font_family("IBM Plex Mono")
+                    .child(Input::new(prompt).appearance(false).w_full()),
+            )
+            .child(
+                div()
+                    .font_family("IBM Plex Sans")
+                    .text_size(px(11.0))
+                    .text_color(cx.theme().muted_foreground)
+                    .child(format!("1-{}", go_to_line::line_count(text))),
+            )
+            .child(
+                Button::new("go-to-line-close-btn")
+                    .label("Close")
+                    .xsmall()
+                    .rounded(ButtonRounded::Small)
+                    .with_variant(ButtonVariant::Ghost)
+                    .font_family("IBM Plex Sans")
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.toggle_go_to_line(window, cx);
+                    })),
+            )
+    }
+
+    fn render_find_bar(&self, bar: &FindBar, cx: &mut Context<Self>) -> impl IntoElement {
+        let status = match (bar.matches.len(), bar.current) {
+            (0, _) => "No matches".to_string(),
+            (count, Some(index)) => format!("{} of {}", index + 1, count),
+            (1, None) => "1 match".to_string(),
+            (count, None) => format!("{} matches", count),
+        };
+        let field = |input: &Entity<InputState>| {
+            div()
+                .w(px(180.0))
+                .px(px(6.0))
+                .rounded(px(3.0))
+                .bg(colors::panel_background())
+                .font_family("IBM Plex Mono")
+                .child(Input::new(input).appearance(false).w_full())
+        };
+        let button = |id: &'static str, label: &'static str, variant: ButtonVariant| {
+            Button::new(id)
+                .label(label)
+                .xsmall()
+                .rounded(ButtonRounded::Small)
+                .with_variant(variant)
+                .font_family("IBM Plex Sans")
+        };
+        let case_variant = if bar.case_sensitive {
+            ButtonVariant::Primary
+        } else {
+            ButtonVariant::Ghost
+        };
+
+        div()
+            .flex()
+            .flex_row()
+            .flex_wrap()
+            .items_center()
+            .gap(px(6.0))
+            .child(field(&bar.query))
+            .child(
+                button("find-case-btn", "Aa", case_variant).on_click(cx.listener(
+                    |this, _, _window, cx| {
+                        this.toggle_case_sensitive(cx);
+                    },
+                )),
+            )
+            .child(
+                button("find-prev-btn", "Prev", ButtonVariant::Ghost).on_click(cx.listener(
+                    |this, _, window, cx| {
+                        this.step_match(false, window, cx);
+                    },
+                )),
+            )
+            .child(
+                button("find-next-btn", "Next", ButtonVariant::Ghost).on_click(cx.listener(
+                    |this, _, window, cx| {
+                        this.step_match(true, window, cx);
+                    },
+                )),
+            )
+            .child(
+                div()
+                    .font_family("IBM Plex Sans")
+                    .text_size(px(11.0))
+                    .text_color(cx.theme().muted_foreground)
+                    .child(status),
+            )
+            .child(field(&bar.replacement))
+            .child(
+                button("replace-btn", "Replace", ButtonVariant::Ghost).on_click(cx.listener(
+                    |this, _, window, cx| {
+                        this.replace_current(window, cx);
+                    },
+                )),
+            )
+            .child(
+                button("replace-all-btn", "Replace All", ButtonVariant::Ghost).on_click(
+                    cx.listener(|this, _, window, cx| {
+                        this.replace_all(window, cx);
+                    }),
+                ),
+            )
+            .child(
+                button("find-close-btn", "Close", ButtonVariant::Ghost).on_click(cx.listener(
+                    |this, _, window, cx| {
+                        this.toggle_find(window, cx);
+                    },
+                )),
+            )
+    }
+
     fn render_feature_details(&self, cx: &mut Context<Self>) -> impl IntoElement {
         let is_editing = self.is_editing;
 
@@ -775,6 +2070,20 @@ impl FeatureEditor {
                             .into_any_element()
                     }),
             )
+            // Tags section (edit mode only; the header shows them otherwise)
+            .when(is_editing, |d| d.child(self.render_tag_editor(cx)))
+            // Dependency sections
+            .when(!self.blocked_by.is_empty(), |d| {
+                d.child(self.render_linked_features(
+                    "blocked-by-section",
+                    "BLOCKED BY",
+                    &self.blocked_by,
+                    cx,
+                ))
+            })
+            .when(!self.blocks.is_empty(), |d| {
+                d.child(self.render_linked_features("blocks-section", "BLOCKS", &self.blocks, cx))
+            })
             // Details section
             .child(
                 div()
@@ -785,32 +2094,22 @@ impl FeatureEditor {
                     .gap(px(6.0))
                     .child(
                         div()
-                            .font_family("IBM Plex Sans")
-                            .text_size(px(11.0))
-                            .font_weight(gpui::FontWeight::MEDIUM)
-                            .text_color(cx.theme().muted_foreground)
-                            .child("DETAILS"),
+                            .flex()
+                            .flex_row()
+                            .items_center()
+                            .justify_between()
+                            .child(
+                                div()
+                                    .font_family("IBM Plex Sans")
+                                    .text_size(px(11.0))
+                                    .font_weight(gpui::FontWeight::MEDIUM)
+                                    .text_color(cx.theme().muted_foreground)
+                                    .child("DETAILS"),
+                            )
+                            .when(is_editing, |d| d.child(self.render_details_view_toggle(cx))),
                     )
                     .child(if is_editing {
-                        // Edit mode - IBM Plex Mono with panel background
-                        if let Some(ref details_input) = self.details_input {
-                            div()
-                                .flex_1()
-                                .w_full()
-                                .bg(colors::panel_background())
-                                .rounded(px(4.0))
-                                .p(px(8.0))
-                                .font_family("IBM Plex Mono")
-                                .child(
-                                    Input::new(details_input)
-                                        .appearance(false)
-                                        .w_full()
-                                        .h_full(),
-                                )
-                                .into_any_element()
-                        } else {
-                            div().into_any_element()
-                        }
+                        self.render_details_editor(cx)
                     } else {
                         // View mode - render as markdown
                         if self.original_details.is_empty() {
@@ -821,7 +2120,7 @@ impl FeatureEditor {
                                 .child("No details")
                                 .into_any_element()
                         } else {
-                            markdown(self.original_details.clone())
+                            markdown(autolink(&self.original_details))
                                 .selectable(true)
                                 .into_any_element()
                         }
@@ -830,7 +2129,47 @@ impl FeatureEditor {
     }
 }
 
-/// Register key bindings for the feature editor.
-pub fn register_bindings(cx: &mut App) {
-    cx.bind_keys([KeyBinding::new("cmd-s", Save, Some("FeatureEditor"))]);
+/// Register the keymap's feature editor bindings.
+pub fn register_bindings(keymap: &Keymap, cx: &mut App) {
+    let bindings = keymap
+        .bindings("FeatureEditor")
+        .filter_map(|(keystroke, action)| match action {
+            "feature_editor::Save" => Some(KeyBinding::new(keystroke, Save, Some("FeatureEditor"))),
+            "feature_editor::OpenInExternalEditor" => Some(KeyBinding::new(
+                keystroke,
+                OpenInExternalEditor,
+                Some("FeatureEditor"),
+            )),
+            "feature_editor::CopyAsMarkdown" => Some(KeyBinding::new(
+                keystroke,
+                CopyAsMarkdown,
+                Some("FeatureEditor"),
+            )),
+            "feature_editor::Find" => Some(KeyBinding::new(keystroke, Find, Some("FeatureEditor"))),
+            "feature_editor::FindNext" => {
+                Some(KeyBinding::new(keystroke, FindNext, Some("FeatureEditor")))
+            }
+            "feature_editor::FindPrevious" => Some(KeyBinding::new(
+                keystroke,
+                FindPrevious,
+                Some("FeatureEditor"),
+            )),
+            "feature_editor::ToggleTask" => Some(KeyBinding::new(
+                keystroke,
+                ToggleTask,
+                Some("FeatureEditor"),
+            )),
+            "feature_editor::GoToLine" => {
+                Some(KeyBinding::new(keystroke, GoToLine, Some("FeatureEditor")))
+            }
+            "feature_editor::Indent" => {
+                Some(KeyBinding::new(keystroke, Indent, Some("FeatureEditor")))
+            }
+            "feature_editor::Outdent" => {
+                Some(KeyBinding::new(keystroke, Outdent, Some("FeatureEditor")))
+            }
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    cx.bind_keys(bindings);
 }
@@ -0,0 +1,168 @@
+//! Editing feature details in the user's own editor.
+//!
+//! Works like git's commit message editing: the details are written to a
+//! temp file, the editor command runs on it, and the file is read back once
+//! the command exits. GUI editors must be told to wait until the file is
+//! closed, e.g. `code --wait`, `zed --wait` or `subl -w`.
+
+use std::fmt;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Names tried before giving up on creating the temp file.
+const TEMP_FILE_ATTEMPTS: u32 = 100;
+
+/// Why an external edit produced no content.
+#[derive(Debug)]
+pub enum ExternalEditError {
+    /// No editor configured and neither `$VISUAL` nor `$EDITOR` is set.
+    NoEditor,
+    /// The editor program could not be found.
+    NotFound(String),
+    /// The editor exited unsuccessfully.
+    Failed(String),
+    /// The temp file could not be written or read.
+    Io(io::Error),
+    /// The details were edited in the app while the editor was open.
+    DetailsChanged,
+}
+
+impl fmt::Display for ExternalEditError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoEditor => write!(f, "No editor configured; set $VISUAL or $EDITOR"),
+            Self::NotFound(program) => write!(f, "Editor not found: {}", program),
+            Self::Failed(status) => write!(f, "Editor exited with {}", status),
+            Self::Io(e) => write!(f, "Failed to edit temp file: {}", e),
+            Self::DetailsChanged => write!(
+                f,
+                "Details changed in the app while the editor was open; the edit was not applied"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ExternalEditError {}
+
+/// The editor to launch: the configured command, then `$VISUAL`, then `$EDITOR`.
+pub fn editor_command(configured: Option<&str>) -> Option<String> {
+    configured
+        .map(str::to_string)
+        .or_else(|| std::env::var("VISUAL").ok())
+        .or_else(|| std::env::var("EDITOR").ok())
+        .filter(|command| !command.trim().is_empty())
+}
+
+/// Split a command like `code --wait` into the program and its arguments.
+fn split_command(command: &str) -> Option<(&str, Vec<&str>)> {
+    let mut words = command.split_whitespace();
+    let program = words.next()?;
+    Some((program, words.collect()))
+}
+
+/// Open `content` in `command` and return the edited text, or `None` if the
+/// editor exited without changing the file.
+///
+/// Blocks until the editor exits, so call it from a background task.
+pub fn edit(command: &str, name: &str, content: &str) -> Result<Option<String>, ExternalEditError> {
+    let (program, args) = split_command(command).ok_or(ExternalEditError::NoEditor)?;
+
+    let path = create_temp_file(name, content).map_err(ExternalEditError::Io)?;
+    let result = run_editor(program, &args, &path).and_then(|()| {
+        let edited = fs::read_to_string(&path).map_err(ExternalEditError::Io)?;
+        Ok((edited != content).then_some(edited))
+    });
+    let _ = fs::remove_file(&path);
+    result
+}
+
+fn run_editor(program: &str, args: &[&str], path: &Path) -> Result<(), ExternalEditError> {
+    let status = Command::new(program)
+        .args(args)
+        .arg(path)
+        .status()
+        .map_err(|e| match e.kind() {
+            io::ErrorKind::NotFound => ExternalEditError::NotFound(program.to_string()),
+            _ => ExternalEditError::Io(e),
+        })?;
+    if !status.success() {
+        return Err(ExternalEditError::Failed(status.to_string()));
+    }
+    Ok(())
+}
+
+/// Write `content` to a new `.md` file, so editors pick markdown
+/// highlighting. The file must not exist yet, so nothing already at the path
+/// (such as a symlink another user planted) is written through or read back,
+/// and on unix only the user can read it.
+fn create_temp_file(name: &str, content: &str) -> io::Result<PathBuf> {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.subsec_nanos());
+    let mut last_error = None;
+    for attempt in 0..TEMP_FILE_ATTEMPTS {
+        let path = std::env::temp_dir().join(format!(
+            "manifest-{}-{}-{}-{}.md",
+            name,
+            std::process::id(),
+            nanos,
+            attempt
+        ));
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        match options.open(&path) {
+            Ok(mut file) => {
+                file.write_all(content.as_bytes())?;
+                return Ok(path);
+            }
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => last_error = Some(e),
+            Err(e) => return Err(e),
+        }
+    }
+    Err(last_error.expect("at least one temp file name was tried"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_editor_arguments() {
+        assert_eq!(
+            split_command("code  --wait"),
+            Some(("code", vec!["--wait"]))
+        );
+        assert_eq!(split_command("   "), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn unchanged_file_reads_as_no_edit() {
+        assert_eq!(edit("true", "unchanged-test", "Details").unwrap(), None);
+        assert!(matches!(
+            edit("manifest-no-such-editor", "missing-test", "Details"),
+            Err(ExternalEditError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn temp_files_are_new_and_private() {
+        let first = create_temp_file("private-test", "Details").unwrap();
+        let second = create_temp_file("private-test", "Details").unwrap();
+        assert_ne!(first, second);
+        assert_eq!(fs::read_to_string(&first).unwrap(), "Details");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&first).unwrap().permissions().mode();
+            assert_eq!(mode & 0o077, 0);
+        }
+        let _ = fs::remove_file(first);
+        let _ = fs::remove_file(second);
+    }
+}
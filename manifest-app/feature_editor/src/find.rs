@@ -0,0 +1,123 @@
+//! Text search for the details find bar.
+//!
+//! Matches are byte ranges into the searched text. Like most editors, a
+//! search finds non-overlapping matches left to right, so `aa` in `aaaa`
+//! matches twice, not three times.
+
+use std::ops::Range;
+
+/// Every non-overlapping match of `query` in `text`. An empty query matches
+/// nothing.
+pub fn find_matches(text: &str, query: &str, case_sensitive: bool) -> Vec<Range<usize>> {
+    let mut matches = Vec::new();
+    if query.is_empty() {
+        return matches;
+    }
+
+    let mut start = 0;
+    while start < text.len() {
+        match match_len(&text[start..], query, case_sensitive) {
+            Some(len) => {
+                matches.push(start..start + len);
+                start += len;
+            }
+            None => start += text[start..].chars().next().map_or(1, char::len_utf8),
+        }
+    }
+    matches
+}
+
+/// Byte length of `query` at the start of `text`, if it matches there.
+///
+/// Compares character by character rather than lowercasing both strings,
+/// since lowercasing can change byte lengths and the ranges index `text`.
+fn match_len(text: &str, query: &str, case_sensitive: bool) -> Option<usize> {
+    if case_sensitive {
+        return text.starts_with(query).then_some(query.len());
+    }
+    let mut text_chars = text.char_indices();
+    for q in query.chars() {
+        let (_, t) = text_chars.next()?;
+        if !t.to_lowercase().eq(q.to_lowercase()) {
+            return None;
+        }
+    }
+    Some(text_chars.next().map_or(text.len(), |(i, _)| i))
+}
+
+/// `text` with every match of `query` replaced, and how many were replaced.
+pub fn replace_all(
+    text: &str,
+    query: &str,
+    replacement: &str,
+    case_sensitive: bool,
+) -> (String, usize) {
+    let matches = find_matches(text, query, case_sensitive);
+    let mut result = String::with_capacity(text.len());
+    let mut last = 0;
+    for range in &matches {
+        result.push_str(&text[last..range.start]);
+        result.push_str(replacement);
+        last = range.end;
+    }
+    result.push_str(&text[last..]);
+    (result, matches.len())
+}
+
+/// Zero-based line and character column of byte `offset` in `text`.
+pub fn line_and_column(text: &str, offset: usize) -> (u32, u32) {
+    let before = &text[..offset];
+    let line = before.matches('\n').count();
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    let column = before[line_start..].chars().count();
+    (line as u32, column as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_non_overlapping_matches() {
+        assert_eq!(find_matches("aaaa", "aa", true), vec![0..2, 2..4]);
+        assert_eq!(find_matches("Todo: todo", "todo", true), vec![6..10]);
+        assert_eq!(find_matches("Todo: todo", "TODO", false), vec![0..4, 6..10]);
+        assert!(find_matches("anything", "", false).is_empty());
+    }
+
+    #[test]
+    fn case_insensitive_ranges_index_the_original_text() {
+        let text = "Größe und GRÖSSE, größe";
+        let matches = find_matches(text, "größe", false);
+        assert_eq!(matches.len(), 2);
+        assert_eq!(&text[matches[0].clone()], "Größe");
+        assert_eq!(&text[matches[1].clone()], "größe");
+    }
+
+    #[test]
+    fn replaces_every_match() {
+        assert_eq!(
+            replace_all("one Two two", "two", "2", false),
+            ("one 2 2".to_string(), 2)
+        );
+        assert_eq!(
+            replace_all("aaa", "a", "aa", true),
+            ("aaaaaa".to_string(), 3)
+        );
+
+        let large = "needle hay ".repeat(10_000);
+        let (replaced, count) = replace_all(&large, "needle", "pin", true);
+        assert_eq!(count, 10_000);
+        assert!(!replaced.contains("needle"));
+    }
+
+    #[test]
+    fn locates_offsets_by_line_and_column() {
+        let text = "first\nsé\nthird";
+        assert_eq!(line_and_column(text, 0), (0, 0));
+        assert_eq!(line_and_column(text, 6), (1, 0));
+        // é is two bytes but one column
+        assert_eq!(line_and_column(text, 9), (1, 2));
+        assert_eq!(line_and_column(text, 10), (2, 0));
+    }
+}
@@ -0,0 +1,56 @@
+//! Targets typed into the details go-to-line prompt.
+//!
+//! Reviewers refer to spec lines the way editors number them, from 1, so
+//! the prompt takes a one-based `line` or `line:column` and turns it into
+//! the zero-based line and character column the details cursor uses.
+
+/// Number of lines in `text`, counting the empty line after a trailing
+/// newline, as the editor shows it.
+pub fn line_count(text: &str) -> usize {
+    text.split('\n').count()
+}
+
+/// The zero-based line and column `input` points to in `text`, or `None` if
+/// it is not `line` or `line:column`. Numbers past the end, and zero, are
+/// clamped to the nearest line or column that exists.
+pub fn parse_target(input: &str, text: &str) -> Option<(u32, u32)> {
+    let (line, column) = match input.trim().split_once(':') {
+        Some((line, column)) => (line.trim(), Some(column.trim())),
+        None => (input.trim(), None),
+    };
+    let line: usize = line.parse().ok()?;
+    let column: usize = match column {
+        Some(column) => column.parse().ok()?,
+        None => 1,
+    };
+
+    let line = line.clamp(1, line_count(text)) - 1;
+    let width = text.split('\n').nth(line).map_or(0, |l| l.chars().count());
+    let column = (column.max(1) - 1).min(width);
+    Some((line as u32, column as u32))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_lines_and_columns_from_one() {
+        let text = "# Spec\nExports CSV\n";
+        assert_eq!(line_count(text), 3);
+        assert_eq!(parse_target("2", text), Some((1, 0)));
+        assert_eq!(parse_target(" 2:9 ", text), Some((1, 8)));
+        assert_eq!(parse_target("x", text), None);
+        assert_eq!(parse_target("2:", text), None);
+        assert_eq!(parse_target("", text), None);
+    }
+
+    #[test]
+    fn clamps_targets_outside_the_text() {
+        let text = "# Spec\nExports CSV";
+        assert_eq!(parse_target("42", text), Some((1, 0)));
+        assert_eq!(parse_target("0", text), Some((0, 0)));
+        assert_eq!(parse_target("1:99", text), Some((0, 6)));
+        assert_eq!(parse_target("1:0", text), Some((0, 0)));
+    }
+}
@@ -0,0 +1,104 @@
+//! Tab and Shift+Tab indentation for the details editor.
+//!
+//! Indents are spaces. Lines are addressed by zero-based index, and
+//! selections come from the text input in UTF-16 code units.
+
+use std::ops::Range;
+
+/// Spaces per indent level unless configured otherwise.
+pub const DEFAULT_INDENT_WIDTH: usize = 2;
+
+/// The first and last line touched by the UTF-16 `selection` of `text`.
+///
+/// A selection ending at the start of a line, as when whole lines are
+/// selected, does not include that line.
+pub fn selected_lines(text: &str, selection: Range<usize>) -> (usize, usize) {
+    let first = line_at(text, selection.start);
+    let last = if selection.end > selection.start {
+        line_at(text, selection.end - 1)
+    } else {
+        first
+    };
+    (first, last)
+}
+
+/// `text` with `width` spaces added before lines `first` to `last`. Blank
+/// lines stay empty.
+pub fn indent_lines(text: &str, first: usize, last: usize, width: usize) -> String {
+    map_lines(text, first, last, |line| {
+        if line.trim().is_empty() {
+            line.to_string()
+        } else {
+            format!("{}{}", " ".repeat(width), line)
+        }
+    })
+}
+
+/// `text` with one level of indent removed from lines `first` to `last`:
+/// up to `width` leading spaces, or a leading tab.
+pub fn dedent_lines(text: &str, first: usize, last: usize, width: usize) -> String {
+    map_lines(text, first, last, |line| {
+        let removed = if line.starts_with('\t') {
+            1
+        } else {
+            (line.len() - line.trim_start_matches(' ').len()).min(width)
+        };
+        line[removed..].to_string()
+    })
+}
+
+/// Line of the character `offset` UTF-16 code units into `text`.
+fn line_at(text: &str, offset: usize) -> usize {
+    let mut units = 0;
+    let mut line = 0;
+    for c in text.chars() {
+        if units >= offset {
+            break;
+        }
+        units += c.len_utf16();
+        if c == '\n' {
+            line += 1;
+        }
+    }
+    line
+}
+
+fn map_lines(text: &str, first: usize, last: usize, f: impl Fn(&str) -> String) -> String {
+    text.split_inclusive('\n')
+        .enumerate()
+        .map(|(index, line)| {
+            if (first..=last).contains(&index) {
+                f(line)
+            } else {
+                line.to_string()
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn indents_and_dedents_every_selected_line() {
+        let text = "Criteria:\n- [ ] CSV\n\n- [ ] JSON\nDone\n";
+        let indented = indent_lines(text, 1, 3, 2);
+        assert_eq!(indented, "Criteria:\n  - [ ] CSV\n\n  - [ ] JSON\nDone\n");
+        assert_eq!(dedent_lines(&indented, 1, 3, 2), text);
+
+        // Short indents and tabs lose what they have
+        assert_eq!(dedent_lines(" a\n\tb\n    c\nd", 0, 3, 2), "a\nb\n  c\nd");
+    }
+
+    #[test]
+    fn selections_cover_the_lines_they_touch() {
+        let text = "one\ntwo\nthree\n";
+        assert_eq!(selected_lines(text, 5..5), (1, 1));
+        assert_eq!(selected_lines(text, 1..10), (0, 2));
+        // Ending at the start of "three" leaves it out
+        assert_eq!(selected_lines(text, 0..8), (0, 1));
+        // Offsets count UTF-16 units: "é" is one, "😀" is two
+        assert_eq!(selected_lines("é😀\nx", 4..4), (1, 1));
+    }
+}
@@ -1,3 +1,10 @@
+mod autolink;
 mod editor;
+mod external_editor;
+mod find;
+mod go_to_line;
+mod indent;
+mod task_list;
 
-pub use editor::{Event, FeatureEditor, register_bindings};
+pub use editor::{Event, FeatureEditor, LinkedFeature, register_bindings};
+pub use indent::DEFAULT_INDENT_WIDTH;
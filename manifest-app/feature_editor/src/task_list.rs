@@ -0,0 +1,138 @@
+//! Markdown task list items for the details editor.
+//!
+//! Acceptance criteria are often written as `- [ ]` / `- [x]` checklists.
+//! The preview draws them; these helpers let the editor tick an item off in
+//! the source. Lines inside fenced code blocks are never task items.
+
+/// The checkbox of a task list item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TaskItem {
+    pub checked: bool,
+    /// Byte offset in the line of the character between the brackets.
+    pub mark: usize,
+}
+
+/// The checkbox of `line`, if it is a task list item.
+///
+/// The line needs a list marker (`-`, `*`, `+`, `1.` or `1)`), a space, then
+/// `[ ]`, `[x]` or `[X]` followed by whitespace or the end of the line, so a
+/// regular item such as `- [link](url)` or `- [ ]x` is not a task.
+pub fn task_item(line: &str) -> Option<TaskItem> {
+    let indent = line.len() - line.trim_start_matches([' ', '\t']).len();
+    let rest = &line[indent..];
+    let marker_len = if rest.starts_with(['-', '*', '+']) {
+        1
+    } else {
+        let digits = rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+        if digits == 0 || digits > 9 || !rest[digits..].starts_with(['.', ')']) {
+            return None;
+        }
+        digits + 1
+    };
+
+    let after_marker = &rest[marker_len..];
+    let spaces = after_marker.len() - after_marker.trim_start_matches(' ').len();
+    if spaces == 0 {
+        return None;
+    }
+    let checkbox = &after_marker[spaces..];
+    let checked = match checkbox.get(..3)? {
+        "[ ]" => false,
+        "[x]" | "[X]" => true,
+        _ => return None,
+    };
+    if !checkbox[3..].is_empty() && !checkbox[3..].starts_with(char::is_whitespace) {
+        return None;
+    }
+    Some(TaskItem {
+        checked,
+        mark: indent + marker_len + spaces + 1,
+    })
+}
+
+/// `text` with the task item on zero-based `line` ticked or unticked, or
+/// `None` if that line is not a task item.
+pub fn toggle_task(text: &str, line: usize) -> Option<String> {
+    let mut fence: Option<&str> = None;
+    let mut start = 0;
+    for (index, current) in text.split_inclusive('\n').enumerate() {
+        let trimmed = current.trim_start();
+        let marker = ["```", "~~~"]
+            .into_iter()
+            .find(|marker| trimmed.starts_with(marker));
+        let in_code = fence.is_some() || marker.is_some();
+        match (fence, marker) {
+            (None, Some(marker)) => fence = Some(marker),
+            (Some(open), Some(marker)) if open == marker => fence = None,
+            _ => {}
+        }
+
+        if index == line {
+            if in_code {
+                return None;
+            }
+            let item = task_item(current)?;
+            let mark = start + item.mark;
+            let mut toggled = text.to_string();
+            toggled.replace_range(mark..mark + 1, if item.checked { " " } else { "x" });
+            return Some(toggled);
+        }
+        start += current.len();
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recognizes_task_items_only() {
+        assert_eq!(
+            task_item("- [ ] Export CSV"),
+            Some(TaskItem {
+                checked: false,
+                mark: 3
+            })
+        );
+        assert_eq!(
+            task_item("  12. [X] Done"),
+            Some(TaskItem {
+                checked: true,
+                mark: 7
+            })
+        );
+        assert!(task_item("* [x]").is_some());
+
+        assert_eq!(task_item("- [link](https://a.io)"), None);
+        assert_eq!(task_item("- [ ]x"), None);
+        assert_eq!(task_item("-[ ] no space"), None);
+        assert_eq!(task_item("[ ] not a list"), None);
+        assert_eq!(task_item("1 [ ] no marker"), None);
+    }
+
+    #[test]
+    fn toggles_the_item_on_a_line() {
+        let text = "Criteria:\n- [ ] Exports CSV\n- [x] Exports JSON\n";
+        assert_eq!(
+            toggle_task(text, 1).unwrap(),
+            "Criteria:\n- [x] Exports CSV\n- [x] Exports JSON\n"
+        );
+        assert_eq!(
+            toggle_task(text, 2).unwrap(),
+            "Criteria:\n- [ ] Exports CSV\n- [ ] Exports JSON\n"
+        );
+        assert_eq!(toggle_task(text, 0), None);
+        assert_eq!(toggle_task(text, 5), None);
+    }
+
+    #[test]
+    fn ignores_items_in_code_blocks() {
+        let text = "```\n- [ ] sample\n```\n- [ ] real";
+        assert_eq!(toggle_task(text, 1), None);
+        assert_eq!(
+            toggle_task(text, 3).unwrap(),
+            "```\n- [ ] sample\n```\n- [x] real"
+        );
+    }
+}
@@ -2,20 +2,33 @@
 //!
 //! Uses gpui-component's Tree for keyboard navigation and virtualized rendering.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
 
 use gpui::{
-    App, AppContext, Context, Entity, EventEmitter, FocusHandle, Focusable, Hsla,
+    AnyElement, App, AppContext, Context, Div, Entity, EventEmitter, FocusHandle, Focusable, Hsla,
     InteractiveElement, IntoElement, KeyBinding, MouseButton, ParentElement, Render, SharedString,
-    Styled, Window, actions, div, px, rgba,
+    Styled, Window, actions, div, prelude::FluentBuilder, px, rgba,
 };
 
-// Action for opening the selected feature
-actions!(feature_panel, [OpenFeature]);
+// Actions for opening the selected feature and editing the outline
+actions!(
+    feature_panel,
+    [
+        OpenFeature,
+        NewFeature,
+        NewChildFeature,
+        DeleteFeature,
+        RenameFeature,
+        CancelRename
+    ]
+);
+use gpui_component::input::{Input, InputEvent, InputState};
 use gpui_component::list::ListItem;
+use gpui_component::menu::ContextMenuExt;
 use gpui_component::tree::{TreeItem, TreeState, tree};
-use gpui_component::{Icon, IconName};
+use gpui_component::{Icon, IconName, Sizable};
+use keymap::Keymap;
 use manifest_client::{Feature, FeatureState};
 use uuid::Uuid;
 
@@ -104,12 +117,57 @@ mod colors {
             a: 1.0,
         } // #636e80 - text.muted
     }
+
+    /// Agent running indicator.
+    pub fn running_dot() -> Rgba {
+        Rgba {
+            r: 0.863,
+            g: 0.659,
+            b: 0.345,
+            a: 1.0,
+        } // #dca858
+    }
+
+    /// Tag chip background.
+    pub fn tag_background() -> Rgba {
+        Rgba {
+            r: 0.176,
+            g: 0.200,
+            b: 0.227,
+            a: 1.0,
+        } // #2d333a
+    }
+
+    /// Background of the tag the tree is filtered by.
+    pub fn tag_active_background() -> Rgba {
+        Rgba {
+            r: 0.231,
+            g: 0.357,
+            b: 0.490,
+            a: 1.0,
+        } // #3b5b7d
+    }
 }
 
 /// Events emitted by the FeaturePanel.
 #[derive(Clone, Debug)]
 pub enum Event {
     FeatureSelected(Uuid),
+    /// Restore was clicked for a feature in the trash.
+    RestoreFeature(Uuid),
+    /// User asked for a new feature, at the root or under a parent.
+    CreateFeature {
+        parent_id: Option<Uuid>,
+    },
+    /// User asked to delete a feature; the app confirms before deleting.
+    DeleteFeature(Uuid),
+    /// A feature was renamed in place; its row already shows the new title.
+    RenameFeature {
+        feature_id: Uuid,
+        title: String,
+    },
+    /// The states hidden from the tree were changed from the panel.
+    HiddenStatesChanged(Vec<FeatureState>),
 }
 
 /// State of data loading.
@@ -135,9 +193,53 @@ struct FeatureMetadata {
     has_children: bool,
 }
 
+/// Task progress for a feature with an active session.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FeatureProgress {
+    pub completed: usize,
+    pub total: usize,
+    /// Whether any task is currently running.
+    pub running: bool,
+}
+
+/// A deleted feature listed in the trash view.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TrashedFeature {
+    pub id: Uuid,
+    pub title: String,
+    /// When it was deleted, formatted for display.
+    pub deleted_at: String,
+}
+
+/// A feature being renamed in place.
+struct Renaming {
+    feature_id: Uuid,
+    /// Title before the rename, kept when the input is left unchanged or blank.
+    original: SharedString,
+    input: Entity<InputState>,
+}
+
 /// Special ID used for the directory root node.
 const DIRECTORY_ROOT_ID: &str = "__directory_root__";
 
+/// How the sidebar arranges features.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SidebarGrouping {
+    /// The feature hierarchy, under the directory root.
+    #[default]
+    Tree,
+    /// One collapsible section per feature state.
+    State,
+}
+
+/// State sections in display order, with their labels.
+const STATE_GROUPS: [(FeatureState, &str); 4] = [
+    (FeatureState::Proposed, "Proposed"),
+    (FeatureState::Specified, "Specified"),
+    (FeatureState::Implemented, "Implemented"),
+    (FeatureState::Deprecated, "Deprecated"),
+];
+
 /// GPUI Entity for displaying a feature tree.
 pub struct FeaturePanel {
     tree_state: Entity<TreeState>,
@@ -151,12 +253,48 @@ pub struct FeaturePanel {
     pending_click_open: bool,
     /// Directory name shown as root node of the feature tree.
     directory_name: Option<String>,
+    /// Session progress for features with an active session.
+    feature_progress: Rc<HashMap<Uuid, FeatureProgress>>,
+    /// Features as last set, kept to rebuild the tree when the tag filter changes.
+    features: Vec<Feature>,
+    /// Tags for each feature, shown as chips and used for filtering.
+    feature_tags: Rc<HashMap<Uuid, Vec<String>>>,
+    /// When non-empty, only features with all of these tags (and their
+    /// ancestors) are shown.
+    tag_filter: Vec<String>,
+    /// Text a feature's title must contain to be shown, ignoring case.
+    title_filter: Entity<InputState>,
+    /// States whose features are left out of the tree, unless they have
+    /// children that are shown.
+    hidden_states: Vec<FeatureState>,
+    /// Features waiting on a dependency that isn't implemented yet.
+    blocked: Rc<HashSet<Uuid>>,
+    /// Whether features are shown as a hierarchy or grouped by state.
+    grouping: SidebarGrouping,
+    /// Deleted features of the project, newest first.
+    trash: Vec<TrashedFeature>,
+    /// Whether the trash is shown in place of the tree.
+    show_trash: bool,
+    /// Feature whose label is replaced by a title input.
+    renaming: Option<Renaming>,
+    /// Titles of features renamed since the tree was last rebuilt, keyed by
+    /// tree item ID, so a rename updates its row without collapsing the tree.
+    renamed: Rc<HashMap<String, SharedString>>,
 }
 
 impl FeaturePanel {
     /// Create a new empty feature panel.
-    pub fn new(cx: &mut Context<Self>) -> Self {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
         let tree_state = cx.new(|cx| TreeState::new(cx));
+        let title_filter = cx.new(|cx| InputState::new(window, cx).placeholder("Filter features"));
+
+        // Filter the tree as the user types
+        cx.subscribe(&title_filter, |this, _input, event: &InputEvent, cx| {
+            if let InputEvent::Change = event {
+                this.rebuild_tree(cx);
+            }
+        })
+        .detach();
 
         // Observe tree state changes to detect click-triggered selection
         cx.observe(&tree_state, |this, _state, cx| {
@@ -172,6 +310,18 @@ impl FeaturePanel {
             feature_metadata: Rc::new(HashMap::new()),
             pending_click_open: false,
             directory_name: None,
+            feature_progress: Rc::new(HashMap::new()),
+            features: Vec::new(),
+            feature_tags: Rc::new(HashMap::new()),
+            tag_filter: Vec::new(),
+            title_filter,
+            hidden_states: vec![FeatureState::Deprecated],
+            blocked: Rc::new(HashSet::new()),
+            grouping: SidebarGrouping::default(),
+            trash: Vec::new(),
+            show_trash: false,
+            renaming: None,
+            renamed: Rc::new(HashMap::new()),
         }
     }
 
@@ -193,20 +343,187 @@ impl FeaturePanel {
         directory_name: Option<String>,
         cx: &mut Context<Self>,
     ) {
-        self.directory_name = directory_name.clone();
+        self.directory_name = directory_name;
+        self.features = features;
+        self.rebuild_tree(cx);
+
+        self.load_state = LoadState::Loaded;
+        cx.notify();
+    }
+
+    /// Replace the tags shown next to features.
+    pub fn set_tags(&mut self, tags: HashMap<Uuid, Vec<String>>, cx: &mut Context<Self>) {
+        if *self.feature_tags != tags {
+            self.feature_tags = Rc::new(tags);
+            self.on_tags_changed(cx);
+        }
+    }
+
+    /// Replace the set of features marked as blocked.
+    pub fn set_blocked(&mut self, blocked: HashSet<Uuid>, cx: &mut Context<Self>) {
+        if *self.blocked != blocked {
+            self.blocked = Rc::new(blocked);
+            cx.notify();
+        }
+    }
+
+    /// Update the tags of a single feature, e.g. after they were edited.
+    pub fn set_feature_tags(
+        &mut self,
+        feature_id: Uuid,
+        tags: Vec<String>,
+        cx: &mut Context<Self>,
+    ) {
+        let mut all = (*self.feature_tags).clone();
+        if tags.is_empty() {
+            all.remove(&feature_id);
+        } else {
+            all.insert(feature_id, tags);
+        }
+        self.set_tags(all, cx);
+    }
+
+    /// Every tag used by a feature, sorted.
+    pub fn all_tags(&self) -> Vec<String> {
+        let mut tags: Vec<String> = self.feature_tags.values().flatten().cloned().collect();
+        tags.sort();
+        tags.dedup();
+        tags
+    }
+
+    /// Show only features carrying every one of `tags`, or everything when
+    /// empty.
+    pub fn set_tag_filter(&mut self, tags: Vec<String>, cx: &mut Context<Self>) {
+        if self.tag_filter != tags {
+            self.tag_filter = tags;
+            self.rebuild_tree(cx);
+            cx.notify();
+        }
+    }
+
+    /// Add `tag` to the filter, or take it out if it is already there.
+    pub fn toggle_tag_filter(&mut self, tag: String, cx: &mut Context<Self>) {
+        let mut tags = self.tag_filter.clone();
+        match tags.iter().position(|t| *t == tag) {
+            Some(ix) => {
+                tags.remove(ix);
+            }
+            None => tags.push(tag),
+        }
+        self.set_tag_filter(tags, cx);
+    }
+
+    /// Hide features in `states` from the tree.
+    pub fn set_hidden_states(&mut self, states: Vec<FeatureState>, cx: &mut Context<Self>) {
+        if self.hidden_states != states {
+            self.hidden_states = states;
+            self.rebuild_tree(cx);
+        }
+    }
+
+    /// Show or hide features in `state`, as clicked in the state filter.
+    fn toggle_state_filter(&mut self, state: FeatureState, cx: &mut Context<Self>) {
+        if let Some(ix) = self.hidden_states.iter().position(|s| *s == state) {
+            self.hidden_states.remove(ix);
+        } else {
+            self.hidden_states.push(state);
+        }
+        self.rebuild_tree(cx);
+        cx.emit(Event::HiddenStatesChanged(self.hidden_states.clone()));
+        cx.notify();
+    }
+
+    /// Switch between the feature hierarchy and the by-state sections.
+    pub fn set_grouping(&mut self, grouping: SidebarGrouping, cx: &mut Context<Self>) {
+        if self.grouping != grouping {
+            self.grouping = grouping;
+            self.rebuild_tree(cx);
+            cx.notify();
+        }
+    }
+
+    /// Replace the features listed in the trash. The trash view closes when
+    /// it empties.
+    pub fn set_trash(&mut self, trash: Vec<TrashedFeature>, cx: &mut Context<Self>) {
+        if self.trash != trash {
+            if trash.is_empty() {
+                self.show_trash = false;
+            }
+            self.trash = trash;
+            cx.notify();
+        }
+    }
+
+    /// Toggle between the feature tree and the trash.
+    pub fn toggle_trash(&mut self, cx: &mut Context<Self>) {
+        self.show_trash = !self.show_trash && !self.trash.is_empty();
+        // A click in one view must not open a feature in the other
+        self.pending_click_open = false;
+        cx.notify();
+    }
+
+    fn on_tags_changed(&mut self, cx: &mut Context<Self>) {
+        // Drop filter tags no feature carries any more
+        let before = self.tag_filter.len();
+        self.tag_filter
+            .retain(|tag| self.feature_tags.values().flatten().any(|t| t == tag));
+        if !self.tag_filter.is_empty() || self.tag_filter.len() != before {
+            self.rebuild_tree(cx);
+        }
+        cx.notify();
+    }
 
-        // Convert features to TreeItems and collect metadata
-        let (tree_items, metadata) =
-            Self::convert_features_to_tree_items(&features, directory_name.as_deref());
+    /// Rebuild the tree items from `features`, applying the tag and title
+    /// filters.
+    fn rebuild_tree(&mut self, cx: &mut Context<Self>) {
+        let query = self.title_filter.read(cx).value().trim().to_lowercase();
+        let filtering = !self.tag_filter.is_empty() || !query.is_empty();
+        let filtered;
+        let features = if filtering || !self.hidden_states.is_empty() {
+            filtered = filter_features(&self.features, &|feature| {
+                let feature_tags = self.feature_tags.get(&feature.id);
+                let tagged = self
+                    .tag_filter
+                    .iter()
+                    .all(|tag| feature_tags.is_some_and(|tags| tags.contains(tag)));
+                !self.hidden_states.contains(&feature.state)
+                    && tagged
+                    && feature.title.to_lowercase().contains(&query)
+            });
+            &filtered
+        } else {
+            &self.features
+        };
+
+        // Convert features to TreeItems and collect metadata. Trees filtered
+        // by tag or title start expanded so the matching features are visible.
+        let (tree_items, metadata) = match self.grouping {
+            SidebarGrouping::Tree => Self::convert_features_to_tree_items(
+                features,
+                self.directory_name.as_deref(),
+                filtering,
+            ),
+            SidebarGrouping::State => Self::group_features_by_state(features),
+        };
         self.feature_metadata = Rc::new(metadata);
+        self.renamed = Rc::new(HashMap::new());
 
         // Update tree state
         self.tree_state.update(cx, |state, cx| {
             state.set_items(tree_items, cx);
         });
+    }
 
-        self.load_state = LoadState::Loaded;
-        cx.notify();
+    /// Replace the session progress shown next to features.
+    pub fn set_progress(
+        &mut self,
+        progress: HashMap<Uuid, FeatureProgress>,
+        cx: &mut Context<Self>,
+    ) {
+        if *self.feature_progress != progress {
+            self.feature_progress = Rc::new(progress);
+            cx.notify();
+        }
     }
 
     /// Set an error state.
@@ -247,14 +564,125 @@ impl FeaturePanel {
         self.open_selected_feature(cx);
     }
 
+    /// The selected feature, if a feature (not a folder root) is selected.
+    fn selected_feature_id(&self, cx: &App) -> Option<Uuid> {
+        let item = self.tree_state.read(cx).selected_item()?;
+        self.feature_metadata
+            .get(item.id.as_ref())
+            .map(|metadata| metadata.id)
+    }
+
+    fn on_new_feature(&mut self, _: &NewFeature, _window: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(Event::CreateFeature { parent_id: None });
+    }
+
+    fn on_new_child_feature(
+        &mut self,
+        _: &NewChildFeature,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(parent_id) = self.selected_feature_id(cx) {
+            cx.emit(Event::CreateFeature {
+                parent_id: Some(parent_id),
+            });
+        }
+    }
+
+    fn on_delete_feature(
+        &mut self,
+        _: &DeleteFeature,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(feature_id) = self.selected_feature_id(cx) {
+            cx.emit(Event::DeleteFeature(feature_id));
+        }
+    }
+
+    fn on_rename_feature(
+        &mut self,
+        _: &RenameFeature,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.start_rename(window, cx);
+    }
+
+    fn on_cancel_rename(&mut self, _: &CancelRename, window: &mut Window, cx: &mut Context<Self>) {
+        self.finish_rename(false, window, cx);
+    }
+
+    /// Replace the selected feature's label with an input holding its title.
+    fn start_rename(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(feature_id) = self.selected_feature_id(cx) else {
+            return;
+        };
+        let Some(original) = self.tree_state.read(cx).selected_item().map(|item| {
+            self.renamed
+                .get(item.id.as_ref())
+                .cloned()
+                .unwrap_or_else(|| item.label.clone())
+        }) else {
+            return;
+        };
+
+        let input = cx.new(|cx| {
+            let mut state = InputState::new(window, cx);
+            state.set_value(original.clone(), window, cx);
+            state
+        });
+        // Enter or clicking away keeps the new title; Escape cancels
+        cx.subscribe_in(
+            &input,
+            window,
+            |this, _input, event: &InputEvent, window, cx| match event {
+                InputEvent::PressEnter { .. } | InputEvent::Blur => {
+                    this.finish_rename(true, window, cx)
+                }
+                _ => {}
+            },
+        )
+        .detach();
+        input.focus_handle(cx).focus(window, cx);
+
+        self.renaming = Some(Renaming {
+            feature_id,
+            original,
+            input,
+        });
+        cx.notify();
+    }
+
+    /// Put the label back, applying the typed title if `commit` and it is
+    /// neither blank nor unchanged.
+    fn finish_rename(&mut self, commit: bool, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(renaming) = self.renaming.take() else {
+            return;
+        };
+        let title = renaming.input.read(cx).value().trim().to_string();
+        if commit && !title.is_empty() && title != renaming.original.as_ref() {
+            Rc::make_mut(&mut self.renamed)
+                .insert(renaming.feature_id.to_string(), title.clone().into());
+            set_feature_title(&mut self.features, renaming.feature_id, &title);
+            cx.emit(Event::RenameFeature {
+                feature_id: renaming.feature_id,
+                title,
+            });
+        }
+        self.focus_handle.focus(window, cx);
+        cx.notify();
+    }
+
     /// Convert Feature tree to TreeItem tree, collecting metadata.
     /// If directory_name is provided, wraps all features under a root directory node.
     fn convert_features_to_tree_items(
         features: &[Feature],
         directory_name: Option<&str>,
+        expanded: bool,
     ) -> (Vec<TreeItem>, HashMap<String, FeatureMetadata>) {
         let mut metadata = HashMap::new();
-        let feature_items = Self::convert_features_recursive(features, &mut metadata);
+        let feature_items = Self::convert_features_recursive(features, &mut metadata, expanded);
 
         // If we have a directory name, wrap features under a root node
         let items = if let Some(dir_name) = directory_name {
@@ -273,6 +701,7 @@ impl FeaturePanel {
     fn convert_features_recursive(
         features: &[Feature],
         metadata: &mut HashMap<String, FeatureMetadata>,
+        expanded: bool,
     ) -> Vec<TreeItem> {
         features
             .iter()
@@ -290,15 +719,59 @@ impl FeaturePanel {
                     },
                 );
 
-                let children = Self::convert_features_recursive(&feature.children, metadata);
+                let children =
+                    Self::convert_features_recursive(&feature.children, metadata, expanded);
 
                 TreeItem::new(id_str, feature.title.clone())
                     .children(children)
-                    .expanded(false) // Start collapsed
+                    .expanded(expanded)
             })
             .collect()
     }
 
+    /// Build one section per state listing its features flat, in tree order,
+    /// with the count in the section label. States with no features are left
+    /// out.
+    fn group_features_by_state(
+        features: &[Feature],
+    ) -> (Vec<TreeItem>, HashMap<String, FeatureMetadata>) {
+        let mut flat = Vec::new();
+        flatten_features(features, &mut flat);
+
+        let mut metadata = HashMap::new();
+        let mut items = Vec::new();
+        for (state, label) in STATE_GROUPS {
+            let children: Vec<TreeItem> = flat
+                .iter()
+                .filter(|feature| feature.state == state)
+                .map(|feature| {
+                    let id_str = feature.id.to_string();
+                    // Parents are listed as leaves here, so they can be opened
+                    metadata.insert(
+                        id_str.clone(),
+                        FeatureMetadata {
+                            id: feature.id,
+                            state: feature.state,
+                            has_children: false,
+                        },
+                    );
+                    TreeItem::new(id_str, feature.title.clone())
+                })
+                .collect();
+
+            if !children.is_empty() {
+                let section_id = format!("__state_{}__", label.to_lowercase());
+                items.push(
+                    TreeItem::new(section_id, format!("{} ({})", label, children.len()))
+                        .children(children)
+                        .expanded(true),
+                );
+            }
+        }
+
+        (items, metadata)
+    }
+
     /// Render a folder icon using gpui-component's SVG icons.
     fn render_folder_icon(is_expanded: bool) -> impl IntoElement {
         let icon_name = if is_expanded {
@@ -366,6 +839,238 @@ impl FeaturePanel {
             )
     }
 
+    /// Render a session progress badge: completed/total tasks, with a dot
+    /// while an agent is running.
+    fn render_progress_badge(progress: &FeatureProgress) -> impl IntoElement {
+        div()
+            .flex()
+            .items_center()
+            .gap(px(4.0))
+            .when(progress.running, |badge| {
+                badge.child(
+                    div()
+                        .w(px(6.0))
+                        .h(px(6.0))
+                        .rounded(px(3.0))
+                        .bg(colors::running_dot()),
+                )
+            })
+            .child(
+                div()
+                    .font_family("IBM Plex Mono")
+                    .text_color(colors::text_muted())
+                    .text_size(px(11.0))
+                    .child(format!("{}/{}", progress.completed, progress.total)),
+            )
+    }
+
+    /// Render the marker for a feature waiting on an unimplemented dependency.
+    fn render_blocked_label() -> impl IntoElement {
+        div()
+            .font_family("IBM Plex Sans")
+            .text_color(colors::proposed_amber())
+            .text_size(px(10.0))
+            .whitespace_nowrap()
+            .child("blocked")
+    }
+
+    /// Render a small tag chip.
+    fn render_tag_chip(tag: &str, active: bool) -> Div {
+        div()
+            .px(px(5.0))
+            .rounded(px(3.0))
+            .bg(if active {
+                colors::tag_active_background()
+            } else {
+                colors::tag_background()
+            })
+            .font_family("IBM Plex Sans")
+            .text_color(colors::text_muted())
+            .text_size(px(10.0))
+            .whitespace_nowrap()
+            .child(tag.to_string())
+    }
+
+    /// Render the row of tags the tree can be filtered by. Clicking a tag
+    /// adds it to the filter, narrowing the tree to features carrying every
+    /// selected tag; clicking a selected tag removes it.
+    fn render_tag_filter(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .id("tag-filter")
+            .px(px(12.0))
+            .py(px(6.0))
+            .flex()
+            .flex_wrap()
+            .gap(px(4.0))
+            .border_b_1()
+            .border_color(rgba(0x2d333aff))
+            .children(self.all_tags().into_iter().enumerate().map(|(ix, tag)| {
+                let active = self.tag_filter.contains(&tag);
+                Self::render_tag_chip(&tag, active)
+                    .id(("tag-filter-chip", ix))
+                    .cursor_pointer()
+                    .on_click(cx.listener(move |this, _, _, cx| {
+                        this.toggle_tag_filter(tag.clone(), cx);
+                    }))
+            }))
+    }
+
+    /// Render the title filter box above the tree.
+    fn render_title_filter(&self) -> impl IntoElement {
+        div()
+            .px(px(8.0))
+            .py(px(6.0))
+            .border_b_1()
+            .border_color(rgba(0x2d333aff))
+            .child(Input::new(&self.title_filter).small())
+    }
+
+    /// Render the control that switches between tree and by-state grouping.
+    fn render_grouping_toggle(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let (label, next) = match self.grouping {
+            SidebarGrouping::Tree => ("By state", SidebarGrouping::State),
+            SidebarGrouping::State => ("As tree", SidebarGrouping::Tree),
+        };
+        div()
+            .id("grouping-toggle")
+            .px(px(6.0))
+            .rounded(px(3.0))
+            .cursor_pointer()
+            .hover(|d| d.bg(colors::tag_background()))
+            .font_family("IBM Plex Sans")
+            .text_color(colors::text_muted())
+            .text_size(px(11.0))
+            .child(label)
+            .on_click(cx.listener(move |this, _, _, cx| {
+                this.set_grouping(next, cx);
+            }))
+    }
+
+    /// Render the footer that opens and closes the trash.
+    fn render_trash_toggle(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let label = if self.show_trash {
+            "Back to features".to_string()
+        } else {
+            format!("Trash ({})", self.trash.len())
+        };
+        div()
+            .id("trash-toggle")
+            .h(px(28.0))
+            .px(px(12.0))
+            .flex()
+            .items_center()
+            .gap(px(6.0))
+            .border_t_1()
+            .border_color(rgba(0x2d333aff))
+            .cursor_pointer()
+            .hover(|d| d.bg(colors::tag_background()))
+            .child(
+                Icon::new(IconName::Delete)
+                    .size_3()
+                    .text_color(Hsla::from(colors::text_muted())),
+            )
+            .child(
+                div()
+                    .font_family("IBM Plex Sans")
+                    .text_color(colors::text_muted())
+                    .text_size(px(11.0))
+                    .child(label),
+            )
+            .on_click(cx.listener(|this, _, _, cx| this.toggle_trash(cx)))
+    }
+
+    /// Render the deleted features, each with a button to restore it.
+    fn render_trash(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .id("trash-list")
+            .size_full()
+            .overflow_y_scroll()
+            .py(px(4.0))
+            .children(self.trash.iter().enumerate().map(|(ix, feature)| {
+                let id = feature.id;
+                div()
+                    .id(("trash-item", ix))
+                    .px(px(12.0))
+                    .py(px(4.0))
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .gap(px(6.0))
+                    .child(
+                        div()
+                            .flex()
+                            .flex_col()
+                            .overflow_hidden()
+                            .child(
+                                div()
+                                    .font_family("IBM Plex Sans")
+                                    .text_color(colors::text_primary())
+                                    .text_size(px(13.0))
+                                    .whitespace_nowrap()
+                                    .text_ellipsis()
+                                    .child(feature.title.clone()),
+                            )
+                            .child(
+                                div()
+                                    .font_family("IBM Plex Sans")
+                                    .text_color(colors::text_muted())
+                                    .text_size(px(10.0))
+                                    .child(format!("Deleted {}", feature.deleted_at)),
+                            ),
+                    )
+                    .child(
+                        div()
+                            .id(("trash-restore", ix))
+                            .px(px(6.0))
+                            .rounded(px(3.0))
+                            .cursor_pointer()
+                            .hover(|d| d.bg(colors::tag_background()))
+                            .font_family("IBM Plex Sans")
+                            .text_color(colors::text_muted())
+                            .text_size(px(11.0))
+                            .child("Restore")
+                            .on_click(cx.listener(move |_this, _, _, cx| {
+                                cx.emit(Event::RestoreFeature(id));
+                            })),
+                    )
+            }))
+    }
+
+    /// Render the icon for `state`.
+    fn render_state_icon(state: FeatureState) -> AnyElement {
+        match state {
+            FeatureState::Proposed => Self::render_proposed_icon().into_any_element(),
+            FeatureState::Specified => Self::render_specified_icon().into_any_element(),
+            FeatureState::Implemented => Self::render_implemented_icon().into_any_element(),
+            FeatureState::Deprecated => Self::render_deprecated_icon().into_any_element(),
+        }
+    }
+
+    /// Render one toggle per state, dimmed while its features are hidden.
+    fn render_state_filter(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .flex()
+            .items_center()
+            .children(
+                STATE_GROUPS
+                    .into_iter()
+                    .enumerate()
+                    .map(|(ix, (state, _))| {
+                        let hidden = self.hidden_states.contains(&state);
+                        div()
+                            .id(("state-filter", ix))
+                            .rounded(px(3.0))
+                            .cursor_pointer()
+                            .hover(|d| d.bg(colors::tag_background()))
+                            .when(hidden, |d| d.opacity(0.3))
+                            .child(Self::render_state_icon(state))
+                            .on_click(cx.listener(move |this, _, _, cx| {
+                                this.toggle_state_filter(state, cx);
+                            }))
+                    }),
+            )
+    }
+
     /// Render a deprecated state icon using gpui-component's Inbox (archive-like).
     fn render_deprecated_icon() -> impl IntoElement {
         Icon::new(IconName::Inbox)
@@ -388,6 +1093,18 @@ impl Render for FeaturePanel {
 
         // Cheap Rc clone for use in render closure (just increments refcount)
         let metadata = Rc::clone(&self.feature_metadata);
+        let progress = Rc::clone(&self.feature_progress);
+        let tags = Rc::clone(&self.feature_tags);
+        let blocked = Rc::clone(&self.blocked);
+        let tag_filter = self.tag_filter.clone();
+        let has_tags = !self.feature_tags.is_empty();
+        let has_trash = !self.trash.is_empty();
+        let tree_state = self.tree_state.clone();
+        let renamed = Rc::clone(&self.renamed);
+        let renaming = self
+            .renaming
+            .as_ref()
+            .map(|renaming| (renaming.feature_id.to_string(), renaming.input.clone()));
 
         div()
             .id("feature-panel")
@@ -399,6 +1116,11 @@ impl Render for FeaturePanel {
             .track_focus(&self.focus_handle)
             .key_context("FeaturePanel")
             .on_action(cx.listener(Self::on_open_feature))
+            .on_action(cx.listener(Self::on_new_feature))
+            .on_action(cx.listener(Self::on_new_child_feature))
+            .on_action(cx.listener(Self::on_delete_feature))
+            .on_action(cx.listener(Self::on_rename_feature))
+            .on_action(cx.listener(Self::on_cancel_rename))
             .child(
                 // Header
                 div()
@@ -406,6 +1128,7 @@ impl Render for FeaturePanel {
                     .px(px(12.0))
                     .flex()
                     .items_center()
+                    .justify_between()
                     .bg(colors::header_background())
                     .border_b_1()
                     .border_color(rgba(0x2d333aff))
@@ -416,8 +1139,20 @@ impl Render for FeaturePanel {
                             .text_size(px(12.0))
                             .font_weight(gpui::FontWeight::BOLD)
                             .child("MANIFEST"),
+                    )
+                    .child(
+                        div()
+                            .flex()
+                            .items_center()
+                            .gap(px(6.0))
+                            .child(self.render_state_filter(cx))
+                            .child(self.render_grouping_toggle(cx)),
                     ),
             )
+            .when(!self.show_trash, |d| d.child(self.render_title_filter()))
+            .when(has_tags && !self.show_trash, |d| {
+                d.child(self.render_tag_filter(cx))
+            })
             .child(
                 // Tree content
                 div()
@@ -433,6 +1168,7 @@ impl Render for FeaturePanel {
                         }),
                     )
                     .child(match &self.load_state {
+                        _ if self.show_trash => self.render_trash(cx).into_any_element(),
                         LoadState::Loading => div()
                             .p(px(12.0))
                             .font_family("IBM Plex Sans")
@@ -448,15 +1184,18 @@ impl Render for FeaturePanel {
                             .child(format!("Error: {}", err))
                             .into_any_element(),
                         LoadState::Loaded => {
-                            tree(
+                            let feature_tree = tree(
                                 &self.tree_state,
-                                move |_ix, entry, selected, _window, _cx| {
+                                move |ix, entry, selected, _window, _cx| {
                                     let depth = entry.depth();
                                     let indent = px(16.0 * depth as f32 + 8.0);
                                     let is_expanded = entry.is_expanded();
                                     let is_folder = entry.is_folder();
                                     let item_id = entry.item().id.to_string();
-                                    let label: SharedString = entry.item().label.clone();
+                                    let label: SharedString = renamed
+                                        .get(&item_id)
+                                        .cloned()
+                                        .unwrap_or_else(|| entry.item().label.clone());
 
                                     // Get metadata for this entry to render custom icon
                                     let meta = metadata.get(&item_id);
@@ -465,56 +1204,168 @@ impl Render for FeaturePanel {
                                     let icon = if is_folder {
                                         Self::render_folder_icon(is_expanded).into_any_element()
                                     } else if let Some(m) = meta {
-                                        match m.state {
-                                            FeatureState::Proposed => {
-                                                Self::render_proposed_icon().into_any_element()
-                                            }
-                                            FeatureState::Specified => {
-                                                Self::render_specified_icon().into_any_element()
-                                            }
-                                            FeatureState::Implemented => {
-                                                Self::render_implemented_icon().into_any_element()
-                                            }
-                                            FeatureState::Deprecated => {
-                                                Self::render_deprecated_icon().into_any_element()
-                                            }
-                                        }
+                                        Self::render_state_icon(m.state)
                                     } else {
                                         Self::render_proposed_icon().into_any_element()
                                     };
 
+                                    let badge = meta
+                                        .and_then(|m| progress.get(&m.id))
+                                        .map(Self::render_progress_badge);
+                                    let is_blocked = meta.is_some_and(|m| blocked.contains(&m.id));
+                                    let chips = meta
+                                        .and_then(|m| tags.get(&m.id))
+                                        .into_iter()
+                                        .flatten()
+                                        .map(|tag| {
+                                            let active = tag_filter.contains(tag);
+                                            Self::render_tag_chip(tag, active)
+                                        });
+
+                                    let title = match &renaming {
+                                        Some((id, input)) if *id == item_id => div()
+                                            .flex_1()
+                                            .child(Input::new(input).small())
+                                            .into_any_element(),
+                                        _ => div()
+                                            .font_family("IBM Plex Sans")
+                                            .text_color(colors::text_primary())
+                                            .text_size(px(13.0))
+                                            .overflow_hidden()
+                                            .whitespace_nowrap()
+                                            .text_ellipsis()
+                                            .child(label)
+                                            .into_any_element(),
+                                    };
+
+                                    let tree_state = tree_state.clone();
                                     ListItem::new(item_id)
                                         .py_0()
                                         .pl(indent)
                                         .selected(selected)
                                         .child(
                                             div()
+                                                // Right-click selects the row the menu acts on
+                                                .on_mouse_down(
+                                                    MouseButton::Right,
+                                                    move |_, _window, cx| {
+                                                        tree_state.update(cx, |state, cx| {
+                                                            state.set_selected_index(Some(ix), cx)
+                                                        });
+                                                    },
+                                                )
                                                 .flex()
                                                 .items_center()
                                                 .gap(px(6.0))
                                                 .child(icon)
-                                                .child(
-                                                    div()
-                                                        .font_family("IBM Plex Sans")
-                                                        .text_color(colors::text_primary())
-                                                        .text_size(px(13.0))
-                                                        .overflow_hidden()
-                                                        .whitespace_nowrap()
-                                                        .text_ellipsis()
-                                                        .child(label),
-                                                ),
+                                                .child(title)
+                                                .when(is_blocked, |d| {
+                                                    d.child(Self::render_blocked_label())
+                                                })
+                                                .children(chips)
+                                                .children(badge),
                                         )
                                 },
                             )
-                            .size_full()
-                            .into_any_element()
+                            .size_full();
+                            let metadata = Rc::clone(&self.feature_metadata);
+                            let tree_state = self.tree_state.clone();
+                            div()
+                                .size_full()
+                                .child(feature_tree)
+                                .context_menu(move |menu, _window, cx| {
+                                    let menu = menu.menu("New Feature", Box::new(NewFeature));
+                                    let has_feature =
+                                        tree_state.read(cx).selected_item().is_some_and(|item| {
+                                            metadata.contains_key(item.id.as_ref())
+                                        });
+                                    if has_feature {
+                                        menu.menu("New Child Feature", Box::new(NewChildFeature))
+                                            .menu("Rename", Box::new(RenameFeature))
+                                            .separator()
+                                            .menu("Delete", Box::new(DeleteFeature))
+                                    } else {
+                                        menu
+                                    }
+                                })
+                                .into_any_element()
                         }
                     }),
             )
+            .when(has_trash, |d| d.child(self.render_trash_toggle(cx)))
     }
 }
 
-/// Register key bindings for the feature panel.
-pub fn register_bindings(cx: &mut App) {
-    cx.bind_keys([KeyBinding::new("enter", OpenFeature, Some("FeaturePanel"))]);
+/// Prune `features` to those matching `keep`, keeping their ancestors so
+/// the matches stay in place in the tree.
+fn filter_features(features: &[Feature], keep: &dyn Fn(&Feature) -> bool) -> Vec<Feature> {
+    features
+        .iter()
+        .filter_map(|feature| {
+            let children = filter_features(&feature.children, keep);
+            (keep(feature) || !children.is_empty()).then(|| Feature {
+                children,
+                ..feature.clone()
+            })
+        })
+        .collect()
+}
+
+/// Collect every feature in `features`, depth first.
+fn flatten_features<'a>(features: &'a [Feature], out: &mut Vec<&'a Feature>) {
+    for feature in features {
+        out.push(feature);
+        flatten_features(&feature.children, out);
+    }
+}
+
+/// Set the title of feature `id` in `features`, wherever it is nested.
+fn set_feature_title(features: &mut [Feature], id: Uuid, title: &str) -> bool {
+    features.iter_mut().any(|feature| {
+        if feature.id == id {
+            feature.title = title.to_string();
+            true
+        } else {
+            set_feature_title(&mut feature.children, id, title)
+        }
+    })
+}
+
+/// Register the keymap's feature panel bindings.
+pub fn register_bindings(keymap: &Keymap, cx: &mut App) {
+    let bindings = keymap
+        .bindings("FeaturePanel")
+        .filter_map(|(keystroke, action)| match action {
+            "feature_panel::OpenFeature" => Some(KeyBinding::new(
+                keystroke,
+                OpenFeature,
+                Some("FeaturePanel"),
+            )),
+            "feature_panel::NewFeature" => {
+                Some(KeyBinding::new(keystroke, NewFeature, Some("FeaturePanel")))
+            }
+            "feature_panel::NewChildFeature" => Some(KeyBinding::new(
+                keystroke,
+                NewChildFeature,
+                Some("FeaturePanel"),
+            )),
+            "feature_panel::DeleteFeature" => Some(KeyBinding::new(
+                keystroke,
+                DeleteFeature,
+                Some("FeaturePanel"),
+            )),
+            "feature_panel::RenameFeature" => Some(KeyBinding::new(
+                keystroke,
+                RenameFeature,
+                Some("FeaturePanel"),
+            )),
+            "feature_panel::CancelRename" => Some(KeyBinding::new(
+                keystroke,
+                CancelRename,
+                Some("FeaturePanel"),
+            )),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    cx.bind_keys(bindings);
 }
@@ -0,0 +1,240 @@
+//! User-configurable key bindings.
+//!
+//! The keymap maps a context name and a keystroke to an action name. Built-in
+//! defaults live in `default-keymap.toml`; a user file at
+//! `~/.manifest/keymap.toml` with the same layout is merged over them. Each
+//! crate turns the action names it knows into GPUI bindings, and views that
+//! intercept keys themselves (the terminal) look keystrokes up directly.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context as _, Result};
+use gpui::{Global, Keystroke};
+
+/// Built-in bindings, also the reference for the user file's layout.
+pub const DEFAULT_KEYMAP: &str = include_str!("../default-keymap.toml");
+
+/// Context name for bindings that apply everywhere.
+pub const GLOBAL_CONTEXT: &str = "global";
+
+const USER_KEYMAP_DIR: &str = ".manifest";
+const USER_KEYMAP_FILE: &str = "keymap.toml";
+
+/// Modifier names in the order keystrokes are normalized to.
+const MODIFIERS: [&str; 5] = ["ctrl", "alt", "shift", "cmd", "fn"];
+
+/// Key bindings grouped by context: context -> keystroke -> action.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Keymap {
+    contexts: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+impl Global for Keymap {}
+
+impl Keymap {
+    /// Load the defaults merged with the user's keymap file.
+    /// Falls back to the defaults alone if the user file fails to parse.
+    pub fn load() -> Self {
+        let mut keymap = Self::defaults();
+        match Self::load_user() {
+            Ok(Some(user)) => keymap.merge(user),
+            Ok(None) => {}
+            Err(e) => eprintln!("Failed to load keymap, using defaults: {}", e),
+        }
+        keymap
+    }
+
+    /// The built-in bindings.
+    pub fn defaults() -> Self {
+        Self::from_toml(DEFAULT_KEYMAP).expect("default keymap is valid")
+    }
+
+    fn load_user() -> Result<Option<Self>> {
+        let path = user_keymap_path()?;
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let content = fs::read_to_string(&path).context("Failed to read keymap file")?;
+        Self::from_toml(&content).map(Some)
+    }
+
+    /// Parse a keymap in the `default-keymap.toml` layout.
+    ///
+    /// Keystrokes are normalized so `shift-ctrl-tab` and `ctrl-shift-tab`
+    /// refer to the same binding. An empty action is kept as an unbinding
+    /// marker for [`Keymap::merge`].
+    pub fn from_toml(content: &str) -> Result<Self> {
+        let raw: BTreeMap<String, BTreeMap<String, String>> =
+            toml::from_str(content).context("Failed to parse keymap")?;
+
+        let mut contexts = BTreeMap::new();
+        for (context, bindings) in raw {
+            let mut normalized = BTreeMap::new();
+            for (keystroke, action) in bindings {
+                let name = normalize_keystroke(&keystroke).with_context(|| {
+                    format!("Invalid keystroke in [{}]: {}", context, keystroke)
+                })?;
+                normalized.insert(name, action);
+            }
+            contexts.insert(context, normalized);
+        }
+
+        Ok(Self { contexts })
+    }
+
+    /// Apply `overrides` on top of this keymap. A keystroke bound to an empty
+    /// action removes the existing binding.
+    pub fn merge(&mut self, overrides: Keymap) {
+        for (context, bindings) in overrides.contexts {
+            let existing = self.contexts.entry(context).or_default();
+            for (keystroke, action) in bindings {
+                if action.is_empty() {
+                    existing.remove(&keystroke);
+                } else {
+                    existing.insert(keystroke, action);
+                }
+            }
+        }
+    }
+
+    /// Keystroke and action pairs bound in `context`.
+    pub fn bindings<'a>(&'a self, context: &str) -> impl Iterator<Item = (&'a str, &'a str)> {
+        self.contexts
+            .get(context)
+            .into_iter()
+            .flat_map(|bindings| bindings.iter())
+            .filter(|(_, action)| !action.is_empty())
+            .map(|(keystroke, action)| (keystroke.as_str(), action.as_str()))
+    }
+
+    /// Action bound to `keystroke` in `context`, if any.
+    pub fn action_for(&self, context: &str, keystroke: &Keystroke) -> Option<&str> {
+        let name = keystroke_name(keystroke);
+        self.contexts
+            .get(context)?
+            .get(&name)
+            .map(String::as_str)
+            .filter(|action| !action.is_empty())
+    }
+}
+
+/// Canonical name of a GPUI keystroke, matching normalized keymap entries.
+pub fn keystroke_name(keystroke: &Keystroke) -> String {
+    let m = &keystroke.modifiers;
+    let held = [m.control, m.alt, m.shift, m.platform, m.function];
+    join_keystroke(held, &keystroke.key.to_lowercase())
+}
+
+fn join_keystroke(held: [bool; MODIFIERS.len()], key: &str) -> String {
+    let mut name = String::new();
+    for (modifier, _) in MODIFIERS.iter().zip(held).filter(|(_, held)| *held) {
+        name.push_str(modifier);
+        name.push('-');
+    }
+    name.push_str(key);
+    name
+}
+
+/// Rewrite a keystroke like `Shift-Ctrl-Tab` as `ctrl-shift-tab`, accepting
+/// the usual aliases for each modifier.
+fn normalize_keystroke(keystroke: &str) -> Result<String> {
+    let keystroke = keystroke.trim().to_lowercase();
+    // A trailing "-" after a separator is the minus key itself.
+    let (modifiers, key) = match keystroke.strip_suffix("--") {
+        Some(rest) => (rest, "-"),
+        None => match keystroke.rsplit_once('-') {
+            Some((modifiers, key)) => (modifiers, key),
+            None => ("", keystroke.as_str()),
+        },
+    };
+    if key.is_empty() || key.contains(char::is_whitespace) {
+        anyhow::bail!("missing key");
+    }
+
+    let mut held = [false; MODIFIERS.len()];
+    for modifier in modifiers.split('-').filter(|m| !m.is_empty()) {
+        let canonical = match modifier {
+            "control" => "ctrl",
+            "option" => "alt",
+            "super" | "platform" | "win" => "cmd",
+            "function" => "fn",
+            other => other,
+        };
+        let index = MODIFIERS
+            .iter()
+            .position(|m| *m == canonical)
+            .with_context(|| format!("unknown modifier \"{}\"", modifier))?;
+        held[index] = true;
+    }
+
+    let name = join_keystroke(held, key);
+    if Keystroke::parse(&name).is_err() {
+        anyhow::bail!("unrecognized key \"{}\"", key);
+    }
+    Ok(name)
+}
+
+fn user_keymap_path() -> Result<PathBuf> {
+    let mut path =
+        dirs::home_dir().ok_or_else(|| anyhow::anyhow!("Could not determine home directory"))?;
+    path.push(USER_KEYMAP_DIR);
+    path.push(USER_KEYMAP_FILE);
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_modifier_order_and_aliases() {
+        assert_eq!(
+            normalize_keystroke("Shift-Control-Tab").unwrap(),
+            "ctrl-shift-tab"
+        );
+        assert_eq!(normalize_keystroke("cmd--").unwrap(), "cmd--");
+        assert!(normalize_keystroke("hyper-k").is_err());
+    }
+
+    #[test]
+    fn user_bindings_override_and_remove_defaults() {
+        let mut keymap = Keymap::defaults();
+        let user = Keymap::from_toml(
+            r#"
+            [FeatureEditor]
+            "ctrl-s" = "feature_editor::Save"
+            "cmd-s" = ""
+
+            [global]
+            "cmd-q" = "app::Open"
+            "#,
+        )
+        .unwrap();
+        keymap.merge(user);
+
+        let editor: Vec<_> = keymap.bindings("FeatureEditor").collect();
+        assert_eq!(
+            editor,
+            vec![
+                ("cmd-f", "feature_editor::Find"),
+                ("cmd-g", "feature_editor::FindNext"),
+                ("cmd-shift-c", "feature_editor::CopyAsMarkdown"),
+                ("cmd-shift-e", "feature_editor::OpenInExternalEditor"),
+                ("cmd-shift-g", "feature_editor::FindPrevious"),
+                ("cmd-shift-x", "feature_editor::ToggleTask"),
+                ("ctrl-g", "feature_editor::GoToLine"),
+                ("ctrl-s", "feature_editor::Save"),
+                ("shift-tab", "feature_editor::Outdent"),
+                ("tab", "feature_editor::Indent"),
+            ]
+        );
+        assert!(
+            keymap
+                .bindings(GLOBAL_CONTEXT)
+                .any(|binding| binding == ("cmd-q", "app::Open"))
+        );
+    }
+}
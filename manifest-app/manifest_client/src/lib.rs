@@ -1,5 +1,7 @@
 //! HTTP client for the Manifest API.
 
+use std::io::{BufRead, BufReader, Read};
+
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use uuid::Uuid;
@@ -13,6 +15,8 @@ pub enum ClientError {
     Io(#[from] std::io::Error),
     #[error("Server returned error: {0}")]
     Server(String),
+    #[error("Feature changed elsewhere: {0}")]
+    Conflict(String),
 }
 
 /// Feature state in the Manifest system.
@@ -80,6 +84,8 @@ pub struct Task {
     pub worktree_path: Option<String>,
     #[serde(default)]
     pub branch: Option<String>,
+    #[serde(default)]
+    pub log_path: Option<String>,
     pub created_at: String,
 }
 
@@ -157,6 +163,13 @@ impl ManifestClient {
         Self::new("http://localhost:17010/api/v1")
     }
 
+    /// Check that the server is up (blocking).
+    pub fn health(&self) -> Result<(), ClientError> {
+        let url = format!("{}/health", self.base_url);
+        ureq::get(&url).call()?;
+        Ok(())
+    }
+
     /// Get the list of projects (blocking).
     pub fn get_projects(&self) -> Result<Vec<Project>, ClientError> {
         let url = format!("{}/projects", self.base_url);
@@ -221,11 +234,15 @@ impl ManifestClient {
     }
 
     /// Update a feature's title and details (blocking).
+    ///
+    /// When `expected_updated_at` is set, the server rejects the write with
+    /// `ClientError::Conflict` if the feature was modified after that time.
     pub fn update_feature_full(
         &self,
         id: &Uuid,
         title: Option<String>,
         details: Option<String>,
+        expected_updated_at: Option<&str>,
     ) -> Result<Feature, ClientError> {
         let url = format!("{}/features/{}", self.base_url, id);
         let mut body = serde_json::Map::new();
@@ -235,9 +252,27 @@ impl ManifestClient {
         if let Some(d) = details {
             body.insert("details".to_string(), serde_json::Value::String(d));
         }
-        let response: Feature = ureq::put(&url)
-            .send_json(&serde_json::Value::Object(body))?
-            .into_json()?;
+        if let Some(v) = expected_updated_at {
+            body.insert(
+                "expected_updated_at".to_string(),
+                serde_json::Value::String(v.to_string()),
+            );
+        }
+        match ureq::put(&url).send_json(&serde_json::Value::Object(body)) {
+            Ok(response) => Ok(response.into_json()?),
+            Err(ureq::Error::Status(409, response)) => Err(ClientError::Conflict(
+                response.into_string().unwrap_or_default(),
+            )),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Replace a feature's tags (blocking). Returns the tags as stored,
+    /// normalized to lowercase and sorted.
+    pub fn set_feature_tags(&self, id: &Uuid, tags: &[String]) -> Result<Vec<String>, ClientError> {
+        let url = format!("{}/features/{}/tags", self.base_url, id);
+        let body = serde_json::json!({ "tags": tags });
+        let response: Vec<String> = ureq::put(&url).send_json(&body)?.into_json()?;
         Ok(response)
     }
 
@@ -254,4 +289,116 @@ impl ManifestClient {
         let response: Vec<Task> = ureq::get(&url).call()?.into_json()?;
         Ok(response)
     }
+
+    /// Open the server's change stream (blocking). Iterating the result
+    /// blocks until the next event and ends when the connection closes.
+    pub fn events(&self) -> Result<EventStream, ClientError> {
+        let url = format!("{}/events", self.base_url);
+        let response = ureq::get(&url).call()?;
+        Ok(EventStream::new(response.into_reader()))
+    }
+}
+
+/// One server-sent event, such as a `change` whose data is a JSON change.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerEvent {
+    pub event: String,
+    pub data: String,
+}
+
+/// Events read off a server-sent event stream.
+pub struct EventStream {
+    reader: BufReader<Box<dyn Read + Send + Sync>>,
+}
+
+impl EventStream {
+    fn new(reader: Box<dyn Read + Send + Sync>) -> Self {
+        Self {
+            reader: BufReader::new(reader),
+        }
+    }
+}
+
+impl Iterator for EventStream {
+    type Item = Result<ServerEvent, ClientError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut event = String::new();
+        let mut data = Vec::new();
+        let mut line = String::new();
+        loop {
+            line.clear();
+            match self.reader.read_line(&mut line) {
+                Ok(0) => return None,
+                Ok(_) => {}
+                Err(e) => return Some(Err(e.into())),
+            }
+            let line = line.trim_end_matches(['\r', '\n']);
+            if line.is_empty() {
+                // Keep-alive comments end without an event
+                if event.is_empty() && data.is_empty() {
+                    continue;
+                }
+                let event = if event.is_empty() {
+                    "message".to_string()
+                } else {
+                    event
+                };
+                return Some(Ok(ServerEvent {
+                    event,
+                    data: data.join("\n"),
+                }));
+            }
+            let (field, value) = line.split_once(':').unwrap_or((line, ""));
+            let value = value.strip_prefix(' ').unwrap_or(value);
+            match field {
+                "event" => event = value.to_string(),
+                "data" => data.push(value.to_string()),
+                // Comments (empty field), ids and retry hints are ignored
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn events(stream: &str) -> Vec<ServerEvent> {
+        let reader: Box<dyn Read + Send + Sync> =
+            Box::new(std::io::Cursor::new(stream.to_string()));
+        EventStream::new(reader).map(|e| e.unwrap()).collect()
+    }
+
+    #[test]
+    fn reads_events_and_skips_keep_alives() {
+        let stream =
+            ":\n\nevent: change\ndata: {\"a\":1}\n\n:\n\nevent: resync\r\ndata: {}\r\n\r\n";
+        assert_eq!(
+            events(stream),
+            vec![
+                ServerEvent {
+                    event: "change".into(),
+                    data: "{\"a\":1}".into(),
+                },
+                ServerEvent {
+                    event: "resync".into(),
+                    data: "{}".into(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn joins_data_lines_and_drops_an_unfinished_event() {
+        let stream = "data: one\ndata: two\n\nevent: change\ndata: cut off";
+        assert_eq!(
+            events(stream),
+            vec![ServerEvent {
+                event: "message".into(),
+                data: "one\ntwo".into(),
+            }]
+        );
+    }
 }
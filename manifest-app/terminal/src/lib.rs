@@ -1,7 +1,11 @@
 //! Terminal emulation layer wrapping alacritty_terminal for GPUI applications.
 
 pub mod mappings;
+pub mod pty_log;
+pub mod scrollbar;
 pub mod terminal_hyperlinks;
+#[cfg(test)]
+mod test_support;
 
 pub use alacritty_terminal;
 use alacritty_terminal::term::search::Match;
@@ -10,11 +14,14 @@ use alacritty_terminal::{
     Term,
     event::{Event as AlacTermEvent, EventListener, Notify, WindowSize},
     event_loop::{EventLoop, Notifier},
-    grid::Dimensions,
+    grid::{Dimensions, Scroll},
     index::{Column, Line, Point as AlacPoint},
     selection::SelectionRange,
     sync::FairMutex,
-    term::{Config, RenderableCursor, TermMode, cell::Cell},
+    term::{
+        Config, RenderableCursor, TermMode,
+        cell::{Cell, Flags},
+    },
     tty,
     vte::ansi::{CursorShape as AlacCursorShape, CursorStyle},
 };
@@ -22,11 +29,20 @@ use anyhow::{Context as _, Result};
 use futures::StreamExt;
 use futures::channel::mpsc::{UnboundedReceiver, UnboundedSender, unbounded};
 use gpui::{
-    Bounds, Context, EventEmitter, Keystroke, Modifiers, MouseButton, Pixels, Point, Size, Task, px,
+    Bounds, ClipboardItem, Context, EventEmitter, Keystroke, Modifiers, MouseButton, Pixels, Point,
+    Size, Task, px,
+};
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    ops::Deref,
+    path::{Path, PathBuf},
+    sync::Arc,
 };
-use std::{borrow::Cow, ops::Deref, path::PathBuf, sync::Arc};
 
 use crate::mappings::keys::to_esc_str;
+use crate::mappings::mouse::{MouseReportButton, MouseReportKind, mouse_report, mouse_reporting};
+use crate::pty_log::{LoggingPty, OUTPUT_LOG_MAX_BYTES, OutputLog, PtyLog};
 use crate::terminal_hyperlinks::{UrlSearch, find_url_at_point};
 
 // Re-export key types
@@ -35,6 +51,10 @@ pub use alacritty_terminal::term::TermMode as Mode;
 
 const DEFAULT_SCROLL_HISTORY_LINES: usize = 10_000;
 
+/// Characters that end a word for double-click (semantic) selection. Matches
+/// the alacritty default, so paths like `src/main.rs` select as one word.
+pub const DEFAULT_WORD_SEPARATORS: &str = ",│`|:\"' ()[]{}<>\t";
+
 /// Events emitted by the Terminal entity upward to the view layer.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Event {
@@ -161,6 +181,8 @@ pub struct TerminalContent {
     pub cells: Vec<IndexedCell>,
     pub mode: TermMode,
     pub display_offset: usize,
+    /// Lines of scrollback above the screen.
+    pub history_size: usize,
     pub selection: Option<SelectionRange>,
     pub cursor: RenderableCursor,
     pub cursor_char: char,
@@ -175,6 +197,7 @@ impl Default for TerminalContent {
             cells: Vec::new(),
             mode: TermMode::empty(),
             display_offset: 0,
+            history_size: 0,
             selection: None,
             cursor: RenderableCursor {
                 shape: AlacCursorShape::Block,
@@ -187,17 +210,78 @@ impl Default for TerminalContent {
     }
 }
 
+impl TerminalContent {
+    /// The visible screen as plain text, without styling or the padding of
+    /// the render grid: trailing blanks are trimmed from each row, trailing
+    /// empty rows are dropped, and rows the terminal soft-wrapped are joined
+    /// back into one line.
+    pub fn to_plain_string(&self) -> String {
+        let mut text = String::new();
+        let mut row = String::new();
+        let mut row_wrapped = false;
+        let mut current_line = None;
+
+        for cell in &self.cells {
+            if current_line != Some(cell.point.line) {
+                if current_line.is_some() {
+                    push_row(&mut text, &row, row_wrapped);
+                }
+                current_line = Some(cell.point.line);
+                row.clear();
+                row_wrapped = false;
+            }
+            // The last cell of a soft-wrapped row carries the flag
+            row_wrapped = cell.flags.contains(Flags::WRAPLINE);
+            if cell
+                .flags
+                .intersects(Flags::WIDE_CHAR_SPACER | Flags::LEADING_WIDE_CHAR_SPACER)
+            {
+                continue;
+            }
+            row.push(cell.c);
+            if let Some(zerowidth) = cell.zerowidth() {
+                row.extend(zerowidth);
+            }
+        }
+        if current_line.is_some() {
+            push_row(&mut text, &row, row_wrapped);
+        }
+
+        text.truncate(text.trim_end().len());
+        text
+    }
+}
+
+/// Append a screen row to `text`, ending the line unless it was soft-wrapped.
+fn push_row(text: &mut String, row: &str, wrapped: bool) {
+    if wrapped {
+        text.push_str(row);
+    } else {
+        text.push_str(row.trim_end());
+        text.push('\n');
+    }
+}
+
 /// Builder for creating a Terminal with a PTY.
 pub struct TerminalBuilder {
     pub term: Arc<FairMutex<Term<ManifestListener>>>,
     pub pty_tx: Notifier,
     pub events_rx: UnboundedReceiver<AlacTermEvent>,
+    pub pty_log: PtyLog,
+    pub output_log: OutputLog,
 }
 
 impl TerminalBuilder {
     /// Create a new terminal with a PTY connected to the user's default shell.
-    pub fn new(working_directory: Option<PathBuf>, window_id: u64) -> Result<Self> {
-        Self::new_with_shell(working_directory, window_id, None, vec![])
+    ///
+    /// The shell gets the app's environment with `env` set on top; see
+    /// [`shell_env`].
+    pub fn new(
+        working_directory: Option<PathBuf>,
+        window_id: u64,
+        env: HashMap<String, String>,
+    ) -> Result<Self> {
+        Self::new_with_shell(working_directory, window_id, None, vec![], env)
     }
 
     /// Create a new terminal with a PTY connected to a specific shell/command.
@@ -206,6 +290,7 @@ impl TerminalBuilder {
         window_id: u64,
         shell: Option<String>,
         args: Vec<String>,
+        env: HashMap<String, String>,
     ) -> Result<Self> {
         let shell_config = shell.map(|program| tty::Shell::new(program, args));
 
@@ -213,23 +298,20 @@ impl TerminalBuilder {
             shell: shell_config,
             working_directory,
             drain_on_exit: true,
-            env: std::env::vars().collect(),
+            env: shell_env(env),
         };
 
-        let config = Config {
-            scrolling_history: DEFAULT_SCROLL_HISTORY_LINES,
-            default_cursor_style: CursorStyle {
-                shape: AlacCursorShape::Block,
-                blinking: false,
-            },
-            ..Config::default()
-        };
+        let config = term_config(DEFAULT_WORD_SEPARATORS);
 
         let (events_tx, events_rx) = unbounded();
         let listener = ManifestListener(events_tx);
 
         let pty = tty::new(&pty_options, TerminalBounds::default().into(), window_id)
             .context("Failed to create PTY")?;
+        let pty_log = PtyLog::default();
+        let output_log = OutputLog::default();
+        let pty = LoggingPty::new(pty, pty_log.clone(), output_log.clone())
+            .context("Failed to wrap PTY")?;
 
         let term = Term::new(config, &TerminalBounds::default(), listener.clone());
         let term = Arc::new(FairMutex::new(term));
@@ -252,6 +334,8 @@ impl TerminalBuilder {
             term,
             pty_tx,
             events_rx,
+            pty_log,
+            output_log,
         })
     }
 
@@ -259,6 +343,8 @@ impl TerminalBuilder {
     pub fn build(self, cx: &mut Context<Terminal>) -> Terminal {
         let term = self.term;
         let pty_tx = self.pty_tx;
+        let pty_log = self.pty_log;
+        let output_log = self.output_log;
         let mut events_rx = self.events_rx;
 
         let event_loop_task = cx.spawn(async move |terminal, cx| {
@@ -278,6 +364,15 @@ impl TerminalBuilder {
             url_search: UrlSearch::new(),
             mouse_down_url: None,
             hovered_hyperlink: None,
+            pty_log,
+            output_log,
+            scroll_px: px(0.),
+            follow_output: true,
+            history_size: 0,
+            mouse_report_button: None,
+            last_mouse_report_point: None,
+            allow_clipboard_writes: false,
+            option_as_meta: false,
         }
     }
 }
@@ -295,16 +390,79 @@ pub struct Terminal {
     mouse_down_url: Option<String>,
     /// Currently hovered hyperlink range (when Cmd is held).
     hovered_hyperlink: Option<Match>,
+    /// Raw PTY traffic log, inactive unless `start_logging` is called.
+    pty_log: PtyLog,
+    /// Program output saved to a file, inactive unless `start_output_log`
+    /// is called.
+    output_log: OutputLog,
+    /// Scroll wheel movement not yet amounting to a whole line.
+    scroll_px: Pixels,
+    /// Whether new output keeps the view on the live screen. Cleared when the
+    /// user scrolls up, restored when they return to the bottom or type.
+    follow_output: bool,
+    /// Scrollback length at the last wakeup, to detect new output lines.
+    history_size: usize,
+    /// Button held while the program is receiving mouse reports.
+    mouse_report_button: Option<MouseReportButton>,
+    /// Cell of the last reported motion, to avoid repeating it.
+    last_mouse_report_point: Option<AlacPoint>,
+    /// Whether programs may set the clipboard with OSC 52.
+    allow_clipboard_writes: bool,
+    /// Whether Option+key sends ESC followed by the key on macOS.
+    option_as_meta: bool,
 }
 
 impl EventEmitter<Event> for Terminal {}
 
+impl Drop for Terminal {
+    /// Stop the event loop when the tab goes away. Its thread otherwise keeps
+    /// polling the PTY, and the shell and anything started from it keep
+    /// running. Dropping the PTY sends the child SIGHUP and reaps it.
+    fn drop(&mut self) {
+        self.pty_tx
+            .0
+            .send(alacritty_terminal::event_loop::Msg::Shutdown)
+            .ok();
+    }
+}
+
 impl Terminal {
     /// Get the current terminal content for rendering.
     pub fn last_content(&self) -> &TerminalContent {
         &self.last_content
     }
 
+    /// The whole buffer, scrollback included, as plain text.
+    pub fn scrollback_text(&self) -> String {
+        scrollback_text(&self.term.lock())
+    }
+
+    /// Drop the scrollback, keeping the lines on screen.
+    pub fn clear_scrollback(&mut self) {
+        clear_history(&mut self.term.lock());
+        self.history_size = 0;
+        self.follow_output = true;
+        self.scroll_px = px(0.);
+        self.sync_content();
+    }
+
+    /// Let programs set the clipboard with OSC 52 (`ESC ] 52 ; c ; <base64> BEL`).
+    ///
+    /// Off by default: any output, such as `cat` of an untrusted file, could
+    /// otherwise replace the clipboard contents.
+    pub fn set_allow_clipboard_writes(&mut self, allow: bool) {
+        self.allow_clipboard_writes = allow;
+    }
+
+    /// Send Option+key on macOS as ESC followed by the key, the Meta
+    /// behavior Emacs and readline expect.
+    ///
+    /// Off by default, so Option keeps typing the characters macOS layouts
+    /// put on it. Alt always sends ESC on other platforms.
+    pub fn set_option_as_meta(&mut self, option_as_meta: bool) {
+        self.option_as_meta = option_as_meta;
+    }
+
     /// Update the terminal size.
     pub fn set_size(&mut self, bounds: TerminalBounds) {
         let mut term = self.term.lock();
@@ -316,15 +474,77 @@ impl Terminal {
         self.last_content.terminal_bounds = bounds;
     }
 
-    /// Write input to the PTY.
+    /// Write user input to the PTY, returning the view to the live screen.
     pub fn input(&mut self, input: impl Into<Cow<'static, [u8]>>) {
+        if !self.follow_output || self.last_content.display_offset != 0 {
+            self.scroll_to_bottom();
+        }
         self.pty_tx.notify(input.into().into_owned());
     }
 
+    /// Whether new output keeps the view on the live screen.
+    pub fn follow_output(&self) -> bool {
+        self.follow_output
+    }
+
+    /// Turn output following on (jumping to the bottom) or off, which holds
+    /// the view in place as output arrives.
+    pub fn set_follow_output(&mut self, follow: bool) {
+        if follow {
+            self.scroll_to_bottom();
+        } else {
+            self.follow_output = false;
+        }
+    }
+
+    /// Characters that end a word for semantic selection.
+    pub fn word_separators(&self) -> String {
+        self.term.lock().semantic_escape_chars().to_string()
+    }
+
+    /// Change the characters that end a word for semantic selection.
+    pub fn set_word_separators(&mut self, separators: &str) {
+        self.term.lock().set_options(term_config(separators));
+    }
+
+    /// Start appending all bytes sent to and received from the PTY to `path`.
+    ///
+    /// Errors opening the file are returned; later write failures only
+    /// disable logging, leaving the terminal unaffected.
+    pub fn start_logging(&mut self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        self.pty_log.start(path.as_ref())
+    }
+
+    /// Stop logging PTY traffic.
+    pub fn stop_logging(&mut self) {
+        self.pty_log.stop();
+    }
+
+    /// Path of the active PTY log, if logging is on.
+    pub fn log_path(&self) -> Option<PathBuf> {
+        self.pty_log.path()
+    }
+
+    /// Start appending program output to `path`, rotating it to `<path>.1`
+    /// once it reaches 10 MiB.
+    pub fn start_output_log(&mut self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        self.output_log.start(path.as_ref(), OUTPUT_LOG_MAX_BYTES)
+    }
+
+    /// Stop saving program output.
+    pub fn stop_output_log(&mut self) {
+        self.output_log.stop();
+    }
+
+    /// Path of the file program output is saved to, if any.
+    pub fn output_log_path(&self) -> Option<PathBuf> {
+        self.output_log.path()
+    }
+
     /// Try to handle a keystroke, returning true if handled.
     pub fn try_keystroke(&mut self, keystroke: &Keystroke) -> bool {
         let mode = self.last_content.mode;
-        if let Some(esc_str) = to_esc_str(keystroke, &mode, false) {
+        if let Some(esc_str) = to_esc_str(keystroke, &mode, self.option_as_meta) {
             self.input(esc_str.into_owned().into_bytes());
             true
         } else if let Some(key_char) = &keystroke.key_char {
@@ -343,6 +563,87 @@ impl Terminal {
         }
     }
 
+    /// Scroll the viewport through scrollback. Positive values move up into history.
+    pub fn scroll_lines(&mut self, lines: i32) {
+        self.term.lock().scroll_display(Scroll::Delta(lines));
+        self.sync_content();
+        self.follow_output = self.last_content.display_offset == 0;
+    }
+
+    /// Scroll by a fraction of the visible height, at least one line.
+    /// Positive values move up into history.
+    pub fn scroll_pages(&mut self, pages: f32) {
+        let num_lines = self.last_content.terminal_bounds.num_lines() as f32;
+        let lines = (num_lines * pages).round() as i32;
+        let lines = if lines == 0 {
+            pages.signum() as i32
+        } else {
+            lines
+        };
+        self.scroll_lines(lines);
+    }
+
+    /// Scroll so the screen starts `offset` lines up into the scrollback.
+    pub fn scroll_to_offset(&mut self, offset: usize) {
+        let delta = offset as i32 - self.last_content.display_offset as i32;
+        if delta != 0 {
+            self.scroll_lines(delta);
+        }
+    }
+
+    /// Scroll to the oldest line of scrollback.
+    pub fn scroll_to_top(&mut self) {
+        self.term.lock().scroll_display(Scroll::Top);
+        self.sync_content();
+        self.follow_output = self.last_content.display_offset == 0;
+    }
+
+    /// Scroll back to the live screen and resume following output.
+    pub fn scroll_to_bottom(&mut self) {
+        self.term.lock().scroll_display(Scroll::Bottom);
+        self.sync_content();
+        self.follow_output = true;
+    }
+
+    /// Handle a vertical scroll wheel movement in pixels (positive is up).
+    ///
+    /// Programs with mouse reporting on receive wheel reports. Otherwise the
+    /// alternate screen has no scrollback, so full-screen programs (vim,
+    /// less, htop) receive the movement as arrow keys instead.
+    pub fn scroll_wheel(&mut self, delta_y: Pixels, position: Point<Pixels>, modifiers: Modifiers) {
+        let line_height = self.last_content.terminal_bounds.line_height;
+        if line_height <= px(0.) {
+            return;
+        }
+
+        self.scroll_px += delta_y;
+        let lines = (self.scroll_px / line_height) as i32;
+        if lines == 0 {
+            return;
+        }
+        self.scroll_px -= line_height * lines as f32;
+
+        let mode = self.last_content.mode;
+        if self.reports_mouse(modifiers) {
+            let button = if lines > 0 {
+                MouseReportButton::WheelUp
+            } else {
+                MouseReportButton::WheelDown
+            };
+            for _ in 0..lines.unsigned_abs() {
+                self.report_mouse(button, MouseReportKind::Press, position, modifiers);
+            }
+        } else if mode.contains(TermMode::ALT_SCREEN | TermMode::ALTERNATE_SCROLL)
+            && !modifiers.shift
+        {
+            // Alternate scroll mode (DECSET 1007), on unless the program
+            // turns it off, lets pagers and editors scroll with the wheel.
+            self.input(alt_screen_scroll(lines, mode));
+        } else {
+            self.scroll_lines(lines);
+        }
+    }
+
     /// Get the terminal mode flags.
     pub fn mode(&self) -> TermMode {
         *self.term.lock().mode()
@@ -352,6 +653,7 @@ impl Terminal {
     fn process_event(&mut self, event: AlacTermEvent, cx: &mut Context<Self>) {
         match event {
             AlacTermEvent::Wakeup => {
+                self.hold_scroll_position();
                 self.sync_content();
                 cx.emit(Event::Wakeup);
                 cx.notify();
@@ -366,7 +668,15 @@ impl Terminal {
                 cx.emit(Event::CloseTerminal);
             }
             AlacTermEvent::PtyWrite(text) => {
-                self.input(text.into_bytes());
+                // Replies to terminal queries, not user input: leave the view alone
+                self.pty_tx.notify(text.into_bytes());
+            }
+            AlacTermEvent::ClipboardStore(_, text) => {
+                // alacritty has already decoded the base64 payload and drops
+                // sequences that aren't valid base64 or UTF-8
+                if self.allow_clipboard_writes {
+                    cx.write_to_clipboard(ClipboardItem::new_string(text));
+                }
             }
             _ => {}
         }
@@ -379,6 +689,15 @@ impl Terminal {
         position: Point<Pixels>,
         modifiers: Modifiers,
     ) -> bool {
+        if self.reports_mouse(modifiers) {
+            if let Some(button) = MouseReportButton::from_button(button) {
+                self.mouse_report_button = Some(button);
+                self.report_mouse(button, MouseReportKind::Press, position, modifiers);
+            }
+            self.hovered_hyperlink = None;
+            return true;
+        }
+
         // Only handle Cmd+left click for hyperlinks
         if button != MouseButton::Left || !modifiers.platform {
             self.hovered_hyperlink = None;
@@ -408,6 +727,14 @@ impl Terminal {
         modifiers: Modifiers,
         cx: &mut Context<Self>,
     ) -> bool {
+        if let Some(held) = self.mouse_report_button.take() {
+            if self.reports_mouse(modifiers) {
+                let button = MouseReportButton::from_button(button).unwrap_or(held);
+                self.report_mouse(button, MouseReportKind::Release, position, modifiers);
+            }
+            return false;
+        }
+
         // Only handle left click release
         if button != MouseButton::Left {
             return false;
@@ -437,6 +764,12 @@ impl Terminal {
 
     /// Update hover state when mouse moves with Cmd held.
     pub fn mouse_move(&mut self, position: Point<Pixels>, modifiers: Modifiers) {
+        if self.reports_mouse(modifiers) {
+            let button = self.mouse_report_button.unwrap_or(MouseReportButton::None);
+            self.report_mouse(button, MouseReportKind::Motion, position, modifiers);
+            return;
+        }
+
         if !modifiers.platform {
             if self.hovered_hyperlink.is_some() {
                 self.hovered_hyperlink = None;
@@ -466,56 +799,209 @@ impl Terminal {
         self.hovered_hyperlink = None;
     }
 
-    /// Convert a pixel position (relative to terminal bounds origin) to a grid point.
-    fn pixel_to_grid_point(&self, position: Point<Pixels>) -> Option<AlacPoint> {
-        let bounds = &self.last_content.terminal_bounds;
+    /// Whether mouse events go to the program. Holding Shift keeps them local,
+    /// as in other terminals.
+    fn reports_mouse(&self, modifiers: Modifiers) -> bool {
+        mouse_reporting(&self.last_content.mode) && !modifiers.shift
+    }
 
-        // Check if position is within bounds
-        if position.x < Pixels::ZERO || position.y < Pixels::ZERO {
-            return None;
+    /// Send a mouse report for a pixel position if the current mode wants it.
+    fn report_mouse(
+        &mut self,
+        button: MouseReportButton,
+        kind: MouseReportKind,
+        position: Point<Pixels>,
+        modifiers: Modifiers,
+    ) {
+        let Some(point) = self.pixel_to_viewport_point(position, kind != MouseReportKind::Press)
+        else {
+            return;
+        };
+
+        if kind == MouseReportKind::Motion {
+            if self.last_mouse_report_point == Some(point) {
+                return;
+            }
+            self.last_mouse_report_point = Some(point);
         }
 
-        let col = (position.x / bounds.cell_width).floor() as i32;
-        let line = (position.y / bounds.line_height).floor() as i32;
+        let mode = self.last_content.mode;
+        if let Some(bytes) = mouse_report(point, button, kind, modifiers, &mode) {
+            self.input(bytes);
+        }
+    }
 
-        // Clamp to valid range
+    /// Convert a pixel position to a visible cell, ignoring scrollback. Positions
+    /// outside the grid are clamped to its edge when `clamp` is set, so drags
+    /// that leave the terminal still report a cell.
+    fn pixel_to_viewport_point(&self, position: Point<Pixels>, clamp: bool) -> Option<AlacPoint> {
+        let bounds = &self.last_content.terminal_bounds;
         let num_cols = bounds.num_columns() as i32;
         let num_lines = bounds.num_lines() as i32;
+        if num_cols == 0 || num_lines == 0 {
+            return None;
+        }
 
-        if col < 0 || col >= num_cols || line < 0 || line >= num_lines {
+        let col = (position.x / bounds.cell_width).floor() as i32;
+        let line = (position.y / bounds.line_height).floor() as i32;
+        if !clamp && (col < 0 || col >= num_cols || line < 0 || line >= num_lines) {
             return None;
         }
 
-        // Account for display offset (scrollback)
-        let display_offset = self.last_content.display_offset as i32;
-        let adjusted_line = line - display_offset;
+        Some(AlacPoint::new(
+            Line(line.clamp(0, num_lines - 1)),
+            Column(col.clamp(0, num_cols - 1) as usize),
+        ))
+    }
+
+    /// Convert a pixel position (relative to terminal bounds origin) to a grid point.
+    fn pixel_to_grid_point(&self, position: Point<Pixels>) -> Option<AlacPoint> {
+        let term = self.term.lock();
+        grid_point_at(
+            &term,
+            &self.last_content.terminal_bounds,
+            self.last_content.display_offset,
+            position,
+        )
+    }
+
+    /// Keep the view on the same lines while output is not being followed.
+    ///
+    /// Alacritty already holds the position once scrolled into history, but
+    /// at the bottom new lines would still move the view.
+    fn hold_scroll_position(&mut self) {
+        let mut term = self.term.lock();
+        let history_size = term.grid().history_size();
+        let new_lines = history_size.saturating_sub(self.history_size);
+        self.history_size = history_size;
 
-        Some(AlacPoint::new(Line(adjusted_line), Column(col as usize)))
+        if !self.follow_output && new_lines > 0 && term.grid().display_offset() == 0 {
+            term.scroll_display(Scroll::Delta(new_lines as i32));
+        }
     }
 
     /// Sync the content snapshot from the terminal grid.
     fn sync_content(&mut self) {
         let term = self.term.lock();
+        self.last_content = snapshot_content(
+            &term,
+            self.last_content.terminal_bounds,
+            self.hovered_hyperlink.clone(),
+        );
+    }
+}
+
+/// The grid point under a pixel position, accounting for scrollback. Both
+/// halves of a wide character map to the character's own column.
+fn grid_point_at(
+    term: &Term<ManifestListener>,
+    bounds: &TerminalBounds,
+    display_offset: usize,
+    position: Point<Pixels>,
+) -> Option<AlacPoint> {
+    // Check if position is within bounds
+    if position.x < Pixels::ZERO || position.y < Pixels::ZERO {
+        return None;
+    }
 
-        let mut cells = Vec::new();
-        let content = term.renderable_content();
+    let col = (position.x / bounds.cell_width).floor() as i32;
+    let line = (position.y / bounds.line_height).floor() as i32;
 
-        for cell in content.display_iter {
-            cells.push(IndexedCell {
-                point: cell.point,
-                cell: cell.cell.clone(),
-            });
-        }
+    // Clamp to valid range
+    let num_cols = bounds.num_columns().min(term.columns()) as i32;
+    let num_lines = bounds.num_lines().min(term.screen_lines()) as i32;
 
-        self.last_content = TerminalContent {
-            cells,
-            mode: *term.mode(),
-            display_offset: term.grid().display_offset(),
-            selection: content.selection.map(|s| s.clone()),
-            cursor: content.cursor,
-            cursor_char: term.grid()[content.cursor.point].c,
-            terminal_bounds: self.last_content.terminal_bounds,
-            hovered_hyperlink: self.hovered_hyperlink.clone(),
-        };
+    if col < 0 || col >= num_cols || line < 0 || line >= num_lines {
+        return None;
+    }
+
+    // Account for display offset (scrollback)
+    let adjusted_line = line - display_offset as i32;
+    let point = AlacPoint::new(Line(adjusted_line), Column(col as usize));
+
+    if point.column.0 > 0 && term.grid()[point].flags.contains(Flags::WIDE_CHAR_SPACER) {
+        return Some(AlacPoint::new(point.line, point.column - 1));
+    }
+    Some(point)
+}
+
+/// Environment for a terminal's shell: the app's own, with `TERM` and
+/// `COLORTERM` describing this emulator, then `overrides` on top.
+///
+/// The inherited `TERM` belongs to whatever launched the app, if anything
+/// (apps opened from the Finder have none), so it is always replaced.
+pub fn shell_env(overrides: HashMap<String, String>) -> HashMap<String, String> {
+    let mut env: HashMap<String, String> = std::env::vars().collect();
+    env.insert("TERM".to_string(), "xterm-256color".to_string());
+    env.insert("COLORTERM".to_string(), "truecolor".to_string());
+    env.extend(overrides);
+    env
+}
+
+/// Emulator options shared by every terminal.
+fn term_config(word_separators: &str) -> Config {
+    Config {
+        scrolling_history: DEFAULT_SCROLL_HISTORY_LINES,
+        default_cursor_style: CursorStyle {
+            shape: AlacCursorShape::Block,
+            blinking: false,
+        },
+        semantic_escape_chars: word_separators.to_string(),
+        ..Config::default()
+    }
+}
+
+/// Arrow key sequences equivalent to scrolling `lines` in the alternate screen.
+fn alt_screen_scroll(lines: i32, mode: TermMode) -> Vec<u8> {
+    let app_cursor = mode.contains(TermMode::APP_CURSOR);
+    let sequence: &[u8] = match (lines > 0, app_cursor) {
+        (true, true) => b"\x1bOA",
+        (true, false) => b"\x1b[A",
+        (false, true) => b"\x1bOB",
+        (false, false) => b"\x1b[B",
+    };
+    sequence.repeat(lines.unsigned_abs() as usize)
+}
+
+/// Every line from the top of the scrollback to the bottom of the screen,
+/// with soft-wrapped rows joined.
+fn scrollback_text(term: &Term<ManifestListener>) -> String {
+    let start = AlacPoint::new(term.topmost_line(), Column(0));
+    let end = AlacPoint::new(term.bottommost_line(), term.last_column());
+    let text = term.bounds_to_string(start, end);
+    text.trim_end().to_string()
+}
+
+/// Drop the lines scrolled off the top of the screen.
+fn clear_history(term: &mut Term<ManifestListener>) {
+    term.grid_mut().clear_history();
+}
+
+/// Capture the renderable state of a terminal grid.
+fn snapshot_content(
+    term: &Term<ManifestListener>,
+    terminal_bounds: TerminalBounds,
+    hovered_hyperlink: Option<Match>,
+) -> TerminalContent {
+    let mut cells = Vec::new();
+    let content = term.renderable_content();
+
+    for cell in content.display_iter {
+        cells.push(IndexedCell {
+            point: cell.point,
+            cell: cell.cell.clone(),
+        });
+    }
+
+    TerminalContent {
+        cells,
+        mode: *term.mode(),
+        display_offset: term.grid().display_offset(),
+        history_size: term.grid().history_size(),
+        selection: content.selection.map(|s| s.clone()),
+        cursor: content.cursor,
+        cursor_char: term.grid()[content.cursor.point].c,
+        terminal_bounds,
+        hovered_hyperlink,
     }
 }
@@ -91,90 +91,261 @@ impl TerminalColors {
     }
 }
 
-/// Get an ANSI color by index (0-15).
-pub fn get_ansi_color(index: u8) -> Rgba {
-    match index {
-        0 => TerminalColors::ansi_black(),
-        1 => TerminalColors::ansi_red(),
-        2 => TerminalColors::ansi_green(),
-        3 => TerminalColors::ansi_yellow(),
-        4 => TerminalColors::ansi_blue(),
-        5 => TerminalColors::ansi_magenta(),
-        6 => TerminalColors::ansi_cyan(),
-        7 => TerminalColors::ansi_white(),
-        8 => TerminalColors::ansi_bright_black(),
-        9 => TerminalColors::ansi_bright_red(),
-        10 => TerminalColors::ansi_bright_green(),
-        11 => TerminalColors::ansi_bright_yellow(),
-        12 => TerminalColors::ansi_bright_blue(),
-        13 => TerminalColors::ansi_bright_magenta(),
-        14 => TerminalColors::ansi_bright_cyan(),
-        15 => TerminalColors::ansi_bright_white(),
-        _ => TerminalColors::foreground(),
+/// Colors for drawing terminal cells: the 16 ANSI colors plus the default
+/// foreground, background and cursor. Indexed colors 16-255 are the standard
+/// color cube and grayscale ramp in every theme.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TerminalTheme {
+    pub foreground: Rgba,
+    pub background: Rgba,
+    pub cursor: Rgba,
+    /// ANSI colors 0-7 (black, red, green, yellow, blue, magenta, cyan,
+    /// white) followed by their bright variants 8-15.
+    pub ansi: [Rgba; 16],
+}
+
+impl Default for TerminalTheme {
+    fn default() -> Self {
+        Self::pigs_in_space()
     }
 }
 
-/// Convert an indexed color (0-255) to RGBA.
-pub fn get_indexed_color(index: u8) -> Rgba {
-    if index < 16 {
-        return get_ansi_color(index);
+impl TerminalTheme {
+    /// Names accepted by [`TerminalTheme::named`].
+    pub const NAMES: &[&str] = &["pigs-in-space", "solarized-dark", "solarized-light"];
+
+    /// Look up a preset by name.
+    pub fn named(name: &str) -> Option<Self> {
+        match name {
+            "pigs-in-space" => Some(Self::pigs_in_space()),
+            "solarized-dark" => Some(Self::solarized_dark()),
+            "solarized-light" => Some(Self::solarized_light()),
+            _ => None,
+        }
+    }
+
+    /// The app's own palette, matching [`TerminalColors`].
+    pub fn pigs_in_space() -> Self {
+        Self {
+            foreground: TerminalColors::foreground(),
+            background: TerminalColors::background(),
+            cursor: TerminalColors::cursor(),
+            ansi: [
+                TerminalColors::ansi_black(),
+                TerminalColors::ansi_red(),
+                TerminalColors::ansi_green(),
+                TerminalColors::ansi_yellow(),
+                TerminalColors::ansi_blue(),
+                TerminalColors::ansi_magenta(),
+                TerminalColors::ansi_cyan(),
+                TerminalColors::ansi_white(),
+                TerminalColors::ansi_bright_black(),
+                TerminalColors::ansi_bright_red(),
+                TerminalColors::ansi_bright_green(),
+                TerminalColors::ansi_bright_yellow(),
+                TerminalColors::ansi_bright_blue(),
+                TerminalColors::ansi_bright_magenta(),
+                TerminalColors::ansi_bright_cyan(),
+                TerminalColors::ansi_bright_white(),
+            ],
+        }
+    }
+
+    pub fn solarized_dark() -> Self {
+        Self {
+            foreground: rgba(0x839496ff),
+            background: rgba(0x002b36ff),
+            cursor: rgba(0x93a1a1ff),
+            ansi: SOLARIZED_ANSI,
+        }
     }
 
-    // Colors 16-231: 6x6x6 color cube
-    if index < 232 {
-        let index = index - 16;
-        let r = (index / 36) % 6;
-        let g = (index / 6) % 6;
-        let b = index % 6;
+    pub fn solarized_light() -> Self {
+        Self {
+            foreground: rgba(0x657b83ff),
+            background: rgba(0xfdf6e3ff),
+            cursor: rgba(0x586e75ff),
+            ansi: SOLARIZED_ANSI,
+        }
+    }
+
+    /// Convert an alacritty color to GPUI Hsla.
+    pub fn color(&self, color: &alacritty_terminal::vte::ansi::Color) -> Hsla {
+        use alacritty_terminal::vte::ansi::{Color, NamedColor};
+
+        match color {
+            Color::Named(named) => {
+                let rgba = match named {
+                    NamedColor::Foreground => self.foreground,
+                    NamedColor::Background => self.background,
+                    NamedColor::Cursor => self.cursor,
+                    // Black through BrightWhite are numbered 0-15
+                    named if (*named as usize) < 16 => self.ansi[*named as usize],
+                    _ => self.foreground,
+                };
+                rgba.into()
+            }
+            Color::Spec(rgb) => Hsla::from(Rgba {
+                r: rgb.r as f32 / 255.0,
+                g: rgb.g as f32 / 255.0,
+                b: rgb.b as f32 / 255.0,
+                a: 1.0,
+            }),
+            Color::Indexed(index) => self.indexed_color(*index).into(),
+        }
+    }
+
+    /// Convert an indexed color (0-255) to RGBA.
+    fn indexed_color(&self, index: u8) -> Rgba {
+        if index < 16 {
+            return self.ansi[index as usize];
+        }
+
+        // Colors 16-231: 6x6x6 color cube
+        if index < 232 {
+            let index = index - 16;
+            let r = (index / 36) % 6;
+            let g = (index / 6) % 6;
+            let b = index % 6;
 
-        let r = if r > 0 { r * 40 + 55 } else { 0 };
-        let g = if g > 0 { g * 40 + 55 } else { 0 };
-        let b = if b > 0 { b * 40 + 55 } else { 0 };
+            let r = if r > 0 { r * 40 + 55 } else { 0 };
+            let g = if g > 0 { g * 40 + 55 } else { 0 };
+            let b = if b > 0 { b * 40 + 55 } else { 0 };
 
-        return rgba(((r as u32) << 24) | ((g as u32) << 16) | ((b as u32) << 8) | 0xff);
+            return rgba(((r as u32) << 24) | ((g as u32) << 16) | ((b as u32) << 8) | 0xff);
+        }
+
+        // Colors 232-255: grayscale
+        let gray = (index - 232) * 10 + 8;
+        rgba(((gray as u32) << 24) | ((gray as u32) << 16) | ((gray as u32) << 8) | 0xff)
     }
+}
 
-    // Colors 232-255: grayscale
-    let gray = (index - 232) * 10 + 8;
-    rgba(((gray as u32) << 24) | ((gray as u32) << 16) | ((gray as u32) << 8) | 0xff)
+/// ANSI colors shared by the dark and light Solarized themes.
+const SOLARIZED_ANSI: [Rgba; 16] = [
+    solarized(0x073642),
+    solarized(0xdc322f),
+    solarized(0x859900),
+    solarized(0xb58900),
+    solarized(0x268bd2),
+    solarized(0xd33682),
+    solarized(0x2aa198),
+    solarized(0xeee8d5),
+    solarized(0x002b36),
+    solarized(0xcb4b16),
+    solarized(0x586e75),
+    solarized(0x657b83),
+    solarized(0x839496),
+    solarized(0x6c71c4),
+    solarized(0x93a1a1),
+    solarized(0xfdf6e3),
+];
+
+/// Opaque color from `0xRRGGBB`, usable in constants unlike `rgba()`.
+const fn solarized(hex: u32) -> Rgba {
+    Rgba {
+        r: ((hex >> 16) & 0xff) as f32 / 255.0,
+        g: ((hex >> 8) & 0xff) as f32 / 255.0,
+        b: (hex & 0xff) as f32 / 255.0,
+        a: 1.0,
+    }
 }
 
-/// Convert an alacritty color to GPUI Hsla.
-pub fn convert_color(color: &alacritty_terminal::vte::ansi::Color) -> Hsla {
+/// Bright (8-15) variant of a normal (0-7) ANSI color, used to draw bold text
+/// the way legacy programs expect. Other colors are returned unchanged.
+pub fn bright_variant(
+    color: alacritty_terminal::vte::ansi::Color,
+) -> alacritty_terminal::vte::ansi::Color {
     use alacritty_terminal::vte::ansi::{Color, NamedColor};
 
     match color {
-        Color::Named(named) => {
-            let rgba = match named {
-                NamedColor::Black => TerminalColors::ansi_black(),
-                NamedColor::Red => TerminalColors::ansi_red(),
-                NamedColor::Green => TerminalColors::ansi_green(),
-                NamedColor::Yellow => TerminalColors::ansi_yellow(),
-                NamedColor::Blue => TerminalColors::ansi_blue(),
-                NamedColor::Magenta => TerminalColors::ansi_magenta(),
-                NamedColor::Cyan => TerminalColors::ansi_cyan(),
-                NamedColor::White => TerminalColors::ansi_white(),
-                NamedColor::BrightBlack => TerminalColors::ansi_bright_black(),
-                NamedColor::BrightRed => TerminalColors::ansi_bright_red(),
-                NamedColor::BrightGreen => TerminalColors::ansi_bright_green(),
-                NamedColor::BrightYellow => TerminalColors::ansi_bright_yellow(),
-                NamedColor::BrightBlue => TerminalColors::ansi_bright_blue(),
-                NamedColor::BrightMagenta => TerminalColors::ansi_bright_magenta(),
-                NamedColor::BrightCyan => TerminalColors::ansi_bright_cyan(),
-                NamedColor::BrightWhite => TerminalColors::ansi_bright_white(),
-                NamedColor::Foreground => TerminalColors::foreground(),
-                NamedColor::Background => TerminalColors::background(),
-                NamedColor::Cursor => TerminalColors::cursor(),
-                _ => TerminalColors::foreground(),
-            };
-            rgba.into()
-        }
-        Color::Spec(rgb) => Hsla::from(Rgba {
-            r: rgb.r as f32 / 255.0,
-            g: rgb.g as f32 / 255.0,
-            b: rgb.b as f32 / 255.0,
-            a: 1.0,
+        Color::Named(named) => Color::Named(match named {
+            NamedColor::Black => NamedColor::BrightBlack,
+            NamedColor::Red => NamedColor::BrightRed,
+            NamedColor::Green => NamedColor::BrightGreen,
+            NamedColor::Yellow => NamedColor::BrightYellow,
+            NamedColor::Blue => NamedColor::BrightBlue,
+            NamedColor::Magenta => NamedColor::BrightMagenta,
+            NamedColor::Cyan => NamedColor::BrightCyan,
+            NamedColor::White => NamedColor::BrightWhite,
+            other => other,
         }),
-        Color::Indexed(index) => get_indexed_color(*index).into(),
+        Color::Indexed(index) if index < 8 => Color::Indexed(index + 8),
+        other => other,
+    }
+}
+
+/// How far faint (SGR 2) text is blended from its color toward the background.
+const DIM_BLEND: f32 = 0.6;
+
+/// Foreground for faint text: `fg` blended toward `bg` so it reads at reduced
+/// intensity on any background.
+pub fn dim_color(fg: Hsla, bg: Hsla) -> Hsla {
+    let fg = Rgba::from(fg);
+    let bg = Rgba::from(bg);
+    let mix = |from: f32, to: f32| from + (to - from) * DIM_BLEND;
+    Rgba {
+        r: mix(fg.r, bg.r),
+        g: mix(fg.g, bg.g),
+        b: mix(fg.b, bg.b),
+        a: fg.a,
+    }
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alacritty_terminal::vte::ansi::{Color, NamedColor};
+
+    #[test]
+    fn brightens_only_normal_ansi_colors() {
+        assert_eq!(
+            bright_variant(Color::Named(NamedColor::Red)),
+            Color::Named(NamedColor::BrightRed)
+        );
+        assert_eq!(bright_variant(Color::Indexed(4)), Color::Indexed(12));
+        assert_eq!(bright_variant(Color::Indexed(12)), Color::Indexed(12));
+        assert_eq!(bright_variant(Color::Indexed(100)), Color::Indexed(100));
+        assert_eq!(
+            bright_variant(Color::Named(NamedColor::Foreground)),
+            Color::Named(NamedColor::Foreground)
+        );
+    }
+
+    #[test]
+    fn named_and_indexed_colors_come_from_the_theme() {
+        let theme = TerminalTheme::solarized_dark();
+        let red: Hsla = rgba(0xdc322fff).into();
+        assert_eq!(theme.color(&Color::Named(NamedColor::Red)), red);
+        assert_eq!(theme.color(&Color::Indexed(1)), red);
+        assert_eq!(
+            theme.color(&Color::Named(NamedColor::BrightWhite)),
+            rgba(0xfdf6e3ff).into()
+        );
+        assert_eq!(
+            theme.color(&Color::Named(NamedColor::Background)),
+            theme.background.into()
+        );
+        // The color cube doesn't depend on the theme
+        assert_eq!(
+            theme.color(&Color::Indexed(196)),
+            TerminalTheme::default().color(&Color::Indexed(196))
+        );
+        assert!(
+            TerminalTheme::NAMES
+                .iter()
+                .all(|name| TerminalTheme::named(name).is_some())
+        );
+    }
+
+    #[test]
+    fn dims_toward_background() {
+        let white: Hsla = rgba(0xffffffff).into();
+        let black: Hsla = rgba(0x000000ff).into();
+        let dimmed = Rgba::from(dim_color(white, black));
+        assert!((dimmed.r - 0.4).abs() < 0.01);
+        assert!((dimmed.g - 0.4).abs() < 0.01);
+        assert_eq!(dimmed.a, 1.0);
     }
 }
@@ -48,8 +48,18 @@ pub fn to_esc_str(
 ) -> Option<Cow<'static, str>> {
     let modifiers = Modifiers::new(keystroke);
 
+    if modifiers == Modifiers::None {
+        if let Some(esc_str) = keypad_esc_str(&keystroke.key, mode) {
+            return Some(Cow::Borrowed(esc_str));
+        }
+    }
+
+    // Shift is part of the modifier set, so Ctrl+Shift+A may arrive as "a"
+    // or "A" depending on the platform. Match on the lowercase key.
+    let key = keystroke.key.to_ascii_lowercase();
+
     // Manual bindings including modifiers
-    let manual_esc_str: Option<&'static str> = match (keystroke.key.as_ref(), &modifiers) {
+    let manual_esc_str: Option<&'static str> = match (key.as_str(), &modifiers) {
         // Basic special keys
         ("tab", Modifiers::None) => Some("\x09"),
         ("escape", Modifiers::None) => Some("\x1b"),
@@ -100,65 +110,54 @@ pub fn to_esc_str(
         ("f11", Modifiers::None) => Some("\x1b[23~"),
         ("f12", Modifiers::None) => Some("\x1b[24~"),
         // Ctrl+letter mappings (caret notation)
-        ("a", Modifiers::Ctrl) => Some("\x01"),
-        ("A", Modifiers::CtrlShift) => Some("\x01"),
-        ("b", Modifiers::Ctrl) => Some("\x02"),
-        ("B", Modifiers::CtrlShift) => Some("\x02"),
-        ("c", Modifiers::Ctrl) => Some("\x03"),
-        ("C", Modifiers::CtrlShift) => Some("\x03"),
-        ("d", Modifiers::Ctrl) => Some("\x04"),
-        ("D", Modifiers::CtrlShift) => Some("\x04"),
-        ("e", Modifiers::Ctrl) => Some("\x05"),
-        ("E", Modifiers::CtrlShift) => Some("\x05"),
-        ("f", Modifiers::Ctrl) => Some("\x06"),
-        ("F", Modifiers::CtrlShift) => Some("\x06"),
-        ("g", Modifiers::Ctrl) => Some("\x07"),
-        ("G", Modifiers::CtrlShift) => Some("\x07"),
-        ("h", Modifiers::Ctrl) => Some("\x08"),
-        ("H", Modifiers::CtrlShift) => Some("\x08"),
-        ("i", Modifiers::Ctrl) => Some("\x09"),
-        ("I", Modifiers::CtrlShift) => Some("\x09"),
-        ("j", Modifiers::Ctrl) => Some("\x0a"),
-        ("J", Modifiers::CtrlShift) => Some("\x0a"),
-        ("k", Modifiers::Ctrl) => Some("\x0b"),
-        ("K", Modifiers::CtrlShift) => Some("\x0b"),
-        ("l", Modifiers::Ctrl) => Some("\x0c"),
-        ("L", Modifiers::CtrlShift) => Some("\x0c"),
-        ("m", Modifiers::Ctrl) => Some("\x0d"),
-        ("M", Modifiers::CtrlShift) => Some("\x0d"),
-        ("n", Modifiers::Ctrl) => Some("\x0e"),
-        ("N", Modifiers::CtrlShift) => Some("\x0e"),
-        ("o", Modifiers::Ctrl) => Some("\x0f"),
-        ("O", Modifiers::CtrlShift) => Some("\x0f"),
-        ("p", Modifiers::Ctrl) => Some("\x10"),
-        ("P", Modifiers::CtrlShift) => Some("\x10"),
-        ("q", Modifiers::Ctrl) => Some("\x11"),
-        ("Q", Modifiers::CtrlShift) => Some("\x11"),
-        ("r", Modifiers::Ctrl) => Some("\x12"),
-        ("R", Modifiers::CtrlShift) => Some("\x12"),
-        ("s", Modifiers::Ctrl) => Some("\x13"),
-        ("S", Modifiers::CtrlShift) => Some("\x13"),
-        ("t", Modifiers::Ctrl) => Some("\x14"),
-        ("T", Modifiers::CtrlShift) => Some("\x14"),
-        ("u", Modifiers::Ctrl) => Some("\x15"),
-        ("U", Modifiers::CtrlShift) => Some("\x15"),
-        ("v", Modifiers::Ctrl) => Some("\x16"),
-        ("V", Modifiers::CtrlShift) => Some("\x16"),
-        ("w", Modifiers::Ctrl) => Some("\x17"),
-        ("W", Modifiers::CtrlShift) => Some("\x17"),
-        ("x", Modifiers::Ctrl) => Some("\x18"),
-        ("X", Modifiers::CtrlShift) => Some("\x18"),
-        ("y", Modifiers::Ctrl) => Some("\x19"),
-        ("Y", Modifiers::CtrlShift) => Some("\x19"),
-        ("z", Modifiers::Ctrl) => Some("\x1a"),
-        ("Z", Modifiers::CtrlShift) => Some("\x1a"),
-        ("@", Modifiers::Ctrl) => Some("\x00"),
+        ("a", Modifiers::Ctrl | Modifiers::CtrlShift) => Some("\x01"),
+        ("b", Modifiers::Ctrl | Modifiers::CtrlShift) => Some("\x02"),
+        ("c", Modifiers::Ctrl | Modifiers::CtrlShift) => Some("\x03"),
+        ("d", Modifiers::Ctrl | Modifiers::CtrlShift) => Some("\x04"),
+        ("e", Modifiers::Ctrl | Modifiers::CtrlShift) => Some("\x05"),
+        ("f", Modifiers::Ctrl | Modifiers::CtrlShift) => Some("\x06"),
+        ("g", Modifiers::Ctrl | Modifiers::CtrlShift) => Some("\x07"),
+        ("h", Modifiers::Ctrl | Modifiers::CtrlShift) => Some("\x08"),
+        ("i", Modifiers::Ctrl | Modifiers::CtrlShift) => Some("\x09"),
+        ("j", Modifiers::Ctrl | Modifiers::CtrlShift) => Some("\x0a"),
+        ("k", Modifiers::Ctrl | Modifiers::CtrlShift) => Some("\x0b"),
+        ("l", Modifiers::Ctrl | Modifiers::CtrlShift) => Some("\x0c"),
+        ("m", Modifiers::Ctrl | Modifiers::CtrlShift) => Some("\x0d"),
+        ("n", Modifiers::Ctrl | Modifiers::CtrlShift) => Some("\x0e"),
+        ("o", Modifiers::Ctrl | Modifiers::CtrlShift) => Some("\x0f"),
+        ("p", Modifiers::Ctrl | Modifiers::CtrlShift) => Some("\x10"),
+        ("q", Modifiers::Ctrl | Modifiers::CtrlShift) => Some("\x11"),
+        ("r", Modifiers::Ctrl | Modifiers::CtrlShift) => Some("\x12"),
+        ("s", Modifiers::Ctrl | Modifiers::CtrlShift) => Some("\x13"),
+        ("t", Modifiers::Ctrl | Modifiers::CtrlShift) => Some("\x14"),
+        ("u", Modifiers::Ctrl | Modifiers::CtrlShift) => Some("\x15"),
+        ("v", Modifiers::Ctrl | Modifiers::CtrlShift) => Some("\x16"),
+        ("w", Modifiers::Ctrl | Modifiers::CtrlShift) => Some("\x17"),
+        ("x", Modifiers::Ctrl | Modifiers::CtrlShift) => Some("\x18"),
+        ("y", Modifiers::Ctrl | Modifiers::CtrlShift) => Some("\x19"),
+        ("z", Modifiers::Ctrl | Modifiers::CtrlShift) => Some("\x1a"),
+        // Punctuation in caret notation. Shifted symbols like "@" and "^" may
+        // arrive with or without the shift modifier depending on the layout.
+        ("@", Modifiers::Ctrl | Modifiers::CtrlShift) => Some("\x00"),
         ("[", Modifiers::Ctrl) => Some("\x1b"),
         ("\\", Modifiers::Ctrl) => Some("\x1c"),
         ("]", Modifiers::Ctrl) => Some("\x1d"),
-        ("^", Modifiers::Ctrl) => Some("\x1e"),
-        ("_", Modifiers::Ctrl) => Some("\x1f"),
-        ("?", Modifiers::Ctrl) => Some("\x7f"),
+        ("^", Modifiers::Ctrl | Modifiers::CtrlShift) => Some("\x1e"),
+        ("_", Modifiers::Ctrl | Modifiers::CtrlShift) => Some("\x1f"),
+        ("?", Modifiers::Ctrl | Modifiers::CtrlShift) => Some("\x7f"),
+        ("/", Modifiers::Ctrl) => Some("\x1f"),
+        ("/", Modifiers::CtrlShift) => Some("\x7f"),
+        ("-", Modifiers::Ctrl | Modifiers::CtrlShift) => Some("\x1f"),
+        ("`", Modifiers::Ctrl) => Some("\x00"),
+        // Digit row, as in xterm: Ctrl+2 is Ctrl+@, Ctrl+6 is Ctrl+^ and so on,
+        // so these work whether or not the layout needs shift for the symbol
+        ("2", Modifiers::Ctrl | Modifiers::CtrlShift) => Some("\x00"),
+        ("3", Modifiers::Ctrl) => Some("\x1b"),
+        ("4", Modifiers::Ctrl) => Some("\x1c"),
+        ("5", Modifiers::Ctrl) => Some("\x1d"),
+        ("6", Modifiers::Ctrl | Modifiers::CtrlShift) => Some("\x1e"),
+        ("7", Modifiers::Ctrl) => Some("\x1f"),
+        ("8", Modifiers::Ctrl) => Some("\x7f"),
         _ => None,
     };
 
@@ -217,6 +216,41 @@ pub fn to_esc_str(
     None
 }
 
+/// Sequence for an unmodified numeric keypad key.
+///
+/// In application keypad mode (DECKPAM) each key sends its own SS3 sequence
+/// so programs can tell the keypad apart from the main keyboard. Otherwise
+/// Enter sends a carriage return and the remaining keys fall through to the
+/// character they type.
+fn keypad_esc_str(key: &str, mode: &TermMode) -> Option<&'static str> {
+    let key = key.strip_prefix("numpad")?;
+    if !mode.contains(TermMode::APP_KEYPAD) {
+        return (key == "enter").then_some("\x0d");
+    }
+
+    match key {
+        "0" => Some("\x1bOp"),
+        "1" => Some("\x1bOq"),
+        "2" => Some("\x1bOr"),
+        "3" => Some("\x1bOs"),
+        "4" => Some("\x1bOt"),
+        "5" => Some("\x1bOu"),
+        "6" => Some("\x1bOv"),
+        "7" => Some("\x1bOw"),
+        "8" => Some("\x1bOx"),
+        "9" => Some("\x1bOy"),
+        "." | "decimal" => Some("\x1bOn"),
+        "," | "separator" => Some("\x1bOl"),
+        "+" | "add" => Some("\x1bOk"),
+        "-" | "subtract" => Some("\x1bOm"),
+        "*" | "multiply" => Some("\x1bOj"),
+        "/" | "divide" => Some("\x1bOo"),
+        "=" | "equal" => Some("\x1bOX"),
+        "enter" => Some("\x1bOM"),
+        _ => None,
+    }
+}
+
 /// Calculate modifier code for escape sequences.
 ///   Code     Modifiers
 /// ---------+---------------------------
@@ -240,3 +274,75 @@ fn modifier_code(keystroke: &Keystroke) -> u32 {
     }
     modifier_code + 1
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn esc(key: &str, mode: TermMode) -> Option<String> {
+        let keystroke = Keystroke::parse(key).unwrap();
+        to_esc_str(&keystroke, &mode, false).map(Cow::into_owned)
+    }
+
+    #[test]
+    fn control_codes_cover_readline_and_tmux_shortcuts() {
+        let cases = [
+            ("ctrl-space", "\x00"),
+            ("ctrl-2", "\x00"),
+            ("ctrl-shift-2", "\x00"),
+            ("ctrl-@", "\x00"),
+            ("ctrl-3", "\x1b"),
+            ("ctrl-[", "\x1b"),
+            ("ctrl-4", "\x1c"),
+            ("ctrl-\\", "\x1c"),
+            ("ctrl-5", "\x1d"),
+            ("ctrl-]", "\x1d"),
+            ("ctrl-6", "\x1e"),
+            ("ctrl-shift-6", "\x1e"),
+            ("ctrl-shift-^", "\x1e"),
+            ("ctrl-7", "\x1f"),
+            ("ctrl-/", "\x1f"),
+            ("ctrl--", "\x1f"),
+            ("ctrl-shift-_", "\x1f"),
+            ("ctrl-8", "\x7f"),
+            ("ctrl-?", "\x7f"),
+            ("ctrl-shift-/", "\x7f"),
+        ];
+        for (key, expected) in cases {
+            assert_eq!(
+                esc(key, TermMode::empty()).as_deref(),
+                Some(expected),
+                "{}",
+                key
+            );
+        }
+    }
+
+    #[test]
+    fn keypad_sends_application_sequences_in_app_keypad_mode() {
+        let mode = TermMode::APP_KEYPAD;
+        assert_eq!(esc("numpad0", mode).as_deref(), Some("\x1bOp"));
+        assert_eq!(esc("numpad9", mode).as_deref(), Some("\x1bOy"));
+        assert_eq!(esc("numpadenter", mode).as_deref(), Some("\x1bOM"));
+        assert_eq!(esc("numpaddecimal", mode).as_deref(), Some("\x1bOn"));
+    }
+
+    #[test]
+    fn ctrl_shift_letters_match_either_case() {
+        let lower = Keystroke::parse("ctrl-shift-a").unwrap();
+        let mut upper = lower.clone();
+        upper.key = "A".into();
+        for keystroke in [lower, upper] {
+            let esc_str = to_esc_str(&keystroke, &TermMode::empty(), false);
+            assert_eq!(esc_str.as_deref(), Some("\x01"));
+        }
+    }
+
+    #[test]
+    fn keypad_falls_back_to_normal_input_outside_app_keypad_mode() {
+        let mode = TermMode::empty();
+        assert_eq!(esc("numpad5", mode), None);
+        assert_eq!(esc("numpadenter", mode).as_deref(), Some("\x0d"));
+        assert_eq!(esc("5", TermMode::APP_KEYPAD), None);
+    }
+}
@@ -0,0 +1,232 @@
+//! Mouse event to terminal mouse report conversion.
+//!
+//! Programs enable mouse reporting with the DECSET 1000/1002/1003 modes and
+//! optionally ask for the SGR (1006) or UTF-8 (1005) encodings. This module
+//! decides which events a mode wants and encodes them as the escape sequences
+//! the program expects.
+
+use alacritty_terminal::index::Point as AlacPoint;
+use alacritty_terminal::term::TermMode;
+use gpui::{Modifiers, MouseButton};
+
+/// Button reported to the program.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MouseReportButton {
+    Left,
+    Middle,
+    Right,
+    WheelUp,
+    WheelDown,
+    /// Motion with no button held (any-event tracking only).
+    None,
+}
+
+impl MouseReportButton {
+    /// Map a GPUI button, ignoring the navigation buttons terminals have no code for.
+    pub fn from_button(button: MouseButton) -> Option<Self> {
+        match button {
+            MouseButton::Left => Some(Self::Left),
+            MouseButton::Middle => Some(Self::Middle),
+            MouseButton::Right => Some(Self::Right),
+            _ => None,
+        }
+    }
+
+    fn is_wheel(self) -> bool {
+        matches!(self, Self::WheelUp | Self::WheelDown)
+    }
+
+    fn code(self) -> u32 {
+        match self {
+            Self::Left => 0,
+            Self::Middle => 1,
+            Self::Right => 2,
+            Self::None => 3,
+            Self::WheelUp => 64,
+            Self::WheelDown => 65,
+        }
+    }
+}
+
+/// What happened to the button.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MouseReportKind {
+    Press,
+    Release,
+    Motion,
+}
+
+/// Whether the program has enabled any form of mouse reporting.
+pub fn mouse_reporting(mode: &TermMode) -> bool {
+    mode.intersects(TermMode::MOUSE_MODE)
+}
+
+/// Whether `mode` asks for this event.
+///
+/// Normal tracking (1000) reports presses and releases, button-event
+/// tracking (1002) adds motion while a button is held, and any-event
+/// tracking (1003) reports all motion.
+fn wants_report(mode: &TermMode, kind: MouseReportKind, button: MouseReportButton) -> bool {
+    match kind {
+        MouseReportKind::Press | MouseReportKind::Release => mouse_reporting(mode),
+        MouseReportKind::Motion => {
+            mode.contains(TermMode::MOUSE_MOTION)
+                || (mode.contains(TermMode::MOUSE_DRAG) && button != MouseReportButton::None)
+        }
+    }
+}
+
+/// Encode a mouse event at a viewport cell, or `None` if the mode does not want it.
+pub fn mouse_report(
+    point: AlacPoint,
+    button: MouseReportButton,
+    kind: MouseReportKind,
+    modifiers: Modifiers,
+    mode: &TermMode,
+) -> Option<Vec<u8>> {
+    if !wants_report(mode, kind, button) {
+        return None;
+    }
+
+    let mut code = button.code();
+    if kind == MouseReportKind::Motion {
+        code += 32;
+    }
+    if modifiers.shift {
+        code += 4;
+    }
+    if modifiers.alt {
+        code += 8;
+    }
+    if modifiers.control {
+        code += 16;
+    }
+
+    // Reports are 1-based.
+    let col = point.column.0 as u32 + 1;
+    let line = point.line.0.max(0) as u32 + 1;
+
+    if mode.contains(TermMode::SGR_MOUSE) {
+        let suffix = if kind == MouseReportKind::Release {
+            'm'
+        } else {
+            'M'
+        };
+        return Some(format!("\x1b[<{};{};{}{}", code, col, line, suffix).into_bytes());
+    }
+
+    // Legacy encodings cannot say which button was released, and wheels
+    // have no release at all.
+    if kind == MouseReportKind::Release {
+        if button.is_wheel() {
+            return None;
+        }
+        code = (code & !0b11) | 3;
+    }
+
+    let mut bytes = b"\x1b[M".to_vec();
+    bytes.push(32 + code as u8);
+    if mode.contains(TermMode::UTF8_MOUSE) {
+        encode_utf8_coordinate(col, &mut bytes)?;
+        encode_utf8_coordinate(line, &mut bytes)?;
+    } else {
+        // Coordinates past 223 do not fit in a single byte.
+        bytes.push(u8::try_from(32 + col).ok()?);
+        bytes.push(u8::try_from(32 + line).ok()?);
+    }
+    Some(bytes)
+}
+
+fn encode_utf8_coordinate(value: u32, bytes: &mut Vec<u8>) -> Option<()> {
+    let c = char::from_u32(32 + value).filter(|_| value <= 2015)?;
+    let mut buf = [0; 4];
+    bytes.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+    Some(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alacritty_terminal::index::{Column, Line};
+
+    fn point(line: i32, col: usize) -> AlacPoint {
+        AlacPoint::new(Line(line), Column(col))
+    }
+
+    #[test]
+    fn encodes_sgr_press_and_release() {
+        let mode = TermMode::MOUSE_REPORT_CLICK | TermMode::SGR_MOUSE;
+        let press = mouse_report(
+            point(4, 9),
+            MouseReportButton::Left,
+            MouseReportKind::Press,
+            Modifiers::default(),
+            &mode,
+        );
+        let release = mouse_report(
+            point(4, 9),
+            MouseReportButton::Left,
+            MouseReportKind::Release,
+            Modifiers::default(),
+            &mode,
+        );
+        assert_eq!(press.as_deref(), Some(&b"\x1b[<0;10;5M"[..]));
+        assert_eq!(release.as_deref(), Some(&b"\x1b[<0;10;5m"[..]));
+    }
+
+    #[test]
+    fn reports_motion_only_in_matching_tracking_modes() {
+        let drag = TermMode::MOUSE_DRAG | TermMode::SGR_MOUSE;
+        let held = mouse_report(
+            point(0, 0),
+            MouseReportButton::Left,
+            MouseReportKind::Motion,
+            Modifiers::default(),
+            &drag,
+        );
+        let hover = mouse_report(
+            point(0, 0),
+            MouseReportButton::None,
+            MouseReportKind::Motion,
+            Modifiers::default(),
+            &drag,
+        );
+        assert_eq!(held.as_deref(), Some(&b"\x1b[<32;1;1M"[..]));
+        assert_eq!(hover, None);
+
+        let any = TermMode::MOUSE_MOTION | TermMode::SGR_MOUSE;
+        let hover = mouse_report(
+            point(0, 0),
+            MouseReportButton::None,
+            MouseReportKind::Motion,
+            Modifiers::default(),
+            &any,
+        );
+        assert_eq!(hover.as_deref(), Some(&b"\x1b[<35;1;1M"[..]));
+    }
+
+    #[test]
+    fn encodes_legacy_release_as_button_three() {
+        let mode = TermMode::MOUSE_REPORT_CLICK;
+        let release = mouse_report(
+            point(0, 1),
+            MouseReportButton::Right,
+            MouseReportKind::Release,
+            Modifiers::default(),
+            &mode,
+        );
+        assert_eq!(
+            release,
+            Some(vec![0x1b, b'[', b'M', 32 + 3, 32 + 2, 32 + 1])
+        );
+
+        let wheel_release = mouse_report(
+            point(0, 1),
+            MouseReportButton::WheelUp,
+            MouseReportKind::Release,
+            Modifiers::default(),
+            &mode,
+        );
+        assert_eq!(wheel_release, None);
+    }
+}
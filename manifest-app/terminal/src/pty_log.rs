@@ -0,0 +1,354 @@
+//! Opt-in logging of raw PTY traffic for debugging agent sessions.
+//!
+//! The PTY is wrapped in [`LoggingPty`], which tees every chunk the event loop
+//! reads from or writes to the child process into a shared [`PtyLog`]. The log
+//! is inert until [`PtyLog::start`] is called, so the wrapper costs one mutex
+//! check per chunk when logging is off.
+//!
+//! Each chunk is written as one line prefixed with a direction marker
+//! (`>>>` for bytes sent to the PTY, `<<<` for bytes received from it), with
+//! control and non-ASCII bytes escaped so the log stays readable as text.
+//!
+//! The wrapper also feeds an [`OutputLog`], which keeps only the program's
+//! output, unescaped, so an agent's session can be read back after its tab is
+//! closed. Both are written from the PTY event loop thread, never the UI.
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use alacritty_terminal::event::{OnResize, WindowSize};
+use alacritty_terminal::tty::{self, ChildEvent, EventedPty, EventedReadWrite};
+use parking_lot::Mutex;
+use polling::{Event as PollEvent, PollMode, Poller};
+
+/// Direction of a logged chunk relative to the child process.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    /// Bytes written to the PTY (keyboard input, responses to queries).
+    Input,
+    /// Bytes read from the PTY (program output).
+    Output,
+}
+
+impl Direction {
+    fn marker(self) -> &'static str {
+        match self {
+            Direction::Input => ">>>",
+            Direction::Output => "<<<",
+        }
+    }
+}
+
+struct ActiveLog {
+    path: PathBuf,
+    file: File,
+}
+
+/// Shared handle to the log file, cloned into the PTY wrapper.
+#[derive(Clone, Default)]
+pub struct PtyLog(Arc<Mutex<Option<ActiveLog>>>);
+
+impl PtyLog {
+    /// Start appending traffic to `path`, replacing any log already open.
+    pub fn start(&self, path: &Path) -> io::Result<()> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        *self.0.lock() = Some(ActiveLog {
+            path: path.to_path_buf(),
+            file,
+        });
+        Ok(())
+    }
+
+    /// Stop logging and close the file.
+    pub fn stop(&self) {
+        *self.0.lock() = None;
+    }
+
+    /// Path of the active log file, if logging is on.
+    pub fn path(&self) -> Option<PathBuf> {
+        self.0.lock().as_ref().map(|log| log.path.clone())
+    }
+
+    /// Append a chunk to the log. A failed write disables logging rather than
+    /// surfacing an error to the terminal.
+    pub fn record(&self, direction: Direction, bytes: &[u8]) {
+        let mut guard = self.0.lock();
+        let Some(log) = guard.as_mut() else {
+            return;
+        };
+
+        let result = writeln!(log.file, "{} {}", direction.marker(), bytes.escape_ascii());
+        if let Err(e) = result {
+            eprintln!(
+                "Failed to write PTY log {}, disabling: {}",
+                log.path.display(),
+                e
+            );
+            *guard = None;
+        }
+    }
+}
+
+/// Size at which an [`OutputLog`] is rotated unless told otherwise.
+pub const OUTPUT_LOG_MAX_BYTES: u64 = 10 * 1024 * 1024;
+
+struct ActiveOutputLog {
+    path: PathBuf,
+    file: File,
+    len: u64,
+    max_bytes: u64,
+}
+
+/// Shared handle to a file receiving a terminal's output as the program
+/// wrote it, escape sequences included, so `cat` replays it.
+///
+/// When a chunk would take the file past its size cap, the file is moved to
+/// `<path>.1`, replacing the previous one, and a new file is started. At most
+/// twice the cap is kept on disk.
+#[derive(Clone, Default)]
+pub struct OutputLog(Arc<Mutex<Option<ActiveOutputLog>>>);
+
+impl OutputLog {
+    /// Start appending output to `path`, rotating it at `max_bytes`.
+    pub fn start(&self, path: &Path, max_bytes: u64) -> io::Result<()> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let len = file.metadata()?.len();
+        *self.0.lock() = Some(ActiveOutputLog {
+            path: path.to_path_buf(),
+            file,
+            len,
+            max_bytes,
+        });
+        Ok(())
+    }
+
+    /// Stop saving output and close the file.
+    pub fn stop(&self) {
+        *self.0.lock() = None;
+    }
+
+    /// Path of the active log file, if output is being saved.
+    pub fn path(&self) -> Option<PathBuf> {
+        self.0.lock().as_ref().map(|log| log.path.clone())
+    }
+
+    /// Append a chunk of output. As with [`PtyLog::record`], a failure
+    /// disables the log instead of reaching the terminal.
+    pub fn append(&self, bytes: &[u8]) {
+        let mut guard = self.0.lock();
+        let Some(log) = guard.as_mut() else {
+            return;
+        };
+
+        let result = log.rotate_if_full(bytes.len() as u64).and_then(|_| {
+            log.file.write_all(bytes)?;
+            log.len += bytes.len() as u64;
+            Ok(())
+        });
+        if let Err(e) = result {
+            eprintln!(
+                "Failed to write output log {}, disabling: {}",
+                log.path.display(),
+                e
+            );
+            *guard = None;
+        }
+    }
+}
+
+impl ActiveOutputLog {
+    fn rotate_if_full(&mut self, incoming: u64) -> io::Result<()> {
+        if self.len == 0 || self.len + incoming <= self.max_bytes {
+            return Ok(());
+        }
+        let mut rotated = self.path.clone().into_os_string();
+        rotated.push(".1");
+        std::fs::rename(&self.path, &rotated)?;
+        self.file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        self.len = 0;
+        Ok(())
+    }
+}
+
+/// Reader or writer half that records each chunk it moves.
+pub struct Tee {
+    file: File,
+    log: PtyLog,
+    direction: Direction,
+    /// Receives what the reader half reads; `None` on the writer half.
+    output: Option<OutputLog>,
+}
+
+impl Read for Tee {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.file.read(buf)?;
+        if n > 0 {
+            self.log.record(self.direction, &buf[..n]);
+            if let Some(output) = &self.output {
+                output.append(&buf[..n]);
+            }
+        }
+        Ok(n)
+    }
+}
+
+impl Write for Tee {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = self.file.write(buf)?;
+        if n > 0 {
+            self.log.record(self.direction, &buf[..n]);
+        }
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+/// PTY wrapper that mirrors traffic into a [`PtyLog`] and output into an
+/// [`OutputLog`].
+///
+/// The reader and writer are duplicated file descriptors of the PTY master,
+/// so reads and writes go to the same open file while polling stays
+/// registered on the original.
+pub struct LoggingPty {
+    pty: tty::Pty,
+    reader: Tee,
+    writer: Tee,
+}
+
+impl LoggingPty {
+    pub fn new(mut pty: tty::Pty, log: PtyLog, output: OutputLog) -> io::Result<Self> {
+        let reader = Tee {
+            file: pty.reader().try_clone()?,
+            log: log.clone(),
+            direction: Direction::Output,
+            output: Some(output),
+        };
+        let writer = Tee {
+            file: pty.writer().try_clone()?,
+            log,
+            direction: Direction::Input,
+            output: None,
+        };
+        Ok(Self {
+            pty,
+            reader,
+            writer,
+        })
+    }
+}
+
+impl EventedReadWrite for LoggingPty {
+    type Reader = Tee;
+    type Writer = Tee;
+
+    unsafe fn register(
+        &mut self,
+        poll: &Arc<Poller>,
+        interest: PollEvent,
+        mode: PollMode,
+    ) -> io::Result<()> {
+        // SAFETY: the wrapped PTY lives as long as this wrapper, which the
+        // event loop deregisters before dropping.
+        unsafe { self.pty.register(poll, interest, mode) }
+    }
+
+    fn reregister(
+        &mut self,
+        poll: &Arc<Poller>,
+        interest: PollEvent,
+        mode: PollMode,
+    ) -> io::Result<()> {
+        self.pty.reregister(poll, interest, mode)
+    }
+
+    fn deregister(&mut self, poll: &Arc<Poller>) -> io::Result<()> {
+        self.pty.deregister(poll)
+    }
+
+    fn reader(&mut self) -> &mut Tee {
+        &mut self.reader
+    }
+
+    fn writer(&mut self) -> &mut Tee {
+        &mut self.writer
+    }
+}
+
+impl EventedPty for LoggingPty {
+    fn next_child_event(&mut self) -> Option<ChildEvent> {
+        self.pty.next_child_event()
+    }
+}
+
+impl OnResize for LoggingPty {
+    fn on_resize(&mut self, window_size: WindowSize) {
+        self.pty.on_resize(window_size);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_log_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "manifest-pty-log-{}-{}.log",
+            name,
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn records_chunks_with_direction_markers() {
+        let path = temp_log_path("markers");
+        let log = PtyLog::default();
+        log.start(&path).unwrap();
+
+        log.record(Direction::Input, b"ls\r");
+        log.record(Direction::Output, b"\x1b[1mfile\x1b[0m\r\n");
+        log.stop();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(content, ">>> ls\\r\n<<< \\x1b[1mfile\\x1b[0m\\r\\n\n");
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn ignores_chunks_when_not_started() {
+        let path = temp_log_path("inactive");
+        let log = PtyLog::default();
+
+        log.record(Direction::Output, b"dropped");
+
+        assert!(log.path().is_none());
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn output_log_keeps_bytes_and_rotates_at_the_cap() {
+        let path = temp_log_path("output");
+        let rotated = path.with_extension("log.1");
+        let _ = std::fs::remove_file(&rotated);
+        let log = OutputLog::default();
+        log.start(&path, 8).unwrap();
+
+        log.append(b"\x1b[1mab");
+        log.append(b"cdefgh");
+        log.stop();
+
+        assert_eq!(std::fs::read(&rotated).unwrap(), b"\x1b[1mab");
+        assert_eq!(std::fs::read(&path).unwrap(), b"cdefgh");
+        std::fs::remove_file(&path).ok();
+        std::fs::remove_file(&rotated).ok();
+    }
+}
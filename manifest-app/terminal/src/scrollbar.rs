@@ -0,0 +1,85 @@
+//! Geometry of the scrollbar drawn along a terminal's right edge.
+//!
+//! The track is as tall as the screen and stands for the whole buffer, the
+//! scrollback followed by the visible lines. Positions are fractions of the
+//! track height so the renderer and the mouse handling agree on them
+//! whatever the pixel size.
+
+/// Shortest thumb, as a fraction of the track, so a long scrollback still
+/// leaves something to grab.
+const MIN_THUMB_HEIGHT: f32 = 0.05;
+
+/// The scrollbar thumb, in fractions of the track height.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScrollThumb {
+    pub top: f32,
+    pub height: f32,
+}
+
+impl ScrollThumb {
+    /// The thumb for a screen of `screen_lines` scrolled `display_offset`
+    /// lines up into `history_size` lines of scrollback, or `None` at the
+    /// bottom, where there is nothing to show.
+    pub fn new(history_size: usize, screen_lines: usize, display_offset: usize) -> Option<Self> {
+        if display_offset == 0 || history_size == 0 || screen_lines == 0 {
+            return None;
+        }
+        let total = (history_size + screen_lines) as f32;
+        let height = (screen_lines as f32 / total).max(MIN_THUMB_HEIGHT);
+        let above = history_size.saturating_sub(display_offset) as f32;
+        Some(ScrollThumb {
+            top: above / history_size as f32 * (1.0 - height),
+            height,
+        })
+    }
+
+    /// Whether `y`, a fraction of the track height, is on the thumb.
+    pub fn contains(&self, y: f32) -> bool {
+        y >= self.top && y <= self.top + self.height
+    }
+
+    /// The display offset that moves this thumb's top to `top`, clamped to
+    /// the scrollback.
+    pub fn offset_at(&self, top: f32, history_size: usize) -> usize {
+        let travel = 1.0 - self.height;
+        if travel <= 0.0 {
+            return 0;
+        }
+        let fraction = (top / travel).clamp(0.0, 1.0);
+        history_size - (fraction * history_size as f32).round() as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn thumb_is_hidden_at_the_bottom_and_tracks_the_offset() {
+        assert_eq!(ScrollThumb::new(100, 20, 0), None);
+        assert_eq!(ScrollThumb::new(0, 20, 0), None);
+
+        let top = ScrollThumb::new(100, 20, 100).unwrap();
+        assert_eq!(top.top, 0.0);
+        assert!((top.height - 20.0 / 120.0).abs() < 1e-6);
+
+        let near_bottom = ScrollThumb::new(100, 20, 1).unwrap();
+        assert!(near_bottom.top > 0.8 && near_bottom.top < 1.0 - near_bottom.height);
+
+        let long = ScrollThumb::new(100_000, 20, 50_000).unwrap();
+        assert_eq!(long.height, MIN_THUMB_HEIGHT);
+    }
+
+    #[test]
+    fn offset_at_inverts_the_thumb_position() {
+        for offset in [1, 37, 100] {
+            let thumb = ScrollThumb::new(100, 20, offset).unwrap();
+            assert_eq!(thumb.offset_at(thumb.top, 100), offset);
+        }
+        let thumb = ScrollThumb::new(100, 20, 50).unwrap();
+        assert_eq!(thumb.offset_at(-1.0, 100), 100);
+        assert_eq!(thumb.offset_at(2.0, 100), 0);
+        assert!(thumb.contains(thumb.top + thumb.height / 2.0));
+        assert!(!thumb.contains(thumb.top + thumb.height + 0.01));
+    }
+}
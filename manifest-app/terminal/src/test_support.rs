@@ -0,0 +1,236 @@
+//! Test harness that drives a real PTY running a scripted command.
+//!
+//! Tests spawn a small program (`cat`, `printf`) instead of a shell so the
+//! screen reaches a known state, then assert on the same `TerminalContent`
+//! snapshot the renderer consumes.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use alacritty_terminal::event::{Event as AlacTermEvent, Notify};
+use alacritty_terminal::index::{Column, Line, Point as AlacPoint};
+use futures::StreamExt;
+use gpui::{Pixels, Point};
+
+use crate::{TerminalBounds, TerminalBuilder, TerminalContent, grid_point_at, snapshot_content};
+
+/// How long to wait for expected output before failing the test.
+const WAIT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A PTY-backed terminal driven without a GPUI context.
+pub(crate) struct TestTerminal {
+    builder: TerminalBuilder,
+}
+
+impl TestTerminal {
+    /// Spawn `program` with `args` in a PTY of the default size.
+    pub(crate) fn spawn(program: &str, args: &[&str]) -> Self {
+        Self::spawn_with_env(program, args, HashMap::new())
+    }
+
+    /// Spawn `program` with `args` and `env` set on top of the shell environment.
+    pub(crate) fn spawn_with_env(
+        program: &str,
+        args: &[&str],
+        env: HashMap<String, String>,
+    ) -> Self {
+        let builder = TerminalBuilder::new_with_shell(
+            None,
+            0,
+            Some(program.to_string()),
+            args.iter().map(|arg| arg.to_string()).collect(),
+            env,
+        )
+        .expect("Failed to spawn test PTY");
+        Self { builder }
+    }
+
+    /// Write bytes to the PTY as if typed.
+    pub(crate) fn input(&self, bytes: &[u8]) {
+        self.builder.pty_tx.notify(bytes.to_vec());
+    }
+
+    /// Scrollback and screen as plain text.
+    pub(crate) fn scrollback_text(&self) -> String {
+        crate::scrollback_text(&self.builder.term.lock())
+    }
+
+    /// Drop the scrollback, keeping the screen.
+    pub(crate) fn clear_scrollback(&self) {
+        crate::clear_history(&mut self.builder.term.lock());
+    }
+
+    /// The grid point under `position`, for the default bounds.
+    pub(crate) fn grid_point_at(&self, position: Point<Pixels>) -> Option<AlacPoint> {
+        let term = self.builder.term.lock();
+        grid_point_at(&term, &TerminalBounds::default(), 0, position)
+    }
+
+    /// Current snapshot of the grid.
+    pub(crate) fn content(&self) -> TerminalContent {
+        let term = self.builder.term.lock();
+        snapshot_content(&term, TerminalBounds::default(), None)
+    }
+
+    /// Wait for wakeups until the screen text satisfies `predicate`.
+    ///
+    /// Panics with the last screen text if the timeout elapses first.
+    pub(crate) fn wait_for(&mut self, predicate: impl Fn(&str) -> bool) -> TerminalContent {
+        let deadline = Instant::now() + WAIT_TIMEOUT;
+        loop {
+            let content = self.content();
+            let text = content.to_plain_string();
+            if predicate(&text) {
+                return content;
+            }
+
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                panic!(
+                    "Timed out waiting for terminal content, screen was:\n{}",
+                    text
+                );
+            }
+            self.next_event(remaining);
+        }
+    }
+
+    /// Wait for the first event `select` picks out and return what it kept.
+    ///
+    /// Panics if the timeout elapses first.
+    pub(crate) fn wait_for_event<T>(
+        &mut self,
+        mut select: impl FnMut(AlacTermEvent) -> Option<T>,
+    ) -> T {
+        let deadline = Instant::now() + WAIT_TIMEOUT;
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                panic!("Timed out waiting for terminal event");
+            }
+            if let Some(found) = self.next_event(remaining).and_then(&mut select) {
+                return found;
+            }
+        }
+    }
+
+    /// Block until the terminal emits an event or `timeout` elapses.
+    fn next_event(&mut self, timeout: Duration) -> Option<AlacTermEvent> {
+        let events_rx = &mut self.builder.events_rx;
+        smol::block_on(smol::future::or(async { events_rx.next().await }, async {
+            smol::Timer::after(timeout).await;
+            None
+        }))
+    }
+}
+
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cat_echoes_input() {
+        let mut terminal = TestTerminal::spawn("cat", &[]);
+        terminal.input(b"hello\r");
+
+        // Once from the PTY's line discipline echo, once from cat itself
+        let content = terminal.wait_for(|text| text.matches("hello").count() >= 2);
+
+        assert_eq!(content.to_plain_string(), "hello\nhello");
+        assert_eq!(content.cursor.point, AlacPoint::new(Line(2), Column(0)));
+    }
+
+    #[test]
+    fn cursor_follows_printed_text() {
+        let mut terminal = TestTerminal::spawn("printf", &["one\\ntwo"]);
+
+        let content = terminal.wait_for(|text| text == "one\ntwo");
+
+        assert_eq!(content.cursor.point, AlacPoint::new(Line(1), Column(3)));
+        assert_eq!(content.cursor_char, ' ');
+    }
+
+    #[test]
+    fn plain_text_joins_soft_wrapped_rows() {
+        let long_line = "x".repeat(100);
+        let mut terminal = TestTerminal::spawn("printf", &[long_line.as_str()]);
+
+        let content = terminal.wait_for(|text| text.matches('x').count() == 100);
+
+        assert_eq!(content.to_plain_string(), long_line);
+    }
+
+    #[test]
+    fn scrollback_text_includes_lines_scrolled_off_screen() {
+        let mut terminal = TestTerminal::spawn("seq", &["1", "50"]);
+
+        terminal.wait_for(|text| text.ends_with("50"));
+
+        let text = terminal.scrollback_text();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 50);
+        assert_eq!(lines.first(), Some(&"1"));
+        assert_eq!(lines.last(), Some(&"50"));
+    }
+
+    #[test]
+    fn clear_scrollback_keeps_the_screen() {
+        let mut terminal = TestTerminal::spawn("seq", &["1", "50"]);
+        let screen = terminal
+            .wait_for(|text| text.ends_with("50"))
+            .to_plain_string();
+
+        terminal.clear_scrollback();
+
+        let text = terminal.scrollback_text();
+        assert_eq!(
+            text.lines().collect::<Vec<_>>(),
+            screen.lines().collect::<Vec<_>>()
+        );
+        assert_ne!(text.lines().next(), Some("1"));
+    }
+
+    #[test]
+    fn osc_52_reports_decoded_clipboard_text() {
+        // The first sequence isn't valid base64 and is dropped
+        let mut terminal =
+            TestTerminal::spawn("printf", &["\\033]52;c;!!!\\a\\033]52;c;aGVsbG8=\\a"]);
+
+        let text = terminal.wait_for_event(|event| match event {
+            AlacTermEvent::ClipboardStore(_, text) => Some(text),
+            _ => None,
+        });
+
+        assert_eq!(text, "hello");
+    }
+
+    #[test]
+    fn wide_characters_map_to_their_own_column() {
+        let mut terminal = TestTerminal::spawn("printf", &["ab你好c"]);
+        terminal.wait_for(|text| text == "ab你好c");
+
+        // Default bounds use 7px cells; a 3px offset lands inside a cell
+        let column_at = |cell: usize| {
+            let position = gpui::point(gpui::px(cell as f32 * 7.0 + 3.0), gpui::px(3.0));
+            terminal.grid_point_at(position).map(|point| point.column.0)
+        };
+
+        // 你 covers columns 2-3 and 好 covers 4-5
+        assert_eq!(column_at(1), Some(1));
+        assert_eq!(column_at(2), Some(2));
+        assert_eq!(column_at(3), Some(2));
+        assert_eq!(column_at(4), Some(4));
+        assert_eq!(column_at(5), Some(4));
+        assert_eq!(column_at(6), Some(6));
+    }
+
+    #[test]
+    fn shell_env_describes_the_emulator_and_applies_overrides() {
+        let env = HashMap::from([
+            ("MANIFEST_TASK_ID".to_string(), "42".to_string()),
+            ("COLORTERM".to_string(), "24bit".to_string()),
+        ]);
+        let script = r#"printf '%s %s %s' "$TERM" "$COLORTERM" "$MANIFEST_TASK_ID""#;
+        let mut terminal = TestTerminal::spawn_with_env("sh", &["-c", script], env);
+        terminal.wait_for(|text| text == "xterm-256color 24bit 42");
+    }
+}
@@ -3,5 +3,5 @@
 mod terminal_element;
 mod terminal_view;
 
-pub use terminal_element::TerminalElement;
-pub use terminal_view::TerminalView;
+pub use terminal_element::{DEFAULT_FONT_SIZE, TerminalElement};
+pub use terminal_view::{Event, TerminalView};
@@ -14,9 +14,36 @@ use itertools::Itertools;
 use std::panic::Location;
 use terminal::{
     Mode, Terminal, TerminalBounds, TerminalContent,
-    mappings::colors::{TerminalColors, convert_color},
+    mappings::colors::{TerminalTheme, bright_variant, dim_color},
+    scrollbar::ScrollThumb,
 };
 
+/// Cell flags that change how a run's glyphs are drawn, so a run ends
+/// wherever they change even if the color stays the same.
+const RUN_STYLE_FLAGS: Flags = Flags::BOLD
+    .union(Flags::ITALIC)
+    .union(Flags::UNDERLINE)
+    .union(Flags::STRIKEOUT);
+
+/// Font size terminals start at, in pixels.
+pub const DEFAULT_FONT_SIZE: f32 = 14.0;
+
+/// Width of the scrollbar track along the right edge of the content, in pixels.
+const SCROLLBAR_WIDTH: f32 = 8.0;
+
+/// The scrollbar track for a terminal whose content fills `content_bounds`.
+/// The thumb is drawn over the last column rather than beside it, so the
+/// grid keeps its width whether or not the scrollbar shows.
+pub(crate) fn scrollbar_track(content_bounds: Bounds<Pixels>) -> Bounds<Pixels> {
+    Bounds {
+        origin: point(
+            content_bounds.origin.x + content_bounds.size.width - px(SCROLLBAR_WIDTH),
+            content_bounds.origin.y,
+        ),
+        size: size(px(SCROLLBAR_WIDTH), content_bounds.size.height),
+    }
+}
+
 /// Layout state computed during prepaint, used for painting.
 pub struct LayoutState {
     #[allow(dead_code)] // Will be used for mouse interaction
@@ -25,6 +52,7 @@ pub struct LayoutState {
     cursor: Option<CursorLayout>,
     background_rects: Vec<BackgroundRect>,
     text_runs: Vec<TextRunLayout>,
+    scrollbar_thumb: Option<Bounds<Pixels>>,
 }
 
 struct CursorLayout {
@@ -50,6 +78,13 @@ pub struct TerminalElement {
     #[allow(dead_code)] // Will be used for focus tracking
     focus: FocusHandle,
     focused: bool,
+    /// Draw bold text in normal ANSI colors with their bright variant.
+    bold_is_bright: bool,
+    theme: TerminalTheme,
+    /// Size of the text; the cell size is measured from it.
+    font_size: Pixels,
+    /// Draw the scrollbar thumb while scrolled back into history.
+    scrollbar_visible: bool,
 }
 
 impl TerminalElement {
@@ -58,9 +93,38 @@ impl TerminalElement {
             terminal,
             focus,
             focused,
+            bold_is_bright: true,
+            theme: TerminalTheme::default(),
+            font_size: px(DEFAULT_FONT_SIZE),
+            scrollbar_visible: false,
         }
     }
 
+    /// Whether bold text in ANSI colors 0-7 is drawn with colors 8-15.
+    pub fn bold_is_bright(mut self, bold_is_bright: bool) -> Self {
+        self.bold_is_bright = bold_is_bright;
+        self
+    }
+
+    /// Colors to draw cells with.
+    pub fn theme(mut self, theme: TerminalTheme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Size to draw text at.
+    pub fn font_size(mut self, font_size: Pixels) -> Self {
+        self.font_size = font_size;
+        self
+    }
+
+    /// Whether to draw the scrollbar thumb. It is never drawn at the bottom
+    /// of the scrollback.
+    pub fn scrollbar_visible(mut self, visible: bool) -> Self {
+        self.scrollbar_visible = visible;
+        self
+    }
+
     fn layout_grid(
         &self,
         content: &TerminalContent,
@@ -75,9 +139,10 @@ impl TerminalElement {
         let line_height = dimensions.line_height();
         let cell_width = dimensions.cell_width();
 
-        let fg_default = TerminalColors::foreground();
-        let bg_default = TerminalColors::background();
-        let link_color: Hsla = TerminalColors::ansi_blue().into();
+        let theme = &self.theme;
+        let fg_default = theme.foreground;
+        let bg_default = theme.background;
+        let link_color: Hsla = theme.ansi[4].into();
 
         // Group cells by line
         for (line_idx, line_cells) in &content.cells.iter().chunk_by(|c| c.point.line.0) {
@@ -91,18 +156,35 @@ impl TerminalElement {
             let mut current_is_link = false;
 
             for cell in line_cells {
+                // The spacer after a wide character is covered by the character itself
+                if cell.flags.contains(Flags::WIDE_CHAR_SPACER) {
+                    continue;
+                }
+                let wide = cell.flags.contains(Flags::WIDE_CHAR);
                 let col = cell.point.column.0 as i32;
                 let x = origin.x + (col as f32) * cell_width;
+                let width = if wide { cell_width * 2.0 } else { cell_width };
 
                 // Check if this cell is part of a hovered hyperlink
                 let is_link = Self::is_in_hyperlink(&cell.point, &content.hovered_hyperlink);
 
+                let fg = if self.bold_is_bright && cell.flags.contains(Flags::BOLD) {
+                    bright_variant(cell.fg)
+                } else {
+                    cell.fg
+                };
                 // Get cell colors, respecting INVERSE flag for reverse video
                 // TUI apps like Claude Code use reverse video to render their cursors
                 let (fg_color, bg_color) = if cell.flags.contains(Flags::INVERSE) {
-                    (convert_color(&cell.bg), convert_color(&cell.fg))
+                    (theme.color(&cell.bg), theme.color(&fg))
+                } else {
+                    (theme.color(&fg), theme.color(&cell.bg))
+                };
+
+                let fg_color = if cell.flags.contains(Flags::DIM) {
+                    dim_color(fg_color, bg_color)
                 } else {
-                    (convert_color(&cell.fg), convert_color(&cell.bg))
+                    fg_color
                 };
 
                 // Use link color for hovered hyperlinks
@@ -115,7 +197,7 @@ impl TerminalElement {
                     background_rects.push(BackgroundRect {
                         bounds: Bounds {
                             origin: point(x, y),
-                            size: size(cell_width, line_height),
+                            size: size(width, line_height),
                         },
                         color: bg_color,
                     });
@@ -128,8 +210,9 @@ impl TerminalElement {
                     current_rgba != new_rgba
                 };
                 let link_changed = is_link != current_is_link;
+                let style_changed = (cell.flags ^ current_flags).intersects(RUN_STYLE_FLAGS);
 
-                if (fg_changed || link_changed) && !current_text.is_empty() {
+                if (fg_changed || link_changed || style_changed) && !current_text.is_empty() {
                     // Flush current text run
                     if let Some(text_run) = self.shape_text_run(
                         &current_text,
@@ -153,12 +236,18 @@ impl TerminalElement {
                     current_is_link = is_link;
                 }
 
-                // Add character to current run
+                // Add character to current run. Underlined and struck-through
+                // blanks stay in the run so the line continues across them
                 let c = cell.c;
-                if c != ' ' && c != '\0' {
-                    current_text.push(c);
-                } else if !current_text.is_empty() {
-                    // Space - flush if we have accumulated text
+                let decorated = cell.flags.intersects(Flags::UNDERLINE | Flags::STRIKEOUT);
+                let is_blank = (c == ' ' || c == '\0') && !decorated;
+                if !is_blank {
+                    current_text.push(if c == '\0' { ' ' } else { c });
+                }
+                // Spaces end a run, and so do wide characters: their glyphs
+                // rarely advance exactly two cells, so the next character is
+                // placed by its own column instead
+                if (is_blank || wide) && !current_text.is_empty() {
                     if let Some(text_run) = self.shape_text_run(
                         &current_text,
                         current_fg,
@@ -216,7 +305,7 @@ impl TerminalElement {
             return None;
         }
 
-        let font_size = px(14.0);
+        let font_size = self.font_size;
         let font_weight = if flags.contains(Flags::BOLD) {
             FontWeight::BOLD
         } else {
@@ -307,6 +396,16 @@ impl TerminalElement {
         let x = origin.x + col * dimensions.cell_width();
         let y = origin.y + line * dimensions.line_height();
 
+        let cursor_cell = content.cells.iter().find(|cell| cell.point == cursor.point);
+
+        // A cursor on a wide character covers both of its cells
+        let on_wide_char = cursor_cell.is_some_and(|cell| cell.flags.contains(Flags::WIDE_CHAR));
+        let cursor_width = if on_wide_char {
+            dimensions.cell_width() * 2.0
+        } else {
+            dimensions.cell_width()
+        };
+
         let shape = if !focused {
             CursorShape::HollowBlock
         } else {
@@ -318,6 +417,13 @@ impl TerminalElement {
             && content.cursor_char != ' '
             && content.cursor_char != '\0'
         {
+            // The glyph takes the color the cell shows as its background, so
+            // reverse video text under the cursor reads the same way it does
+            // everywhere else
+            let color = match cursor_cell {
+                Some(cell) if cell.flags.contains(Flags::INVERSE) => self.theme.color(&cell.fg),
+                _ => self.theme.background.into(),
+            };
             let cursor_char = content.cursor_char.to_string();
             let run = TextRun {
                 len: cursor_char.len(),
@@ -328,7 +434,7 @@ impl TerminalElement {
                     weight: FontWeight::NORMAL,
                     style: FontStyle::Normal,
                 },
-                color: TerminalColors::background().into(),
+                color,
                 background_color: None,
                 underline: None,
                 strikethrough: None,
@@ -345,7 +451,7 @@ impl TerminalElement {
 
         Some(CursorLayout {
             point: point(x, y),
-            size: size(dimensions.cell_width(), dimensions.line_height()),
+            size: size(cursor_width, dimensions.line_height()),
             shape,
             text,
         })
@@ -405,7 +511,7 @@ impl Element for TerminalElement {
         let hitbox = window.insert_hitbox(bounds, HitboxBehavior::Normal);
 
         // Calculate dimensions based on font metrics
-        let font_size = px(14.0);
+        let font_size = self.font_size;
         let line_height = font_size * 1.2; // Standard line height for terminal rendering
 
         // Measure actual cell width by shaping a reference character
@@ -458,12 +564,38 @@ impl Element for TerminalElement {
         // Layout cursor
         let cursor = self.layout_cursor(&content, &dimensions, origin, self.focused, window, cx);
 
+        let scrollbar_thumb = self
+            .scrollbar_visible
+            .then(|| {
+                ScrollThumb::new(
+                    content.history_size,
+                    dimensions.num_lines(),
+                    content.display_offset,
+                )
+            })
+            .flatten()
+            .map(|thumb| {
+                let track = scrollbar_track(content_bounds);
+                let inset = px(2.0);
+                Bounds {
+                    origin: point(
+                        track.origin.x + inset,
+                        track.origin.y + track.size.height * thumb.top,
+                    ),
+                    size: size(
+                        track.size.width - inset * 2.0,
+                        track.size.height * thumb.height,
+                    ),
+                }
+            });
+
         LayoutState {
             hitbox,
             dimensions,
             cursor,
             background_rects,
             text_runs,
+            scrollbar_thumb,
         }
     }
 
@@ -478,7 +610,7 @@ impl Element for TerminalElement {
         cx: &mut App,
     ) {
         // Paint background
-        window.paint_quad(fill(bounds, TerminalColors::background()));
+        window.paint_quad(fill(bounds, self.theme.background));
 
         // Paint cell backgrounds
         for rect in &prepaint.background_rects {
@@ -502,7 +634,7 @@ impl Element for TerminalElement {
 
         // Paint cursor
         if let Some(cursor) = &prepaint.cursor {
-            let cursor_color: Hsla = TerminalColors::cursor().into();
+            let cursor_color: Hsla = self.theme.cursor.into();
 
             match cursor.shape {
                 CursorShape::Block => {
@@ -601,5 +733,11 @@ impl Element for TerminalElement {
                 CursorShape::Hidden => {}
             }
         }
+
+        // Paint the scrollbar thumb over the text
+        if let Some(thumb) = prepaint.scrollbar_thumb {
+            let color: Hsla = self.theme.foreground.into();
+            window.paint_quad(fill(thumb, color.opacity(0.4)).corner_radii(px(3.0)));
+        }
     }
 }
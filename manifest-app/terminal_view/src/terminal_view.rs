@@ -1,23 +1,40 @@
 //! TerminalView - GPUI view container for multiple terminal tabs.
 
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Duration;
+
 use gpui::{
     App, AsyncWindowContext, Context, Entity, EventEmitter, FocusHandle, Focusable,
     InteractiveElement, IntoElement, KeyDownEvent, ModifiersChangedEvent, MouseButton,
-    MouseDownEvent, MouseMoveEvent, MouseUpEvent, ParentElement, Render, Styled, WeakEntity,
-    Window, div, prelude::*, px,
+    MouseDownEvent, MouseMoveEvent, MouseUpEvent, ParentElement, Pixels, Point, Render,
+    ScrollWheelEvent, Styled, Task, WeakEntity, Window, div, prelude::*, px,
 };
 use gpui_component::ActiveTheme;
+use keymap::Keymap;
 use terminal::{
-    Event as TerminalEvent, Terminal, TerminalBuilder, mappings::colors::TerminalColors,
+    DEFAULT_WORD_SEPARATORS, Event as TerminalEvent, Mode, Terminal, TerminalBuilder,
+    mappings::colors::TerminalTheme, scrollbar::ScrollThumb,
 };
 
-use crate::TerminalElement;
+use crate::terminal_element::scrollbar_track;
+use crate::{DEFAULT_FONT_SIZE, TerminalElement};
+
+/// Font sizes the zoom keys stay within, and the change per key press.
+const MIN_FONT_SIZE: f32 = 6.0;
+const MAX_FONT_SIZE: f32 = 36.0;
+const FONT_SIZE_STEP: f32 = 1.0;
+
+/// How long the scrollbar stays after the last scroll or hover.
+const SCROLLBAR_FADE_DELAY: Duration = Duration::from_millis(1500);
 
 /// Events emitted by the TerminalView.
 #[derive(Clone, Debug)]
 pub enum Event {
     TitleChanged,
     Closed,
+    /// The user zoomed the text to a new font size.
+    FontSizeChanged(f32),
 }
 
 /// A single terminal tab.
@@ -33,6 +50,27 @@ pub struct TerminalView {
     active_tab_idx: usize,
     next_tab_id: usize,
     focus_handle: FocusHandle,
+    /// Directory for raw PTY traffic logs, one file per tab. Off when `None`.
+    pty_log_dir: Option<PathBuf>,
+    /// Characters that end a word for double-click selection in every tab.
+    word_separators: String,
+    /// Draw bold text in ANSI colors 0-7 with the bright variants 8-15.
+    bold_is_bright: bool,
+    /// Colors for every tab's cells.
+    theme: TerminalTheme,
+    /// Whether programs in any tab may set the clipboard with OSC 52.
+    allow_clipboard_writes: bool,
+    /// Whether Option+key sends ESC followed by the key in every tab on macOS.
+    option_as_meta: bool,
+    /// Size of every tab's text, changed with the zoom keys.
+    font_size: f32,
+    /// Whether the scrollbar is drawn. Scrolling or hovering the track shows
+    /// it and `scrollbar_fade` hides it again.
+    scrollbar_visible: bool,
+    scrollbar_fade: Option<Task<()>>,
+    /// The thumb being dragged, and where on it the mouse grabbed it as a
+    /// fraction of the track height.
+    scrollbar_drag: Option<(ScrollThumb, f32)>,
 }
 
 impl TerminalView {
@@ -45,10 +83,27 @@ impl TerminalView {
             active_tab_idx: 0,
             next_tab_id: 0,
             focus_handle,
+            pty_log_dir: None,
+            word_separators: DEFAULT_WORD_SEPARATORS.to_string(),
+            bold_is_bright: true,
+            theme: TerminalTheme::default(),
+            allow_clipboard_writes: false,
+            option_as_meta: false,
+            font_size: DEFAULT_FONT_SIZE,
+            scrollbar_visible: false,
+            scrollbar_fade: None,
+            scrollbar_drag: None,
         };
 
         // Create the first tab
-        view.create_tab_internal(window, cx);
+        view.create_tab_internal(
+            None,
+            "Terminal".to_string(),
+            None,
+            HashMap::new(),
+            window,
+            cx,
+        );
         view
     }
 
@@ -61,6 +116,16 @@ impl TerminalView {
             active_tab_idx: 0,
             next_tab_id: 1,
             focus_handle,
+            pty_log_dir: None,
+            word_separators: DEFAULT_WORD_SEPARATORS.to_string(),
+            bold_is_bright: true,
+            theme: TerminalTheme::default(),
+            allow_clipboard_writes: false,
+            option_as_meta: false,
+            font_size: DEFAULT_FONT_SIZE,
+            scrollbar_visible: false,
+            scrollbar_fade: None,
+            scrollbar_drag: None,
         };
 
         let tab = TerminalTab {
@@ -73,20 +138,149 @@ impl TerminalView {
         view
     }
 
+    /// Log raw PTY traffic for every tab into `dir`, or stop logging with `None`.
+    pub fn set_pty_log_dir(&mut self, dir: Option<PathBuf>, cx: &mut Context<Self>) {
+        self.pty_log_dir = dir;
+        let tabs: Vec<_> = self
+            .tabs
+            .iter()
+            .filter_map(|tab| tab.terminal.clone().map(|t| (tab.id, t)))
+            .collect();
+        for (tab_id, terminal) in tabs {
+            self.apply_pty_logging(tab_id, &terminal, cx);
+        }
+    }
+
+    /// Use `separators` to end words for double-click selection in every tab.
+    pub fn set_word_separators(&mut self, separators: String, cx: &mut Context<Self>) {
+        self.word_separators = separators;
+        for terminal in self.tabs.iter().filter_map(|tab| tab.terminal.clone()) {
+            let separators = self.word_separators.clone();
+            terminal.update(cx, |terminal, _cx| {
+                terminal.set_word_separators(&separators)
+            });
+        }
+    }
+
+    /// Draw every tab with `theme`.
+    pub fn set_theme(&mut self, theme: TerminalTheme, cx: &mut Context<Self>) {
+        self.theme = theme;
+        cx.notify();
+    }
+
+    /// Let programs in every tab set the clipboard with OSC 52.
+    pub fn set_allow_clipboard_writes(&mut self, allow: bool, cx: &mut Context<Self>) {
+        self.allow_clipboard_writes = allow;
+        for terminal in self.tabs.iter().filter_map(|tab| tab.terminal.clone()) {
+            terminal.update(cx, |terminal, _cx| {
+                terminal.set_allow_clipboard_writes(allow)
+            });
+        }
+    }
+
+    /// Make Option+key send ESC followed by the key in every tab on macOS.
+    pub fn set_option_as_meta(&mut self, option_as_meta: bool, cx: &mut Context<Self>) {
+        self.option_as_meta = option_as_meta;
+        for terminal in self.tabs.iter().filter_map(|tab| tab.terminal.clone()) {
+            terminal.update(cx, |terminal, _cx| {
+                terminal.set_option_as_meta(option_as_meta)
+            });
+        }
+    }
+
+    /// Draw bold text in normal ANSI colors with their bright variants.
+    pub fn set_bold_is_bright(&mut self, bold_is_bright: bool, cx: &mut Context<Self>) {
+        self.bold_is_bright = bold_is_bright;
+        cx.notify();
+    }
+
+    /// Draw every tab's text at `font_size`, kept within the zoom range.
+    pub fn set_font_size(&mut self, font_size: f32, cx: &mut Context<Self>) {
+        self.font_size = if font_size.is_finite() {
+            font_size.clamp(MIN_FONT_SIZE, MAX_FONT_SIZE)
+        } else {
+            DEFAULT_FONT_SIZE
+        };
+        cx.notify();
+    }
+
+    /// Change the font size from a zoom key, reporting it if it changed.
+    /// The grid reflows on the next paint, which measures cells at the new size.
+    fn zoom(&mut self, font_size: f32, cx: &mut Context<Self>) {
+        let before = self.font_size;
+        self.set_font_size(font_size, cx);
+        if self.font_size != before {
+            cx.emit(Event::FontSizeChanged(self.font_size));
+        }
+    }
+
+    /// Start or stop PTY logging on a tab's terminal to match `pty_log_dir`.
+    fn apply_pty_logging(
+        &self,
+        tab_id: usize,
+        terminal: &Entity<Terminal>,
+        cx: &mut Context<Self>,
+    ) {
+        let dir = self.pty_log_dir.clone();
+        terminal.update(cx, |terminal, _cx| match dir {
+            Some(dir) => {
+                let path = dir.join(format!("terminal-{}.log", tab_id));
+                let result =
+                    std::fs::create_dir_all(&dir).and_then(|_| terminal.start_logging(&path));
+                if let Err(e) = result {
+                    eprintln!("Failed to start PTY log {}: {}", path.display(), e);
+                }
+            }
+            None => terminal.stop_logging(),
+        });
+    }
+
     /// Add a new terminal tab and switch to it.
     fn add_tab(&mut self, window: &mut Window, cx: &mut Context<Self>) {
-        self.create_tab_internal(window, cx);
+        self.create_tab_internal(
+            None,
+            "Terminal".to_string(),
+            None,
+            HashMap::new(),
+            window,
+            cx,
+        );
+        cx.notify();
+    }
+
+    /// Open a shell tab in `working_directory` and switch to it, saving its
+    /// output to `output_log` when given. `env` is set on top of the shell's
+    /// inherited environment.
+    pub fn open_tab_in(
+        &mut self,
+        working_directory: PathBuf,
+        title: String,
+        output_log: Option<PathBuf>,
+        env: HashMap<String, String>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.create_tab_internal(Some(working_directory), title, output_log, env, window, cx);
+        window.focus(&self.focus_handle);
         cx.notify();
     }
 
     /// Internal method to create a new tab.
-    fn create_tab_internal(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+    fn create_tab_internal(
+        &mut self,
+        working_directory: Option<PathBuf>,
+        title: String,
+        output_log: Option<PathBuf>,
+        env: HashMap<String, String>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
         let tab_id = self.next_tab_id;
         self.next_tab_id += 1;
 
         let tab = TerminalTab {
             id: tab_id,
-            title: "Terminal".to_string(),
+            title,
             terminal: None,
         };
         self.tabs.push(tab);
@@ -95,7 +289,7 @@ impl TerminalView {
         // Spawn the terminal creation in the background
         let task = cx
             .background_executor()
-            .spawn(async { TerminalBuilder::new(None, 0) });
+            .spawn(async move { TerminalBuilder::new(working_directory, 0, env) });
 
         let tab_idx = self.tabs.len() - 1;
         cx.spawn_in(
@@ -103,8 +297,28 @@ impl TerminalView {
             async move |this: WeakEntity<Self>, cx: &mut AsyncWindowContext| match task.await {
                 Ok(builder) => {
                     this.update_in(cx, |this, _window, cx| {
-                        let terminal = cx.new(|cx| builder.build(cx));
+                        let terminal = cx.new(|cx| {
+                            let mut terminal = builder.build(cx);
+                            if this.word_separators != DEFAULT_WORD_SEPARATORS {
+                                terminal.set_word_separators(&this.word_separators);
+                            }
+                            terminal.set_allow_clipboard_writes(this.allow_clipboard_writes);
+                            terminal.set_option_as_meta(this.option_as_meta);
+                            if let Some(path) = &output_log {
+                                let result = path
+                                    .parent()
+                                    .map_or(Ok(()), std::fs::create_dir_all)
+                                    .and_then(|_| terminal.start_output_log(path));
+                                if let Err(e) = result {
+                                    eprintln!("Failed to save output to {}: {}", path.display(), e);
+                                }
+                            }
+                            terminal
+                        });
                         this.subscribe_to_terminal(tab_idx, &terminal, cx);
+                        if this.pty_log_dir.is_some() {
+                            this.apply_pty_logging(tab_id, &terminal, cx);
+                        }
                         if let Some(tab) = this.tabs.get_mut(tab_idx) {
                             tab.terminal = Some(terminal);
                         }
@@ -216,6 +430,20 @@ impl TerminalView {
         _window: &mut Window,
         cx: &mut Context<Self>,
     ) {
+        if event.button == MouseButton::Left {
+            if let Some((thumb, y)) = self.scrollbar_hit(event.position, cx) {
+                // Pressing the track off the thumb centers the thumb there
+                let grab = if thumb.contains(y) {
+                    y - thumb.top
+                } else {
+                    thumb.height / 2.0
+                };
+                self.scrollbar_drag = Some((thumb, grab));
+                self.drag_scrollbar(event.position, cx);
+                return;
+            }
+        }
+
         if let Some(tab) = self.tabs.get(self.active_tab_idx) {
             if let Some(terminal) = &tab.terminal {
                 // Get terminal bounds to calculate position relative to content area
@@ -240,6 +468,11 @@ impl TerminalView {
     }
 
     fn on_mouse_up(&mut self, event: &MouseUpEvent, _window: &mut Window, cx: &mut Context<Self>) {
+        if self.scrollbar_drag.take().is_some() {
+            self.show_scrollbar(cx);
+            return;
+        }
+
         if let Some(tab) = self.tabs.get(self.active_tab_idx) {
             if let Some(terminal) = &tab.terminal {
                 // Get terminal bounds to calculate position relative to content area
@@ -269,6 +502,18 @@ impl TerminalView {
         _window: &mut Window,
         cx: &mut Context<Self>,
     ) {
+        if self.scrollbar_drag.is_some() {
+            // The button may have been released outside the view
+            if event.pressed_button == Some(MouseButton::Left) {
+                self.drag_scrollbar(event.position, cx);
+                return;
+            }
+            self.scrollbar_drag = None;
+        }
+        if self.scrollbar_hit(event.position, cx).is_some() {
+            self.show_scrollbar(cx);
+        }
+
         if let Some(tab) = self.tabs.get(self.active_tab_idx) {
             if let Some(terminal) = &tab.terminal {
                 // Get terminal bounds to calculate position relative to content area
@@ -293,6 +538,32 @@ impl TerminalView {
         }
     }
 
+    fn on_scroll_wheel(
+        &mut self,
+        event: &ScrollWheelEvent,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(tab) = self.tabs.get(self.active_tab_idx) {
+            if let Some(terminal) = &tab.terminal {
+                let terminal_bounds = terminal.read(cx).last_content().terminal_bounds;
+                let bounds_origin = terminal_bounds.bounds.origin;
+
+                // Convert window position to position relative to terminal content origin
+                let content_position = gpui::point(
+                    event.position.x - bounds_origin.x,
+                    event.position.y - bounds_origin.y,
+                );
+                let delta = event.delta.pixel_delta(terminal_bounds.line_height);
+
+                terminal.update(cx, |terminal, _cx| {
+                    terminal.scroll_wheel(delta.y, content_position, event.modifiers);
+                });
+                self.show_scrollbar(cx);
+            }
+        }
+    }
+
     fn on_modifiers_changed(
         &mut self,
         event: &ModifiersChangedEvent,
@@ -315,20 +586,43 @@ impl TerminalView {
     fn on_key_down(&mut self, event: &KeyDownEvent, window: &mut Window, cx: &mut Context<Self>) {
         let keystroke = &event.keystroke;
 
-        // Handle Ctrl+Tab to cycle through tabs
-        if keystroke.modifiers.control && keystroke.key.as_str() == "tab" {
-            if keystroke.modifiers.shift {
-                self.prev_tab(cx);
-            } else {
+        // Keys bound in the keymap's Terminal context are handled here
+        // instead of being sent to the shell.
+        let action = cx
+            .try_global::<Keymap>()
+            .and_then(|keymap| keymap.action_for("Terminal", keystroke))
+            .map(str::to_owned);
+        match action.as_deref() {
+            Some("terminal::NextTab") => {
                 self.next_tab(cx);
+                return;
             }
-            return;
-        }
-
-        // Handle Cmd+T to create new terminal tab
-        if keystroke.modifiers.platform && keystroke.key.as_str() == "t" {
-            self.add_tab(window, cx);
-            return;
+            Some("terminal::PreviousTab") => {
+                self.prev_tab(cx);
+                return;
+            }
+            Some("terminal::NewTab") => {
+                self.add_tab(window, cx);
+                return;
+            }
+            Some("terminal::ClearScrollback") => {
+                self.clear_scrollback(cx);
+                return;
+            }
+            Some("terminal::IncreaseFontSize") => {
+                self.zoom(self.font_size + FONT_SIZE_STEP, cx);
+                return;
+            }
+            Some("terminal::DecreaseFontSize") => {
+                self.zoom(self.font_size - FONT_SIZE_STEP, cx);
+                return;
+            }
+            Some("terminal::ResetFontSize") => {
+                self.zoom(DEFAULT_FONT_SIZE, cx);
+                return;
+            }
+            Some(action) if self.scroll_for_action(action, cx) => return,
+            _ => {}
         }
 
         if let Some(tab) = self.tabs.get(self.active_tab_idx) {
@@ -340,21 +634,133 @@ impl TerminalView {
         }
     }
 
+    /// Drop the active terminal's scrollback, keeping what is on screen.
+    fn clear_scrollback(&mut self, cx: &mut Context<Self>) {
+        if let Some(terminal) = self
+            .tabs
+            .get(self.active_tab_idx)
+            .and_then(|tab| tab.terminal.clone())
+        {
+            terminal.update(cx, |terminal, _cx| terminal.clear_scrollback());
+            cx.notify();
+        }
+    }
+
+    /// Scroll the active terminal's scrollback for a keymap scroll action.
+    ///
+    /// Returns false for other actions, and in the alternate screen, where
+    /// there is no scrollback and the key belongs to the running program.
+    fn scroll_for_action(&mut self, action: &str, cx: &mut Context<Self>) -> bool {
+        let Some(terminal) = self
+            .tabs
+            .get(self.active_tab_idx)
+            .and_then(|tab| tab.terminal.clone())
+        else {
+            return false;
+        };
+        if terminal
+            .read(cx)
+            .last_content()
+            .mode
+            .contains(Mode::ALT_SCREEN)
+        {
+            return false;
+        }
+
+        let handled = terminal.update(cx, |terminal, _cx| {
+            match action {
+                "terminal::ScrollLineUp" => terminal.scroll_lines(1),
+                "terminal::ScrollLineDown" => terminal.scroll_lines(-1),
+                "terminal::ScrollHalfPageUp" => terminal.scroll_pages(0.5),
+                "terminal::ScrollHalfPageDown" => terminal.scroll_pages(-0.5),
+                "terminal::ScrollPageUp" => terminal.scroll_pages(1.0),
+                "terminal::ScrollPageDown" => terminal.scroll_pages(-1.0),
+                "terminal::ScrollToTop" => terminal.scroll_to_top(),
+                "terminal::ScrollToBottom" => terminal.scroll_to_bottom(),
+                _ => return false,
+            }
+            true
+        });
+        if handled {
+            self.show_scrollbar(cx);
+        }
+        handled
+    }
+
+    /// Show the scrollbar, hiding it again once it has been left alone for
+    /// `SCROLLBAR_FADE_DELAY`.
+    fn show_scrollbar(&mut self, cx: &mut Context<Self>) {
+        self.scrollbar_visible = true;
+        let background_executor = cx.background_executor().clone();
+        self.scrollbar_fade = Some(cx.spawn(async move |this, cx| {
+            background_executor.timer(SCROLLBAR_FADE_DELAY).await;
+            this.update(cx, |view, cx| {
+                if view.scrollbar_drag.is_none() {
+                    view.scrollbar_visible = false;
+                    cx.notify();
+                }
+            })
+            .ok();
+        }));
+        cx.notify();
+    }
+
+    /// The active terminal's scrollbar thumb and how far down the track
+    /// `position` is, as a fraction of its height, when `position` is over
+    /// the track of a terminal scrolled back into history.
+    fn scrollbar_hit(&self, position: Point<Pixels>, cx: &App) -> Option<(ScrollThumb, f32)> {
+        let terminal = self.tabs.get(self.active_tab_idx)?.terminal.as_ref()?;
+        let content = terminal.read(cx).last_content();
+        let thumb = ScrollThumb::new(
+            content.history_size,
+            content.terminal_bounds.num_lines(),
+            content.display_offset,
+        )?;
+        let track = scrollbar_track(content.terminal_bounds.bounds);
+        track
+            .contains(&position)
+            .then(|| (thumb, (position.y - track.origin.y) / track.size.height))
+    }
+
+    /// Scroll the active terminal so the dragged thumb follows `position`.
+    fn drag_scrollbar(&mut self, position: Point<Pixels>, cx: &mut Context<Self>) {
+        let Some((thumb, grab)) = self.scrollbar_drag else {
+            return;
+        };
+        let Some(terminal) = self
+            .tabs
+            .get(self.active_tab_idx)
+            .and_then(|tab| tab.terminal.clone())
+        else {
+            return;
+        };
+        terminal.update(cx, |terminal, _cx| {
+            let content = terminal.last_content();
+            let track = scrollbar_track(content.terminal_bounds.bounds);
+            let y = (position.y - track.origin.y) / track.size.height;
+            let offset = thumb.offset_at(y - grab, content.history_size);
+            terminal.scroll_to_offset(offset);
+        });
+        self.show_scrollbar(cx);
+    }
+
     /// Render the active terminal content.
     fn render_terminal_content(&self, window: &mut Window, cx: &App) -> impl IntoElement {
         let focused = self.focus_handle.is_focused(window);
-        let bg_color: gpui::Hsla = TerminalColors::background().into();
+        let bg_color: gpui::Hsla = self.theme.background.into();
 
         if let Some(tab) = self.tabs.get(self.active_tab_idx) {
             if let Some(terminal) = &tab.terminal {
                 div()
                     .size_full()
                     .bg(bg_color)
-                    .child(TerminalElement::new(
-                        terminal.clone(),
-                        self.focus_handle.clone(),
-                        focused,
-                    ))
+                    .child(
+                        TerminalElement::new(terminal.clone(), self.focus_handle.clone(), focused)
+                            .bold_is_bright(self.bold_is_bright)
+                            .theme(self.theme)
+                            .font_size(px(self.font_size))
+                            .scrollbar_visible(self.scrollbar_visible),
+                    )
                     .into_any_element()
             } else {
                 // Terminal still loading
@@ -517,8 +923,13 @@ impl Render for TerminalView {
             .key_context("Terminal")
             .on_key_down(cx.listener(Self::on_key_down))
             .on_mouse_down(MouseButton::Left, cx.listener(Self::on_mouse_down))
+            .on_mouse_down(MouseButton::Middle, cx.listener(Self::on_mouse_down))
+            .on_mouse_down(MouseButton::Right, cx.listener(Self::on_mouse_down))
             .on_mouse_up(MouseButton::Left, cx.listener(Self::on_mouse_up))
+            .on_mouse_up(MouseButton::Middle, cx.listener(Self::on_mouse_up))
+            .on_mouse_up(MouseButton::Right, cx.listener(Self::on_mouse_up))
             .on_mouse_move(cx.listener(Self::on_mouse_move))
+            .on_scroll_wheel(cx.listener(Self::on_scroll_wheel))
             .on_modifiers_changed(cx.listener(Self::on_modifiers_changed))
             // Tab bar
             .child(tab_bar)
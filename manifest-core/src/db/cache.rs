@@ -0,0 +1,183 @@
+//! Bounded in-memory cache for hot read paths.
+//!
+//! When enabled with [`Database::with_cache`](super::Database::with_cache),
+//! project, feature and feature-tree reads are served from memory and the
+//! write methods drop the entries they affect. Only writes made through the
+//! same handle (or its clones) are seen, so leave the cache off when another
+//! process writes to the same database file.
+
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+use std::sync::Mutex;
+
+use uuid::Uuid;
+
+use crate::models::{Feature, FeatureTreeNode, Project};
+
+/// Map that evicts its oldest entry once `capacity` is reached.
+struct BoundedMap<K, V> {
+    entries: HashMap<K, V>,
+    order: VecDeque<K>,
+    capacity: usize,
+}
+
+impl<K: Eq + Hash + Clone, V: Clone> BoundedMap<K, V> {
+    fn new(capacity: usize) -> Self {
+        Self {
+            entries: HashMap::new(),
+            order: VecDeque::new(),
+            capacity,
+        }
+    }
+
+    fn get(&self, key: &K) -> Option<V> {
+        self.entries.get(key).cloned()
+    }
+
+    fn insert(&mut self, key: K, value: V) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.entries.insert(key.clone(), value).is_none() {
+            self.order.push_back(key);
+        }
+        while self.entries.len() > self.capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.entries.remove(&oldest);
+            }
+        }
+    }
+
+    fn remove(&mut self, key: &K) {
+        if self.entries.remove(key).is_some() {
+            self.order.retain(|k| k != key);
+        }
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.order.clear();
+    }
+}
+
+struct Entries {
+    /// Bumped on every invalidation so a read that raced a write is not stored.
+    generation: u64,
+    projects: BoundedMap<Uuid, Project>,
+    features: BoundedMap<Uuid, Feature>,
+    trees: BoundedMap<Uuid, Vec<FeatureTreeNode>>,
+}
+
+/// Cached reads keyed by ID, each kind holding at most `capacity` entries.
+pub(crate) struct ReadCache {
+    entries: Mutex<Entries>,
+}
+
+impl ReadCache {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            entries: Mutex::new(Entries {
+                generation: 0,
+                projects: BoundedMap::new(capacity),
+                features: BoundedMap::new(capacity),
+                trees: BoundedMap::new(capacity),
+            }),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Entries> {
+        self.entries.lock().expect("cache lock poisoned")
+    }
+
+    /// Current generation, taken before loading a value to cache.
+    pub(crate) fn generation(&self) -> u64 {
+        self.lock().generation
+    }
+
+    pub(crate) fn project(&self, id: Uuid) -> Option<Project> {
+        self.lock().projects.get(&id)
+    }
+
+    pub(crate) fn put_project(&self, generation: u64, project: &Project) {
+        let mut entries = self.lock();
+        if entries.generation == generation {
+            entries.projects.insert(project.id, project.clone());
+        }
+    }
+
+    pub(crate) fn feature(&self, id: Uuid) -> Option<Feature> {
+        self.lock().features.get(&id)
+    }
+
+    pub(crate) fn put_feature(&self, generation: u64, feature: &Feature) {
+        let mut entries = self.lock();
+        if entries.generation == generation {
+            entries.features.insert(feature.id, feature.clone());
+        }
+    }
+
+    pub(crate) fn feature_tree(&self, project_id: Uuid) -> Option<Vec<FeatureTreeNode>> {
+        self.lock().trees.get(&project_id)
+    }
+
+    pub(crate) fn put_feature_tree(
+        &self,
+        generation: u64,
+        project_id: Uuid,
+        tree: &[FeatureTreeNode],
+    ) {
+        let mut entries = self.lock();
+        if entries.generation == generation {
+            entries.trees.insert(project_id, tree.to_vec());
+        }
+    }
+
+    pub(crate) fn invalidate_project(&self, id: Uuid) {
+        let mut entries = self.lock();
+        entries.generation += 1;
+        entries.projects.remove(&id);
+        entries.trees.remove(&id);
+    }
+
+    /// Drop a feature and every cached tree, since any tree may contain it.
+    pub(crate) fn invalidate_feature(&self, id: Uuid) {
+        let mut entries = self.lock();
+        entries.generation += 1;
+        entries.features.remove(&id);
+        entries.trees.clear();
+    }
+
+    /// Drop all features and trees, for writes that cascade to unknown rows.
+    pub(crate) fn invalidate_features(&self) {
+        let mut entries = self.lock();
+        entries.generation += 1;
+        entries.features.clear();
+        entries.trees.clear();
+    }
+
+    pub(crate) fn clear(&self) {
+        let mut entries = self.lock();
+        entries.generation += 1;
+        entries.projects.clear();
+        entries.features.clear();
+        entries.trees.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bounded_map_evicts_oldest_entry() {
+        let mut map = BoundedMap::new(2);
+        map.insert(1, "one");
+        map.insert(2, "two");
+        map.insert(1, "uno");
+        map.insert(3, "three");
+
+        assert_eq!(map.get(&1), None);
+        assert_eq!(map.get(&2), Some("two"));
+        assert_eq!(map.get(&3), Some("three"));
+    }
+}
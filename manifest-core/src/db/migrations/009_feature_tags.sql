@@ -0,0 +1,19 @@
+-- Free-form tags for cross-cutting concerns ("frontend", "tech-debt").
+-- Tag names are scoped to a project and stored normalized to lowercase.
+
+CREATE TABLE tags (
+    id TEXT PRIMARY KEY,
+    project_id TEXT NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    UNIQUE (project_id, name)
+);
+
+CREATE TABLE feature_tags (
+    feature_id TEXT NOT NULL REFERENCES features(id) ON DELETE CASCADE,
+    tag_id TEXT NOT NULL REFERENCES tags(id) ON DELETE CASCADE,
+    PRIMARY KEY (feature_id, tag_id)
+);
+
+-- Index for finding features by tag
+CREATE INDEX idx_feature_tags_tag ON feature_tags(tag_id);
@@ -0,0 +1,13 @@
+-- "Blocked by" relationships between features, independent of the tree.
+-- A row means feature_id cannot be finished until depends_on_id is.
+
+CREATE TABLE feature_dependencies (
+    feature_id TEXT NOT NULL REFERENCES features(id) ON DELETE CASCADE,
+    depends_on_id TEXT NOT NULL REFERENCES features(id) ON DELETE CASCADE,
+    created_at TEXT NOT NULL,
+    PRIMARY KEY (feature_id, depends_on_id),
+    CHECK (feature_id != depends_on_id)
+);
+
+-- Index for finding the features a feature blocks
+CREATE INDEX idx_feature_dependencies_depends_on ON feature_dependencies(depends_on_id);
@@ -0,0 +1,28 @@
+-- Full-text index over feature titles and details, kept in step with the
+-- features table by triggers. The trigram tokenizer matches any substring of
+-- three or more characters, so partial words and text like "C++" are found.
+-- A migration that rebuilds the features table must recreate these triggers.
+
+CREATE VIRTUAL TABLE features_fts USING fts5(
+    feature_id UNINDEXED,
+    title,
+    details,
+    tokenize = 'trigram'
+);
+
+INSERT INTO features_fts (feature_id, title, details)
+SELECT id, title, COALESCE(details, '') FROM features;
+
+CREATE TRIGGER features_fts_insert AFTER INSERT ON features BEGIN
+    INSERT INTO features_fts (feature_id, title, details)
+    VALUES (new.id, new.title, COALESCE(new.details, ''));
+END;
+
+CREATE TRIGGER features_fts_update AFTER UPDATE OF title, details ON features BEGIN
+    UPDATE features_fts SET title = new.title, details = COALESCE(new.details, '')
+    WHERE feature_id = old.id;
+END;
+
+CREATE TRIGGER features_fts_delete AFTER DELETE ON features BEGIN
+    DELETE FROM features_fts WHERE feature_id = old.id;
+END;
@@ -0,0 +1,9 @@
+-- Deleting a feature moves it to the trash by setting deleted_at, so it can
+-- be restored. Every feature query excludes rows where it is set; rows are
+-- only removed when the trash is purged. Existing features are not deleted,
+-- so the column starts out NULL everywhere.
+
+ALTER TABLE features ADD COLUMN deleted_at TEXT;
+
+-- Index for listing and purging the trash
+CREATE INDEX idx_features_deleted_at ON features(deleted_at);
@@ -0,0 +1,5 @@
+-- The desktop app saves the output of a task's terminal to a log file so it
+-- can be read after the tab is closed. The column records where; it stays
+-- NULL for tasks never opened in a terminal.
+
+ALTER TABLE tasks ADD COLUMN log_path TEXT;
@@ -0,0 +1,6 @@
+-- Links between features come in kinds. Existing rows are "blocked by"
+-- dependencies; 'relates' links features with no ordering between them.
+-- A "blocks" link is stored as the other feature depending on this one.
+
+ALTER TABLE feature_dependencies ADD COLUMN kind TEXT NOT NULL DEFAULT 'depends_on'
+    CHECK (kind IN ('depends_on', 'relates'));
@@ -1,16 +1,21 @@
+mod cache;
 mod schema;
 
+use std::cell::Cell;
+use std::collections::HashMap;
 use std::fmt;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::sync::{Arc, Mutex};
 
 use anyhow::Result;
 use chrono::{DateTime, Utc};
+use rusqlite::trace::{TraceEvent, TraceEventCodes};
 use rusqlite::Connection;
 use uuid::Uuid;
 
 use crate::models::*;
+use cache::ReadCache;
 
 /// Domain errors that can be meaningfully handled by callers.
 /// These are distinct from infrastructure errors (SQLite failures, etc.)
@@ -23,6 +28,8 @@ pub enum ManifestError {
     Validation(String),
     /// Operation not allowed in current state (e.g., session not active)
     InvalidState(String),
+    /// Write rejected because the resource changed since the caller read it
+    Conflict(String),
 }
 
 impl ManifestError {
@@ -38,6 +45,10 @@ impl ManifestError {
         ManifestError::InvalidState(msg.into())
     }
 
+    pub fn conflict(msg: impl Into<String>) -> Self {
+        ManifestError::Conflict(msg.into())
+    }
+
     /// Returns true if this is a client error (4xx), false if server error (5xx)
     pub fn is_client_error(&self) -> bool {
         true // All ManifestError variants are client errors
@@ -50,14 +61,22 @@ impl fmt::Display for ManifestError {
             ManifestError::NotFound(msg) => write!(f, "{}", msg),
             ManifestError::Validation(msg) => write!(f, "{}", msg),
             ManifestError::InvalidState(msg) => write!(f, "{}", msg),
+            ManifestError::Conflict(msg) => write!(f, "{}", msg),
         }
     }
 }
 
 impl std::error::Error for ManifestError {}
 
+/// Callback invoked with each committed change.
+type ChangeListener = Arc<dyn Fn(&ChangeEvent) + Send + Sync>;
+
 pub struct Database {
     conn: Arc<Mutex<Connection>>,
+    /// Read cache, off unless enabled with `with_cache`.
+    cache: Option<Arc<ReadCache>>,
+    /// Change listener, set with `with_change_listener`.
+    listener: Option<ChangeListener>,
 }
 
 impl Database {
@@ -70,6 +89,8 @@ impl Database {
         conn.pragma_update(None, "journal_mode", "WAL")?;
         Ok(Self {
             conn: Arc::new(Mutex::new(conn)),
+            cache: None,
+            listener: None,
         })
     }
 
@@ -89,9 +110,59 @@ impl Database {
         let conn = Connection::open_in_memory()?;
         Ok(Self {
             conn: Arc::new(Mutex::new(conn)),
+            cache: None,
+            listener: None,
         })
     }
 
+    /// Cache project, feature and feature-tree reads, holding up to
+    /// `capacity` entries of each. Writes through this handle or its clones
+    /// invalidate affected entries; writes from other processes are not seen.
+    pub fn with_cache(mut self, capacity: usize) -> Self {
+        self.cache = Some(Arc::new(ReadCache::new(capacity)));
+        self
+    }
+
+    /// Call `listener` after each write through this handle or its clones
+    /// commits. It runs on the writing thread with no lock held, so it should
+    /// hand the event off (to a channel, say) rather than do slow work.
+    pub fn with_change_listener(
+        mut self,
+        listener: impl Fn(&ChangeEvent) + Send + Sync + 'static,
+    ) -> Self {
+        self.listener = Some(Arc::new(listener));
+        self
+    }
+
+    /// Run `f` and count the SQL statements it executes on this thread, for
+    /// checking that an operation's query count doesn't grow with the data.
+    pub fn count_statements<T>(&self, f: impl FnOnce() -> T) -> (T, usize) {
+        thread_local! {
+            static STATEMENTS: Cell<usize> = const { Cell::new(0) };
+        }
+        fn count(_: TraceEvent<'_>) {
+            STATEMENTS.with(|n| n.set(n.get() + 1));
+        }
+
+        let trace = |f: Option<fn(TraceEvent<'_>)>| {
+            self.conn
+                .lock()
+                .expect("database lock poisoned")
+                .trace_v2(TraceEventCodes::SQLITE_TRACE_STMT, f)
+        };
+        STATEMENTS.with(|n| n.set(0));
+        trace(Some(count));
+        let result = f();
+        trace(None);
+        (result, STATEMENTS.with(Cell::get))
+    }
+
+    fn notify(&self, event: ChangeEvent) {
+        if let Some(listener) = &self.listener {
+            listener(&event);
+        }
+    }
+
     pub fn migrate(&self) -> Result<()> {
         let conn = self.conn.lock().expect("database lock poisoned");
         schema::run_migrations(&conn)
@@ -125,6 +196,20 @@ impl Database {
     }
 
     pub fn get_project(&self, id: Uuid) -> Result<Option<Project>> {
+        if let Some(project) = self.cache.as_ref().and_then(|c| c.project(id)) {
+            return Ok(Some(project));
+        }
+
+        let generation = self.cache.as_ref().map(|c| c.generation());
+        let project = self.load_project(id)?;
+        if let (Some(cache), Some(generation), Some(project)) = (&self.cache, generation, &project)
+        {
+            cache.put_project(generation, project);
+        }
+        Ok(project)
+    }
+
+    fn load_project(&self, id: Uuid) -> Result<Option<Project>> {
         let conn = self.conn.lock().expect("database lock poisoned");
         let mut stmt = conn.prepare(
             "SELECT id, name, description, instructions, created_at, updated_at
@@ -196,6 +281,10 @@ impl Database {
             ),
         )?;
 
+        if let Some(cache) = &self.cache {
+            cache.invalidate_project(id);
+        }
+
         Ok(Some(Project {
             id,
             name,
@@ -209,6 +298,11 @@ impl Database {
     pub fn delete_project(&self, id: Uuid) -> Result<bool> {
         let conn = self.conn.lock().expect("database lock poisoned");
         let rows = conn.execute("DELETE FROM projects WHERE id = ?", [id.to_string()])?;
+
+        // Features and their trees cascade with the project
+        if let Some(cache) = &self.cache {
+            cache.clear();
+        }
         Ok(rows > 0)
     }
 
@@ -349,22 +443,22 @@ impl Database {
         let (sql, params): (String, Vec<Box<dyn rusqlite::ToSql>>) = match (limit, offset) {
             (Some(lim), Some(off)) => (
                 "SELECT id, project_id, parent_id, title, details, desired_details, state, priority, created_at, updated_at
-                 FROM features ORDER BY priority, title LIMIT ? OFFSET ?".to_string(),
+                 FROM features WHERE deleted_at IS NULL ORDER BY priority, title, id LIMIT ? OFFSET ?".to_string(),
                 vec![Box::new(lim) as Box<dyn rusqlite::ToSql>, Box::new(off)],
             ),
             (Some(lim), None) => (
                 "SELECT id, project_id, parent_id, title, details, desired_details, state, priority, created_at, updated_at
-                 FROM features ORDER BY priority, title LIMIT ?".to_string(),
+                 FROM features WHERE deleted_at IS NULL ORDER BY priority, title, id LIMIT ?".to_string(),
                 vec![Box::new(lim) as Box<dyn rusqlite::ToSql>],
             ),
             (None, Some(off)) => (
                 "SELECT id, project_id, parent_id, title, details, desired_details, state, priority, created_at, updated_at
-                 FROM features ORDER BY priority, title LIMIT -1 OFFSET ?".to_string(),
+                 FROM features WHERE deleted_at IS NULL ORDER BY priority, title, id LIMIT -1 OFFSET ?".to_string(),
                 vec![Box::new(off) as Box<dyn rusqlite::ToSql>],
             ),
             (None, None) => (
                 "SELECT id, project_id, parent_id, title, details, desired_details, state, priority, created_at, updated_at
-                 FROM features ORDER BY priority, title".to_string(),
+                 FROM features WHERE deleted_at IS NULL ORDER BY priority, title, id".to_string(),
                 vec![],
             ),
         };
@@ -410,7 +504,7 @@ impl Database {
         let (sql, params): (String, Vec<Box<dyn rusqlite::ToSql>>) = match (limit, offset) {
             (Some(lim), Some(off)) => (
                 "SELECT id, project_id, parent_id, title, details, desired_details, state, priority, created_at, updated_at
-                 FROM features WHERE project_id = ? ORDER BY priority, title LIMIT ? OFFSET ?".to_string(),
+                 FROM features WHERE project_id = ? AND deleted_at IS NULL ORDER BY priority, title, id LIMIT ? OFFSET ?".to_string(),
                 vec![
                     Box::new(project_id_str.clone()) as Box<dyn rusqlite::ToSql>,
                     Box::new(lim),
@@ -419,7 +513,7 @@ impl Database {
             ),
             (Some(lim), None) => (
                 "SELECT id, project_id, parent_id, title, details, desired_details, state, priority, created_at, updated_at
-                 FROM features WHERE project_id = ? ORDER BY priority, title LIMIT ?".to_string(),
+                 FROM features WHERE project_id = ? AND deleted_at IS NULL ORDER BY priority, title, id LIMIT ?".to_string(),
                 vec![
                     Box::new(project_id_str.clone()) as Box<dyn rusqlite::ToSql>,
                     Box::new(lim),
@@ -427,7 +521,7 @@ impl Database {
             ),
             (None, Some(off)) => (
                 "SELECT id, project_id, parent_id, title, details, desired_details, state, priority, created_at, updated_at
-                 FROM features WHERE project_id = ? ORDER BY priority, title LIMIT -1 OFFSET ?".to_string(),
+                 FROM features WHERE project_id = ? AND deleted_at IS NULL ORDER BY priority, title, id LIMIT -1 OFFSET ?".to_string(),
                 vec![
                     Box::new(project_id_str.clone()) as Box<dyn rusqlite::ToSql>,
                     Box::new(off),
@@ -435,7 +529,7 @@ impl Database {
             ),
             (None, None) => (
                 "SELECT id, project_id, parent_id, title, details, desired_details, state, priority, created_at, updated_at
-                 FROM features WHERE project_id = ? ORDER BY priority, title".to_string(),
+                 FROM features WHERE project_id = ? AND deleted_at IS NULL ORDER BY priority, title, id".to_string(),
                 vec![Box::new(project_id_str.clone()) as Box<dyn rusqlite::ToSql>],
             ),
         };
@@ -469,10 +563,24 @@ impl Database {
     }
 
     pub fn get_feature(&self, id: Uuid) -> Result<Option<Feature>> {
+        if let Some(feature) = self.cache.as_ref().and_then(|c| c.feature(id)) {
+            return Ok(Some(feature));
+        }
+
+        let generation = self.cache.as_ref().map(|c| c.generation());
+        let feature = self.load_feature(id)?;
+        if let (Some(cache), Some(generation), Some(feature)) = (&self.cache, generation, &feature)
+        {
+            cache.put_feature(generation, feature);
+        }
+        Ok(feature)
+    }
+
+    fn load_feature(&self, id: Uuid) -> Result<Option<Feature>> {
         let conn = self.conn.lock().expect("database lock poisoned");
         let mut stmt = conn.prepare(
             "SELECT id, project_id, parent_id, title, details, desired_details, state, priority, created_at, updated_at
-             FROM features WHERE id = ?",
+             FROM features WHERE id = ? AND deleted_at IS NULL",
         )?;
 
         let mut rows = stmt.query([id.to_string()])?;
@@ -512,10 +620,251 @@ impl Database {
         }))
     }
 
+    /// Render a feature as Markdown for sharing: the title as a heading, its
+    /// state, priority and tags, then the details verbatim. With
+    /// `include_tasks`, the tasks of its latest session follow as a checklist.
+    pub fn feature_to_markdown(&self, id: Uuid, include_tasks: bool) -> Result<Option<String>> {
+        let Some(feature) = self.get_feature(id)? else {
+            return Ok(None);
+        };
+
+        let mut markdown = String::new();
+        self.push_feature_markdown(&mut markdown, &feature, 1, include_tasks)?;
+        Ok(Some(markdown))
+    }
+
+    /// Render a whole project as one Markdown document for reviewers: its name
+    /// and description, then each feature as in `feature_to_markdown`, with
+    /// heading levels following the tree and open tasks as checklists.
+    /// Returns `None` if the project doesn't exist.
+    pub fn export_project_markdown(&self, project_id: Uuid) -> Result<Option<String>> {
+        let Some(project) = self.get_project(project_id)? else {
+            return Ok(None);
+        };
+
+        let mut markdown = format!("# {}\n", project.name);
+        if let Some(description) = project
+            .description
+            .as_deref()
+            .filter(|d| !d.trim().is_empty())
+        {
+            markdown.push('\n');
+            markdown.push_str(description.trim_end());
+            markdown.push('\n');
+        }
+
+        let tree = self.get_feature_tree(project_id)?;
+        if tree.is_empty() {
+            markdown.push_str("\nNo features yet.\n");
+        }
+        self.push_tree_markdown(&mut markdown, &tree, 2)?;
+        Ok(Some(markdown))
+    }
+
+    fn push_tree_markdown(
+        &self,
+        markdown: &mut String,
+        nodes: &[FeatureTreeNode],
+        level: usize,
+    ) -> Result<()> {
+        for node in nodes {
+            markdown.push('\n');
+            self.push_feature_markdown(markdown, &node.feature, level, true)?;
+            self.push_tree_markdown(markdown, &node.children, level + 1)?;
+        }
+        Ok(())
+    }
+
+    /// Append `feature` with its title at heading `level` (capped at 6).
+    fn push_feature_markdown(
+        &self,
+        markdown: &mut String,
+        feature: &Feature,
+        level: usize,
+        include_tasks: bool,
+    ) -> Result<()> {
+        let mut metadata = vec![
+            format!("**State:** {}", feature.state.as_str()),
+            format!("**Priority:** {}", feature.priority),
+        ];
+        let tags = self.get_feature_tags(feature.id)?;
+        if !tags.is_empty() {
+            metadata.push(format!("**Tags:** {}", tags.join(", ")));
+        }
+
+        markdown.push_str(&format!(
+            "{} {}\n\n{}\n",
+            "#".repeat(level.min(6)),
+            feature.title,
+            metadata.join(" · ")
+        ));
+        if let Some(details) = feature.details.as_deref().filter(|d| !d.trim().is_empty()) {
+            markdown.push('\n');
+            markdown.push_str(details.trim_end());
+            markdown.push('\n');
+        }
+
+        let latest_session = if include_tasks {
+            self.get_sessions_by_feature(feature.id)?.into_iter().next()
+        } else {
+            None
+        };
+        if let Some(session) = latest_session {
+            let tasks = self.get_tasks_by_session(session.id)?;
+            if !tasks.is_empty() {
+                markdown.push_str(&format!(
+                    "\n{} Tasks\n\n{}\n\n",
+                    "#".repeat((level + 1).min(6)),
+                    session.goal
+                ));
+                for task in tasks {
+                    let mark = if task.status == TaskStatus::Completed {
+                        "x"
+                    } else {
+                        " "
+                    };
+                    markdown.push_str(&format!("- [{}] {}\n", mark, task.title));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Everything recorded about a project, for `import_project` to recreate
+    /// elsewhere. Returns `None` if the project doesn't exist.
+    pub fn export_project(&self, project_id: Uuid) -> Result<Option<ProjectExport>> {
+        let Some(project) = self.get_project(project_id)? else {
+            return Ok(None);
+        };
+
+        let tree = self.get_feature_tree(project_id)?;
+        Ok(Some(ProjectExport {
+            version: EXPORT_VERSION,
+            directories: self.get_project_directories(project_id)?,
+            features: self.export_features(tree)?,
+            project,
+        }))
+    }
+
+    fn export_features(&self, nodes: Vec<FeatureTreeNode>) -> Result<Vec<FeatureExport>> {
+        nodes
+            .into_iter()
+            .map(|node| {
+                let id = node.feature.id;
+                let sessions = self
+                    .get_sessions_by_feature(id)?
+                    .into_iter()
+                    .map(|session| {
+                        Ok(SessionExport {
+                            tasks: self.get_tasks_by_session(session.id)?,
+                            session,
+                        })
+                    })
+                    .collect::<Result<_>>()?;
+
+                Ok(FeatureExport {
+                    tags: self.get_feature_tags(id)?,
+                    depends_on: self
+                        .get_dependencies(id)?
+                        .into_iter()
+                        .map(|f| f.id)
+                        .collect(),
+                    related: self.get_related(id)?.into_iter().map(|f| f.id).collect(),
+                    history: self.get_feature_history(id)?,
+                    sessions,
+                    children: self.export_features(node.children)?,
+                    feature: node.feature,
+                })
+            })
+            .collect()
+    }
+
+    /// Recreate a project from `export_project` output, returning it.
+    ///
+    /// Ids are kept where they are free, so links to the project survive a
+    /// move between databases. An id already in use is replaced with a fresh
+    /// one, and parents, dependencies, sessions, tasks and history follow it.
+    /// The import runs in one transaction, so a failure changes nothing.
+    pub fn import_project(&self, export: ProjectExport) -> Result<Project> {
+        if export.version > EXPORT_VERSION {
+            return Err(ManifestError::validation(format!(
+                "Export format version {} is newer than this version of Manifest supports ({})",
+                export.version, EXPORT_VERSION
+            ))
+            .into());
+        }
+
+        let mut conn = self.conn.lock().expect("database lock poisoned");
+        let tx = conn.transaction()?;
+
+        let project = Project {
+            id: import_id(&tx, "projects", export.project.id)?,
+            ..export.project
+        };
+        tx.execute(
+            "INSERT INTO projects (id, name, description, instructions, created_at, updated_at)
+             VALUES (?, ?, ?, ?, ?, ?)",
+            (
+                project.id.to_string(),
+                &project.name,
+                &project.description,
+                &project.instructions,
+                project.created_at.to_rfc3339(),
+                project.updated_at.to_rfc3339(),
+            ),
+        )?;
+
+        for directory in &export.directories {
+            tx.execute(
+                "INSERT INTO project_directories (id, project_id, path, git_remote, is_primary, instructions, created_at)
+                 VALUES (?, ?, ?, ?, ?, ?, ?)",
+                (
+                    import_id(&tx, "project_directories", directory.id)?.to_string(),
+                    project.id.to_string(),
+                    &directory.path,
+                    &directory.git_remote,
+                    directory.is_primary,
+                    &directory.instructions,
+                    directory.created_at.to_rfc3339(),
+                ),
+            )?;
+        }
+
+        // Map every feature first so dependencies can point anywhere in the tree
+        let mut feature_ids = HashMap::new();
+        map_feature_ids(&tx, &export.features, &mut feature_ids)?;
+        import_features(&tx, project.id, None, &export.features, &feature_ids)?;
+
+        tx.commit()?;
+        drop(conn);
+
+        if let Some(cache) = &self.cache {
+            cache.invalidate_features();
+        }
+        for &feature_id in feature_ids.values() {
+            self.notify(ChangeEvent::FeatureChanged {
+                project_id: project.id,
+                feature_id,
+            });
+        }
+        Ok(project)
+    }
+
     pub fn create_feature(&self, project_id: Uuid, input: CreateFeatureInput) -> Result<Feature> {
         // Verify project exists
         self.get_project(project_id)?
             .ok_or_else(|| ManifestError::not_found("Project"))?;
+        if let Some(parent_id) = input.parent_id {
+            let parent = self
+                .get_feature(parent_id)?
+                .ok_or_else(|| ManifestError::not_found("Parent feature"))?;
+            if parent.project_id != project_id {
+                return Err(ManifestError::validation(
+                    "Parent feature belongs to a different project",
+                )
+                .into());
+            }
+        }
 
         let conn = self.conn.lock().expect("database lock poisoned");
         let id = input.id.unwrap_or_else(Uuid::new_v4);
@@ -539,6 +888,16 @@ impl Database {
             ),
         )?;
 
+        drop(conn);
+
+        if let Some(cache) = &self.cache {
+            cache.invalidate_feature(id);
+        }
+        self.notify(ChangeEvent::FeatureChanged {
+            project_id,
+            feature_id: id,
+        });
+
         Ok(Feature {
             id,
             project_id,
@@ -606,24 +965,167 @@ impl Database {
         }
 
         tx.commit()?;
+        drop(conn);
+
+        if let Some(cache) = &self.cache {
+            cache.invalidate_features();
+        }
+        for feature in &features {
+            self.notify(ChangeEvent::FeatureChanged {
+                project_id,
+                feature_id: feature.id,
+            });
+        }
         Ok(features)
     }
 
+    /// Create a copy of a feature as a starting point for a similar one.
+    ///
+    /// The copy sits under the same parent with the title suffixed "(copy)",
+    /// the same details and priority, and starts as `Proposed`. Pending
+    /// desired details, sessions, tasks and history stay with the original.
+    pub fn duplicate_feature(&self, id: Uuid) -> Result<Option<Feature>> {
+        let Some(original) = self.get_feature(id)? else {
+            return Ok(None);
+        };
+
+        self.create_feature(
+            original.project_id,
+            CreateFeatureInput {
+                id: None,
+                parent_id: original.parent_id,
+                title: format!("{} (copy)", original.title),
+                details: original.details,
+                state: Some(FeatureState::Proposed),
+                priority: Some(original.priority),
+            },
+        )
+        .map(Some)
+    }
+
+    /// Templates available when creating features in a project: the built-in
+    /// ones plus any found in the project's directories. Project templates
+    /// replace built-ins of the same name.
+    pub fn get_feature_templates(&self, project_id: Uuid) -> Result<Vec<FeatureTemplate>> {
+        self.get_project(project_id)?
+            .ok_or_else(|| ManifestError::not_found("Project"))?;
+
+        let mut templates = FeatureTemplate::builtin();
+        for dir in self.get_project_directories(project_id)? {
+            let found = match FeatureTemplate::load_dir(Path::new(&dir.path)) {
+                Ok(found) => found,
+                Err(e) => {
+                    tracing::warn!("Skipping templates in {}: {}", dir.path, e);
+                    continue;
+                }
+            };
+            for template in found {
+                match templates.iter_mut().find(|t| t.name == template.name) {
+                    Some(existing) => *existing = template,
+                    None => templates.push(template),
+                }
+            }
+        }
+        Ok(templates)
+    }
+
+    /// Create a feature whose details start from the named template.
+    /// Details given in `input` take precedence over the template content.
+    pub fn create_feature_from_template(
+        &self,
+        project_id: Uuid,
+        mut input: CreateFeatureInput,
+        template: &str,
+    ) -> Result<Feature> {
+        let template = self
+            .get_feature_templates(project_id)?
+            .into_iter()
+            .find(|t| t.name == template)
+            .ok_or_else(|| ManifestError::validation(format!("Unknown template: {}", template)))?;
+
+        if input.details.is_none() {
+            input.details = Some(template.content);
+        }
+        self.create_feature(project_id, input)
+    }
+
     pub fn update_feature(&self, id: Uuid, input: UpdateFeatureInput) -> Result<Option<Feature>> {
         let Some(existing) = self.get_feature(id)? else {
             return Ok(None);
         };
 
-        let conn = self.conn.lock().expect("database lock poisoned");
+        // The foreign key alone would accept a parent in the trash
+        let new_parent_id = input.parent_id.filter(|&p| Some(p) != existing.parent_id);
+        if let Some(parent_id) = new_parent_id {
+            let parent = self
+                .get_feature(parent_id)?
+                .ok_or_else(|| ManifestError::not_found("Parent feature"))?;
+            if parent.project_id != existing.project_id {
+                return Err(ManifestError::validation(
+                    "Parent feature belongs to a different project",
+                )
+                .into());
+            }
+        }
+
+        let mut conn = self.conn.lock().expect("database lock poisoned");
+        let tx = conn.transaction()?;
+        if let Some(parent_id) = new_parent_id {
+            // Under itself or a descendant, the feature would drop out of the tree
+            let creates_cycle: bool = tx.query_row(
+                "WITH RECURSIVE ancestors(id) AS (
+                     SELECT ?1
+                     UNION
+                     SELECT f.parent_id FROM features f
+                     JOIN ancestors a ON f.id = a.id
+                     WHERE f.parent_id IS NOT NULL
+                 )
+                 SELECT EXISTS(SELECT 1 FROM ancestors WHERE id = ?2)",
+                (parent_id.to_string(), id.to_string()),
+                |row| row.get(0),
+            )?;
+            if creates_cycle {
+                return Err(ManifestError::validation(
+                    "A feature cannot be moved under itself or one of its descendants",
+                )
+                .into());
+            }
+        }
         let now = Utc::now();
         let title = input.title.unwrap_or(existing.title);
         let details = input.details.or(existing.details);
         let desired_details = input.desired_details.or(existing.desired_details);
-        let state = input.state.unwrap_or(existing.state);
         let parent_id = input.parent_id.or(existing.parent_id);
         let priority = input.priority.unwrap_or(existing.priority);
 
-        conn.execute(
+        // Read the timestamp under the lock so a writer that got in since
+        // `existing` was loaded is caught. Parsing copes with every format
+        // parse_datetime accepts, which comparing strings would not. The
+        // state is read here too so the history logs the move actually made
+        let (current_state, current_updated_at) = {
+            let mut stmt = tx.prepare(
+                "SELECT state, updated_at FROM features WHERE id = ? AND deleted_at IS NULL",
+            )?;
+            let mut rows = stmt.query([id.to_string()])?;
+            match rows.next()? {
+                Some(row) => (
+                    FeatureState::from_str(&row.get::<_, String>(0)?)
+                        .unwrap_or(FeatureState::Proposed),
+                    parse_datetime(row.get::<_, String>(1)?),
+                ),
+                None => return Ok(None),
+            }
+        };
+        if let Some(expected) = input.expected_updated_at {
+            if expected != current_updated_at {
+                return Err(
+                    ManifestError::conflict("Feature was modified by another writer").into(),
+                );
+            }
+        }
+        let state = input.state.unwrap_or(current_state);
+
+        tx.execute(
             "UPDATE features SET parent_id = ?, title = ?, details = ?, desired_details = ?, state = ?, priority = ?, updated_at = ? WHERE id = ?",
             (
                 parent_id.map(|u| u.to_string()),
@@ -637,6 +1139,35 @@ impl Database {
             ),
         )?;
 
+        // Any state may follow any other; the history records each move
+        if state != current_state {
+            insert_history_entry(
+                &tx,
+                CreateHistoryInput {
+                    feature_id: id,
+                    session_id: None,
+                    details: HistoryDetails {
+                        summary: format!(
+                            "State changed from {} to {}",
+                            current_state.as_str(),
+                            state.as_str()
+                        ),
+                        commits: Vec::new(),
+                    },
+                },
+            )?;
+        }
+        tx.commit()?;
+        drop(conn);
+
+        if let Some(cache) = &self.cache {
+            cache.invalidate_feature(id);
+        }
+        self.notify(ChangeEvent::FeatureChanged {
+            project_id: existing.project_id,
+            feature_id: id,
+        });
+
         Ok(Some(Feature {
             id,
             project_id: existing.project_id,
@@ -651,17 +1182,269 @@ impl Database {
         }))
     }
 
+    /// Give sibling features the priorities 0, 1, 2... in the order listed,
+    /// so they sort in that order. Siblings left out follow them, keeping
+    /// their order among themselves.
+    ///
+    /// Fails if a feature doesn't exist or is listed twice, or if the
+    /// features don't share a parent. Returns the reordered features.
+    pub fn reorder_features(&self, feature_ids: &[Uuid]) -> Result<Vec<Feature>> {
+        let mut features = Vec::with_capacity(feature_ids.len());
+        for &id in feature_ids {
+            let feature = self
+                .get_feature(id)?
+                .ok_or_else(|| ManifestError::not_found("Feature"))?;
+            if features.iter().any(|f: &Feature| f.id == id) {
+                return Err(
+                    ManifestError::validation(format!("Feature {} is listed twice", id)).into(),
+                );
+            }
+            if let Some(first) = features.first() {
+                if (feature.project_id, feature.parent_id) != (first.project_id, first.parent_id) {
+                    return Err(ManifestError::validation(
+                        "Only features with the same parent can be reordered",
+                    )
+                    .into());
+                }
+            }
+            features.push(feature);
+        }
+
+        let mut conn = self.conn.lock().expect("database lock poisoned");
+        let tx = conn.transaction()?;
+        let now = Utc::now();
+        let mut changed = Vec::new();
+        for (priority, feature) in features.iter_mut().enumerate() {
+            let priority = priority as i32;
+            if feature.priority == priority {
+                continue;
+            }
+            tx.execute(
+                "UPDATE features SET priority = ?, updated_at = ? WHERE id = ?",
+                (priority, now.to_rfc3339(), feature.id.to_string()),
+            )?;
+            feature.priority = priority;
+            feature.updated_at = now;
+            changed.push(feature.id);
+        }
+
+        // Left at their old priorities, unlisted siblings would tie with
+        // listed ones and sort in among them
+        if let Some(first) = features.first() {
+            let unlisted = {
+                let mut stmt = tx.prepare(
+                    "SELECT id, priority FROM features
+                     WHERE project_id = ?1 AND parent_id IS ?2 AND deleted_at IS NULL
+                     ORDER BY priority, title, id",
+                )?;
+                let siblings = stmt
+                    .query_map(
+                        (
+                            first.project_id.to_string(),
+                            first.parent_id.map(|u| u.to_string()),
+                        ),
+                        |row| Ok((parse_uuid(row.get(0)?), row.get::<_, i32>(1)?)),
+                    )?
+                    .filter(|row| !matches!(row, Ok((id, _)) if feature_ids.contains(id)))
+                    .collect::<rusqlite::Result<Vec<_>>>()?;
+                siblings
+            };
+            for (offset, (id, current)) in unlisted.into_iter().enumerate() {
+                let priority = (features.len() + offset) as i32;
+                if current == priority {
+                    continue;
+                }
+                tx.execute(
+                    "UPDATE features SET priority = ?, updated_at = ? WHERE id = ?",
+                    (priority, now.to_rfc3339(), id.to_string()),
+                )?;
+                changed.push(id);
+            }
+        }
+        tx.commit()?;
+        drop(conn);
+
+        if let Some(cache) = &self.cache {
+            cache.invalidate_features();
+        }
+        for feature_id in changed {
+            self.notify(ChangeEvent::FeatureChanged {
+                project_id: features[0].project_id,
+                feature_id,
+            });
+        }
+        Ok(features)
+    }
+
+    /// Move a feature and its descendants to the trash.
+    ///
+    /// They disappear from every query but keep their sessions, history, tags
+    /// and dependencies, and come back together with `restore_feature`.
+    /// Returns false if the feature doesn't exist or is already deleted.
     pub fn delete_feature(&self, id: Uuid) -> Result<bool> {
+        let Some(feature) = self.get_feature(id)? else {
+            return Ok(false);
+        };
+
+        // Descendants share the timestamp, which is how a restore finds them
         let conn = self.conn.lock().expect("database lock poisoned");
-        let rows = conn.execute("DELETE FROM features WHERE id = ?", [id.to_string()])?;
-        Ok(rows > 0)
+        let mut stmt = conn.prepare(
+            "WITH RECURSIVE subtree(id) AS (
+                 SELECT ?1
+                 UNION
+                 SELECT f.id FROM features f
+                 JOIN subtree s ON f.parent_id = s.id
+                 WHERE f.deleted_at IS NULL
+             )
+             UPDATE features SET deleted_at = ?2
+             WHERE id IN (SELECT id FROM subtree) AND deleted_at IS NULL
+             RETURNING id",
+        )?;
+        let deleted = stmt
+            .query_map((id.to_string(), Utc::now().to_rfc3339()), |row| {
+                row.get::<_, String>(0)
+            })?
+            .map(|id| id.map(parse_uuid))
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        drop(stmt);
+        drop(conn);
+
+        if let Some(cache) = &self.cache {
+            cache.invalidate_features();
+        }
+        // Listeners showing a descendant need to hear it went too
+        for &feature_id in &deleted {
+            self.notify(ChangeEvent::FeatureDeleted {
+                project_id: feature.project_id,
+                feature_id,
+            });
+        }
+        Ok(!deleted.is_empty())
+    }
+
+    /// Take a feature out of the trash, along with the descendants deleted
+    /// with it. Descendants deleted on their own before it stay in the trash.
+    ///
+    /// A feature whose parent is in the trash can't be restored until the
+    /// parent is. Restoring a feature that isn't deleted does nothing.
+    /// Returns `None` if the feature doesn't exist or was purged.
+    pub fn restore_feature(&self, id: Uuid) -> Result<Option<Feature>> {
+        let conn = self.conn.lock().expect("database lock poisoned");
+        let mut stmt = conn.prepare(
+            "SELECT f.deleted_at, p.title, p.deleted_at IS NOT NULL
+             FROM features f LEFT JOIN features p ON p.id = f.parent_id
+             WHERE f.id = ?",
+        )?;
+        let mut rows = stmt.query([id.to_string()])?;
+        let Some(row) = rows.next()? else {
+            return Ok(None);
+        };
+        let deleted_at: Option<String> = row.get(0)?;
+        let parent_title: Option<String> = row.get(1)?;
+        let parent_deleted = row.get::<_, Option<bool>>(2)?.unwrap_or(false);
+        drop(rows);
+        drop(stmt);
+
+        let Some(deleted_at) = deleted_at else {
+            drop(conn);
+            return self.get_feature(id);
+        };
+        if parent_deleted {
+            return Err(ManifestError::invalid_state(format!(
+                "Its parent '{}' is in the trash; restore that first",
+                parent_title.unwrap_or_default()
+            ))
+            .into());
+        }
+
+        let mut stmt = conn.prepare(
+            "WITH RECURSIVE subtree(id) AS (
+                 SELECT ?1
+                 UNION
+                 SELECT f.id FROM features f
+                 JOIN subtree s ON f.parent_id = s.id
+                 WHERE f.deleted_at = ?2
+             )
+             UPDATE features SET deleted_at = NULL WHERE id IN (SELECT id FROM subtree)
+             RETURNING id",
+        )?;
+        let restored = stmt
+            .query_map((id.to_string(), &deleted_at), |row| row.get::<_, String>(0))?
+            .map(|id| id.map(parse_uuid))
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        drop(stmt);
+        drop(conn);
+
+        if let Some(cache) = &self.cache {
+            cache.invalidate_features();
+        }
+        let feature = self.get_feature(id)?;
+        if let Some(feature) = &feature {
+            for &feature_id in &restored {
+                self.notify(ChangeEvent::FeatureChanged {
+                    project_id: feature.project_id,
+                    feature_id,
+                });
+            }
+        }
+        Ok(feature)
+    }
+
+    /// Features of a project in the trash, most recently deleted first.
+    ///
+    /// Only features whose parent is not in the trash are listed; their
+    /// deleted descendants come back with them.
+    pub fn get_deleted_features(&self, project_id: Uuid) -> Result<Vec<DeletedFeature>> {
+        let conn = self.conn.lock().expect("database lock poisoned");
+        let mut stmt = conn.prepare(
+            "SELECT f.id, f.project_id, f.parent_id, f.title, f.details, f.desired_details, f.state, f.priority, f.created_at, f.updated_at, f.deleted_at
+             FROM features f LEFT JOIN features p ON p.id = f.parent_id
+             WHERE f.project_id = ? AND f.deleted_at IS NOT NULL AND p.deleted_at IS NULL
+             ORDER BY julianday(f.deleted_at) DESC, f.title",
+        )?;
+
+        let features = stmt
+            .query_map([project_id.to_string()], |row| {
+                Ok(DeletedFeature {
+                    feature: Feature {
+                        id: parse_uuid(row.get::<_, String>(0)?),
+                        project_id: parse_uuid(row.get::<_, String>(1)?),
+                        parent_id: row.get::<_, Option<String>>(2)?.map(parse_uuid),
+                        title: row.get(3)?,
+                        details: row.get(4)?,
+                        desired_details: row.get(5)?,
+                        state: FeatureState::from_str(&row.get::<_, String>(6)?)
+                            .unwrap_or(FeatureState::Proposed),
+                        priority: row.get(7)?,
+                        created_at: parse_datetime(row.get::<_, String>(8)?),
+                        updated_at: parse_datetime(row.get::<_, String>(9)?),
+                    },
+                    deleted_at: parse_datetime(row.get::<_, String>(10)?),
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(features)
+    }
+
+    /// Permanently remove features deleted before `older_than`, with their
+    /// descendants and everything attached to them. Returns how many
+    /// features were in the trash that long.
+    pub fn purge_deleted(&self, older_than: DateTime<Utc>) -> Result<usize> {
+        let conn = self.conn.lock().expect("database lock poisoned");
+        let rows = conn.execute(
+            "DELETE FROM features
+             WHERE deleted_at IS NOT NULL AND julianday(deleted_at) < julianday(?)",
+            [older_than.to_rfc3339()],
+        )?;
+        Ok(rows)
     }
 
     pub fn get_root_features(&self, project_id: Uuid) -> Result<Vec<Feature>> {
         let conn = self.conn.lock().expect("database lock poisoned");
         let mut stmt = conn.prepare(
             "SELECT id, project_id, parent_id, title, details, desired_details, state, priority, created_at, updated_at
-             FROM features WHERE project_id = ? AND parent_id IS NULL ORDER BY priority, title",
+             FROM features WHERE project_id = ? AND parent_id IS NULL AND deleted_at IS NULL ORDER BY priority, title, id",
         )?;
 
         let features = stmt
@@ -689,7 +1472,7 @@ impl Database {
         let conn = self.conn.lock().expect("database lock poisoned");
         let mut stmt = conn.prepare(
             "SELECT id, project_id, parent_id, title, details, desired_details, state, priority, created_at, updated_at
-             FROM features WHERE parent_id = ? ORDER BY priority, title",
+             FROM features WHERE parent_id = ? AND deleted_at IS NULL ORDER BY priority, title, id",
         )?;
 
         let features = stmt
@@ -716,7 +1499,7 @@ impl Database {
     pub fn is_leaf(&self, feature_id: Uuid) -> Result<bool> {
         let conn = self.conn.lock().expect("database lock poisoned");
         let count: i32 = conn.query_row(
-            "SELECT COUNT(*) FROM features WHERE parent_id = ?",
+            "SELECT COUNT(*) FROM features WHERE parent_id = ? AND deleted_at IS NULL",
             [feature_id.to_string()],
             |row| row.get(0),
         )?;
@@ -735,18 +1518,24 @@ impl Database {
 
         // Use LIKE for case-insensitive search
         // Ranking: title matches get higher priority than details matches
-        let search_pattern = format!("%{}%", query);
+        // Escape LIKE wildcards so "100%" or "snake_case" match literally
+        let escaped = query
+            .replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_");
+        let search_pattern = format!("%{}%", escaped);
         let limit_val = limit.unwrap_or(10) as i64;
 
         let (sql, params): (String, Vec<Box<dyn rusqlite::ToSql>>) = match project_id {
             Some(pid) => (
                 "SELECT id, project_id, parent_id, title, state, priority
                  FROM features
-                 WHERE project_id = ?1 AND (title LIKE ?2 OR details LIKE ?2)
+                 WHERE project_id = ?1 AND deleted_at IS NULL AND (title LIKE ?2 ESCAPE '\\' OR details LIKE ?2 ESCAPE '\\')
                  ORDER BY
-                     CASE WHEN title LIKE ?2 THEN 0 ELSE 1 END,
+                     CASE WHEN title LIKE ?2 ESCAPE '\\' THEN 0 ELSE 1 END,
                      priority,
-                     title
+                     title,
+                     id
                  LIMIT ?3"
                     .to_string(),
                 vec![
@@ -758,11 +1547,12 @@ impl Database {
             None => (
                 "SELECT id, project_id, parent_id, title, state, priority
                  FROM features
-                 WHERE title LIKE ?1 OR details LIKE ?1
+                 WHERE deleted_at IS NULL AND (title LIKE ?1 ESCAPE '\\' OR details LIKE ?1 ESCAPE '\\')
                  ORDER BY
-                     CASE WHEN title LIKE ?1 THEN 0 ELSE 1 END,
+                     CASE WHEN title LIKE ?1 ESCAPE '\\' THEN 0 ELSE 1 END,
                      priority,
-                     title
+                     title,
+                     id
                  LIMIT ?2"
                     .to_string(),
                 vec![Box::new(search_pattern), Box::new(limit_val)],
@@ -789,15 +1579,98 @@ impl Database {
         Ok(features)
     }
 
-    pub fn get_feature_tree(&self, project_id: Uuid) -> Result<Vec<FeatureTreeNode>> {
-        let features = self.get_features_by_project(project_id)?;
+    /// Search features by title and details using the full-text index.
+    ///
+    /// Every word of three or more characters in `query` must appear in the
+    /// title or details, whole or as part of a longer word, case-insensitively.
+    /// Hits are ranked by relevance with title matches weighted highest. The
+    /// index can't look up shorter words, so a query made only of them falls
+    /// back to `search_features` and its hits have no snippet.
+    pub fn search(
+        &self,
+        query: &str,
+        project_id: Option<Uuid>,
+        limit: Option<u32>,
+    ) -> Result<Vec<FeatureHit>> {
+        // Quote each word so FTS5 operators in the query are matched literally
+        let terms: Vec<String> = query
+            .split_whitespace()
+            .filter(|word| word.chars().count() >= 3)
+            .map(|word| format!("\"{}\"", word.replace('"', "\"\"")))
+            .collect();
+        if terms.is_empty() {
+            return Ok(self
+                .search_features(query, project_id, limit)?
+                .into_iter()
+                .map(|feature| FeatureHit {
+                    feature,
+                    snippet: None,
+                })
+                .collect());
+        }
 
-        // Group features by parent_id
-        let mut children_map: std::collections::HashMap<Option<Uuid>, Vec<Feature>> =
-            std::collections::HashMap::new();
-        for feature in features {
-            children_map
-                .entry(feature.parent_id)
+        // Trigram tokens are about a character each, so snippets run to
+        // roughly 64 characters, the most FTS5 allows
+        let conn = self.conn.lock().expect("database lock poisoned");
+        let mut stmt = conn.prepare(
+            "SELECT f.id, f.project_id, f.parent_id, f.title, f.state, f.priority,
+                    snippet(features_fts, -1, '**', '**', '…', 64)
+             FROM features_fts
+             JOIN features f ON f.id = features_fts.feature_id
+             WHERE features_fts MATCH ?1 AND f.deleted_at IS NULL AND (?2 IS NULL OR f.project_id = ?2)
+             ORDER BY bm25(features_fts, 0.0, 10.0, 1.0), f.priority, f.title, f.id
+             LIMIT ?3",
+        )?;
+
+        let hits = stmt
+            .query_map(
+                (
+                    terms.join(" "),
+                    project_id.map(|id| id.to_string()),
+                    limit.unwrap_or(10) as i64,
+                ),
+                |row| {
+                    Ok(FeatureHit {
+                        feature: FeatureSummary {
+                            id: parse_uuid(row.get::<_, String>(0)?),
+                            project_id: parse_uuid(row.get::<_, String>(1)?),
+                            parent_id: row.get::<_, Option<String>>(2)?.map(parse_uuid),
+                            title: row.get(3)?,
+                            state: FeatureState::from_str(&row.get::<_, String>(4)?)
+                                .unwrap_or(FeatureState::Proposed),
+                            priority: row.get(5)?,
+                        },
+                        snippet: row.get(6)?,
+                    })
+                },
+            )?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(hits)
+    }
+
+    pub fn get_feature_tree(&self, project_id: Uuid) -> Result<Vec<FeatureTreeNode>> {
+        if let Some(tree) = self.cache.as_ref().and_then(|c| c.feature_tree(project_id)) {
+            return Ok(tree);
+        }
+
+        let generation = self.cache.as_ref().map(|c| c.generation());
+        let tree = self.load_feature_tree(project_id)?;
+        if let (Some(cache), Some(generation)) = (&self.cache, generation) {
+            cache.put_feature_tree(generation, project_id, &tree);
+        }
+        Ok(tree)
+    }
+
+    fn load_feature_tree(&self, project_id: Uuid) -> Result<Vec<FeatureTreeNode>> {
+        let features = self.get_features_by_project(project_id)?;
+
+        // Group features by parent_id
+        let mut children_map: std::collections::HashMap<Option<Uuid>, Vec<Feature>> =
+            std::collections::HashMap::new();
+        for feature in features {
+            children_map
+                .entry(feature.parent_id)
                 .or_default()
                 .push(feature);
         }
@@ -824,6 +1697,432 @@ impl Database {
         Ok(build_subtree(None, &children_map))
     }
 
+    /// Get a feature with its descendants, optionally cut off `max_depth`
+    /// levels below it (0 returns the feature alone).
+    /// Returns `None` if the feature doesn't exist.
+    pub fn get_feature_subtree(
+        &self,
+        id: Uuid,
+        max_depth: Option<u32>,
+    ) -> Result<Option<FeatureTreeNode>> {
+        let Some(feature) = self.get_feature(id)? else {
+            return Ok(None);
+        };
+
+        fn find(nodes: Vec<FeatureTreeNode>, id: Uuid) -> Option<FeatureTreeNode> {
+            nodes.into_iter().find_map(|node| {
+                if node.feature.id == id {
+                    Some(node)
+                } else {
+                    find(node.children, id)
+                }
+            })
+        }
+
+        fn prune(node: &mut FeatureTreeNode, depth: u32) {
+            if depth == 0 {
+                node.children.clear();
+            } else {
+                for child in &mut node.children {
+                    prune(child, depth - 1);
+                }
+            }
+        }
+
+        let mut subtree = find(self.get_feature_tree(feature.project_id)?, id);
+        if let (Some(node), Some(depth)) = (subtree.as_mut(), max_depth) {
+            prune(node, depth);
+        }
+        Ok(subtree)
+    }
+
+    // ============================================================
+    // Tag operations
+    // ============================================================
+
+    /// Replace a feature's tags, returning the stored names.
+    ///
+    /// Names are trimmed, lowercased and deduplicated; empty names are
+    /// dropped. Tags no feature uses any more are removed from the project.
+    /// Returns `None` if the feature doesn't exist.
+    pub fn set_feature_tags(
+        &self,
+        feature_id: Uuid,
+        tags: &[String],
+    ) -> Result<Option<Vec<String>>> {
+        let Some(feature) = self.get_feature(feature_id)? else {
+            return Ok(None);
+        };
+        let names = normalize_tags(tags);
+
+        let mut conn = self.conn.lock().expect("database lock poisoned");
+        let tx = conn.transaction()?;
+        let now = Utc::now().to_rfc3339();
+        let project_id = feature.project_id.to_string();
+
+        tx.execute(
+            "DELETE FROM feature_tags WHERE feature_id = ?",
+            [feature_id.to_string()],
+        )?;
+        for name in &names {
+            tx.execute(
+                "INSERT OR IGNORE INTO tags (id, project_id, name, created_at) VALUES (?, ?, ?, ?)",
+                (Uuid::new_v4().to_string(), &project_id, name, &now),
+            )?;
+            tx.execute(
+                "INSERT INTO feature_tags (feature_id, tag_id)
+                 SELECT ?, id FROM tags WHERE project_id = ? AND name = ?",
+                (feature_id.to_string(), &project_id, name),
+            )?;
+        }
+        tx.execute(
+            "DELETE FROM tags WHERE project_id = ?
+             AND id NOT IN (SELECT tag_id FROM feature_tags)",
+            [&project_id],
+        )?;
+        tx.commit()?;
+        drop(conn);
+
+        self.notify(ChangeEvent::FeatureChanged {
+            project_id: feature.project_id,
+            feature_id,
+        });
+        Ok(Some(names))
+    }
+
+    /// Tags on a feature, sorted by name.
+    pub fn get_feature_tags(&self, feature_id: Uuid) -> Result<Vec<String>> {
+        let conn = self.conn.lock().expect("database lock poisoned");
+        let mut stmt = conn.prepare(
+            "SELECT t.name FROM tags t
+             JOIN feature_tags ft ON ft.tag_id = t.id
+             WHERE ft.feature_id = ? ORDER BY t.name",
+        )?;
+
+        let tags = stmt
+            .query_map([feature_id.to_string()], |row| row.get(0))?
+            .collect::<Result<Vec<String>, _>>()?;
+
+        Ok(tags)
+    }
+
+    /// Tags for every tagged feature in a project, in one query.
+    /// Used to decorate the feature tree.
+    pub fn get_project_feature_tags(
+        &self,
+        project_id: Uuid,
+    ) -> Result<std::collections::HashMap<Uuid, Vec<String>>> {
+        let conn = self.conn.lock().expect("database lock poisoned");
+        let mut stmt = conn.prepare(
+            "SELECT ft.feature_id, t.name FROM tags t
+             JOIN feature_tags ft ON ft.tag_id = t.id
+             JOIN features f ON f.id = ft.feature_id
+             WHERE t.project_id = ? AND f.deleted_at IS NULL ORDER BY t.name",
+        )?;
+
+        let mut tags: std::collections::HashMap<Uuid, Vec<String>> =
+            std::collections::HashMap::new();
+        let rows = stmt.query_map([project_id.to_string()], |row| {
+            Ok((
+                parse_uuid(row.get::<_, String>(0)?),
+                row.get::<_, String>(1)?,
+            ))
+        })?;
+        for row in rows {
+            let (feature_id, name) = row?;
+            tags.entry(feature_id).or_default().push(name);
+        }
+
+        Ok(tags)
+    }
+
+    /// Tag names in use in a project, sorted, optionally limited to those
+    /// starting with `prefix` (for autocompletion).
+    pub fn get_project_tags(&self, project_id: Uuid, prefix: Option<&str>) -> Result<Vec<String>> {
+        let conn = self.conn.lock().expect("database lock poisoned");
+        let mut stmt = conn.prepare(
+            "SELECT name FROM tags WHERE project_id = ? AND substr(name, 1, length(?2)) = ?2
+             ORDER BY name",
+        )?;
+
+        let prefix = prefix.map(|p| p.trim().to_lowercase()).unwrap_or_default();
+        let tags = stmt
+            .query_map((project_id.to_string(), prefix), |row| row.get(0))?
+            .collect::<Result<Vec<String>, _>>()?;
+
+        Ok(tags)
+    }
+
+    /// Features carrying `tag`, optionally limited to one project, ordered
+    /// by priority then title.
+    pub fn get_features_by_tag(&self, tag: &str, project_id: Option<Uuid>) -> Result<Vec<Feature>> {
+        self.get_features_by_tags(&[tag.to_string()], project_id)
+    }
+
+    /// Features carrying every one of `tags`, optionally limited to one
+    /// project, ordered by priority then title. Names are normalized as in
+    /// `set_feature_tags`; with no names left, nothing matches.
+    pub fn get_features_by_tags(
+        &self,
+        tags: &[String],
+        project_id: Option<Uuid>,
+    ) -> Result<Vec<Feature>> {
+        let names = normalize_tags(tags);
+        if names.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let conn = self.conn.lock().expect("database lock poisoned");
+        let mut stmt = conn.prepare(
+            "SELECT f.id, f.project_id, f.parent_id, f.title, f.details, f.desired_details, f.state, f.priority, f.created_at, f.updated_at
+             FROM features f
+             JOIN feature_tags ft ON ft.feature_id = f.id
+             JOIN tags t ON t.id = ft.tag_id
+             WHERE t.name IN (SELECT value FROM json_each(?1)) AND f.deleted_at IS NULL
+               AND (?2 IS NULL OR t.project_id = ?2)
+             GROUP BY f.id
+             HAVING COUNT(*) = ?3
+             ORDER BY f.priority, f.title, f.id",
+        )?;
+
+        let features = stmt
+            .query_map(
+                (
+                    serde_json::to_string(&names)?,
+                    project_id.map(|id| id.to_string()),
+                    names.len(),
+                ),
+                |row| {
+                    Ok(Feature {
+                        id: parse_uuid(row.get::<_, String>(0)?),
+                        project_id: parse_uuid(row.get::<_, String>(1)?),
+                        parent_id: row.get::<_, Option<String>>(2)?.map(parse_uuid),
+                        title: row.get(3)?,
+                        details: row.get(4)?,
+                        desired_details: row.get(5)?,
+                        state: FeatureState::from_str(&row.get::<_, String>(6)?)
+                            .unwrap_or(FeatureState::Proposed),
+                        priority: row.get(7)?,
+                        created_at: parse_datetime(row.get::<_, String>(8)?),
+                        updated_at: parse_datetime(row.get::<_, String>(9)?),
+                    })
+                },
+            )?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(features)
+    }
+
+    // ============================================================
+    // Dependency operations
+    // ============================================================
+
+    /// Record that `feature_id` is blocked by `depends_on_id`.
+    pub fn add_dependency(&self, feature_id: Uuid, depends_on_id: Uuid) -> Result<()> {
+        self.add_link(feature_id, depends_on_id, LinkKind::DependsOn)
+    }
+
+    /// Link `feature_id` to `other_id`.
+    ///
+    /// Both features must exist and belong to the same project, and a
+    /// feature cannot be linked to itself. Two features have at most one
+    /// link: adding the same one again is a no-op, and adding another kind
+    /// is rejected until the first is removed. A dependency that would close
+    /// a cycle is rejected, counting `Blocks` links as the dependency they
+    /// reverse; `Relates` links are never part of a cycle.
+    pub fn add_link(&self, feature_id: Uuid, other_id: Uuid, kind: LinkKind) -> Result<()> {
+        if feature_id == other_id {
+            return Err(ManifestError::validation("A feature cannot be linked to itself").into());
+        }
+        let feature = self
+            .get_feature(feature_id)?
+            .ok_or_else(|| ManifestError::not_found("Feature"))?;
+        let other = self
+            .get_feature(other_id)?
+            .ok_or_else(|| ManifestError::not_found("Linked feature"))?;
+        if feature.project_id != other.project_id {
+            return Err(ManifestError::validation(
+                "Links must be between features in the same project",
+            )
+            .into());
+        }
+
+        // Rows read "from depends on to" or "from relates to"; a Blocks
+        // link is stored as the dependency it reverses
+        let (from, to, stored) = match kind {
+            LinkKind::DependsOn => (&feature, &other, LinkKind::DependsOn),
+            LinkKind::Blocks => (&other, &feature, LinkKind::DependsOn),
+            LinkKind::Relates => (&feature, &other, LinkKind::Relates),
+        };
+
+        let conn = self.conn.lock().expect("database lock poisoned");
+
+        if stored == LinkKind::DependsOn {
+            // A cycle exists if `from` is already reachable from `to`.
+            let creates_cycle: bool = conn.query_row(
+                "WITH RECURSIVE reachable(id) AS (
+                     SELECT ?1
+                     UNION
+                     SELECT d.depends_on_id FROM feature_dependencies d
+                     JOIN reachable r ON d.feature_id = r.id
+                     WHERE d.kind = 'depends_on'
+                 )
+                 SELECT EXISTS(SELECT 1 FROM reachable WHERE id = ?2)",
+                (to.id.to_string(), from.id.to_string()),
+                |row| row.get(0),
+            )?;
+            if creates_cycle {
+                return Err(ManifestError::validation(format!(
+                    "'{}' already depends on '{}'; adding this dependency would create a cycle",
+                    to.title, from.title
+                ))
+                .into());
+            }
+        }
+
+        let mut stmt = conn.prepare(
+            "SELECT feature_id, kind FROM feature_dependencies
+             WHERE (feature_id = ?1 AND depends_on_id = ?2)
+                OR (feature_id = ?2 AND depends_on_id = ?1)",
+        )?;
+        let mut rows = stmt.query((from.id.to_string(), to.id.to_string()))?;
+        if let Some(row) = rows.next()? {
+            let same_way = parse_uuid(row.get::<_, String>(0)?) == from.id;
+            let existing = row.get::<_, String>(1)?;
+            if existing == stored.as_str() && (same_way || stored == LinkKind::Relates) {
+                return Ok(());
+            }
+            return Err(ManifestError::validation(format!(
+                "'{}' and '{}' are already linked; remove that link first",
+                feature.title, other.title
+            ))
+            .into());
+        }
+        drop(rows);
+        drop(stmt);
+
+        conn.execute(
+            "INSERT INTO feature_dependencies (feature_id, depends_on_id, kind, created_at)
+             VALUES (?, ?, ?, ?)",
+            (
+                from.id.to_string(),
+                to.id.to_string(),
+                stored.as_str(),
+                Utc::now().to_rfc3339(),
+            ),
+        )?;
+        drop(conn);
+
+        self.notify(ChangeEvent::FeatureChanged {
+            project_id: feature.project_id,
+            feature_id,
+        });
+        Ok(())
+    }
+
+    /// Remove the link between two features, whichever way it points.
+    /// Returns false if they weren't linked.
+    pub fn remove_dependency(&self, feature_id: Uuid, depends_on_id: Uuid) -> Result<bool> {
+        let conn = self.conn.lock().expect("database lock poisoned");
+        let rows = conn.execute(
+            "DELETE FROM feature_dependencies
+             WHERE (feature_id = ?1 AND depends_on_id = ?2)
+                OR (feature_id = ?2 AND depends_on_id = ?1)",
+            (feature_id.to_string(), depends_on_id.to_string()),
+        )?;
+        drop(conn);
+
+        if rows > 0 {
+            if let Some(feature) = self.get_feature(feature_id)? {
+                self.notify(ChangeEvent::FeatureChanged {
+                    project_id: feature.project_id,
+                    feature_id,
+                });
+            }
+        }
+        Ok(rows > 0)
+    }
+
+    /// Features that `feature_id` is blocked by, ordered by priority then title.
+    pub fn get_dependencies(&self, feature_id: Uuid) -> Result<Vec<Feature>> {
+        self.get_dependency_features(
+            "JOIN feature_dependencies d ON d.depends_on_id = f.id
+             WHERE d.feature_id = ?1 AND d.kind = 'depends_on'",
+            feature_id,
+        )
+    }
+
+    /// Features blocked by `feature_id`, ordered by priority then title.
+    pub fn get_dependents(&self, feature_id: Uuid) -> Result<Vec<Feature>> {
+        self.get_dependency_features(
+            "JOIN feature_dependencies d ON d.feature_id = f.id
+             WHERE d.depends_on_id = ?1 AND d.kind = 'depends_on'",
+            feature_id,
+        )
+    }
+
+    /// Features linked to `feature_id` with `Relates`, ordered by priority then title.
+    pub fn get_related(&self, feature_id: Uuid) -> Result<Vec<Feature>> {
+        self.get_dependency_features(
+            "JOIN feature_dependencies d
+               ON (d.feature_id = ?1 AND d.depends_on_id = f.id)
+               OR (d.depends_on_id = ?1 AND d.feature_id = f.id)
+             WHERE d.kind = 'relates'",
+            feature_id,
+        )
+    }
+
+    fn get_dependency_features(&self, join: &str, feature_id: Uuid) -> Result<Vec<Feature>> {
+        let conn = self.conn.lock().expect("database lock poisoned");
+        let mut stmt = conn.prepare(&format!(
+            "SELECT f.id, f.project_id, f.parent_id, f.title, f.details, f.desired_details, f.state, f.priority, f.created_at, f.updated_at
+             FROM features f {} AND f.deleted_at IS NULL ORDER BY f.priority, f.title, f.id",
+            join
+        ))?;
+
+        let features = stmt
+            .query_map([feature_id.to_string()], |row| {
+                Ok(Feature {
+                    id: parse_uuid(row.get::<_, String>(0)?),
+                    project_id: parse_uuid(row.get::<_, String>(1)?),
+                    parent_id: row.get::<_, Option<String>>(2)?.map(parse_uuid),
+                    title: row.get(3)?,
+                    details: row.get(4)?,
+                    desired_details: row.get(5)?,
+                    state: FeatureState::from_str(&row.get::<_, String>(6)?)
+                        .unwrap_or(FeatureState::Proposed),
+                    priority: row.get(7)?,
+                    created_at: parse_datetime(row.get::<_, String>(8)?),
+                    updated_at: parse_datetime(row.get::<_, String>(9)?),
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(features)
+    }
+
+    /// IDs of features in a project with at least one dependency that is not
+    /// yet implemented (or deprecated). Used to mark blocked features in the tree.
+    pub fn get_blocked_feature_ids(&self, project_id: Uuid) -> Result<Vec<Uuid>> {
+        let conn = self.conn.lock().expect("database lock poisoned");
+        let mut stmt = conn.prepare(
+            "SELECT DISTINCT d.feature_id FROM feature_dependencies d
+             JOIN features f ON f.id = d.feature_id
+             JOIN features dep ON dep.id = d.depends_on_id
+             WHERE f.project_id = ? AND d.kind = 'depends_on'
+               AND dep.state NOT IN ('implemented', 'deprecated')
+               AND f.deleted_at IS NULL AND dep.deleted_at IS NULL",
+        )?;
+
+        let ids = stmt
+            .query_map([project_id.to_string()], |row| {
+                Ok(parse_uuid(row.get::<_, String>(0)?))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(ids)
+    }
+
     // ============================================================
     // Session operations
     // ============================================================
@@ -878,6 +2177,48 @@ impl Database {
         Ok(sessions)
     }
 
+    /// Task counts for every feature in a project with an active session,
+    /// keyed by feature ID. Loaded in one query so the feature tree can show
+    /// progress without a lookup per feature.
+    pub fn get_active_task_counts(
+        &self,
+        project_id: Uuid,
+    ) -> Result<std::collections::HashMap<Uuid, TaskCounts>> {
+        let conn = self.conn.lock().expect("database lock poisoned");
+        let mut stmt = conn.prepare(
+            "SELECT s.feature_id, t.status, COUNT(t.id)
+             FROM sessions s
+             JOIN features f ON f.id = s.feature_id
+             LEFT JOIN tasks t ON t.session_id = s.id
+             WHERE f.project_id = ? AND f.deleted_at IS NULL AND s.status = 'active'
+             GROUP BY s.feature_id, t.status",
+        )?;
+
+        let rows = stmt.query_map([project_id.to_string()], |row| {
+            Ok((
+                parse_uuid(row.get::<_, String>(0)?),
+                row.get::<_, Option<String>>(1)?,
+                row.get::<_, usize>(2)?,
+            ))
+        })?;
+
+        let mut counts: std::collections::HashMap<Uuid, TaskCounts> =
+            std::collections::HashMap::new();
+        for row in rows {
+            let (feature_id, status, count) = row?;
+            let entry = counts.entry(feature_id).or_default();
+            entry.total += count;
+            match status.as_deref().and_then(|s| TaskStatus::from_str(s).ok()) {
+                Some(TaskStatus::Pending) => entry.pending += count,
+                Some(TaskStatus::Running) => entry.running += count,
+                Some(TaskStatus::Completed) => entry.completed += count,
+                Some(TaskStatus::Failed) => entry.failed += count,
+                None => {}
+            }
+        }
+        Ok(counts)
+    }
+
     pub fn create_session(&self, input: CreateSessionInput) -> Result<SessionResponse> {
         let feature = self
             .get_feature(input.feature_id)?
@@ -953,11 +2294,29 @@ impl Database {
                 agent_type: task_input.agent_type,
                 worktree_path: None,
                 branch: None,
+                log_path: None,
                 created_at: now,
             });
         }
 
         tx.commit()?;
+        drop(conn);
+
+        // The feature may have moved from proposed to specified
+        if let Some(cache) = &self.cache {
+            cache.invalidate_feature(input.feature_id);
+        }
+        self.notify(ChangeEvent::FeatureChanged {
+            project_id: feature.project_id,
+            feature_id: feature.id,
+        });
+        for task in &tasks {
+            self.notify(ChangeEvent::TaskChanged {
+                project_id: feature.project_id,
+                session_id,
+                task_id: task.id,
+            });
+        }
         Ok(SessionResponse { session, tasks })
     }
 
@@ -983,6 +2342,44 @@ impl Database {
         }))
     }
 
+    pub fn get_session_summary(&self, id: Uuid) -> Result<Option<SessionSummary>> {
+        let status = match self.get_session_status(id)? {
+            Some(s) => s,
+            None => return Ok(None),
+        };
+
+        let entries: Vec<HistoryDetails> = {
+            let conn = self.conn.lock().expect("database lock poisoned");
+            let mut stmt = conn.prepare(
+                "SELECT details FROM feature_history WHERE session_id = ? ORDER BY created_at ASC",
+            )?;
+            let entries = stmt
+                .query_map([id.to_string()], |row| {
+                    let details_json: String = row.get(0)?;
+                    Ok(serde_json::from_str(&details_json).unwrap_or_default())
+                })?
+                .collect::<Result<Vec<_>, _>>()?;
+            entries
+        };
+
+        let summary = entries.last().map(|d| d.summary.clone());
+        let mut seen = std::collections::HashSet::new();
+        let commits = entries
+            .into_iter()
+            .flat_map(|d| d.commits)
+            .filter(|c| seen.insert(c.sha.clone()))
+            .collect();
+
+        Ok(Some(SessionSummary {
+            task_counts: TaskCounts::from_tasks(&status.tasks),
+            session: status.session,
+            feature: status.feature,
+            tasks: status.tasks,
+            summary,
+            commits,
+        }))
+    }
+
     pub fn complete_session(
         &self,
         id: Uuid,
@@ -1072,6 +2469,17 @@ impl Database {
         }
 
         tx.commit()?;
+        drop(conn);
+
+        if let Some(cache) = &self.cache {
+            cache.invalidate_feature(session.feature_id);
+        }
+        if let Some(feature) = self.get_feature(session.feature_id)? {
+            self.notify(ChangeEvent::FeatureChanged {
+                project_id: feature.project_id,
+                feature_id: feature.id,
+            });
+        }
 
         let completed_session = Session {
             id: session.id,
@@ -1095,7 +2503,7 @@ impl Database {
     pub fn get_task(&self, id: Uuid) -> Result<Option<Task>> {
         let conn = self.conn.lock().expect("database lock poisoned");
         let mut stmt = conn.prepare(
-            "SELECT id, session_id, parent_id, title, scope, status, agent_type, worktree_path, branch, created_at
+            "SELECT id, session_id, parent_id, title, scope, status, agent_type, worktree_path, branch, log_path, created_at
              FROM tasks WHERE id = ?"
         )?;
 
@@ -1113,7 +2521,8 @@ impl Database {
                     .unwrap_or(AgentType::Claude),
                 worktree_path: row.get(7)?,
                 branch: row.get(8)?,
-                created_at: parse_datetime(row.get::<_, String>(9)?),
+                log_path: row.get(9)?,
+                created_at: parse_datetime(row.get::<_, String>(10)?),
             }))
         } else {
             Ok(None)
@@ -1123,7 +2532,7 @@ impl Database {
     pub fn get_tasks_by_session(&self, session_id: Uuid) -> Result<Vec<Task>> {
         let conn = self.conn.lock().expect("database lock poisoned");
         let mut stmt = conn.prepare(
-            "SELECT id, session_id, parent_id, title, scope, status, agent_type, worktree_path, branch, created_at
+            "SELECT id, session_id, parent_id, title, scope, status, agent_type, worktree_path, branch, log_path, created_at
              FROM tasks WHERE session_id = ? ORDER BY created_at"
         )?;
 
@@ -1141,7 +2550,8 @@ impl Database {
                         .unwrap_or(AgentType::Claude),
                     worktree_path: row.get(7)?,
                     branch: row.get(8)?,
-                    created_at: parse_datetime(row.get::<_, String>(9)?),
+                    log_path: row.get(9)?,
+                    created_at: parse_datetime(row.get::<_, String>(10)?),
                 })
             })?
             .collect::<Result<Vec<_>, _>>()?;
@@ -1152,7 +2562,7 @@ impl Database {
     pub fn get_task_children(&self, parent_id: Uuid) -> Result<Vec<Task>> {
         let conn = self.conn.lock().expect("database lock poisoned");
         let mut stmt = conn.prepare(
-            "SELECT id, session_id, parent_id, title, scope, status, agent_type, worktree_path, branch, created_at
+            "SELECT id, session_id, parent_id, title, scope, status, agent_type, worktree_path, branch, log_path, created_at
              FROM tasks WHERE parent_id = ? ORDER BY created_at"
         )?;
 
@@ -1170,7 +2580,8 @@ impl Database {
                         .unwrap_or(AgentType::Claude),
                     worktree_path: row.get(7)?,
                     branch: row.get(8)?,
-                    created_at: parse_datetime(row.get::<_, String>(9)?),
+                    log_path: row.get(9)?,
+                    created_at: parse_datetime(row.get::<_, String>(10)?),
                 })
             })?
             .collect::<Result<Vec<_>, _>>()?;
@@ -1207,7 +2618,14 @@ impl Database {
                 now.to_rfc3339(),
             ),
         )?;
+        let project_id = session_project_id(&conn, session_id)?;
+        drop(conn);
 
+        self.notify(ChangeEvent::TaskChanged {
+            project_id,
+            session_id,
+            task_id: id,
+        });
         Ok(Task {
             id,
             session_id,
@@ -1218,6 +2636,7 @@ impl Database {
             agent_type: input.agent_type,
             worktree_path: None,
             branch: None,
+            log_path: None,
             created_at: now,
         })
     }
@@ -1240,6 +2659,10 @@ impl Database {
             updates.push("branch = ?");
             params.push(Box::new(branch));
         }
+        if let Some(log_path) = input.log_path {
+            updates.push("log_path = ?");
+            params.push(Box::new(log_path));
+        }
 
         if updates.is_empty() {
             return Ok(false);
@@ -1250,7 +2673,26 @@ impl Database {
         let sql = format!("UPDATE tasks SET {} WHERE id = ?", updates.join(", "));
         let params_ref: Vec<&dyn rusqlite::ToSql> = params.iter().map(|p| p.as_ref()).collect();
         let rows = conn.execute(&sql, params_ref.as_slice())?;
+        let change = if rows > 0 && self.listener.is_some() {
+            let session_id = conn.query_row(
+                "SELECT session_id FROM tasks WHERE id = ?",
+                [id.to_string()],
+                |row| row.get::<_, String>(0),
+            )?;
+            let session_id = parse_uuid(session_id);
+            Some(ChangeEvent::TaskChanged {
+                project_id: session_project_id(&conn, session_id)?,
+                session_id,
+                task_id: id,
+            })
+        } else {
+            None
+        };
+        drop(conn);
 
+        if let Some(change) = change {
+            self.notify(change);
+        }
         Ok(rows > 0)
     }
 
@@ -1260,34 +2702,7 @@ impl Database {
 
     pub fn create_history_entry(&self, input: CreateHistoryInput) -> Result<FeatureHistory> {
         let conn = self.conn.lock().expect("database lock poisoned");
-        let id = Uuid::new_v4();
-        let now = Utc::now();
-
-        let details_json = serde_json::to_string(&input.details)?;
-
-        // Note: files_changed and author columns are deprecated but kept for schema compatibility
-        conn.execute(
-            "INSERT INTO feature_history (id, feature_id, session_id, summary, files_changed, author, details, created_at)
-             VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
-            (
-                id.to_string(),
-                input.feature_id.to_string(),
-                input.session_id.map(|u| u.to_string()),
-                &input.details.summary,
-                "[]", // deprecated
-                "",   // deprecated
-                &details_json,
-                now.to_rfc3339(),
-            ),
-        )?;
-
-        Ok(FeatureHistory {
-            id,
-            feature_id: input.feature_id,
-            session_id: input.session_id,
-            details: input.details,
-            created_at: now,
-        })
+        insert_history_entry(&conn, input)
     }
 
     pub fn get_feature_history(&self, feature_id: Uuid) -> Result<Vec<FeatureHistory>> {
@@ -1321,10 +2736,239 @@ impl Clone for Database {
     fn clone(&self) -> Self {
         Self {
             conn: self.conn.clone(),
+            cache: self.cache.clone(),
+            listener: self.listener.clone(),
         }
     }
 }
 
+/// Insert a history entry using an already locked connection.
+fn insert_history_entry(conn: &Connection, input: CreateHistoryInput) -> Result<FeatureHistory> {
+    let id = Uuid::new_v4();
+    let now = Utc::now();
+
+    let details_json = serde_json::to_string(&input.details)?;
+
+    // Note: files_changed and author columns are deprecated but kept for schema compatibility
+    conn.execute(
+        "INSERT INTO feature_history (id, feature_id, session_id, summary, files_changed, author, details, created_at)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+        (
+            id.to_string(),
+            input.feature_id.to_string(),
+            input.session_id.map(|u| u.to_string()),
+            &input.details.summary,
+            "[]", // deprecated
+            "",   // deprecated
+            &details_json,
+            now.to_rfc3339(),
+        ),
+    )?;
+
+    Ok(FeatureHistory {
+        id,
+        feature_id: input.feature_id,
+        session_id: input.session_id,
+        details: input.details,
+        created_at: now,
+    })
+}
+
+/// The project of the feature a session works on.
+fn session_project_id(conn: &Connection, session_id: Uuid) -> Result<Uuid> {
+    let project_id: String = conn.query_row(
+        "SELECT f.project_id FROM sessions s JOIN features f ON f.id = s.feature_id
+         WHERE s.id = ?",
+        [session_id.to_string()],
+        |row| row.get(0),
+    )?;
+    Ok(parse_uuid(project_id))
+}
+
+/// `id` if no row of `table` has it yet, otherwise a fresh id.
+fn import_id(conn: &Connection, table: &str, id: Uuid) -> Result<Uuid> {
+    let taken: bool = conn.query_row(
+        &format!("SELECT EXISTS(SELECT 1 FROM {} WHERE id = ?)", table),
+        [id.to_string()],
+        |row| row.get(0),
+    )?;
+    Ok(if taken { Uuid::new_v4() } else { id })
+}
+
+/// Record the id each exported feature will be imported under.
+fn map_feature_ids(
+    conn: &Connection,
+    features: &[FeatureExport],
+    ids: &mut HashMap<Uuid, Uuid>,
+) -> Result<()> {
+    for exported in features {
+        ids.insert(
+            exported.feature.id,
+            import_id(conn, "features", exported.feature.id)?,
+        );
+        map_feature_ids(conn, &exported.children, ids)?;
+    }
+    Ok(())
+}
+
+/// Insert exported features under `parent_id`, with everything attached to them.
+fn import_features(
+    conn: &Connection,
+    project_id: Uuid,
+    parent_id: Option<Uuid>,
+    features: &[FeatureExport],
+    feature_ids: &HashMap<Uuid, Uuid>,
+) -> Result<()> {
+    for exported in features {
+        let feature = &exported.feature;
+        let id = feature_ids[&feature.id];
+        conn.execute(
+            "INSERT INTO features (id, project_id, parent_id, title, details, desired_details, state, priority, created_at, updated_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            (
+                id.to_string(),
+                project_id.to_string(),
+                parent_id.map(|u| u.to_string()),
+                &feature.title,
+                &feature.details,
+                &feature.desired_details,
+                feature.state.as_str(),
+                feature.priority,
+                feature.created_at.to_rfc3339(),
+                feature.updated_at.to_rfc3339(),
+            ),
+        )?;
+
+        for name in normalize_tags(&exported.tags) {
+            conn.execute(
+                "INSERT OR IGNORE INTO tags (id, project_id, name, created_at) VALUES (?, ?, ?, ?)",
+                (
+                    Uuid::new_v4().to_string(),
+                    project_id.to_string(),
+                    &name,
+                    Utc::now().to_rfc3339(),
+                ),
+            )?;
+            conn.execute(
+                "INSERT INTO feature_tags (feature_id, tag_id)
+                 SELECT ?, id FROM tags WHERE project_id = ? AND name = ?",
+                (id.to_string(), project_id.to_string(), &name),
+            )?;
+        }
+
+        // Dependencies on features outside the export are dropped
+        for depends_on in exported
+            .depends_on
+            .iter()
+            .filter_map(|d| feature_ids.get(d))
+        {
+            conn.execute(
+                "INSERT OR IGNORE INTO feature_dependencies (feature_id, depends_on_id, created_at)
+                 VALUES (?, ?, ?)",
+                (
+                    id.to_string(),
+                    depends_on.to_string(),
+                    Utc::now().to_rfc3339(),
+                ),
+            )?;
+        }
+        // Both ends list a relation; it is inserted once the second end exists
+        for related in exported.related.iter().filter_map(|r| feature_ids.get(r)) {
+            conn.execute(
+                "INSERT INTO feature_dependencies (feature_id, depends_on_id, kind, created_at)
+                 SELECT ?1, ?2, 'relates', ?3
+                 WHERE EXISTS (SELECT 1 FROM features WHERE id = ?2)
+                 AND NOT EXISTS (
+                     SELECT 1 FROM feature_dependencies
+                     WHERE (feature_id = ?1 AND depends_on_id = ?2)
+                        OR (feature_id = ?2 AND depends_on_id = ?1)
+                 )",
+                (id.to_string(), related.to_string(), Utc::now().to_rfc3339()),
+            )?;
+        }
+
+        let mut session_ids = HashMap::new();
+        for exported_session in &exported.sessions {
+            let session = &exported_session.session;
+            let session_id = import_id(conn, "sessions", session.id)?;
+            session_ids.insert(session.id, session_id);
+            conn.execute(
+                "INSERT INTO sessions (id, feature_id, goal, status, created_at, completed_at)
+                 VALUES (?, ?, ?, ?, ?, ?)",
+                (
+                    session_id.to_string(),
+                    id.to_string(),
+                    &session.goal,
+                    session.status.as_str(),
+                    session.created_at.to_rfc3339(),
+                    session.completed_at.map(|t| t.to_rfc3339()),
+                ),
+            )?;
+
+            let mut task_ids = HashMap::new();
+            for task in &exported_session.tasks {
+                task_ids.insert(task.id, import_id(conn, "tasks", task.id)?);
+            }
+            for task in &exported_session.tasks {
+                conn.execute(
+                    "INSERT INTO tasks (id, session_id, parent_id, title, scope, status, agent_type, worktree_path, branch, log_path, created_at)
+                     VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+                    (
+                        task_ids[&task.id].to_string(),
+                        session_id.to_string(),
+                        task.parent_id
+                            .and_then(|parent| task_ids.get(&parent))
+                            .map(|u| u.to_string()),
+                        &task.title,
+                        &task.scope,
+                        task.status.as_str(),
+                        task.agent_type.as_str(),
+                        &task.worktree_path,
+                        &task.branch,
+                        &task.log_path,
+                        task.created_at.to_rfc3339(),
+                    ),
+                )?;
+            }
+        }
+
+        for entry in &exported.history {
+            conn.execute(
+                "INSERT INTO feature_history (id, feature_id, session_id, summary, files_changed, author, details, created_at)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+                (
+                    import_id(conn, "feature_history", entry.id)?.to_string(),
+                    id.to_string(),
+                    entry
+                        .session_id
+                        .and_then(|session| session_ids.get(&session))
+                        .map(|u| u.to_string()),
+                    &entry.details.summary,
+                    "[]", // deprecated
+                    "",   // deprecated
+                    serde_json::to_string(&entry.details)?,
+                    entry.created_at.to_rfc3339(),
+                ),
+            )?;
+        }
+
+        import_features(conn, project_id, Some(id), &exported.children, feature_ids)?;
+    }
+    Ok(())
+}
+
+/// Trim, lowercase and deduplicate tag names, dropping empty ones.
+fn normalize_tags(tags: &[String]) -> Vec<String> {
+    let mut names: Vec<String> = tags
+        .iter()
+        .map(|tag| tag.trim().to_lowercase())
+        .filter(|tag| !tag.is_empty())
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
 fn parse_uuid(s: String) -> Uuid {
     Uuid::parse_str(&s).unwrap_or_else(|_| panic!("Invalid UUID stored in database: {}", s))
 }
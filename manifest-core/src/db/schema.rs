@@ -48,6 +48,36 @@ const MIGRATIONS: &[Migration] = &[
         name: "remove_history_legacy_columns",
         sql: include_str!("migrations/008_remove_history_legacy_columns.sql"),
     },
+    Migration {
+        version: "009",
+        name: "feature_tags",
+        sql: include_str!("migrations/009_feature_tags.sql"),
+    },
+    Migration {
+        version: "010",
+        name: "feature_dependencies",
+        sql: include_str!("migrations/010_feature_dependencies.sql"),
+    },
+    Migration {
+        version: "011",
+        name: "feature_search",
+        sql: include_str!("migrations/011_feature_search.sql"),
+    },
+    Migration {
+        version: "012",
+        name: "feature_trash",
+        sql: include_str!("migrations/012_feature_trash.sql"),
+    },
+    Migration {
+        version: "013",
+        name: "task_log_path",
+        sql: include_str!("migrations/013_task_log_path.sql"),
+    },
+    Migration {
+        version: "014",
+        name: "dependency_kind",
+        sql: include_str!("migrations/014_dependency_kind.sql"),
+    },
 ];
 
 pub fn run_migrations(conn: &Connection) -> Result<()> {
@@ -164,7 +194,10 @@ mod tests {
         let versions = get_applied_migrations(&conn).unwrap();
         assert_eq!(
             versions,
-            vec!["001", "002", "003", "004", "005", "006", "007", "008"]
+            vec![
+                "001", "002", "003", "004", "005", "006", "007", "008", "009", "010", "011", "012",
+                "013", "014"
+            ]
         );
     }
 
@@ -177,7 +210,10 @@ mod tests {
         let versions = get_applied_migrations(&conn).unwrap();
         assert_eq!(
             versions,
-            vec!["001", "002", "003", "004", "005", "006", "007", "008"]
+            vec![
+                "001", "002", "003", "004", "005", "006", "007", "008", "009", "010", "011", "012",
+                "013", "014"
+            ]
         );
     }
 
@@ -203,8 +239,12 @@ mod tests {
             CREATE TABLE projects (id TEXT PRIMARY KEY, name TEXT, description TEXT, created_at TEXT, updated_at TEXT);
             CREATE TABLE project_directories (id TEXT PRIMARY KEY, project_id TEXT, path TEXT, git_remote TEXT, is_primary INTEGER, created_at TEXT);
             CREATE TABLE feature_history (id TEXT PRIMARY KEY, feature_id TEXT, session_id TEXT, summary TEXT, files_changed JSON, author TEXT, created_at TEXT);
+            CREATE TABLE tasks (id TEXT PRIMARY KEY, session_id TEXT, parent_id TEXT, title TEXT, scope TEXT, status TEXT, agent_type TEXT, worktree_path TEXT, branch TEXT, created_at TEXT);
             CREATE INDEX idx_features_project ON features(project_id);
             CREATE INDEX idx_features_parent ON features(parent_id);
+            INSERT INTO projects (id, name, created_at, updated_at) VALUES ('p1', 'App', '', '');
+            INSERT INTO features (id, project_id, title, details, created_at, updated_at)
+            VALUES ('f1', 'p1', 'Authentication', 'Sign in with a passkey', '', '');
         ").unwrap();
 
         // Run migrations - should detect existing DB and baseline, then apply remaining
@@ -213,7 +253,30 @@ mod tests {
         let versions = get_applied_migrations(&conn).unwrap();
         assert_eq!(
             versions,
-            vec!["001", "002", "003", "004", "005", "006", "007", "008"]
+            vec![
+                "001", "002", "003", "004", "005", "006", "007", "008", "009", "010", "011", "012",
+                "013", "014"
+            ]
         );
+
+        // Existing features are backfilled into the search index
+        let indexed: String = conn
+            .query_row(
+                "SELECT feature_id FROM features_fts WHERE features_fts MATCH 'passkey'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(indexed, "f1");
+
+        // and are not in the trash
+        let deleted_at: Option<String> = conn
+            .query_row(
+                "SELECT deleted_at FROM features WHERE id = 'f1'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(deleted_at, None);
     }
 }
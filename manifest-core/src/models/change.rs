@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A write to the database that other clients may want to react to.
+///
+/// Events carry ids rather than the changed records, so a listener fetches
+/// what it needs and always sees the committed state. They are emitted after
+/// the write commits, by the handle it went through (see
+/// `Database::with_change_listener`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ChangeEvent {
+    /// A feature was created or changed: its fields, tags, dependencies, or
+    /// its state through a session.
+    FeatureChanged { project_id: Uuid, feature_id: Uuid },
+    /// A feature was deleted. Descendants deleted with it each get their own
+    /// event, as do features restored from the trash as `FeatureChanged`.
+    FeatureDeleted { project_id: Uuid, feature_id: Uuid },
+    /// A task was created or updated.
+    TaskChanged {
+        project_id: Uuid,
+        session_id: Uuid,
+        task_id: Uuid,
+    },
+}
+
+impl ChangeEvent {
+    /// The project the change belongs to.
+    pub fn project_id(&self) -> Uuid {
+        match self {
+            ChangeEvent::FeatureChanged { project_id, .. }
+            | ChangeEvent::FeatureDeleted { project_id, .. }
+            | ChangeEvent::TaskChanged { project_id, .. } => *project_id,
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{Feature, FeatureHistory, Project, ProjectDirectory, Session, Task};
+
+/// Version of the [`ProjectExport`] format written by this build.
+pub const EXPORT_VERSION: u32 = 1;
+
+/// A project and everything recorded about it, for moving it to another
+/// database or keeping it alongside the code.
+///
+/// Features are nested under their parents as in [`FeatureTreeNode`](super::FeatureTreeNode),
+/// each carrying its tags, dependencies, history and sessions.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectExport {
+    /// Format version; see [`EXPORT_VERSION`].
+    pub version: u32,
+    pub project: Project,
+    pub directories: Vec<ProjectDirectory>,
+    pub features: Vec<FeatureExport>,
+}
+
+/// A feature in a [`ProjectExport`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeatureExport {
+    #[serde(flatten)]
+    pub feature: Feature,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Ids of the features this one is blocked by.
+    #[serde(default)]
+    pub depends_on: Vec<Uuid>,
+    /// Ids of the features linked to this one with `relates`.
+    #[serde(default)]
+    pub related: Vec<Uuid>,
+    #[serde(default)]
+    pub history: Vec<FeatureHistory>,
+    /// Sessions, newest first. Only active sessions still have tasks.
+    #[serde(default)]
+    pub sessions: Vec<SessionExport>,
+    #[serde(default)]
+    pub children: Vec<FeatureExport>,
+}
+
+/// A session in a [`FeatureExport`], with its tasks.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionExport {
+    #[serde(flatten)]
+    pub session: Session,
+    #[serde(default)]
+    pub tasks: Vec<Task>,
+}
+
+/// Formats a project can be exported in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    /// A [`ProjectExport`], which can be imported again.
+    #[default]
+    Json,
+    /// A readable document for reviewers; not importable.
+    #[serde(alias = "markdown")]
+    Md,
+}
+
+impl ExportFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Md => "md",
+        }
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(Self::Json),
+            "md" | "markdown" => Ok(Self::Md),
+            _ => Err(format!("unknown export format '{}' (use json or md)", s)),
+        }
+    }
+}
@@ -105,6 +105,10 @@ pub struct UpdateFeatureInput {
     pub state: Option<FeatureState>,
     /// Update priority for ordering within parent.
     pub priority: Option<i32>,
+    /// The `updated_at` the caller last read. When set, the update is rejected
+    /// with a conflict if the feature has been modified since.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected_updated_at: Option<DateTime<Utc>>,
 }
 
 /// A feature with its nested children, used for tree responses.
@@ -155,6 +159,25 @@ impl From<Feature> for FeatureSummary {
     }
 }
 
+/// A feature found by full-text search.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeatureHit {
+    #[serde(flatten)]
+    pub feature: FeatureSummary,
+    /// Text around the best match, with matches wrapped in `**`. Absent when
+    /// the query was too short for the index.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snippet: Option<String>,
+}
+
+/// A feature in the trash: deleted, but restorable until purged.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeletedFeature {
+    #[serde(flatten)]
+    pub feature: Feature,
+    pub deleted_at: DateTime<Utc>,
+}
+
 /// Query parameters for listing features.
 #[derive(Debug, Clone, Deserialize, Default)]
 pub struct ListFeaturesQuery {
@@ -162,4 +185,105 @@ pub struct ListFeaturesQuery {
     pub limit: Option<u32>,
     /// Number of features to skip for pagination.
     pub offset: Option<u32>,
+    /// Only list features carrying this tag. Several tags separated by
+    /// commas only match features carrying all of them.
+    pub tag: Option<String>,
+}
+
+/// Input for replacing a feature's tags.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetTagsInput {
+    pub tags: Vec<String>,
+}
+
+/// Input for putting sibling features in order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReorderFeaturesInput {
+    /// Features sharing a parent, first to implement first.
+    pub feature_ids: Vec<Uuid>,
+}
+
+/// How one feature is linked to another.
+///
+/// `Blocks` is the reverse of `DependsOn` and is stored that way, so only
+/// dependencies can form a cycle. `Relates` has no direction or order.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LinkKind {
+    /// The feature can't be finished until the other one is.
+    #[default]
+    DependsOn,
+    /// The other feature can't be finished until this one is.
+    Blocks,
+    /// The features touch the same area, in no particular order.
+    Relates,
+}
+
+impl LinkKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::DependsOn => "depends_on",
+            Self::Blocks => "blocks",
+            Self::Relates => "relates",
+        }
+    }
+}
+
+impl FromStr for LinkKind {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "depends_on" => Ok(Self::DependsOn),
+            "blocks" => Ok(Self::Blocks),
+            "relates" => Ok(Self::Relates),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Input for linking a feature to another.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddDependencyInput {
+    /// The other feature: the one that must be finished first for
+    /// `depends_on`, the one waiting for `blocks`.
+    pub depends_on_id: Uuid,
+    /// Defaults to `depends_on`.
+    #[serde(default)]
+    pub kind: LinkKind,
+}
+
+/// A feature's links in both directions.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeatureDependencies {
+    /// Features this one depends on.
+    pub blocked_by: Vec<FeatureSummary>,
+    /// Features that depend on this one.
+    pub blocks: Vec<FeatureSummary>,
+    /// Features linked to this one with `relates`, either way round.
+    #[serde(default)]
+    pub related: Vec<FeatureSummary>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn link_kinds_round_trip_through_their_names() {
+        for kind in [LinkKind::DependsOn, LinkKind::Blocks, LinkKind::Relates] {
+            assert_eq!(LinkKind::from_str(kind.as_str()), Ok(kind));
+            let json = serde_json::to_string(&kind).unwrap();
+            assert_eq!(json, format!("\"{}\"", kind.as_str()));
+        }
+        assert!(LinkKind::from_str("duplicates").is_err());
+    }
+
+    #[test]
+    fn link_kind_defaults_to_a_dependency() {
+        let id = Uuid::new_v4();
+        let input: AddDependencyInput =
+            serde_json::from_str(&format!("{{\"depends_on_id\": \"{}\"}}", id)).unwrap();
+        assert_eq!(input.kind, LinkKind::DependsOn);
+    }
 }
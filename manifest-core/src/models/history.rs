@@ -10,7 +10,8 @@ use uuid::Uuid;
 /// "what work was done on this feature and when?"
 ///
 /// History entries are typically created automatically when a session completes,
-/// summarizing the tasks that were completed.
+/// summarizing the tasks that were completed. Changing a feature's state through
+/// `update_feature` also records an entry, without a session.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FeatureHistory {
     pub id: Uuid,
@@ -16,14 +16,20 @@
 //! - [`Session`]: Active work session on a leaf feature (one at a time per feature).
 //! - [`Task`]: Work unit within a session, assigned to an AI agent.
 
+mod change;
+mod export;
 mod feature;
 mod history;
 mod project;
 mod session;
 mod task;
+mod template;
 
+pub use change::*;
+pub use export::*;
 pub use feature::*;
 pub use history::*;
 pub use project::*;
 pub use session::*;
 pub use task::*;
+pub use template::*;
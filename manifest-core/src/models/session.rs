@@ -133,3 +133,50 @@ pub struct SessionCompletionResult {
     pub session: Session,
     pub history_entry: super::FeatureHistory,
 }
+
+/// Rollup of what a session accomplished.
+///
+/// Combines the session's tasks with any history recorded for it. Tasks are
+/// deleted when a session completes, so a completed session reports its
+/// outcome through `summary` and `commits` rather than `tasks`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionSummary {
+    pub session: Session,
+    pub feature: SessionFeatureSummary,
+    pub tasks: Vec<super::Task>,
+    pub task_counts: TaskCounts,
+    /// Summary from the session's history entry, if it has completed.
+    pub summary: Option<String>,
+    /// Commits recorded against this session, deduplicated by SHA.
+    pub commits: Vec<CommitRef>,
+}
+
+/// Number of tasks in each status.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TaskCounts {
+    pub total: usize,
+    pub pending: usize,
+    pub running: usize,
+    pub completed: usize,
+    pub failed: usize,
+}
+
+impl TaskCounts {
+    pub fn from_tasks(tasks: &[super::Task]) -> Self {
+        use super::TaskStatus;
+
+        let mut counts = Self {
+            total: tasks.len(),
+            ..Self::default()
+        };
+        for task in tasks {
+            match task.status {
+                TaskStatus::Pending => counts.pending += 1,
+                TaskStatus::Running => counts.running += 1,
+                TaskStatus::Completed => counts.completed += 1,
+                TaskStatus::Failed => counts.failed += 1,
+            }
+        }
+        counts
+    }
+}
@@ -27,6 +27,8 @@ pub struct Task {
     pub worktree_path: Option<String>,
     /// Git branch name for this task's work.
     pub branch: Option<String>,
+    /// File the desktop app saves the task's terminal output to.
+    pub log_path: Option<String>,
     pub created_at: DateTime<Utc>,
 }
 
@@ -125,4 +127,6 @@ pub struct UpdateTaskInput {
     pub worktree_path: Option<String>,
     /// Git branch name for this task's work.
     pub branch: Option<String>,
+    /// File the task's terminal output is saved to.
+    pub log_path: Option<String>,
 }
@@ -0,0 +1,120 @@
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+/// Directory, relative to a project directory, holding custom templates.
+pub const TEMPLATE_DIR: &str = ".manifest/templates";
+
+/// Markdown scaffolding used to pre-fill a new feature's details.
+///
+/// Built-in templates cover common feature shapes. Projects can add their own
+/// as `<name>.md` files in [`TEMPLATE_DIR`] under any project directory; a
+/// project template replaces a built-in one with the same name.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FeatureTemplate {
+    /// Identifier used to select the template, e.g. `user_story`.
+    pub name: String,
+    /// Human-readable name for pickers, e.g. "User Story".
+    pub title: String,
+    /// Markdown copied into the new feature's details.
+    pub content: String,
+}
+
+const USER_STORY: &str = "\
+## User Story
+
+As a [role], I want [capability] so that [benefit].
+
+## Acceptance Criteria
+
+- [ ]
+
+## Notes
+";
+
+const BUG_FIX: &str = "\
+## Problem
+
+## Steps to Reproduce
+
+1.
+
+## Expected Behavior
+
+## Actual Behavior
+
+## Fix
+";
+
+const TECHNICAL_SPEC: &str = "\
+## Overview
+
+## Goals
+
+## Non-Goals
+
+## Design
+
+## Open Questions
+";
+
+impl FeatureTemplate {
+    /// Templates available in every project.
+    pub fn builtin() -> Vec<FeatureTemplate> {
+        [
+            ("user_story", USER_STORY),
+            ("bug_fix", BUG_FIX),
+            ("technical_spec", TECHNICAL_SPEC),
+        ]
+        .into_iter()
+        .map(|(name, content)| FeatureTemplate {
+            name: name.to_string(),
+            title: title_from_name(name),
+            content: content.to_string(),
+        })
+        .collect()
+    }
+
+    /// Read the `*.md` templates in `dir`'s [`TEMPLATE_DIR`], sorted by name.
+    /// A missing template directory yields no templates.
+    pub fn load_dir(dir: &Path) -> std::io::Result<Vec<FeatureTemplate>> {
+        let template_dir = dir.join(TEMPLATE_DIR);
+        if !template_dir.is_dir() {
+            return Ok(Vec::new());
+        }
+
+        let mut templates = Vec::new();
+        for entry in fs::read_dir(&template_dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("md") {
+                continue;
+            }
+            let Some(name) = path.file_stem().and_then(|s| s.to_str()) else {
+                continue;
+            };
+            templates.push(FeatureTemplate {
+                name: name.to_string(),
+                title: title_from_name(name),
+                content: fs::read_to_string(&path)?,
+            });
+        }
+        templates.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(templates)
+    }
+}
+
+/// Turn `technical_spec` or `technical-spec` into "Technical Spec".
+fn title_from_name(name: &str) -> String {
+    name.split(['_', '-', ' '])
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
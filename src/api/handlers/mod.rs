@@ -1,10 +1,15 @@
 use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
-    response::IntoResponse,
-    Json,
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Response,
+    },
+    Extension, Json,
 };
+use futures_util::stream::{self, Stream, StreamExt};
 use serde::Deserialize;
+use tokio::sync::broadcast::{self, error::RecvError};
 use uuid::Uuid;
 
 use crate::db::{Database, ManifestError};
@@ -24,6 +29,7 @@ fn manifest_error(e: ManifestError) -> (StatusCode, String) {
         ManifestError::NotFound(_) => StatusCode::NOT_FOUND,
         ManifestError::Validation(_) => StatusCode::BAD_REQUEST,
         ManifestError::InvalidState(_) => StatusCode::CONFLICT,
+        ManifestError::Conflict(_) => StatusCode::CONFLICT,
     };
     tracing::warn!("Client error: {}", e);
     (status, e.to_string())
@@ -147,6 +153,13 @@ pub async fn list_features(
     State(db): State<Database>,
     Query(query): Query<ListFeaturesQuery>,
 ) -> Result<Json<Vec<FeatureSummary>>, (StatusCode, String)> {
+    if let Some(tags) = &query.tag {
+        let features = db
+            .get_features_by_tags(&split_tags(tags), None)
+            .map_err(internal_error)?;
+        return Ok(Json(paginate_summaries(features, &query)));
+    }
+
     // Use SQL-based pagination for efficiency
     let features = db
         .get_all_features_paginated(query.limit, query.offset)
@@ -162,6 +175,13 @@ pub async fn list_project_features(
     Path(project_id): Path<Uuid>,
     Query(query): Query<ListFeaturesQuery>,
 ) -> Result<Json<Vec<FeatureSummary>>, (StatusCode, String)> {
+    if let Some(tags) = &query.tag {
+        let features = db
+            .get_features_by_tags(&split_tags(tags), Some(project_id))
+            .map_err(internal_error)?;
+        return Ok(Json(paginate_summaries(features, &query)));
+    }
+
     // Use SQL-based pagination for efficiency
     let features = db
         .get_features_by_project_paginated(project_id, query.limit, query.offset)
@@ -171,6 +191,21 @@ pub async fn list_project_features(
     Ok(Json(summaries))
 }
 
+/// Split a comma-separated `tag` query parameter into tag names.
+fn split_tags(tags: &str) -> Vec<String> {
+    tags.split(',').map(str::to_string).collect()
+}
+
+/// Apply `limit`/`offset` to an already filtered feature list.
+fn paginate_summaries(features: Vec<Feature>, query: &ListFeaturesQuery) -> Vec<FeatureSummary> {
+    features
+        .into_iter()
+        .skip(query.offset.unwrap_or(0) as usize)
+        .take(query.limit.map_or(usize::MAX, |l| l as usize))
+        .map(Into::into)
+        .collect()
+}
+
 pub async fn list_root_features(
     State(db): State<Database>,
     Path(project_id): Path<Uuid>,
@@ -189,6 +224,40 @@ pub async fn get_feature_tree(
         .map_err(internal_error)
 }
 
+#[derive(Debug, Deserialize)]
+pub struct FeatureMarkdownQuery {
+    /// Append the tasks of the feature's latest session as a checklist.
+    #[serde(default)]
+    pub include_tasks: bool,
+}
+
+pub async fn get_feature_markdown(
+    State(db): State<Database>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<FeatureMarkdownQuery>,
+) -> Result<String, (StatusCode, String)> {
+    db.feature_to_markdown(id, query.include_tasks)
+        .map_err(internal_error)?
+        .ok_or((StatusCode::NOT_FOUND, "Feature not found".to_string()))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FeatureSubtreeQuery {
+    /// Levels of descendants to include; omit for the whole subtree.
+    pub depth: Option<u32>,
+}
+
+pub async fn get_feature_subtree(
+    State(db): State<Database>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<FeatureSubtreeQuery>,
+) -> Result<Json<FeatureTreeNode>, (StatusCode, String)> {
+    db.get_feature_subtree(id, query.depth)
+        .map_err(internal_error)?
+        .map(Json)
+        .ok_or((StatusCode::NOT_FOUND, "Feature not found".to_string()))
+}
+
 pub async fn list_children(
     State(db): State<Database>,
     Path(parent_id): Path<Uuid>,
@@ -261,16 +330,82 @@ pub async fn get_feature_diff(
         .ok_or((StatusCode::NOT_FOUND, "Feature not found".to_string()))
 }
 
+#[derive(Debug, Deserialize)]
+pub struct CreateFeatureQuery {
+    /// Template whose content pre-fills the feature details.
+    pub template: Option<String>,
+}
+
 pub async fn create_feature(
     State(db): State<Database>,
     Path(project_id): Path<Uuid>,
+    Query(query): Query<CreateFeatureQuery>,
     Json(input): Json<CreateFeatureInput>,
 ) -> Result<(StatusCode, Json<Feature>), (StatusCode, String)> {
-    db.create_feature(project_id, input)
+    let result = match query.template {
+        Some(template) => db.create_feature_from_template(project_id, input, &template),
+        None => db.create_feature(project_id, input),
+    };
+    result
         .map(|f| (StatusCode::CREATED, Json(f)))
         .map_err(internal_error)
 }
 
+/// Body of `POST /features`, which names the project in the body rather
+/// than the path.
+#[derive(Debug, Deserialize)]
+pub struct CreateFeatureWithProjectInput {
+    pub project_id: Uuid,
+    #[serde(flatten)]
+    pub feature: CreateFeatureInput,
+}
+
+pub async fn create_feature_with_project(
+    state: State<Database>,
+    query: Query<CreateFeatureQuery>,
+    Json(input): Json<CreateFeatureWithProjectInput>,
+) -> Result<(StatusCode, Json<Feature>), (StatusCode, String)> {
+    create_feature(state, Path(input.project_id), query, Json(input.feature)).await
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExportQuery {
+    /// `json` (the default) or `md`.
+    #[serde(default)]
+    pub format: ExportFormat,
+}
+
+/// Export a project as JSON for importing elsewhere, or as one Markdown
+/// document for reviewers.
+pub async fn export_project(
+    State(db): State<Database>,
+    Path(project_id): Path<Uuid>,
+    Query(query): Query<ExportQuery>,
+) -> Result<Response, (StatusCode, String)> {
+    let not_found = || (StatusCode::NOT_FOUND, "Project not found".to_string());
+    match query.format {
+        ExportFormat::Json => db
+            .export_project(project_id)
+            .map_err(internal_error)?
+            .map(|export| Json(export).into_response())
+            .ok_or_else(not_found),
+        ExportFormat::Md => db
+            .export_project_markdown(project_id)
+            .map_err(internal_error)?
+            .map(IntoResponse::into_response)
+            .ok_or_else(not_found),
+    }
+}
+
+pub async fn list_feature_templates(
+    State(db): State<Database>,
+    Path(project_id): Path<Uuid>,
+) -> Result<Json<Vec<FeatureTemplate>>, (StatusCode, String)> {
+    db.get_feature_templates(project_id)
+        .map(Json)
+        .map_err(internal_error)
+}
+
 pub async fn update_feature(
     State(db): State<Database>,
     Path(id): Path<Uuid>,
@@ -282,6 +417,110 @@ pub async fn update_feature(
         .ok_or((StatusCode::NOT_FOUND, "Feature not found".to_string()))
 }
 
+pub async fn reorder_features(
+    State(db): State<Database>,
+    Json(input): Json<ReorderFeaturesInput>,
+) -> Result<Json<Vec<Feature>>, (StatusCode, String)> {
+    db.reorder_features(&input.feature_ids)
+        .map(Json)
+        .map_err(internal_error)
+}
+
+pub async fn get_feature_tags(
+    State(db): State<Database>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<String>>, (StatusCode, String)> {
+    db.get_feature(id)
+        .map_err(internal_error)?
+        .ok_or((StatusCode::NOT_FOUND, "Feature not found".to_string()))?;
+    db.get_feature_tags(id).map(Json).map_err(internal_error)
+}
+
+pub async fn set_feature_tags(
+    State(db): State<Database>,
+    Path(id): Path<Uuid>,
+    Json(input): Json<SetTagsInput>,
+) -> Result<Json<Vec<String>>, (StatusCode, String)> {
+    db.set_feature_tags(id, &input.tags)
+        .map_err(internal_error)?
+        .map(Json)
+        .ok_or((StatusCode::NOT_FOUND, "Feature not found".to_string()))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListTagsQuery {
+    /// Only return tags starting with this prefix (for autocompletion).
+    pub prefix: Option<String>,
+}
+
+pub async fn list_project_tags(
+    State(db): State<Database>,
+    Path(project_id): Path<Uuid>,
+    Query(query): Query<ListTagsQuery>,
+) -> Result<Json<Vec<String>>, (StatusCode, String)> {
+    db.get_project_tags(project_id, query.prefix.as_deref())
+        .map(Json)
+        .map_err(internal_error)
+}
+
+pub async fn duplicate_feature(
+    State(db): State<Database>,
+    Path(id): Path<Uuid>,
+) -> Result<(StatusCode, Json<Feature>), (StatusCode, String)> {
+    db.duplicate_feature(id)
+        .map_err(internal_error)?
+        .map(|f| (StatusCode::CREATED, Json(f)))
+        .ok_or((StatusCode::NOT_FOUND, "Feature not found".to_string()))
+}
+
+pub async fn get_feature_dependencies(
+    State(db): State<Database>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<FeatureDependencies>, (StatusCode, String)> {
+    db.get_feature(id)
+        .map_err(internal_error)?
+        .ok_or((StatusCode::NOT_FOUND, "Feature not found".to_string()))?;
+    feature_dependencies(&db, id).map(Json)
+}
+
+pub async fn add_feature_dependency(
+    State(db): State<Database>,
+    Path(id): Path<Uuid>,
+    Json(input): Json<AddDependencyInput>,
+) -> Result<(StatusCode, Json<FeatureDependencies>), (StatusCode, String)> {
+    db.add_link(id, input.depends_on_id, input.kind)
+        .map_err(internal_error)?;
+    feature_dependencies(&db, id).map(|deps| (StatusCode::CREATED, Json(deps)))
+}
+
+pub async fn remove_feature_dependency(
+    State(db): State<Database>,
+    Path((id, depends_on_id)): Path<(Uuid, Uuid)>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    if db
+        .remove_dependency(id, depends_on_id)
+        .map_err(internal_error)?
+    {
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        Err((StatusCode::NOT_FOUND, "Dependency not found".to_string()))
+    }
+}
+
+fn feature_dependencies(
+    db: &Database,
+    id: Uuid,
+) -> Result<FeatureDependencies, (StatusCode, String)> {
+    let blocked_by = db.get_dependencies(id).map_err(internal_error)?;
+    let blocks = db.get_dependents(id).map_err(internal_error)?;
+    let related = db.get_related(id).map_err(internal_error)?;
+    Ok(FeatureDependencies {
+        blocked_by: blocked_by.into_iter().map(Into::into).collect(),
+        blocks: blocks.into_iter().map(Into::into).collect(),
+        related: related.into_iter().map(Into::into).collect(),
+    })
+}
+
 pub async fn delete_feature(
     State(db): State<Database>,
     Path(id): Path<Uuid>,
@@ -305,12 +544,12 @@ pub struct SearchFeaturesQuery {
 }
 
 /// Search features by title and details.
-/// Returns summaries ranked by relevance.
+/// Returns hits ranked by relevance, with snippets of the matching text.
 pub async fn search_features(
     State(db): State<Database>,
     Query(query): Query<SearchFeaturesQuery>,
-) -> Result<Json<Vec<FeatureSummary>>, (StatusCode, String)> {
-    db.search_features(&query.q, query.project_id, query.limit)
+) -> Result<Json<Vec<FeatureHit>>, (StatusCode, String)> {
+    db.search(&query.q, query.project_id, query.limit)
         .map(Json)
         .map_err(internal_error)
 }
@@ -348,6 +587,16 @@ pub async fn get_session_status(
         .ok_or((StatusCode::NOT_FOUND, "Session not found".to_string()))
 }
 
+pub async fn get_session_summary(
+    State(db): State<Database>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<SessionSummary>, (StatusCode, String)> {
+    db.get_session_summary(id)
+        .map_err(internal_error)?
+        .map(Json)
+        .ok_or((StatusCode::NOT_FOUND, "Session not found".to_string()))
+}
+
 pub async fn complete_session(
     State(db): State<Database>,
     Path(id): Path<Uuid>,
@@ -518,3 +767,65 @@ fn flatten_feature_tree(
 
     id
 }
+
+// ============================================================
+// Change Events
+// ============================================================
+
+/// Query parameters for the change stream.
+#[derive(Debug, Deserialize)]
+pub struct EventsQuery {
+    /// Only stream changes in this project, starting with its feature tree.
+    pub project_id: Option<Uuid>,
+}
+
+/// Stream changes as server-sent events.
+///
+/// Each change is a `change` event whose data is a [`ChangeEvent`] as JSON.
+/// With `project_id`, the stream opens with a `snapshot` event holding the
+/// project's feature tree, and changes elsewhere are left out. A `resync`
+/// event means the client fell behind and missed changes, so it should
+/// refetch what it shows.
+pub async fn stream_events(
+    State(db): State<Database>,
+    Extension(changes): Extension<broadcast::Sender<ChangeEvent>>,
+    Query(query): Query<EventsQuery>,
+) -> Result<Sse<impl Stream<Item = Result<Event, axum::Error>>>, (StatusCode, String)> {
+    // Subscribe before reading the snapshot so no change falls between them
+    let receiver = changes.subscribe();
+
+    let snapshot = match query.project_id {
+        Some(project_id) => {
+            db.get_project(project_id)
+                .map_err(internal_error)?
+                .ok_or((StatusCode::NOT_FOUND, "Project not found".to_string()))?;
+            let tree = db.get_feature_tree(project_id).map_err(internal_error)?;
+            Some(Event::default().event("snapshot").json_data(tree))
+        }
+        None => None,
+    };
+
+    let project_id = query.project_id;
+    let changes = stream::unfold(receiver, move |mut receiver| async move {
+        loop {
+            match receiver.recv().await {
+                Ok(change) => {
+                    if project_id.is_some_and(|id| change.project_id() != id) {
+                        continue;
+                    }
+                    let event = Event::default().event("change").json_data(change);
+                    return Some((event, receiver));
+                }
+                Err(RecvError::Lagged(missed)) => {
+                    tracing::debug!("Event stream missed {} changes", missed);
+                    let event = Ok(Event::default().event("resync").data("{}"));
+                    return Some((event, receiver));
+                }
+                // The server is shutting down
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    });
+
+    Ok(Sse::new(stream::iter(snapshot).chain(changes)).keep_alive(KeepAlive::default()))
+}
@@ -2,14 +2,15 @@ mod handlers;
 mod middleware;
 
 use axum::{
-    routing::{delete, get, post, put},
-    Router,
+    routing::{delete, get, patch, post, put},
+    Extension, Router,
 };
+use tokio::sync::broadcast;
 use tower_http::{cors::CorsLayer, trace::TraceLayer};
 
 use crate::db::Database;
 
-pub use middleware::SecurityConfig;
+pub use middleware::{auth_middleware, SecurityConfig};
 
 /// Build CORS layer based on configuration
 fn build_cors_layer(config: &SecurityConfig) -> CorsLayer {
@@ -24,6 +25,7 @@ fn build_cors_layer(config: &SecurityConfig) -> CorsLayer {
                 Method::GET,
                 Method::POST,
                 Method::PUT,
+                Method::PATCH,
                 Method::DELETE,
                 Method::OPTIONS,
             ])
@@ -37,7 +39,20 @@ pub fn create_router(db: Database) -> Router {
     create_router_with_config(db, SecurityConfig::from_env())
 }
 
+/// How many changes an event stream may fall behind before it is told to resync.
+const CHANGE_BUFFER: usize = 256;
+
 pub fn create_router_with_config(db: Database, config: SecurityConfig) -> Router {
+    // Fan database changes out to every open event stream
+    let (changes, _) = broadcast::channel(CHANGE_BUFFER);
+    let db = db.with_change_listener({
+        let changes = changes.clone();
+        move |change| {
+            // No open streams is not an error
+            let _ = changes.send(change.clone());
+        }
+    });
+
     // Health endpoint (unauthenticated)
     let health_router = Router::new().route("/health", get(handlers::health));
 
@@ -78,6 +93,12 @@ pub fn create_router_with_config(db: Database, config: SecurityConfig) -> Router
             "/projects/{id}/features/tree",
             get(handlers::get_feature_tree),
         )
+        .route(
+            "/projects/{id}/templates",
+            get(handlers::list_feature_templates),
+        )
+        .route("/projects/{id}/tags", get(handlers::list_project_tags))
+        .route("/projects/{id}/export", get(handlers::export_project))
         // Directories (for delete by directory id)
         .route(
             "/directories/{id}",
@@ -85,11 +106,35 @@ pub fn create_router_with_config(db: Database, config: SecurityConfig) -> Router
         )
         // Features (by feature id)
         .route("/features", get(handlers::list_features))
+        .route("/features", post(handlers::create_feature_with_project))
         .route("/features/search", get(handlers::search_features))
+        .route("/features/reorder", post(handlers::reorder_features))
         .route("/features/{id}", get(handlers::get_feature))
         .route("/features/{id}", put(handlers::update_feature))
+        .route("/features/{id}", patch(handlers::update_feature))
         .route("/features/{id}", delete(handlers::delete_feature))
         .route("/features/{id}/children", get(handlers::list_children))
+        .route("/features/{id}/tree", get(handlers::get_feature_subtree))
+        .route(
+            "/features/{id}/markdown",
+            get(handlers::get_feature_markdown),
+        )
+        .route(
+            "/features/{id}/duplicate",
+            post(handlers::duplicate_feature),
+        )
+        .route(
+            "/features/{id}/tags",
+            get(handlers::get_feature_tags).put(handlers::set_feature_tags),
+        )
+        .route(
+            "/features/{id}/dependencies",
+            get(handlers::get_feature_dependencies).post(handlers::add_feature_dependency),
+        )
+        .route(
+            "/features/{id}/dependencies/{depends_on_id}",
+            delete(handlers::remove_feature_dependency),
+        )
         .route("/features/{id}/diff", get(handlers::get_feature_diff))
         .route("/features/{id}/history", get(handlers::get_feature_history))
         .route(
@@ -100,6 +145,7 @@ pub fn create_router_with_config(db: Database, config: SecurityConfig) -> Router
         .route("/sessions", post(handlers::create_session))
         .route("/sessions/{id}", get(handlers::get_session))
         .route("/sessions/{id}/status", get(handlers::get_session_status))
+        .route("/sessions/{id}/summary", get(handlers::get_session_summary))
         .route("/sessions/{id}/complete", post(handlers::complete_session))
         .route(
             "/sessions/{id}/tasks",
@@ -107,7 +153,10 @@ pub fn create_router_with_config(db: Database, config: SecurityConfig) -> Router
         )
         // Tasks
         .route("/tasks/{id}", get(handlers::get_task))
-        .route("/tasks/{id}", put(handlers::update_task));
+        .route("/tasks/{id}", put(handlers::update_task))
+        // Change events
+        .route("/events", get(handlers::stream_events))
+        .layer(Extension(changes));
 
     // Apply auth middleware to protected routes if API key is configured
     let protected_api = if config.api_key.is_some() {
@@ -1,6 +1,11 @@
+use std::path::PathBuf;
+
+use anyhow::Context;
 use clap::{Parser, Subcommand};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+use uuid::Uuid;
 
+use manifest::models::{ExportFormat, Project, ProjectExport};
 use manifest::{api, db, mcp};
 
 #[derive(Parser)]
@@ -29,7 +34,59 @@ enum Commands {
         daemon: bool,
     },
     /// Start MCP server via stdio (for Claude Code integration)
-    Mcp,
+    Mcp {
+        /// Serve MCP over HTTP/SSE instead of stdio
+        #[arg(long)]
+        http: bool,
+
+        /// Port for the MCP HTTP endpoint (with --http)
+        #[arg(short, long, default_value = "17011")]
+        port: u16,
+
+        /// Bind address for the MCP HTTP endpoint (with --http). Addresses
+        /// other than loopback require MANIFEST_API_KEY as a bearer token
+        #[arg(short, long, default_value = "127.0.0.1")]
+        bind: String,
+
+        /// Keep the desktop app's active context for this directory in memory,
+        /// refreshed as the user switches features
+        #[arg(long)]
+        watch_context: bool,
+    },
+    /// Export a project to stdout, or to a file with --output
+    Export {
+        /// Project name or id
+        project: String,
+
+        /// Output format: json (importable) or md (for reading)
+        #[arg(short, long, default_value = "json")]
+        format: ExportFormat,
+
+        /// Write to this file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Import a project from a JSON export
+    Import {
+        /// File written by `mfst export`
+        file: PathBuf,
+    },
+    /// List a project's deleted features
+    Trash {
+        /// Project name or id
+        project: String,
+    },
+    /// Restore a deleted feature, with the children deleted along with it
+    Restore {
+        /// Id of the deleted feature
+        feature: Uuid,
+    },
+    /// Permanently remove features that have been deleted for a while
+    Purge {
+        /// Remove features deleted more than this many days ago
+        #[arg(long, default_value = "30")]
+        days: u32,
+    },
     /// Check server status
     Status,
     /// Stop the daemon
@@ -56,12 +113,51 @@ fn init_tracing(use_stderr: bool) {
     }
 }
 
+/// Enable the database read cache when `MANIFEST_READ_CACHE` is set to a
+/// positive entry count. Off by default because the desktop app writes to the
+/// same database file directly, and the cache only sees this server's writes.
+fn with_read_cache(db: db::Database) -> db::Database {
+    match std::env::var("MANIFEST_READ_CACHE")
+        .ok()
+        .and_then(|size| size.parse::<usize>().ok())
+    {
+        Some(capacity) if capacity > 0 => {
+            tracing::info!("Read cache enabled ({} entries per kind)", capacity);
+            db.with_cache(capacity)
+        }
+        _ => db,
+    }
+}
+
+/// Find a project by id, or failing that by exact name.
+fn find_project(db: &db::Database, project: &str) -> anyhow::Result<Project> {
+    if let Ok(id) = Uuid::parse_str(project) {
+        if let Some(found) = db.get_project(id)? {
+            return Ok(found);
+        }
+    }
+    let mut matches: Vec<Project> = db
+        .get_all_projects()?
+        .into_iter()
+        .filter(|p| p.name == project)
+        .collect();
+    match matches.len() {
+        0 => anyhow::bail!("No project named '{}'", project),
+        1 => Ok(matches.remove(0)),
+        n => anyhow::bail!("{} projects are named '{}'; use the project id", n, project),
+    }
+}
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
 
-    // MCP mode needs stderr for logging since stdout is the protocol channel
-    let use_stderr = matches!(cli.command, Some(Commands::Mcp));
+    // Stdio MCP mode and export need stderr for logging since stdout carries
+    // the protocol or the exported project
+    let use_stderr = matches!(
+        cli.command,
+        Some(Commands::Mcp { http: false, .. }) | Some(Commands::Export { .. })
+    );
     init_tracing(use_stderr);
 
     match cli.command {
@@ -77,6 +173,7 @@ async fn main() -> anyhow::Result<()> {
 
             let db = db::Database::open_default()?;
             db.migrate()?;
+            let db = with_read_cache(db);
 
             let app = api::create_router(db);
 
@@ -85,10 +182,90 @@ async fn main() -> anyhow::Result<()> {
 
             axum::serve(listener, app).await?;
         }
-        Some(Commands::Mcp) => {
+        Some(Commands::Mcp {
+            http,
+            port,
+            bind,
+            watch_context,
+        }) => {
             // MCP server uses HTTP client to connect to the API
             // No local database needed - configure via MANIFEST_URL env var
-            mcp::run_stdio_server().await?;
+            if http {
+                let addr = format!("{}:{}", bind, port).parse()?;
+                mcp::run_http_server(addr, watch_context).await?;
+            } else {
+                mcp::run_stdio_server(watch_context).await?;
+            }
+        }
+        Some(Commands::Export {
+            project,
+            format,
+            output,
+        }) => {
+            let db = db::Database::open_default()?;
+            db.migrate()?;
+            let project = find_project(&db, &project)?;
+
+            let exported = match format {
+                ExportFormat::Json => {
+                    let export = db
+                        .export_project(project.id)?
+                        .context("Project was deleted during export")?;
+                    serde_json::to_string_pretty(&export)? + "\n"
+                }
+                ExportFormat::Md => db
+                    .export_project_markdown(project.id)?
+                    .context("Project was deleted during export")?,
+            };
+            match output {
+                Some(path) => std::fs::write(&path, exported)
+                    .with_context(|| format!("Failed to write {}", path.display()))?,
+                None => print!("{}", exported),
+            }
+        }
+        Some(Commands::Import { file }) => {
+            let json = std::fs::read_to_string(&file)
+                .with_context(|| format!("Failed to read {}", file.display()))?;
+            let export: ProjectExport = serde_json::from_str(&json)
+                .with_context(|| format!("{} is not a project export", file.display()))?;
+
+            let db = db::Database::open_default()?;
+            db.migrate()?;
+            let project = db.import_project(export)?;
+            println!("Imported project '{}' ({})", project.name, project.id);
+        }
+        Some(Commands::Trash { project }) => {
+            let db = db::Database::open_default()?;
+            db.migrate()?;
+            let project = find_project(&db, &project)?;
+
+            let deleted = db.get_deleted_features(project.id)?;
+            if deleted.is_empty() {
+                println!("No deleted features in '{}'", project.name);
+            }
+            for entry in deleted {
+                println!(
+                    "{}  {}  {}",
+                    entry.feature.id,
+                    entry.deleted_at.format("%Y-%m-%d %H:%M"),
+                    entry.feature.title
+                );
+            }
+        }
+        Some(Commands::Restore { feature }) => {
+            let db = db::Database::open_default()?;
+            db.migrate()?;
+            let restored = db
+                .restore_feature(feature)?
+                .with_context(|| format!("No feature {}", feature))?;
+            println!("Restored '{}'", restored.title);
+        }
+        Some(Commands::Purge { days }) => {
+            let db = db::Database::open_default()?;
+            db.migrate()?;
+            let older_than = chrono::Utc::now() - chrono::Duration::days(days.into());
+            let purged = db.purge_deleted(older_than)?;
+            println!("Purged {} deleted features", purged);
         }
         Some(Commands::Status) => {
             println!("Checking Manifest server status...");
@@ -111,6 +288,7 @@ async fn main() -> anyhow::Result<()> {
 
             let db = db::Database::open_default()?;
             db.migrate()?;
+            let db = with_read_cache(db);
 
             let app = api::create_router(db);
 
@@ -91,12 +91,30 @@ impl ManifestClient {
             Ok(response.json().await?)
         } else {
             let body = response.text().await.unwrap_or_default();
-            match status {
-                StatusCode::NOT_FOUND => Err(ClientError::NotFound(body)),
-                StatusCode::BAD_REQUEST => Err(ClientError::BadRequest(body)),
-                StatusCode::UNAUTHORIZED => Err(ClientError::Unauthorized),
-                _ => Err(ClientError::Server(format!("{}: {}", status, body))),
-            }
+            Err(Self::status_error(status, body))
+        }
+    }
+
+    /// Handle a plain-text response.
+    async fn handle_text_response(
+        &self,
+        response: reqwest::Response,
+    ) -> Result<String, ClientError> {
+        let status = response.status();
+        if status.is_success() {
+            Ok(response.text().await?)
+        } else {
+            let body = response.text().await.unwrap_or_default();
+            Err(Self::status_error(status, body))
+        }
+    }
+
+    fn status_error(status: StatusCode, body: String) -> ClientError {
+        match status {
+            StatusCode::NOT_FOUND => ClientError::NotFound(body),
+            StatusCode::BAD_REQUEST => ClientError::BadRequest(body),
+            StatusCode::UNAUTHORIZED => ClientError::Unauthorized,
+            _ => ClientError::Server(format!("{}: {}", status, body)),
         }
     }
 
@@ -107,12 +125,7 @@ impl ManifestClient {
             Ok(())
         } else {
             let body = response.text().await.unwrap_or_default();
-            match status {
-                StatusCode::NOT_FOUND => Err(ClientError::NotFound(body)),
-                StatusCode::BAD_REQUEST => Err(ClientError::BadRequest(body)),
-                StatusCode::UNAUTHORIZED => Err(ClientError::Unauthorized),
-                _ => Err(ClientError::Server(format!("{}: {}", status, body))),
-            }
+            Err(Self::status_error(status, body))
         }
     }
 
@@ -211,6 +224,15 @@ impl ManifestClient {
         self.handle_response(response).await
     }
 
+    /// Get a rollup of a session's tasks and recorded commits.
+    pub async fn get_session_summary(&self, id: Uuid) -> Result<SessionSummary, ClientError> {
+        let response = self
+            .request(reqwest::Method::GET, &format!("/sessions/{}/summary", id))
+            .send()
+            .await?;
+        self.handle_response(response).await
+    }
+
     /// Complete a session.
     pub async fn complete_session(
         &self,
@@ -241,6 +263,21 @@ impl ManifestClient {
         self.handle_response(response).await
     }
 
+    /// Get a feature rendered as Markdown, optionally with its latest
+    /// session's tasks.
+    pub async fn get_feature_markdown(
+        &self,
+        id: Uuid,
+        include_tasks: bool,
+    ) -> Result<String, ClientError> {
+        let response = self
+            .request(reqwest::Method::GET, &format!("/features/{}/markdown", id))
+            .query(&[("include_tasks", include_tasks)])
+            .send()
+            .await?;
+        self.handle_text_response(response).await
+    }
+
     /// Get history for a feature.
     pub async fn get_feature_history(&self, id: Uuid) -> Result<Vec<FeatureHistory>, ClientError> {
         let response = self
@@ -250,6 +287,71 @@ impl ManifestClient {
         self.handle_response(response).await
     }
 
+    /// Get a feature with its descendants, up to `depth` levels below it.
+    pub async fn get_feature_subtree(
+        &self,
+        id: Uuid,
+        depth: Option<u32>,
+    ) -> Result<FeatureTreeNode, ClientError> {
+        let mut request = self.request(reqwest::Method::GET, &format!("/features/{}/tree", id));
+        if let Some(depth) = depth {
+            request = request.query(&[("depth", depth)]);
+        }
+        let response = request.send().await?;
+        self.handle_response(response).await
+    }
+
+    /// Get a project's features nested under their parents.
+    pub async fn get_feature_tree(
+        &self,
+        project_id: Uuid,
+    ) -> Result<Vec<FeatureTreeNode>, ClientError> {
+        let response = self
+            .request(
+                reqwest::Method::GET,
+                &format!("/projects/{}/features/tree", project_id),
+            )
+            .send()
+            .await?;
+        self.handle_response(response).await
+    }
+
+    /// Get the features a feature is blocked by and blocks.
+    pub async fn get_feature_dependencies(
+        &self,
+        id: Uuid,
+    ) -> Result<FeatureDependencies, ClientError> {
+        let response = self
+            .request(
+                reqwest::Method::GET,
+                &format!("/features/{}/dependencies", id),
+            )
+            .send()
+            .await?;
+        self.handle_response(response).await
+    }
+
+    /// Link a feature to another, by default as blocked by it.
+    pub async fn add_feature_dependency(
+        &self,
+        id: Uuid,
+        depends_on_id: Uuid,
+        kind: LinkKind,
+    ) -> Result<FeatureDependencies, ClientError> {
+        let response = self
+            .request(
+                reqwest::Method::POST,
+                &format!("/features/{}/dependencies", id),
+            )
+            .json(&AddDependencyInput {
+                depends_on_id,
+                kind,
+            })
+            .send()
+            .await?;
+        self.handle_response(response).await
+    }
+
     /// List all features with optional filtering.
     /// Always returns summaries only - use get_feature for full details.
     pub async fn list_features(
@@ -285,39 +387,46 @@ impl ManifestClient {
     }
 
     /// Search features by title and details.
-    /// Returns summaries ranked by relevance.
+    /// Returns hits ranked by relevance, with snippets of the matching text.
     pub async fn search_features(
         &self,
         query: &str,
         project_id: Option<Uuid>,
         limit: Option<u32>,
-    ) -> Result<Vec<FeatureSummary>, ClientError> {
-        let mut url = "/features/search".to_string();
-
-        // Build query string with manual percent-encoding for the query
-        let encoded_query: String = query
-            .chars()
-            .map(|c| match c {
-                ' ' => "%20".to_string(),
-                '&' => "%26".to_string(),
-                '=' => "%3D".to_string(),
-                '?' => "%3F".to_string(),
-                '#' => "%23".to_string(),
-                '%' => "%25".to_string(),
-                _ => c.to_string(),
-            })
-            .collect();
-        let mut params = vec![format!("q={}", encoded_query)];
+    ) -> Result<Vec<FeatureHit>, ClientError> {
+        // Let reqwest encode the query, since text like "C++" or "a&b" must
+        // reach the server unchanged.
+        let mut params = vec![("q", query.to_string())];
         if let Some(pid) = project_id {
-            params.push(format!("project_id={}", pid));
+            params.push(("project_id", pid.to_string()));
         }
         if let Some(l) = limit {
-            params.push(format!("limit={}", l));
+            params.push(("limit", l.to_string()));
         }
-        url.push('?');
-        url.push_str(&params.join("&"));
 
-        let response = self.request(reqwest::Method::GET, &url).send().await?;
+        let response = self
+            .request(reqwest::Method::GET, "/features/search")
+            .query(&params)
+            .send()
+            .await?;
+        self.handle_response(response).await
+    }
+
+    /// List features carrying every one of `tags`, optionally in one project.
+    pub async fn list_features_by_tags(
+        &self,
+        tags: &[String],
+        project_id: Option<Uuid>,
+    ) -> Result<Vec<FeatureSummary>, ClientError> {
+        let path = match project_id {
+            Some(pid) => format!("/projects/{}/features", pid),
+            None => "/features".to_string(),
+        };
+        let response = self
+            .request(reqwest::Method::GET, &path)
+            .query(&[("tag", tags.join(","))])
+            .send()
+            .await?;
         self.handle_response(response).await
     }
 
@@ -335,20 +444,36 @@ impl ManifestClient {
         self.handle_response(response).await
     }
 
+    /// Give sibling features consecutive priorities in the order listed.
+    pub async fn reorder_features(
+        &self,
+        feature_ids: &[Uuid],
+    ) -> Result<Vec<Feature>, ClientError> {
+        let response = self
+            .request(reqwest::Method::POST, "/features/reorder")
+            .json(&ReorderFeaturesInput {
+                feature_ids: feature_ids.to_vec(),
+            })
+            .send()
+            .await?;
+        self.handle_response(response).await
+    }
+
     /// Create a feature.
     pub async fn create_feature(
         &self,
         project_id: Uuid,
         input: &CreateFeatureInput,
+        template: Option<&str>,
     ) -> Result<Feature, ClientError> {
-        let response = self
-            .request(
-                reqwest::Method::POST,
-                &format!("/projects/{}/features", project_id),
-            )
-            .json(input)
-            .send()
-            .await?;
+        let mut request = self.request(
+            reqwest::Method::POST,
+            &format!("/projects/{}/features", project_id),
+        );
+        if let Some(template) = template {
+            request = request.query(&[("template", template)]);
+        }
+        let response = request.json(input).send().await?;
         self.handle_response(response).await
     }
 
@@ -386,6 +511,15 @@ impl ManifestClient {
         self.handle_response(response).await
     }
 
+    /// List all projects.
+    pub async fn list_projects(&self) -> Result<Vec<Project>, ClientError> {
+        let response = self
+            .request(reqwest::Method::GET, "/projects")
+            .send()
+            .await?;
+        self.handle_response(response).await
+    }
+
     /// Get project by directory path.
     pub async fn get_project_by_directory(
         &self,
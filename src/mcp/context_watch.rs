@@ -0,0 +1,91 @@
+//! Live copy of the desktop app's active context file.
+//!
+//! With `mfst mcp --watch-context`, the server keeps the parsed contents of
+//! one `active_context.json` in memory and refreshes it whenever the file
+//! system reports a change, so `get_active_context` follows the user as they
+//! switch features. The app replaces the file with an atomic rename, which a
+//! watch on the file itself would lose track of, so the watcher is on the
+//! containing directory. A missing file reads as "nothing selected" until it
+//! appears.
+
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+
+/// Shared, continuously refreshed contents of a context file.
+#[derive(Clone)]
+pub struct ContextWatch {
+    path: PathBuf,
+    current: Arc<RwLock<Option<serde_json::Value>>>,
+    /// Kept alive for as long as any clone is; dropping it stops the watch.
+    _watcher: Arc<RecommendedWatcher>,
+}
+
+impl ContextWatch {
+    /// Read `path` now and keep it current as its directory changes. The
+    /// directory is created if it doesn't exist yet, so there is something
+    /// to watch before the app first writes the file.
+    pub fn spawn(path: PathBuf) -> notify::Result<Self> {
+        let dir = path
+            .parent()
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .unwrap_or(Path::new("."))
+            .to_path_buf();
+        std::fs::create_dir_all(&dir)?;
+
+        let current = Arc::new(RwLock::new(read_context(&path)));
+        let mut watcher = notify::recommended_watcher({
+            let path = path.clone();
+            let current = current.clone();
+            move |event: notify::Result<notify::Event>| {
+                let relevant = match &event {
+                    Ok(event) => {
+                        event.need_rescan()
+                            || event
+                                .paths
+                                .iter()
+                                .any(|p| p.file_name() == path.file_name())
+                    }
+                    Err(e) => {
+                        tracing::warn!("Watching {} failed: {}", path.display(), e);
+                        false
+                    }
+                };
+                if !relevant {
+                    return;
+                }
+                // One save can arrive as several events
+                let context = read_context(&path);
+                let mut current = current.write().expect("context lock poisoned");
+                if *current != context {
+                    *current = context;
+                    tracing::debug!("Active context changed: {}", path.display());
+                }
+            }
+        })?;
+        watcher.watch(&dir, RecursiveMode::NonRecursive)?;
+
+        Ok(Self {
+            path,
+            current,
+            _watcher: Arc::new(watcher),
+        })
+    }
+
+    /// Path of the watched context file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Latest parsed contents, or `None` if the file is missing or invalid.
+    pub fn current(&self) -> Option<serde_json::Value> {
+        self.current.read().expect("context lock poisoned").clone()
+    }
+}
+
+/// Parse a context file, or `None` if it is missing or not valid JSON.
+pub(crate) fn read_context(path: &Path) -> Option<serde_json::Value> {
+    let content = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
@@ -1,26 +1,46 @@
 //! MCP server for AI-assisted feature development.
 
 pub mod client;
+mod context_watch;
+mod prompts;
+mod resources;
 mod types;
+mod validation;
 
 use std::str::FromStr;
 
 pub use client::ManifestClient;
+pub use context_watch::ContextWatch;
 pub use types::*;
 
 use rmcp::{
     handler::server::{tool::ToolRouter, wrapper::Parameters},
-    model::{CallToolResult, Content, ServerInfo},
-    tool, tool_handler, tool_router, ErrorData as McpError, ServerHandler, ServiceExt,
+    model::{
+        CallToolResult, Content, GetPromptRequestParam, GetPromptResult, ListPromptsResult,
+        ListResourceTemplatesResult, ListResourcesResult, PaginatedRequestParam, PromptMessage,
+        PromptMessageRole, ReadResourceRequestParam, ReadResourceResult, ResourceContents,
+        ServerInfo,
+    },
+    service::RequestContext,
+    tool, tool_handler, tool_router, ErrorData as McpError, RoleServer, ServerHandler, ServiceExt,
 };
 use uuid::Uuid;
 
 use crate::models::*;
 use client::ClientError;
+use prompts::WorkflowPrompt;
+use resources::ResourceUri;
+use validation::{MAX_ITEMS, MAX_PATH_LEN, MAX_TEXT_LEN, MAX_TITLE_LEN};
+
+/// Default `max_depth` for `get_feature_subtree`.
+const DEFAULT_SUBTREE_DEPTH: u32 = 3;
+/// Default `max_features` for `get_feature_subtree`.
+const DEFAULT_SUBTREE_FEATURES: u32 = 50;
 
 #[derive(Clone)]
 pub struct McpServer {
     client: ManifestClient,
+    context_watch: Option<ContextWatch>,
     tool_router: ToolRouter<Self>,
 }
 
@@ -28,10 +48,17 @@ impl McpServer {
     pub fn new(client: ManifestClient) -> Self {
         Self {
             client,
+            context_watch: None,
             tool_router: Self::tool_router(),
         }
     }
 
+    /// Answer `get_active_context` calls without a directory from `watch`.
+    pub fn with_context_watch(mut self, watch: ContextWatch) -> Self {
+        self.context_watch = Some(watch);
+        self
+    }
+
     /// Create from environment variables.
     pub fn from_env() -> Self {
         Self::new(ManifestClient::from_env())
@@ -42,6 +69,111 @@ impl McpServer {
             .map_err(|e| McpError::invalid_params(format!("Invalid UUID: {}", e), None))
     }
 
+    /// Convert a tree node depth-first, spending one unit of `budget` per
+    /// feature and flagging `truncated` when depth or budget cuts nodes off.
+    fn subtree_node(
+        node: FeatureTreeNode,
+        depth: u32,
+        budget: &mut usize,
+        truncated: &mut bool,
+    ) -> FeatureSubtreeNode {
+        *budget -= 1;
+        let mut children = Vec::new();
+        for child in node.children {
+            if depth == 0 || *budget == 0 {
+                *truncated = true;
+                break;
+            }
+            children.push(Self::subtree_node(child, depth - 1, budget, truncated));
+        }
+        FeatureSubtreeNode {
+            id: node.feature.id.to_string(),
+            title: node.feature.title,
+            state: node.feature.state.as_str().to_string(),
+            details: node.feature.details,
+            children,
+        }
+    }
+
+    /// Convert a project tree node, keeping it if it matches `state` or has
+    /// a descendant that does. Counts the features kept in `count`.
+    fn tree_entry(
+        node: FeatureTreeNode,
+        state: Option<FeatureState>,
+        count: &mut usize,
+    ) -> Option<FeatureTreeEntry> {
+        let children: Vec<_> = node
+            .children
+            .into_iter()
+            .filter_map(|child| Self::tree_entry(child, state, count))
+            .collect();
+        if children.is_empty() && state.is_some_and(|state| node.feature.state != state) {
+            return None;
+        }
+        *count += 1;
+        Some(FeatureTreeEntry {
+            id: node.feature.id.to_string(),
+            title: node.feature.title,
+            state: node.feature.state.as_str().to_string(),
+            children,
+        })
+    }
+
+    fn parse_state(s: &str) -> Result<FeatureState, McpError> {
+        FeatureState::from_str(s).map_err(|_| {
+            McpError::invalid_params(
+                format!(
+                    "Invalid state '{}'. Must be: proposed, specified, implemented, or deprecated",
+                    s
+                ),
+                None,
+            )
+        })
+    }
+
+    fn dependencies_response(
+        feature_id: Uuid,
+        deps: FeatureDependencies,
+    ) -> FeatureDependenciesResponse {
+        let to_info = |f: FeatureSummary| FeatureSummaryInfo {
+            id: f.id.to_string(),
+            title: f.title,
+            state: f.state.as_str().to_string(),
+            priority: f.priority,
+            parent_id: f.parent_id.map(|id| id.to_string()),
+        };
+        FeatureDependenciesResponse {
+            feature_id: feature_id.to_string(),
+            blocked_by: deps.blocked_by.into_iter().map(to_info).collect(),
+            blocks: deps.blocks.into_iter().map(to_info).collect(),
+            related: deps.related.into_iter().map(to_info).collect(),
+        }
+    }
+
+    /// Find the desktop app's context file for `dir`: the nearest one in `dir`
+    /// or its ancestors, then one in any directory of the project `dir`
+    /// belongs to (primary first), since the app writes to the directory it was
+    /// opened on rather than wherever the agent runs.
+    async fn find_context_file(&self, dir: &std::path::Path) -> Option<std::path::PathBuf> {
+        let context_file = |d: &std::path::Path| d.join(".manifest").join("active_context.json");
+
+        if let Some(path) = dir.ancestors().map(context_file).find(|p| p.is_file()) {
+            return Some(path);
+        }
+
+        let mut project = self
+            .client
+            .get_project_by_directory(&dir.to_string_lossy())
+            .await
+            .ok()?;
+        project.directories.sort_by_key(|d| !d.is_primary);
+        project
+            .directories
+            .iter()
+            .map(|d| context_file(std::path::Path::new(&d.path)))
+            .find(|p| p.is_file())
+    }
+
     /// Convert ClientError to McpError.
     fn client_err(e: ClientError) -> McpError {
         match e {
@@ -63,7 +195,7 @@ impl McpServer {
     // ============================================================
 
     #[tool(
-        description = "Retrieve your assigned task with full feature context. Call this FIRST when starting work. Returns: task details (id, title, scope, status), feature specification (title, details), and session goal. Use this information to understand what to implement before writing any code."
+        description = "Retrieve your assigned task with full feature context. Call this FIRST when starting work. Returns: task details (id, title, scope, status), feature specification (title, details), session goal, blocked_by (prerequisite features, with their state) and related (features in the same area to stay consistent with). Use this information to understand what to implement before writing any code."
     )]
     async fn get_task_context(
         &self,
@@ -87,6 +219,12 @@ impl McpServer {
             .get_feature(session.feature_id)
             .await
             .map_err(Self::client_err)?;
+        let deps = self
+            .client
+            .get_feature_dependencies(feature.id)
+            .await
+            .map_err(Self::client_err)?;
+        let deps = Self::dependencies_response(feature.id, deps);
 
         let context = TaskContextResponse {
             task: TaskInfo {
@@ -98,6 +236,8 @@ impl McpServer {
             },
             feature: ManifestClient::feature_to_info(&feature),
             session_goal: session.goal,
+            blocked_by: deps.blocked_by,
+            related: deps.related,
         };
 
         let json = serde_json::to_string_pretty(&context)
@@ -123,6 +263,7 @@ impl McpServer {
                     status: Some(TaskStatus::Running),
                     worktree_path: None,
                     branch: None,
+                    log_path: None,
                 },
             )
             .await
@@ -150,6 +291,7 @@ impl McpServer {
                     status: Some(TaskStatus::Completed),
                     worktree_path: None,
                     branch: None,
+                    log_path: None,
                 },
             )
             .await
@@ -173,6 +315,7 @@ impl McpServer {
     ) -> Result<CallToolResult, McpError> {
         let req = params.0;
         let feature_id = Self::parse_uuid(&req.feature_id)?;
+        validation::required("goal", &req.goal, MAX_TEXT_LEN)?;
 
         let response = self
             .client
@@ -202,6 +345,8 @@ impl McpServer {
     ) -> Result<CallToolResult, McpError> {
         let req = params.0;
         let session_id = Self::parse_uuid(&req.session_id)?;
+        validation::required("title", &req.title, MAX_TITLE_LEN)?;
+        validation::required("scope", &req.scope, MAX_TEXT_LEN)?;
 
         let agent_type = AgentType::from_str(&req.agent_type).map_err(|_| {
             McpError::invalid_params(
@@ -250,12 +395,16 @@ impl McpServer {
     ) -> Result<CallToolResult, McpError> {
         let req = params.0;
         let feature_id = Self::parse_uuid(&req.feature_id)?;
+        validation::required("goal", &req.goal, MAX_TEXT_LEN)?;
+        validation::max_items("tasks", req.tasks.len(), MAX_ITEMS)?;
 
         // Convert TaskInputItem to CreateTaskInput
         let tasks: Result<Vec<CreateTaskInput>, McpError> = req
             .tasks
             .into_iter()
             .map(|t| {
+                validation::required("title", &t.title, MAX_TITLE_LEN)?;
+                validation::required("scope", &t.scope, MAX_TEXT_LEN)?;
                 let agent_type = AgentType::from_str(&t.agent_type).map_err(|_| {
                     McpError::invalid_params(
                         format!(
@@ -343,6 +492,60 @@ impl McpServer {
         Ok(CallToolResult::success(vec![Content::text(json)]))
     }
 
+    #[tool(
+        description = "Get a rollup of what a session accomplished. Returns the session goal and status, its tasks with statuses, counts of completed and failed tasks, and the summary and deduplicated commits recorded when it completed. Use this when writing PR descriptions or reviewing finished work. Note: tasks are deleted on completion, so completed sessions report through summary and commits."
+    )]
+    async fn get_session_summary(
+        &self,
+        params: Parameters<GetSessionSummaryRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let req = params.0;
+        let session_id = Self::parse_uuid(&req.session_id)?;
+
+        let summary = self
+            .client
+            .get_session_summary(session_id)
+            .await
+            .map_err(Self::client_err)?;
+
+        let result = SessionSummaryResponse {
+            session_id: summary.session.id.to_string(),
+            feature_id: summary.feature.id.to_string(),
+            feature_title: summary.feature.title,
+            goal: summary.session.goal,
+            status: summary.session.status.as_str().to_string(),
+            tasks: summary
+                .tasks
+                .into_iter()
+                .map(|t| TaskInfo {
+                    id: t.id.to_string(),
+                    title: t.title,
+                    scope: t.scope,
+                    status: t.status.as_str().to_string(),
+                    agent_type: t.agent_type.as_str().to_string(),
+                })
+                .collect(),
+            total_tasks: summary.task_counts.total,
+            completed_tasks: summary.task_counts.completed,
+            failed_tasks: summary.task_counts.failed,
+            summary: summary.summary,
+            commits: summary
+                .commits
+                .into_iter()
+                .map(|c| CommitInfo {
+                    sha: c.sha,
+                    message: c.message,
+                    author: c.author,
+                })
+                .collect(),
+        };
+
+        let json = serde_json::to_string_pretty(&result)
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+
+        Ok(CallToolResult::success(vec![Content::text(json)]))
+    }
+
     #[tool(
         description = "Complete a session after all tasks are done. Call this when all tasks are completed to finalize the session. Creates a history entry summarizing the work and optionally marks the feature as 'implemented'. IMPORTANT: By default, this marks the feature as implemented. Set mark_implemented=false if the work is partial. Side effects: creates feature_history entry, deletes task records, updates session status to 'completed', optionally updates feature state to 'implemented'."
     )]
@@ -352,6 +555,13 @@ impl McpServer {
     ) -> Result<CallToolResult, McpError> {
         let req = params.0;
         let session_id = Self::parse_uuid(&req.session_id)?;
+        validation::required("summary", &req.summary, MAX_TEXT_LEN)?;
+        validation::max_items("commits", req.commits.len(), MAX_ITEMS)?;
+        for commit in &req.commits {
+            validation::commit_sha(&commit.sha)?;
+            validation::max_len("commit message", &commit.message, MAX_TEXT_LEN)?;
+            validation::optional("commit author", commit.author.as_deref(), MAX_TITLE_LEN)?;
+        }
 
         // Determine feature state to set
         let feature_state = if req.mark_implemented {
@@ -420,6 +630,7 @@ impl McpServer {
             Some(ref pid) => Some(Self::parse_uuid(pid)?),
             None => None,
         };
+        validation::limit(req.limit)?;
 
         // Get features via HTTP client (always returns summaries)
         let features = self
@@ -449,7 +660,7 @@ impl McpServer {
     }
 
     #[tool(
-        description = "Search features by title or content. Use this to find specific features without listing all of them. Returns summaries ranked by relevance. Use get_feature for full details."
+        description = "Search features by title or content. Use this to find specific features without listing all of them. Every word of the query must appear, whole or as part of a longer word. Returns summaries ranked by relevance, with a snippet of the matching text. Use get_feature for full details."
     )]
     async fn search_features(
         &self,
@@ -462,14 +673,75 @@ impl McpServer {
             Some(ref pid) => Some(Self::parse_uuid(pid)?),
             None => None,
         };
+        validation::required("query", &req.query, MAX_TITLE_LEN)?;
+        validation::limit(req.limit)?;
 
         // Get features via HTTP client
-        let features = self
+        let hits = self
             .client
             .search_features(&req.query, project_id, req.limit)
             .await
             .map_err(Self::client_err)?;
 
+        let result = FeatureSearchResponse {
+            features: hits
+                .into_iter()
+                .map(|hit| FeatureHitInfo {
+                    feature: FeatureSummaryInfo {
+                        id: hit.feature.id.to_string(),
+                        title: hit.feature.title,
+                        state: hit.feature.state.as_str().to_string(),
+                        priority: hit.feature.priority,
+                        parent_id: hit.feature.parent_id.map(|id| id.to_string()),
+                    },
+                    snippet: hit.snippet,
+                })
+                .collect(),
+        };
+
+        let json = serde_json::to_string_pretty(&result)
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+
+        Ok(CallToolResult::success(vec![Content::text(json)]))
+    }
+
+    #[tool(
+        description = "Find features carrying all of the given tags, such as [\"frontend\", \"p0\"]. Tags are matched case-insensitively. Returns summaries (id, title, state, priority, parent_id) ordered by priority. Use get_feature for full details."
+    )]
+    async fn filter_features_by_tag(
+        &self,
+        params: Parameters<FilterFeaturesByTagRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let req = params.0;
+
+        let project_id = match req.project_id {
+            Some(ref pid) => Some(Self::parse_uuid(pid)?),
+            None => None,
+        };
+        validation::max_items("tags", req.tags.len(), MAX_ITEMS)?;
+        if req.tags.iter().all(|tag| tag.trim().is_empty()) {
+            return Err(McpError::invalid_params(
+                "tags must contain at least one tag",
+                None,
+            ));
+        }
+        for tag in &req.tags {
+            validation::max_len("tag", tag, MAX_TITLE_LEN)?;
+            // The API takes several tags as one comma-separated parameter
+            if tag.contains(',') {
+                return Err(McpError::invalid_params(
+                    format!("tag '{}' must not contain a comma", tag),
+                    None,
+                ));
+            }
+        }
+
+        let features = self
+            .client
+            .list_features_by_tags(&req.tags, project_id)
+            .await
+            .map_err(Self::client_err)?;
+
         let result = FeatureListSummaryResponse {
             features: features
                 .into_iter()
@@ -558,6 +830,140 @@ impl McpServer {
         Ok(CallToolResult::success(vec![Content::text(json)]))
     }
 
+    #[tool(
+        description = "Get a project's whole feature tree (id, title, state) with sub-features nested under children. Use this to see what features exist and how they group before choosing one to work on. Pass state_filter to keep only features in that state, plus the parents needed to place them. Use get_feature for full details."
+    )]
+    async fn get_feature_tree(
+        &self,
+        params: Parameters<GetFeatureTreeRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let req = params.0;
+        let project_id = Self::parse_uuid(&req.project_id)?;
+        let state = req
+            .state_filter
+            .as_deref()
+            .map(Self::parse_state)
+            .transpose()?;
+
+        // The tree endpoint returns an empty list for unknown projects.
+        self.client
+            .get_project(project_id)
+            .await
+            .map_err(Self::client_err)?;
+        let tree = self
+            .client
+            .get_feature_tree(project_id)
+            .await
+            .map_err(Self::client_err)?;
+
+        let mut feature_count = 0;
+        let features = tree
+            .into_iter()
+            .filter_map(|node| Self::tree_entry(node, state, &mut feature_count))
+            .collect();
+        let result = FeatureTreeResponse {
+            project_id: project_id.to_string(),
+            features,
+            feature_count,
+        };
+
+        let json = serde_json::to_string_pretty(&result)
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+
+        Ok(CallToolResult::success(vec![Content::text(json)]))
+    }
+
+    #[tool(
+        description = "Get a feature together with its descendant features (id, title, state, details) as nested JSON. Use this when working on a parent feature to see how its sub-features are specified, so the implementation stays consistent with them. Limit the payload with max_depth (default 3) and max_features (default 50); truncated is true when features were left out."
+    )]
+    async fn get_feature_subtree(
+        &self,
+        params: Parameters<GetFeatureSubtreeRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let req = params.0;
+        let feature_id = Self::parse_uuid(&req.feature_id)?;
+        let max_depth = req.max_depth.unwrap_or(DEFAULT_SUBTREE_DEPTH);
+        let max_features = req.max_features.unwrap_or(DEFAULT_SUBTREE_FEATURES).max(1) as usize;
+
+        // Fetch one level more than requested so a cut-off can be reported.
+        let tree = self
+            .client
+            .get_feature_subtree(feature_id, Some(max_depth.saturating_add(1)))
+            .await
+            .map_err(Self::client_err)?;
+
+        let mut budget = max_features;
+        let mut truncated = false;
+        let feature = Self::subtree_node(tree, max_depth, &mut budget, &mut truncated);
+        let result = FeatureSubtreeResponse {
+            feature,
+            feature_count: max_features - budget,
+            truncated,
+        };
+
+        let json = serde_json::to_string_pretty(&result)
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+
+        Ok(CallToolResult::success(vec![Content::text(json)]))
+    }
+
+    #[tool(
+        description = "Get a feature's dependencies. Returns blocked_by (features that must be finished first), blocks (features waiting on this one) and related (features linked with 'relates') as summaries. Check blocked_by before starting a session so prerequisites are implemented first."
+    )]
+    async fn get_feature_dependencies(
+        &self,
+        params: Parameters<GetFeatureDependenciesRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let req = params.0;
+        let feature_id = Self::parse_uuid(&req.feature_id)?;
+
+        let deps = self
+            .client
+            .get_feature_dependencies(feature_id)
+            .await
+            .map_err(Self::client_err)?;
+
+        let json = serde_json::to_string_pretty(&Self::dependencies_response(feature_id, deps))
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+
+        Ok(CallToolResult::success(vec![Content::text(json)]))
+    }
+
+    #[tool(
+        description = "Link a feature to another feature in the same project. By default the feature is blocked by the other ('depends_on'); kind 'blocks' records the reverse, and 'relates' links features in the same area with no order between them. Use this when planning reveals a prerequisite or overlap the feature tree does not capture. Rejects self-links, a second link between the same two features, and dependencies that would create a cycle. Returns the feature's updated dependencies."
+    )]
+    async fn add_feature_dependency(
+        &self,
+        params: Parameters<AddFeatureDependencyRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let req = params.0;
+        let feature_id = Self::parse_uuid(&req.feature_id)?;
+        let depends_on_id = Self::parse_uuid(&req.depends_on_id)?;
+        let kind = match req.kind.as_deref() {
+            Some(kind) => LinkKind::from_str(kind).map_err(|_| {
+                McpError::invalid_params(
+                    format!(
+                        "Invalid kind '{}'. Must be: depends_on, blocks, or relates",
+                        kind
+                    ),
+                    None,
+                )
+            })?,
+            None => LinkKind::DependsOn,
+        };
+
+        let deps = self
+            .client
+            .add_feature_dependency(feature_id, depends_on_id, kind)
+            .await
+            .map_err(Self::client_err)?;
+
+        let json = serde_json::to_string_pretty(&Self::dependencies_response(feature_id, deps))
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+
+        Ok(CallToolResult::success(vec![Content::text(json)]))
+    }
+
     #[tool(
         description = "Get project context for a directory path. Given a directory (e.g., your current working directory), returns the associated project with its instructions and coding guidelines. Use this to understand project conventions before starting work."
     )]
@@ -566,6 +972,7 @@ impl McpServer {
         params: Parameters<GetProjectContextRequest>,
     ) -> Result<CallToolResult, McpError> {
         let req = params.0;
+        validation::directory_path("directory_path", &req.directory_path)?;
 
         let result = self
             .client
@@ -621,7 +1028,88 @@ impl McpServer {
     }
 
     #[tool(
-        description = "Update a feature's state, title, or details. Use this to transition features through their lifecycle (proposed → specified → implemented → deprecated) or to update living documentation when implementation reveals new information. At least one field (state, title, or details) must be provided."
+        description = "Get what the user is currently focused on in the Manifest desktop app. Resolves the project for directory_path (or the working directory), reads the feature selected there, and returns it with its current details, or null with a message when nothing is selected or the selected feature no longer exists. Use this to align with the user's focus without being told a feature ID."
+    )]
+    async fn get_active_context(
+        &self,
+        params: Parameters<GetActiveContextRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let req = params.0;
+        let (path, context) = match (req.directory_path, &self.context_watch) {
+            (Some(path), _) => {
+                validation::directory_path("directory_path", &path)?;
+                let path = self.find_context_file(std::path::Path::new(&path)).await;
+                let context = path.as_deref().and_then(context_watch::read_context);
+                (path, context)
+            }
+            (None, Some(watch)) => (Some(watch.path().to_path_buf()), watch.current()),
+            (None, None) => {
+                let dir = std::env::current_dir().map_err(|e| {
+                    McpError::internal_error(
+                        format!("Could not determine current directory: {}", e),
+                        None,
+                    )
+                })?;
+                let path = self.find_context_file(&dir).await;
+                let context = path.as_deref().and_then(context_watch::read_context);
+                (path, context)
+            }
+        };
+
+        let empty = |context_file: Option<String>, message: &str| ActiveContextResponse {
+            active_feature: None,
+            context_file,
+            selected_at: None,
+            message: Some(message.to_string()),
+        };
+
+        let result = match path.filter(|p| p.is_file()) {
+            None => empty(
+                None,
+                "No feature is currently selected in the Manifest app for this project",
+            ),
+            Some(path) => {
+                let file = Some(path.display().to_string());
+                let feature_id = context
+                    .as_ref()
+                    .and_then(|c| c.get("feature_id"))
+                    .and_then(|id| id.as_str())
+                    .and_then(|id| Uuid::parse_str(id).ok());
+
+                match feature_id {
+                    None => empty(
+                        file,
+                        "The context file could not be read; reselect the feature in the Manifest app",
+                    ),
+                    Some(feature_id) => match self.client.get_feature(feature_id).await {
+                        Ok(feature) => ActiveContextResponse {
+                            active_feature: Some(ManifestClient::feature_to_info(&feature)),
+                            context_file: file,
+                            selected_at: context
+                                .as_ref()
+                                .and_then(|c| c.get("updated_at"))
+                                .and_then(|t| t.as_str())
+                                .map(str::to_string),
+                            message: None,
+                        },
+                        Err(ClientError::NotFound(_)) => empty(
+                            file,
+                            "The selected feature no longer exists",
+                        ),
+                        Err(e) => return Err(Self::client_err(e)),
+                    },
+                }
+            }
+        };
+
+        let json = serde_json::to_string_pretty(&result)
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+
+        Ok(CallToolResult::success(vec![Content::text(json)]))
+    }
+
+    #[tool(
+        description = "Update a feature's state, title, or details. Use this to transition features through their lifecycle (proposed → specified → implemented → deprecated) or to update living documentation when implementation reveals new information. At least one field (state, title, or details) must be provided. Any state can follow any other, and every state change is recorded in the feature's history."
     )]
     async fn update_feature_state(
         &self,
@@ -637,22 +1125,13 @@ impl McpServer {
                 None,
             ));
         }
+        if let Some(title) = &req.title {
+            validation::required("title", title, MAX_TITLE_LEN)?;
+        }
+        validation::optional("details", req.details.as_deref(), MAX_TEXT_LEN)?;
 
         // Parse state if provided
-        let new_state = req
-            .state
-            .map(|s| {
-                FeatureState::from_str(&s).map_err(|_| {
-                    McpError::invalid_params(
-                        format!(
-                            "Invalid state '{}'. Must be: proposed, specified, implemented, or deprecated",
-                            s
-                        ),
-                        None,
-                    )
-                })
-            })
-            .transpose()?;
+        let new_state = req.state.as_deref().map(Self::parse_state).transpose()?;
 
         let feature = self
             .client
@@ -665,6 +1144,7 @@ impl McpServer {
                     desired_details: None,
                     state: new_state,
                     priority: None,
+                    expected_updated_at: None,
                 },
             )
             .await
@@ -678,6 +1158,79 @@ impl McpServer {
         Ok(CallToolResult::success(vec![Content::text(json)]))
     }
 
+    #[tool(
+        description = "Set a feature's priority. Features are listed and implemented in priority order, lowest first, with title breaking ties among siblings. Returns the updated feature."
+    )]
+    async fn reprioritize_feature(
+        &self,
+        params: Parameters<ReprioritizeFeatureRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let req = params.0;
+        let feature_id = Self::parse_uuid(&req.feature_id)?;
+
+        let feature = self
+            .client
+            .update_feature(
+                feature_id,
+                &UpdateFeatureInput {
+                    parent_id: None,
+                    title: None,
+                    details: None,
+                    desired_details: None,
+                    state: None,
+                    priority: Some(req.priority),
+                    expected_updated_at: None,
+                },
+            )
+            .await
+            .map_err(Self::client_err)?;
+
+        let json = serde_json::to_string_pretty(&ManifestClient::feature_to_info(&feature))
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+
+        Ok(CallToolResult::success(vec![Content::text(json)]))
+    }
+
+    #[tool(
+        description = "Put the children of one feature, or a project's root features, in implementation order in one call. The listed features get priorities 0, 1, 2... in the order given; siblings left out follow them in their existing order. Returns summaries of the reordered features."
+    )]
+    async fn reorder_features(
+        &self,
+        params: Parameters<ReorderFeaturesRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let req = params.0;
+        validation::max_items("feature_ids", req.feature_ids.len(), MAX_ITEMS)?;
+        let feature_ids = req
+            .feature_ids
+            .iter()
+            .map(|id| Self::parse_uuid(id))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let features = self
+            .client
+            .reorder_features(&feature_ids)
+            .await
+            .map_err(Self::client_err)?;
+
+        let result = FeatureListSummaryResponse {
+            features: features
+                .into_iter()
+                .map(|f| FeatureSummaryInfo {
+                    id: f.id.to_string(),
+                    title: f.title,
+                    state: f.state.as_str().to_string(),
+                    priority: f.priority,
+                    parent_id: f.parent_id.map(|id| id.to_string()),
+                })
+                .collect(),
+        };
+
+        let json = serde_json::to_string_pretty(&result)
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+
+        Ok(CallToolResult::success(vec![Content::text(json)]))
+    }
+
     // ============================================================
     // Setup Tools - Create projects, directories, and features
     // ============================================================
@@ -690,6 +1243,9 @@ impl McpServer {
         params: Parameters<CreateProjectRequest>,
     ) -> Result<CallToolResult, McpError> {
         let req = params.0;
+        validation::required("name", &req.name, MAX_TITLE_LEN)?;
+        validation::optional("description", req.description.as_deref(), MAX_TEXT_LEN)?;
+        validation::optional("instructions", req.instructions.as_deref(), MAX_TEXT_LEN)?;
 
         let project = self
             .client
@@ -723,6 +1279,9 @@ impl McpServer {
     ) -> Result<CallToolResult, McpError> {
         let req = params.0;
         let project_id = Self::parse_uuid(&req.project_id)?;
+        validation::directory_path("path", &req.path)?;
+        validation::optional("git_remote", req.git_remote.as_deref(), MAX_PATH_LEN)?;
+        validation::optional("instructions", req.instructions.as_deref(), MAX_TEXT_LEN)?;
 
         let directory = self
             .client
@@ -765,6 +1324,8 @@ impl McpServer {
             Some(pid) => Some(Self::parse_uuid(&pid)?),
             None => None,
         };
+        validation::required("title", &req.title, MAX_TITLE_LEN)?;
+        validation::optional("details", req.details.as_deref(), MAX_TEXT_LEN)?;
         let state = FeatureState::from_str(&req.state).map_err(|_| {
             McpError::invalid_params(
                 format!(
@@ -787,6 +1348,7 @@ impl McpServer {
                     state: Some(state),
                     priority: req.priority,
                 },
+                req.template.as_deref(),
             )
             .await
             .map_err(Self::client_err)?;
@@ -808,6 +1370,7 @@ impl McpServer {
     ) -> Result<CallToolResult, McpError> {
         let req = params.0;
         let project_id = Self::parse_uuid(&req.project_id)?;
+        validation::planned_features(&req.features)?;
 
         // Use HTTP client to bulk create features
         let response = self
@@ -836,6 +1399,8 @@ impl ServerHandler for McpServer {
             },
             capabilities: rmcp::model::ServerCapabilities::builder()
                 .enable_tools()
+                .enable_resources()
+                .enable_prompts()
                 .build(),
             instructions: Some(
                 r#"Manifest manages feature implementation sessions and tasks.
@@ -868,7 +1433,7 @@ FEATURE NAMING:
 - Name by user capability: "Add Todo", "Filter by Status", "Export Report"
 - Use nouns or short verb phrases: "Router", "Request Validation", "JSON Output"
 - Parent features group related capabilities: "Authentication" contains "Password Login", "OAuth"
-- Use priority field for sequencing, not the title
+- Use priority field for sequencing, not the title; reorder_features resequences siblings later
 
 FEATURE HIERARCHY:
 - Group features by user goal or domain area
@@ -926,7 +1491,11 @@ SETUP (one-time when starting a new project):
 DISCOVERY (find what to work on):
 - get_project_context: Given your CWD, find the project and its instructions
 - list_features: Browse features, filter by project_id or state
+- get_feature_tree: See a project's features nested under their parents
 - get_feature: Get full details of a feature before starting work
+- Resources: read manifest://feature/{id} for a feature as Markdown, or
+  manifest://project/{id}/features for a project's feature tree
+- Prompts: implement-feature and review-feature brief you on a feature_id
 
 AGENT WORKFLOW (when assigned a task_id):
 1. Call get_task_context with your task_id to understand your assignment
@@ -979,6 +1548,7 @@ ORCHESTRATOR WORKFLOW (when managing a feature):
 5. Spawn agents with their task_ids
 6. Call list_session_tasks to monitor progress
 7. Call complete_session when all tasks are done
+8. Call get_session_summary for a rollup of what the session accomplished
 
 IMPORTANT:
 - Read feature details carefully before coding
@@ -989,14 +1559,138 @@ IMPORTANT:
             ..Default::default()
         }
     }
+
+    /// One feature list per project. Single features are reached through
+    /// the `manifest://feature/{id}` template rather than listed.
+    async fn list_resources(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListResourcesResult, McpError> {
+        let projects = self
+            .client
+            .list_projects()
+            .await
+            .map_err(Self::client_err)?;
+        Ok(ListResourcesResult::with_all_items(
+            projects.iter().map(resources::project_resource).collect(),
+        ))
+    }
+
+    async fn list_prompts(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListPromptsResult, McpError> {
+        Ok(ListPromptsResult::with_all_items(
+            WorkflowPrompt::ALL.map(WorkflowPrompt::prompt).to_vec(),
+        ))
+    }
+
+    async fn get_prompt(
+        &self,
+        request: GetPromptRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<GetPromptResult, McpError> {
+        let prompt = WorkflowPrompt::from_name(&request.name).ok_or_else(|| {
+            McpError::invalid_params(format!("Unknown prompt: {}", request.name), None)
+        })?;
+        let feature_id = request
+            .arguments
+            .as_ref()
+            .and_then(|arguments| arguments.get("feature_id"))
+            .and_then(|value| value.as_str())
+            .ok_or_else(|| McpError::invalid_params("feature_id is required", None))?;
+        let feature_id = Self::parse_uuid(feature_id)?;
+
+        let markdown = self
+            .client
+            .get_feature_markdown(feature_id, true)
+            .await
+            .map_err(Self::client_err)?;
+
+        Ok(GetPromptResult {
+            description: Some(prompt.description().to_string()),
+            messages: vec![PromptMessage::new_text(
+                PromptMessageRole::User,
+                prompt.text(&markdown),
+            )],
+        })
+    }
+
+    async fn list_resource_templates(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListResourceTemplatesResult, McpError> {
+        Ok(ListResourceTemplatesResult::with_all_items(
+            resources::templates(),
+        ))
+    }
+
+    async fn read_resource(
+        &self,
+        request: ReadResourceRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ReadResourceResult, McpError> {
+        let not_found = |e: ClientError| match e {
+            ClientError::NotFound(msg) => McpError::resource_not_found(msg, None),
+            e => Self::client_err(e),
+        };
+        let text = match ResourceUri::parse(&request.uri) {
+            Some(ResourceUri::Feature(id)) => self
+                .client
+                .get_feature_markdown(id, false)
+                .await
+                .map_err(not_found)?,
+            Some(ResourceUri::ProjectFeatures(id)) => {
+                let project = self.client.get_project(id).await.map_err(not_found)?;
+                let tree = self.client.get_feature_tree(id).await.map_err(not_found)?;
+                resources::feature_list(&project.project.name, &tree)
+            }
+            None => {
+                return Err(McpError::resource_not_found(
+                    format!("Unknown resource URI: {}", request.uri),
+                    None,
+                ))
+            }
+        };
+
+        Ok(ReadResourceResult {
+            contents: vec![ResourceContents::TextResourceContents {
+                uri: request.uri,
+                mime_type: Some(resources::MIME_TYPE.to_string()),
+                text,
+                meta: None,
+            }],
+        })
+    }
+}
+
+/// Watch the context file for the working directory: the one found the same
+/// way `get_active_context` looks, or `.manifest/active_context.json` in the
+/// working directory if the app has not written one yet.
+async fn watch_working_directory(client: &ManifestClient) -> anyhow::Result<ContextWatch> {
+    let dir = std::env::current_dir()?;
+    let probe = McpServer::new(client.clone());
+    let path = match probe.find_context_file(&dir).await {
+        Some(path) => path,
+        None => dir.join(".manifest").join("active_context.json"),
+    };
+    tracing::info!("Watching active context at {}", path.display());
+    Ok(ContextWatch::spawn(path)?)
 }
 
-pub async fn run_stdio_server() -> anyhow::Result<()> {
+pub async fn run_stdio_server(watch_context: bool) -> anyhow::Result<()> {
     use tokio::io::{stdin, stdout};
 
     tracing::info!("Starting MCP server via stdio");
 
-    let service = McpServer::from_env();
+    let client = ManifestClient::from_env();
+    let mut service = McpServer::new(client.clone());
+    if watch_context {
+        service = service.with_context_watch(watch_working_directory(&client).await?);
+    }
     let server = service.serve((stdin(), stdout())).await?;
 
     let quit_reason = server.waiting().await?;
@@ -1004,3 +1698,71 @@ pub async fn run_stdio_server() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+/// Serve MCP over streamable HTTP (SSE) at `/mcp`, so remote clients or
+/// several local ones can share one server. Each client session gets its own
+/// [`McpServer`]; all of them talk to the same Manifest API.
+pub async fn run_http_server(
+    addr: std::net::SocketAddr,
+    watch_context: bool,
+) -> anyhow::Result<()> {
+    use rmcp::transport::streamable_http_server::{
+        session::local::LocalSessionManager, StreamableHttpServerConfig, StreamableHttpService,
+    };
+
+    // Tool calls reach the API with this server's key, so off loopback
+    // the endpoint takes the same bearer token as the API itself
+    let security = if addr.ip().is_loopback() {
+        None
+    } else {
+        let security = crate::api::SecurityConfig::from_env();
+        if security.api_key.is_none() {
+            anyhow::bail!(
+                "refusing to serve MCP on {} without MANIFEST_API_KEY; set it or bind to a loopback address",
+                addr
+            );
+        }
+        Some(security)
+    };
+
+    let client = ManifestClient::from_env();
+    let watch = if watch_context {
+        Some(watch_working_directory(&client).await?)
+    } else {
+        None
+    };
+    let config = StreamableHttpServerConfig::default();
+    let shutdown = config.cancellation_token.clone();
+    let service = StreamableHttpService::new(
+        move || {
+            let server = McpServer::new(client.clone());
+            Ok(match &watch {
+                Some(watch) => server.with_context_watch(watch.clone()),
+                None => server,
+            })
+        },
+        LocalSessionManager::default().into(),
+        config,
+    );
+
+    let app = axum::Router::new().nest_service("/mcp", service);
+    let app = match security {
+        Some(security) => app.layer(axum::middleware::from_fn_with_state(
+            security,
+            crate::api::auth_middleware,
+        )),
+        None => app,
+    };
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    tracing::info!("MCP server listening on http://{}/mcp", addr);
+
+    axum::serve(listener, app)
+        .with_graceful_shutdown(async move {
+            let _ = tokio::signal::ctrl_c().await;
+            shutdown.cancel();
+        })
+        .await?;
+
+    tracing::info!("MCP server stopped");
+    Ok(())
+}
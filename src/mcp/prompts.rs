@@ -0,0 +1,99 @@
+//! MCP prompts: standard ways to start work on a feature.
+//!
+//! Each prompt takes a `feature_id` and expands into a user message holding
+//! the feature as Markdown (title, state, details and the latest session's
+//! tasks) followed by the steps for that workflow, so every agent is briefed
+//! the same way.
+
+use rmcp::model::{Prompt, PromptArgument};
+
+/// A workflow prompt offered by the server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorkflowPrompt {
+    Implement,
+    Review,
+}
+
+impl WorkflowPrompt {
+    pub const ALL: [Self; 2] = [Self::Implement, Self::Review];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|prompt| prompt.name() == name)
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Implement => "implement-feature",
+            Self::Review => "review-feature",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Self::Implement => {
+                "Implement a feature from its specification, tracking the work as a session"
+            }
+            Self::Review => "Review the code implementing a feature against its specification",
+        }
+    }
+
+    /// The prompt as listed, with its single `feature_id` argument.
+    pub fn prompt(self) -> Prompt {
+        Prompt::new(
+            self.name(),
+            Some(self.description()),
+            Some(vec![PromptArgument {
+                name: "feature_id".to_string(),
+                title: None,
+                description: Some("UUID of the feature".to_string()),
+                required: Some(true),
+            }]),
+        )
+    }
+
+    /// The message text for a feature rendered as `feature_markdown`.
+    pub fn text(self, feature_markdown: &str) -> String {
+        let (intro, steps) = match self {
+            Self::Implement => (
+                "Implement the Manifest feature below.",
+                "\
+1. Read the details closely; they are the specification and acceptance criteria.
+2. Call get_feature_dependencies and make sure everything in blocked_by is implemented.
+3. If there are no open tasks above, call breakdown_feature with a goal and tasks.
+4. Implement each task, running the tests as you go.
+5. Call complete_session with a summary and commits when every task is verified.
+6. If the implementation changed the behavior described, update the details with update_feature_state.",
+            ),
+            Self::Review => (
+                "Review the code that implements the Manifest feature below.",
+                "\
+1. Check each requirement and acceptance criterion in the details against the code.
+2. Check that tests cover the behavior the details describe.
+3. List what is missing, what differs from the details, and any bugs, with file and line.
+4. Say whether the details need updating to match behavior that is correct but undocumented.",
+            ),
+        };
+        format!(
+            "{}\n\n---\n\n{}\n---\n\n{}\n",
+            intro,
+            feature_markdown.trim_end(),
+            steps
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prompts_are_found_by_name_and_wrap_the_feature() {
+        for prompt in WorkflowPrompt::ALL {
+            assert_eq!(WorkflowPrompt::from_name(prompt.name()), Some(prompt));
+            assert!(prompt
+                .text("# Exports\n\nCSV and JSON\n")
+                .contains("---\n\n# Exports\n\nCSV and JSON\n---"));
+        }
+        assert_eq!(WorkflowPrompt::from_name("unknown"), None);
+    }
+}
@@ -0,0 +1,125 @@
+//! MCP resources: features addressable by URI.
+//!
+//! `manifest://feature/{id}` reads a feature as Markdown, and
+//! `manifest://project/{id}/features` reads a project's feature tree as a
+//! nested list linking to each feature's URI. Clients can attach these as
+//! context directly instead of calling `get_feature` or `list_features`.
+
+use std::fmt;
+
+use rmcp::model::{AnnotateAble, RawResource, RawResourceTemplate, Resource, ResourceTemplate};
+use uuid::Uuid;
+
+use crate::models::{FeatureTreeNode, Project};
+
+/// MIME type of every resource's contents.
+pub const MIME_TYPE: &str = "text/markdown";
+
+/// A parsed resource URI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResourceUri {
+    Feature(Uuid),
+    ProjectFeatures(Uuid),
+}
+
+impl ResourceUri {
+    /// Parse a `manifest://` URI; anything else is `None`.
+    pub fn parse(uri: &str) -> Option<Self> {
+        let path = uri.strip_prefix("manifest://")?;
+        if let Some(id) = path.strip_prefix("feature/") {
+            return Uuid::parse_str(id).ok().map(Self::Feature);
+        }
+        let id = path.strip_prefix("project/")?.strip_suffix("/features")?;
+        Uuid::parse_str(id).ok().map(Self::ProjectFeatures)
+    }
+}
+
+impl fmt::Display for ResourceUri {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Feature(id) => write!(f, "manifest://feature/{}", id),
+            Self::ProjectFeatures(id) => write!(f, "manifest://project/{}/features", id),
+        }
+    }
+}
+
+/// The feature list resource of `project`.
+pub fn project_resource(project: &Project) -> Resource {
+    let mut resource = RawResource::new(
+        ResourceUri::ProjectFeatures(project.id).to_string(),
+        format!("{} features", project.name),
+    );
+    resource.description = Some(format!("Feature tree of the {} project", project.name));
+    resource.mime_type = Some(MIME_TYPE.to_string());
+    resource.no_annotation()
+}
+
+/// Templates for reading any feature or project by id.
+pub fn templates() -> Vec<ResourceTemplate> {
+    [
+        (
+            "manifest://feature/{id}",
+            "feature",
+            "A feature's title, state, priority, tags and details",
+        ),
+        (
+            "manifest://project/{id}/features",
+            "project-features",
+            "A project's features, nested under their parents",
+        ),
+    ]
+    .into_iter()
+    .map(|(uri_template, name, description)| {
+        RawResourceTemplate {
+            uri_template: uri_template.to_string(),
+            name: name.to_string(),
+            title: None,
+            description: Some(description.to_string()),
+            mime_type: Some(MIME_TYPE.to_string()),
+        }
+        .no_annotation()
+    })
+    .collect()
+}
+
+/// Render a project's feature tree as a nested Markdown list.
+pub fn feature_list(project_name: &str, tree: &[FeatureTreeNode]) -> String {
+    let mut markdown = format!("# {} features\n\n", project_name);
+    if tree.is_empty() {
+        markdown.push_str("No features yet.\n");
+    }
+    push_features(&mut markdown, tree, 0);
+    markdown
+}
+
+fn push_features(markdown: &mut String, nodes: &[FeatureTreeNode], depth: usize) {
+    for node in nodes {
+        markdown.push_str(&format!(
+            "{}- [{}]({}) ({})\n",
+            "  ".repeat(depth),
+            node.feature.title,
+            ResourceUri::Feature(node.feature.id),
+            node.feature.state.as_str()
+        ));
+        push_features(markdown, &node.children, depth + 1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_the_uris_it_formats() {
+        let id = Uuid::new_v4();
+        for uri in [ResourceUri::Feature(id), ResourceUri::ProjectFeatures(id)] {
+            assert_eq!(ResourceUri::parse(&uri.to_string()), Some(uri));
+        }
+        assert_eq!(ResourceUri::parse("manifest://feature/not-a-uuid"), None);
+        assert_eq!(
+            ResourceUri::parse(&format!("manifest://project/{}", id)),
+            None
+        );
+        assert_eq!(ResourceUri::parse(&format!("file://feature/{}", id)), None);
+    }
+}
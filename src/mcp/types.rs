@@ -59,6 +59,12 @@ pub struct ListSessionTasksRequest {
     pub session_id: String,
 }
 
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct GetSessionSummaryRequest {
+    #[schemars(description = "The UUID of the session to summarize")]
+    pub session_id: String,
+}
+
 #[derive(Debug, Deserialize, JsonSchema)]
 pub struct CompleteSessionRequest {
     #[schemars(description = "The UUID of the session to complete")]
@@ -107,6 +113,14 @@ pub struct ListFeaturesRequest {
     pub offset: Option<u32>,
 }
 
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct FilterFeaturesByTagRequest {
+    #[schemars(description = "Tags the features must all carry, e.g. [\"frontend\", \"p0\"]")]
+    pub tags: Vec<String>,
+    #[schemars(description = "Optional project UUID to limit the results to")]
+    pub project_id: Option<String>,
+}
+
 #[derive(Debug, Deserialize, JsonSchema)]
 pub struct SearchFeaturesRequest {
     #[schemars(description = "Search term to match against title and details")]
@@ -129,6 +143,51 @@ pub struct GetFeatureHistoryRequest {
     pub feature_id: String,
 }
 
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct GetFeatureTreeRequest {
+    #[schemars(description = "The UUID of the project")]
+    pub project_id: String,
+    #[schemars(
+        description = "Optional state filter: 'proposed', 'specified', 'implemented', or 'deprecated'. Parents of matching features are kept so the nesting stays intact."
+    )]
+    pub state_filter: Option<String>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct GetFeatureSubtreeRequest {
+    #[schemars(description = "The UUID of the feature at the root of the subtree")]
+    pub feature_id: String,
+    #[schemars(
+        description = "Levels of descendants to include (0 = the feature alone). Defaults to 3."
+    )]
+    pub max_depth: Option<u32>,
+    #[schemars(
+        description = "Maximum number of features to return, counted depth-first from the root. Defaults to 50."
+    )]
+    pub max_features: Option<u32>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct GetFeatureDependenciesRequest {
+    #[schemars(description = "The UUID of the feature to get dependencies for")]
+    pub feature_id: String,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct AddFeatureDependencyRequest {
+    #[schemars(description = "The UUID of the feature to link from")]
+    pub feature_id: String,
+    #[schemars(
+        description = "The UUID of the other feature: the one that must be finished first for 'depends_on', the one waiting for 'blocks'"
+    )]
+    pub depends_on_id: String,
+    #[schemars(
+        description = "Optional link kind: 'depends_on' (default), 'blocks', or 'relates' for features in the same area with no order between them"
+    )]
+    #[serde(default)]
+    pub kind: Option<String>,
+}
+
 #[derive(Debug, Deserialize, JsonSchema)]
 pub struct GetProjectContextRequest {
     #[schemars(
@@ -143,6 +202,15 @@ pub struct GetProjectContextRequest {
 #[derive(Debug, Deserialize, JsonSchema)]
 pub struct GetActiveFeatureRequest {}
 
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct GetActiveContextRequest {
+    #[schemars(
+        description = "Directory to resolve the project from (e.g., current working directory). Defaults to the MCP server's working directory."
+    )]
+    #[serde(default)]
+    pub directory_path: Option<String>,
+}
+
 #[derive(Debug, Deserialize, JsonSchema)]
 pub struct UpdateFeatureStateRequest {
     #[schemars(description = "The UUID of the feature to update")]
@@ -162,6 +230,24 @@ pub struct UpdateFeatureStateRequest {
     pub details: Option<String>,
 }
 
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ReprioritizeFeatureRequest {
+    #[schemars(description = "The UUID of the feature to reprioritize")]
+    pub feature_id: String,
+    #[schemars(
+        description = "The new priority. Lower numbers are implemented first; siblings with equal priority sort by title."
+    )]
+    pub priority: i32,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ReorderFeaturesRequest {
+    #[schemars(
+        description = "UUIDs of features sharing a parent, in the order they should be implemented. They get priorities 0, 1, 2...; siblings left out keep theirs."
+    )]
+    pub feature_ids: Vec<String>,
+}
+
 #[derive(Debug, Deserialize, JsonSchema)]
 pub struct CreateProjectRequest {
     #[schemars(description = "The project name (e.g., 'RocketShip', 'MyApp')")]
@@ -201,7 +287,9 @@ pub struct AddProjectDirectoryRequest {
 pub struct CreateFeatureRequest {
     #[schemars(description = "The UUID of the project this feature belongs to")]
     pub project_id: String,
-    #[schemars(description = "Optional parent feature UUID for hierarchical features")]
+    #[schemars(
+        description = "Optional parent feature UUID for hierarchical features. The parent must be in the same project."
+    )]
     #[serde(default)]
     pub parent_id: Option<String>,
     #[schemars(description = "Short title for the feature (e.g., 'User Authentication')")]
@@ -221,6 +309,11 @@ pub struct CreateFeatureRequest {
     )]
     #[serde(default)]
     pub priority: Option<i32>,
+    #[schemars(
+        description = "Optional template to pre-fill details: 'user_story', 'bug_fix', 'technical_spec', or a project template from .manifest/templates/"
+    )]
+    #[serde(default)]
+    pub template: Option<String>,
 }
 
 fn default_proposed() -> String {
@@ -254,6 +347,12 @@ pub struct TaskContextResponse {
     pub feature: FeatureInfo,
     /// The session goal describing the overall objective
     pub session_goal: String,
+    /// Features that must be implemented before this one. Check their state
+    /// before starting; build on them rather than around them.
+    pub blocked_by: Vec<FeatureSummaryInfo>,
+    /// Features in the same area, in no particular order. Keep them
+    /// consistent with this one.
+    pub related: Vec<FeatureSummaryInfo>,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -292,6 +391,23 @@ pub struct TaskListResponse {
     pub tasks: Vec<TaskInfo>,
 }
 
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct SessionSummaryResponse {
+    pub session_id: String,
+    pub feature_id: String,
+    pub feature_title: String,
+    pub goal: String,
+    pub status: String,
+    pub tasks: Vec<TaskInfo>,
+    pub total_tasks: usize,
+    pub completed_tasks: usize,
+    pub failed_tasks: usize,
+    /// Summary recorded when the session completed; absent while active.
+    pub summary: Option<String>,
+    /// Commits recorded for the session, deduplicated by SHA.
+    pub commits: Vec<CommitInfo>,
+}
+
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct CompleteSessionResponse {
     pub session_id: String,
@@ -338,12 +454,91 @@ pub struct FeatureSummaryInfo {
     pub parent_id: Option<String>,
 }
 
+/// A feature in a subtree, with its descendants nested under `children`.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct FeatureSubtreeNode {
+    pub id: String,
+    pub title: String,
+    pub state: String,
+    pub details: Option<String>,
+    pub children: Vec<FeatureSubtreeNode>,
+}
+
+/// A feature in a project tree, without details.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct FeatureTreeEntry {
+    pub id: String,
+    pub title: String,
+    pub state: String,
+    pub children: Vec<FeatureTreeEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct FeatureTreeResponse {
+    pub project_id: String,
+    /// Root features, with descendants nested under `children`.
+    pub features: Vec<FeatureTreeEntry>,
+    /// Number of features in the response at every level.
+    pub feature_count: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct FeatureSubtreeResponse {
+    pub feature: FeatureSubtreeNode,
+    /// Number of features in the response, including the root.
+    pub feature_count: usize,
+    /// True if features were left out because of `max_depth` or `max_features`.
+    pub truncated: bool,
+}
+
+/// A feature's dependencies in both directions.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct FeatureDependenciesResponse {
+    pub feature_id: String,
+    /// Features that must be finished before this one.
+    pub blocked_by: Vec<FeatureSummaryInfo>,
+    /// Features waiting on this one.
+    pub blocks: Vec<FeatureSummaryInfo>,
+    /// Features linked with 'relates', in no particular order.
+    pub related: Vec<FeatureSummaryInfo>,
+}
+
 /// Response for list_features in summary mode (default).
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct FeatureListSummaryResponse {
     pub features: Vec<FeatureSummaryInfo>,
 }
 
+/// A search_features result: the feature summary plus matching text.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct FeatureHitInfo {
+    #[serde(flatten)]
+    pub feature: FeatureSummaryInfo,
+    /// Text around the best match, with matches wrapped in `**`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub snippet: Option<String>,
+}
+
+/// Response for search_features, best match first.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct FeatureSearchResponse {
+    pub features: Vec<FeatureHitInfo>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ActiveContextResponse {
+    /// The feature selected in the desktop app, with its current details.
+    /// Null when nothing is selected or the selection is stale.
+    pub active_feature: Option<FeatureInfo>,
+    /// Context file the selection was read from.
+    pub context_file: Option<String>,
+    /// When the desktop app last wrote the selection.
+    pub selected_at: Option<String>,
+    /// Why `active_feature` is null.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct ProjectContextResponse {
     pub project: ProjectInfo,
@@ -0,0 +1,144 @@
+//! Input checks for MCP tool arguments.
+//!
+//! Tool arguments come from agents, so they are checked before any request
+//! reaches the API: text fields are capped so a runaway call cannot bloat the
+//! database, and each rejection names the field so the agent can correct it.
+
+use rmcp::ErrorData as McpError;
+
+use super::types::ProposedFeature;
+
+/// Maximum length in bytes of titles, names and search queries.
+pub const MAX_TITLE_LEN: usize = 500;
+/// Maximum length in bytes of long-form text such as details, scopes and summaries.
+pub const MAX_TEXT_LEN: usize = 64 * 1024;
+/// Maximum length in bytes of a directory path.
+pub const MAX_PATH_LEN: usize = 4096;
+/// Maximum number of tasks or commits in a single call.
+pub const MAX_ITEMS: usize = 100;
+/// Maximum number of features, counting descendants, in one `plan_features` call.
+pub const MAX_PLANNED_FEATURES: usize = 500;
+/// Maximum `limit` accepted by the listing tools.
+pub const MAX_LIST_LIMIT: u32 = 500;
+
+fn invalid(message: String) -> McpError {
+    McpError::invalid_params(message, None)
+}
+
+/// Reject a value longer than `max` bytes.
+pub fn max_len(field: &str, value: &str, max: usize) -> Result<(), McpError> {
+    if value.len() > max {
+        return Err(invalid(format!(
+            "{} is too long ({} bytes, maximum {})",
+            field,
+            value.len(),
+            max
+        )));
+    }
+    Ok(())
+}
+
+/// Reject a blank value or one longer than `max` bytes.
+pub fn required(field: &str, value: &str, max: usize) -> Result<(), McpError> {
+    if value.trim().is_empty() {
+        return Err(invalid(format!("{} must not be empty", field)));
+    }
+    max_len(field, value, max)
+}
+
+/// Like [`max_len`], for a field that may be omitted.
+pub fn optional(field: &str, value: Option<&str>, max: usize) -> Result<(), McpError> {
+    value.map_or(Ok(()), |value| max_len(field, value, max))
+}
+
+/// Reject more than `max` items in a list argument.
+pub fn max_items(field: &str, count: usize, max: usize) -> Result<(), McpError> {
+    if count > max {
+        return Err(invalid(format!(
+            "{} has too many entries ({}, maximum {})",
+            field, count, max
+        )));
+    }
+    Ok(())
+}
+
+/// Require a `limit` between 1 and [`MAX_LIST_LIMIT`] when one is given.
+pub fn limit(value: Option<u32>) -> Result<(), McpError> {
+    match value {
+        Some(limit) if limit == 0 || limit > MAX_LIST_LIMIT => Err(invalid(format!(
+            "limit must be between 1 and {}",
+            MAX_LIST_LIMIT
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Require an absolute directory path without `..` segments or NUL bytes.
+pub fn directory_path(field: &str, value: &str) -> Result<(), McpError> {
+    required(field, value, MAX_PATH_LEN)?;
+    if value.contains('\0') {
+        return Err(invalid(format!("{} must not contain NUL bytes", field)));
+    }
+    let path = std::path::Path::new(value);
+    if !path.is_absolute() {
+        return Err(invalid(format!(
+            "{} must be an absolute path, got '{}'",
+            field, value
+        )));
+    }
+    if path
+        .components()
+        .any(|c| matches!(c, std::path::Component::ParentDir))
+    {
+        return Err(invalid(format!("{} must not contain '..' segments", field)));
+    }
+    Ok(())
+}
+
+/// Require an abbreviated or full git commit hash.
+pub fn commit_sha(value: &str) -> Result<(), McpError> {
+    let valid = (4..=64).contains(&value.len()) && value.chars().all(|c| c.is_ascii_hexdigit());
+    if !valid {
+        return Err(invalid(format!(
+            "Invalid commit sha '{}'. Must be 4-64 hex characters",
+            value
+        )));
+    }
+    Ok(())
+}
+
+/// Check every title and details in a feature plan, and its total size.
+pub fn planned_features(features: &[ProposedFeature]) -> Result<(), McpError> {
+    fn visit(features: &[ProposedFeature], count: &mut usize) -> Result<(), McpError> {
+        for feature in features {
+            *count += 1;
+            max_items("features", *count, MAX_PLANNED_FEATURES)?;
+            required("title", &feature.title, MAX_TITLE_LEN)?;
+            optional("details", feature.details.as_deref(), MAX_TEXT_LEN)?;
+            visit(&feature.children, count)?;
+        }
+        Ok(())
+    }
+    visit(features, &mut 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_relative_and_parent_paths() {
+        assert!(directory_path("path", "/home/me/project").is_ok());
+        assert!(directory_path("path", "project").is_err());
+        assert!(directory_path("path", "/home/me/../other").is_err());
+        assert!(directory_path("path", "").is_err());
+    }
+
+    #[test]
+    fn accepts_short_and_full_commit_hashes() {
+        assert!(commit_sha("abc1234").is_ok());
+        assert!(commit_sha(&"a".repeat(40)).is_ok());
+        assert!(commit_sha("abc").is_err());
+        assert!(commit_sha("not-a-sha").is_err());
+    }
+}
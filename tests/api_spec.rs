@@ -23,6 +23,21 @@ async fn create_test_project(server: &TestServer) -> Project {
         .json::<Project>()
 }
 
+async fn create_feature(server: &TestServer, project: &Project, title: &str) -> Feature {
+    server
+        .post(&format!("/api/v1/projects/{}/features", project.id))
+        .json(&CreateFeatureInput {
+            id: None,
+            parent_id: None,
+            title: title.to_string(),
+            details: None,
+            priority: None,
+            state: None,
+        })
+        .await
+        .json::<Feature>()
+}
+
 mod feature_roots {
     use super::*;
 
@@ -173,6 +188,54 @@ mod feature_children {
         assert_eq!(children[1].title, "Zebra");
     }
 
+    #[tokio::test]
+    async fn reorders_children() {
+        let server = setup();
+        let project = create_test_project(&server).await;
+
+        let mut features = Vec::new();
+        // Zebra and Alpha are children of Parent
+        for title in ["Parent", "Zebra", "Alpha"] {
+            let feature = server
+                .post(&format!("/api/v1/projects/{}/features", project.id))
+                .json(&CreateFeatureInput {
+                    id: None,
+                    parent_id: features.first().map(|f: &Feature| f.id),
+                    title: title.to_string(),
+                    state: None,
+                    details: None,
+                    priority: None,
+                })
+                .await
+                .json::<Feature>();
+            features.push(feature);
+        }
+
+        let response = server
+            .post("/api/v1/features/reorder")
+            .json(&ReorderFeaturesInput {
+                feature_ids: vec![features[1].id, features[2].id],
+            })
+            .await;
+        response.assert_status_ok();
+
+        let children: Vec<Feature> = server
+            .get(&format!("/api/v1/features/{}/children", features[0].id))
+            .await
+            .json();
+        assert_eq!(children[0].title, "Zebra");
+        assert_eq!(children[1].title, "Alpha");
+
+        // The parent is not a sibling of its children
+        server
+            .post("/api/v1/features/reorder")
+            .json(&ReorderFeaturesInput {
+                feature_ids: vec![features[0].id, features[1].id],
+            })
+            .await
+            .assert_status_bad_request();
+    }
+
     #[tokio::test]
     async fn does_not_return_grandchildren() {
         let server = setup();
@@ -945,6 +1008,129 @@ mod features {
         response.assert_status_not_found();
     }
 
+    #[tokio::test]
+    async fn create_accepts_project_id_in_body() {
+        let server = setup();
+        let project = create_test_project(&server).await;
+
+        let response = server
+            .post("/api/v1/features")
+            .json(&serde_json::json!({
+                "project_id": project.id,
+                "title": "Exports",
+                "details": "CSV and JSON"
+            }))
+            .await;
+
+        response.assert_status(StatusCode::CREATED);
+        let feature: Feature = response.json();
+        assert_eq!(feature.project_id, project.id);
+        assert_eq!(feature.title, "Exports");
+        assert_eq!(feature.state, FeatureState::Proposed);
+
+        let response = server
+            .post("/api/v1/features")
+            .json(&serde_json::json!({
+                "project_id": uuid::Uuid::new_v4(),
+                "title": "Orphan"
+            }))
+            .await;
+        response.assert_status_not_found();
+    }
+
+    #[tokio::test]
+    async fn patch_updates_only_provided_fields() {
+        let server = setup();
+        let project = create_test_project(&server).await;
+
+        let feature = server
+            .post(&format!("/api/v1/projects/{}/features", project.id))
+            .json(&CreateFeatureInput {
+                id: None,
+                parent_id: None,
+                title: "Exports".to_string(),
+                details: Some("CSV and JSON".to_string()),
+                state: None,
+                priority: None,
+            })
+            .await
+            .json::<Feature>();
+
+        let response = server
+            .patch(&format!("/api/v1/features/{}", feature.id))
+            .json(&serde_json::json!({ "title": "Data Exports" }))
+            .await;
+
+        response.assert_status_ok();
+        let updated: Feature = response.json();
+        assert_eq!(updated.title, "Data Exports");
+        assert_eq!(updated.details.as_deref(), Some("CSV and JSON"));
+
+        let response = server
+            .patch(&format!("/api/v1/features/{}", uuid::Uuid::new_v4()))
+            .json(&serde_json::json!({ "title": "Missing" }))
+            .await;
+        response.assert_status_not_found();
+    }
+
+    #[tokio::test]
+    async fn rejects_malformed_feature_ids() {
+        let server = setup();
+
+        let response = server.get("/api/v1/features/not-a-uuid").await;
+        response.assert_status_bad_request();
+
+        let response = server.delete("/api/v1/features/not-a-uuid").await;
+        response.assert_status_bad_request();
+    }
+
+    #[tokio::test]
+    async fn delete_returns_not_found_for_nonexistent_feature() {
+        let server = setup();
+
+        let response = server
+            .delete(&format!("/api/v1/features/{}", uuid::Uuid::new_v4()))
+            .await;
+
+        response.assert_status_not_found();
+    }
+
+    #[tokio::test]
+    async fn markdown_renders_feature_or_not_found() {
+        let server = setup();
+        let project = create_test_project(&server).await;
+
+        let feature = server
+            .post(&format!("/api/v1/projects/{}/features", project.id))
+            .json(&CreateFeatureInput {
+                id: None,
+                parent_id: None,
+                title: "Exports".to_string(),
+                details: Some("CSV and JSON".to_string()),
+                state: None,
+                priority: None,
+            })
+            .await
+            .json::<Feature>();
+
+        let response = server
+            .get(&format!("/api/v1/features/{}/markdown", feature.id))
+            .await;
+        response.assert_status_ok();
+        assert_eq!(
+            response.text(),
+            "# Exports\n\n**State:** proposed · **Priority:** 0\n\nCSV and JSON\n"
+        );
+
+        let response = server
+            .get(&format!(
+                "/api/v1/features/{}/markdown",
+                uuid::Uuid::new_v4()
+            ))
+            .await;
+        response.assert_status_not_found();
+    }
+
     #[tokio::test]
     async fn update_modifies_feature() {
         let server = setup();
@@ -973,6 +1159,7 @@ mod features {
                 desired_details: None,
                 priority: None,
                 state: Some(FeatureState::Implemented),
+                expected_updated_at: None,
             })
             .await;
 
@@ -997,11 +1184,53 @@ mod features {
                 desired_details: None,
                 priority: None,
                 state: None,
+                expected_updated_at: None,
             })
             .await;
 
         response.assert_status_not_found();
     }
+
+    #[tokio::test]
+    async fn update_returns_conflict_for_stale_expected_updated_at() {
+        let server = setup();
+        let project = create_test_project(&server).await;
+
+        let feature = server
+            .post(&format!("/api/v1/projects/{}/features", project.id))
+            .json(&CreateFeatureInput {
+                id: None,
+                parent_id: None,
+                title: "Feature".to_string(),
+
+                details: None,
+                priority: None,
+                state: None,
+            })
+            .await
+            .json::<Feature>();
+
+        server
+            .put(&format!("/api/v1/features/{}", feature.id))
+            .json(&serde_json::json!({ "title": "Edited elsewhere" }))
+            .await
+            .assert_status_ok();
+
+        let response = server
+            .put(&format!("/api/v1/features/{}", feature.id))
+            .json(&UpdateFeatureInput {
+                parent_id: None,
+                title: Some("My edit".to_string()),
+                details: None,
+                desired_details: None,
+                priority: None,
+                state: None,
+                expected_updated_at: Some(feature.updated_at),
+            })
+            .await;
+
+        response.assert_status(StatusCode::CONFLICT);
+    }
 }
 
 // ============================================================
@@ -1185,6 +1414,34 @@ mod feature_search {
         assert_eq!(features.len(), 1);
         assert_eq!(features[0].title, "Auth in Project 1");
     }
+
+    #[tokio::test]
+    async fn returns_snippets_around_partial_word_matches() {
+        let server = setup();
+        let project = create_test_project(&server).await;
+
+        server
+            .post(&format!("/api/v1/projects/{}/features", project.id))
+            .json(&CreateFeatureInput {
+                id: None,
+                parent_id: None,
+                title: "Sign in".to_string(),
+                details: Some("Supports passkeys and passwords".to_string()),
+                priority: None,
+                state: None,
+            })
+            .await;
+
+        let response = server.get("/api/v1/features/search?q=passk").await;
+
+        response.assert_status_ok();
+        let hits: Vec<FeatureHit> = response.json();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(
+            hits[0].snippet.as_deref(),
+            Some("Supports **passk**eys and passwords")
+        );
+    }
 }
 
 // ============================================================
@@ -1251,6 +1508,7 @@ mod feature_diff {
                 desired_details: Some("Desired".to_string()),
                 priority: None,
                 state: None,
+                expected_updated_at: None,
             })
             .await;
 
@@ -1342,6 +1600,360 @@ mod feature_tree {
     }
 }
 
+mod feature_duplicate {
+    use super::*;
+
+    #[tokio::test]
+    async fn creates_a_copy() {
+        let server = setup();
+        let project = create_test_project(&server).await;
+
+        let original = server
+            .post(&format!("/api/v1/projects/{}/features", project.id))
+            .json(&CreateFeatureInput {
+                id: None,
+                parent_id: None,
+                title: "Search".to_string(),
+                details: Some("Full-text search".to_string()),
+                priority: None,
+                state: None,
+            })
+            .await
+            .json::<Feature>();
+
+        let response = server
+            .post(&format!("/api/v1/features/{}/duplicate", original.id))
+            .await;
+
+        response.assert_status(StatusCode::CREATED);
+        let copy: Feature = response.json();
+        assert_ne!(copy.id, original.id);
+        assert_eq!(copy.title, "Search (copy)");
+        assert_eq!(copy.details, original.details);
+    }
+
+    #[tokio::test]
+    async fn returns_404_for_missing_feature() {
+        let server = setup();
+        let fake_id = uuid::Uuid::new_v4();
+
+        let response = server
+            .post(&format!("/api/v1/features/{}/duplicate", fake_id))
+            .await;
+
+        response.assert_status(StatusCode::NOT_FOUND);
+    }
+}
+
+mod feature_templates {
+    use super::*;
+
+    #[tokio::test]
+    async fn lists_builtin_templates() {
+        let server = setup();
+        let project = create_test_project(&server).await;
+
+        let response = server
+            .get(&format!("/api/v1/projects/{}/templates", project.id))
+            .await;
+
+        response.assert_status_ok();
+        let templates: Vec<FeatureTemplate> = response.json();
+        let names: Vec<_> = templates.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["user_story", "bug_fix", "technical_spec"]);
+    }
+
+    #[tokio::test]
+    async fn creates_feature_from_template() {
+        let server = setup();
+        let project = create_test_project(&server).await;
+
+        let response = server
+            .post(&format!(
+                "/api/v1/projects/{}/features?template=bug_fix",
+                project.id
+            ))
+            .json(&CreateFeatureInput {
+                id: None,
+                parent_id: None,
+                title: "Crash on save".to_string(),
+                details: None,
+                priority: None,
+                state: None,
+            })
+            .await;
+
+        response.assert_status(StatusCode::CREATED);
+        let feature: Feature = response.json();
+        assert!(feature.details.unwrap().contains("## Steps to Reproduce"));
+    }
+
+    #[tokio::test]
+    async fn rejects_unknown_template() {
+        let server = setup();
+        let project = create_test_project(&server).await;
+
+        let response = server
+            .post(&format!(
+                "/api/v1/projects/{}/features?template=missing",
+                project.id
+            ))
+            .json(&CreateFeatureInput {
+                id: None,
+                parent_id: None,
+                title: "Feature".to_string(),
+                details: None,
+                priority: None,
+                state: None,
+            })
+            .await;
+
+        response.assert_status(StatusCode::BAD_REQUEST);
+    }
+}
+
+mod feature_subtree {
+    use super::*;
+
+    #[tokio::test]
+    async fn returns_feature_with_limited_depth() {
+        let server = setup();
+        let project = create_test_project(&server).await;
+        let mut parent_id = None;
+        let mut ids = Vec::new();
+        for title in ["Payments", "Cards", "3-D Secure"] {
+            let feature = server
+                .post(&format!("/api/v1/projects/{}/features", project.id))
+                .json(&CreateFeatureInput {
+                    id: None,
+                    parent_id,
+                    title: title.to_string(),
+                    details: None,
+                    priority: None,
+                    state: None,
+                })
+                .await
+                .json::<Feature>();
+            parent_id = Some(feature.id);
+            ids.push(feature.id);
+        }
+
+        let response = server
+            .get(&format!("/api/v1/features/{}/tree?depth=1", ids[0]))
+            .await;
+
+        response.assert_status_ok();
+        let subtree: FeatureTreeNode = response.json();
+        assert_eq!(subtree.feature.id, ids[0]);
+        assert_eq!(subtree.children.len(), 1);
+        assert!(subtree.children[0].children.is_empty());
+    }
+
+    #[tokio::test]
+    async fn returns_not_found_for_nonexistent_feature() {
+        let server = setup();
+        let fake_id = uuid::Uuid::new_v4();
+
+        let response = server
+            .get(&format!("/api/v1/features/{}/tree", fake_id))
+            .await;
+
+        response.assert_status_not_found();
+    }
+}
+
+mod feature_tags {
+    use super::*;
+
+    #[tokio::test]
+    async fn sets_and_filters_by_tag() {
+        let server = setup();
+        let project = create_test_project(&server).await;
+        let login = create_feature(&server, &project, "Login").await;
+        create_feature(&server, &project, "Search").await;
+
+        let response = server
+            .put(&format!("/api/v1/features/{}/tags", login.id))
+            .json(&SetTagsInput {
+                tags: vec!["Backend".to_string(), "mvp".to_string()],
+            })
+            .await;
+        response.assert_status_ok();
+        let tags: Vec<String> = response.json();
+        assert_eq!(tags, vec!["backend", "mvp"]);
+
+        let response = server.get("/api/v1/features?tag=mvp").await;
+        response.assert_status_ok();
+        let features: Vec<FeatureSummary> = response.json();
+        assert_eq!(features.len(), 1);
+        assert_eq!(features[0].id, login.id);
+
+        let response = server
+            .get(&format!(
+                "/api/v1/projects/{}/features?tag=backend",
+                project.id
+            ))
+            .await;
+        let features: Vec<FeatureSummary> = response.json();
+        assert_eq!(features.len(), 1);
+
+        // Several tags must all match
+        let response = server.get("/api/v1/features?tag=backend,mvp").await;
+        let features: Vec<FeatureSummary> = response.json();
+        assert_eq!(features.len(), 1);
+        let response = server.get("/api/v1/features?tag=backend,beta").await;
+        let features: Vec<FeatureSummary> = response.json();
+        assert!(features.is_empty());
+
+        let response = server
+            .get(&format!("/api/v1/projects/{}/tags?prefix=ba", project.id))
+            .await;
+        response.assert_status_ok();
+        let completions: Vec<String> = response.json();
+        assert_eq!(completions, vec!["backend"]);
+    }
+
+    #[tokio::test]
+    async fn returns_not_found_for_nonexistent_feature() {
+        let server = setup();
+        let fake_id = uuid::Uuid::new_v4();
+
+        server
+            .get(&format!("/api/v1/features/{}/tags", fake_id))
+            .await
+            .assert_status_not_found();
+        server
+            .put(&format!("/api/v1/features/{}/tags", fake_id))
+            .json(&SetTagsInput { tags: vec![] })
+            .await
+            .assert_status_not_found();
+    }
+}
+
+mod feature_dependencies {
+    use super::*;
+
+    #[tokio::test]
+    async fn adds_lists_and_removes_dependencies() {
+        let server = setup();
+        let project = create_test_project(&server).await;
+        let auth = create_feature(&server, &project, "Auth").await;
+        let billing = create_feature(&server, &project, "Billing").await;
+
+        let response = server
+            .post(&format!("/api/v1/features/{}/dependencies", billing.id))
+            .json(&AddDependencyInput {
+                depends_on_id: auth.id,
+                kind: LinkKind::DependsOn,
+            })
+            .await;
+        response.assert_status(StatusCode::CREATED);
+        let deps: FeatureDependencies = response.json();
+        assert_eq!(deps.blocked_by.len(), 1);
+        assert_eq!(deps.blocked_by[0].id, auth.id);
+
+        let response = server
+            .get(&format!("/api/v1/features/{}/dependencies", auth.id))
+            .await;
+        response.assert_status_ok();
+        let deps: FeatureDependencies = response.json();
+        assert!(deps.blocked_by.is_empty());
+        assert_eq!(deps.blocks[0].id, billing.id);
+
+        server
+            .delete(&format!(
+                "/api/v1/features/{}/dependencies/{}",
+                billing.id, auth.id
+            ))
+            .await
+            .assert_status(StatusCode::NO_CONTENT);
+        server
+            .delete(&format!(
+                "/api/v1/features/{}/dependencies/{}",
+                billing.id, auth.id
+            ))
+            .await
+            .assert_status_not_found();
+    }
+
+    #[tokio::test]
+    async fn adds_relates_links_to_both_ends() {
+        let server = setup();
+        let project = create_test_project(&server).await;
+        let auth = create_feature(&server, &project, "Auth").await;
+        let billing = create_feature(&server, &project, "Billing").await;
+
+        let response = server
+            .post(&format!("/api/v1/features/{}/dependencies", billing.id))
+            .json(&serde_json::json!({ "depends_on_id": auth.id, "kind": "relates" }))
+            .await;
+        response.assert_status(StatusCode::CREATED);
+        let deps: FeatureDependencies = response.json();
+        assert!(deps.blocked_by.is_empty());
+        assert_eq!(deps.related[0].id, auth.id);
+
+        let response = server
+            .get(&format!("/api/v1/features/{}/dependencies", auth.id))
+            .await;
+        let deps: FeatureDependencies = response.json();
+        assert!(deps.blocks.is_empty());
+        assert_eq!(deps.related[0].id, billing.id);
+
+        server
+            .post(&format!("/api/v1/features/{}/dependencies", billing.id))
+            .json(&serde_json::json!({ "depends_on_id": auth.id, "kind": "sideways" }))
+            .await
+            .assert_status_unprocessable_entity();
+    }
+
+    #[tokio::test]
+    async fn rejects_cycles() {
+        let server = setup();
+        let project = create_test_project(&server).await;
+        let auth = create_feature(&server, &project, "Auth").await;
+        let billing = create_feature(&server, &project, "Billing").await;
+
+        server
+            .post(&format!("/api/v1/features/{}/dependencies", billing.id))
+            .json(&AddDependencyInput {
+                depends_on_id: auth.id,
+                kind: LinkKind::DependsOn,
+            })
+            .await
+            .assert_status(StatusCode::CREATED);
+
+        let response = server
+            .post(&format!("/api/v1/features/{}/dependencies", auth.id))
+            .json(&AddDependencyInput {
+                depends_on_id: billing.id,
+                kind: LinkKind::DependsOn,
+            })
+            .await;
+        response.assert_status(StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn returns_not_found_for_nonexistent_feature() {
+        let server = setup();
+        let project = create_test_project(&server).await;
+        let auth = create_feature(&server, &project, "Auth").await;
+        let fake_id = uuid::Uuid::new_v4();
+
+        server
+            .get(&format!("/api/v1/features/{}/dependencies", fake_id))
+            .await
+            .assert_status_not_found();
+        server
+            .post(&format!("/api/v1/features/{}/dependencies", auth.id))
+            .json(&AddDependencyInput {
+                depends_on_id: fake_id,
+                kind: LinkKind::DependsOn,
+            })
+            .await
+            .assert_status_not_found();
+    }
+}
+
 // ============================================================
 // Sessions
 // ============================================================
@@ -1447,6 +2059,69 @@ mod sessions {
         assert_eq!(status.tasks.len(), 1);
         assert_eq!(status.tasks[0].title, "Task 1");
     }
+
+    #[tokio::test]
+    async fn summary_returns_task_counts() {
+        let server = setup();
+        let project = create_test_project(&server).await;
+
+        let feature = server
+            .post(&format!("/api/v1/projects/{}/features", project.id))
+            .json(&CreateFeatureInput {
+                id: None,
+                parent_id: None,
+                title: "Feature Title".to_string(),
+
+                details: None,
+                priority: None,
+                state: None,
+            })
+            .await
+            .json::<Feature>();
+
+        let session_response = server
+            .post("/api/v1/sessions")
+            .json(&CreateSessionInput {
+                feature_id: feature.id,
+                goal: "Goal".to_string(),
+                tasks: vec![CreateTaskInput {
+                    parent_id: None,
+                    title: "Task 1".to_string(),
+                    scope: "Scope".to_string(),
+                    agent_type: AgentType::Claude,
+                }],
+            })
+            .await
+            .json::<SessionResponse>();
+
+        let response = server
+            .get(&format!(
+                "/api/v1/sessions/{}/summary",
+                session_response.session.id
+            ))
+            .await;
+
+        response.assert_status_ok();
+        let summary: SessionSummary = response.json();
+        assert_eq!(summary.session.id, session_response.session.id);
+        assert_eq!(summary.feature.title, "Feature Title");
+        assert_eq!(summary.task_counts.total, 1);
+        assert_eq!(summary.task_counts.pending, 1);
+    }
+
+    #[tokio::test]
+    async fn summary_returns_not_found_for_nonexistent_session() {
+        let server = setup();
+
+        let response = server
+            .get(&format!(
+                "/api/v1/sessions/{}/summary",
+                uuid::Uuid::new_v4()
+            ))
+            .await;
+
+        response.assert_status_not_found();
+    }
 }
 
 // ============================================================
@@ -1554,6 +2229,7 @@ mod tasks {
                 status: Some(TaskStatus::Running),
                 worktree_path: Some("/tmp/worktree".to_string()),
                 branch: Some("feature-branch".to_string()),
+                log_path: Some("/tmp/project/.manifest/logs/task.log".to_string()),
             })
             .await;
 
@@ -1567,6 +2243,10 @@ mod tasks {
         assert_eq!(fetched.status, TaskStatus::Running);
         assert_eq!(fetched.worktree_path, Some("/tmp/worktree".to_string()));
         assert_eq!(fetched.branch, Some("feature-branch".to_string()));
+        assert_eq!(
+            fetched.log_path,
+            Some("/tmp/project/.manifest/logs/task.log".to_string())
+        );
     }
 
     #[tokio::test]
@@ -1580,6 +2260,7 @@ mod tasks {
                 status: Some(TaskStatus::Running),
                 worktree_path: None,
                 branch: None,
+                log_path: None,
             })
             .await;
 
@@ -2047,3 +2728,157 @@ mod security_auth {
         response.assert_status(StatusCode::CREATED);
     }
 }
+
+mod events {
+    use super::*;
+    use std::time::Duration;
+
+    /// Read from an event stream until `event` arrives, returning its data.
+    async fn next_event(
+        response: &mut reqwest::Response,
+        buffer: &mut String,
+        event: &str,
+    ) -> String {
+        let marker = format!("event: {}\ndata: ", event);
+        tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                if let Some(start) = buffer.find(&marker) {
+                    let data_start = start + marker.len();
+                    if let Some(len) = buffer[data_start..].find('\n') {
+                        let data = buffer[data_start..data_start + len].to_string();
+                        buffer.drain(..data_start + len);
+                        return data;
+                    }
+                }
+                let chunk = response.chunk().await.unwrap().expect("stream ended");
+                buffer.push_str(std::str::from_utf8(&chunk).unwrap());
+            }
+        })
+        .await
+        .unwrap_or_else(|_| panic!("no {} event", event))
+    }
+
+    #[tokio::test]
+    async fn streams_a_snapshot_then_project_changes() {
+        let db = Database::open_memory().expect("Failed to create database");
+        db.migrate().expect("Failed to migrate");
+        let server = TestServer::builder()
+            .http_transport()
+            .build(create_router(db))
+            .expect("Failed to create test server");
+        let project = create_test_project(&server).await;
+        let other = create_test_project(&server).await;
+
+        let mut url = server.server_url("/api/v1/events").unwrap();
+        url.set_query(Some(&format!("project_id={}", project.id)));
+        let mut response = reqwest::get(url).await.unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::OK);
+        let mut buffer = String::new();
+        assert_eq!(
+            next_event(&mut response, &mut buffer, "snapshot").await,
+            "[]"
+        );
+
+        let feature_input = |title: &str| CreateFeatureInput {
+            id: None,
+            parent_id: None,
+            title: title.to_string(),
+            details: None,
+            state: None,
+            priority: None,
+        };
+        server
+            .post(&format!("/api/v1/projects/{}/features", other.id))
+            .json(&feature_input("Elsewhere"))
+            .await;
+        let feature: Feature = server
+            .post(&format!("/api/v1/projects/{}/features", project.id))
+            .json(&feature_input("Live"))
+            .await
+            .json();
+
+        // The other project's feature is filtered out, so this is the first change
+        let change: ChangeEvent =
+            serde_json::from_str(&next_event(&mut response, &mut buffer, "change").await).unwrap();
+        assert_eq!(
+            change,
+            ChangeEvent::FeatureChanged {
+                project_id: project.id,
+                feature_id: feature.id,
+            }
+        );
+
+        server
+            .delete(&format!("/api/v1/features/{}", feature.id))
+            .await;
+        let change: ChangeEvent =
+            serde_json::from_str(&next_event(&mut response, &mut buffer, "change").await).unwrap();
+        assert_eq!(
+            change,
+            ChangeEvent::FeatureDeleted {
+                project_id: project.id,
+                feature_id: feature.id,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn returns_not_found_for_unknown_project() {
+        let server = setup();
+
+        let response = server
+            .get("/api/v1/events")
+            .add_query_param("project_id", uuid::Uuid::new_v4())
+            .await;
+
+        response.assert_status(StatusCode::NOT_FOUND);
+    }
+}
+
+mod project_export {
+    use super::*;
+
+    #[tokio::test]
+    async fn exports_json_by_default_and_markdown_on_request() {
+        let server = setup();
+        let project = create_test_project(&server).await;
+        server
+            .post(&format!("/api/v1/projects/{}/features", project.id))
+            .json(&CreateFeatureInput {
+                id: None,
+                parent_id: None,
+                title: "Search".to_string(),
+                details: Some("Find features by title.".to_string()),
+                state: None,
+                priority: None,
+            })
+            .await;
+
+        let response = server
+            .get(&format!("/api/v1/projects/{}/export", project.id))
+            .await;
+        response.assert_status_ok();
+        let export: ProjectExport = response.json();
+        assert_eq!(export.project.id, project.id);
+        assert_eq!(export.features[0].feature.title, "Search");
+
+        let response = server
+            .get(&format!("/api/v1/projects/{}/export", project.id))
+            .add_query_param("format", "md")
+            .await;
+        response.assert_status_ok();
+        assert!(response.text().starts_with("# Test Project\n\n## Search\n"));
+    }
+
+    #[tokio::test]
+    async fn returns_not_found_for_unknown_project() {
+        let server = setup();
+
+        let response = server
+            .get(&format!("/api/v1/projects/{}/export", uuid::Uuid::new_v4()))
+            .add_query_param("format", "md")
+            .await;
+
+        response.assert_status(StatusCode::NOT_FOUND);
+    }
+}
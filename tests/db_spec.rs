@@ -1,4 +1,4 @@
-use manifest::db::Database;
+use manifest::db::{Database, ManifestError};
 use manifest::models::*;
 use speculate2::speculate;
 use uuid::Uuid;
@@ -12,6 +12,18 @@ fn create_test_project(db: &Database) -> Project {
     .expect("Failed to create project")
 }
 
+fn move_under(parent_id: Uuid) -> UpdateFeatureInput {
+    UpdateFeatureInput {
+        parent_id: Some(parent_id),
+        title: None,
+        details: None,
+        desired_details: None,
+        priority: None,
+        state: None,
+        expected_updated_at: None,
+    }
+}
+
 speculate! {
     before {
         let db = Database::open_memory().expect("Failed to create in-memory database");
@@ -192,6 +204,115 @@ speculate! {
                 assert!(feature.details.as_ref().unwrap().contains("As a user"));
                 assert!(feature.details.as_ref().unwrap().contains("PKCE"));
             }
+
+            it "rejects a parent that is missing or in another project" {
+                let project = create_test_project(&db);
+                let other = create_test_project(&db);
+                let foreign_parent = db.create_feature(other.id, CreateFeatureInput { id: None,
+                    parent_id: None,
+                    title: "Billing".to_string(),
+                    details: None,
+                    priority: None,
+                    state: None,
+                }).expect("Failed to create feature");
+                let child = |parent_id| CreateFeatureInput { id: None,
+                    parent_id: Some(parent_id),
+                    title: "Invoices".to_string(),
+                    details: None,
+                    priority: None,
+                    state: None,
+                };
+
+                let err = db.create_feature(project.id, child(foreign_parent.id))
+                    .expect_err("Expected validation error");
+                assert!(matches!(
+                    err.downcast_ref::<ManifestError>(),
+                    Some(ManifestError::Validation(_))
+                ));
+
+                let err = db.create_feature(project.id, child(Uuid::new_v4()))
+                    .expect_err("Expected not found error");
+                assert!(matches!(
+                    err.downcast_ref::<ManifestError>(),
+                    Some(ManifestError::NotFound(_))
+                ));
+            }
+        }
+
+        describe "create_feature_from_template" {
+            it "pre-fills details from a built-in template" {
+                let project = create_test_project(&db);
+                let input = CreateFeatureInput { id: None,
+                    parent_id: None,
+                    title: "Checkout".to_string(),
+                    details: None,
+                    priority: None,
+                    state: None,
+                };
+
+                let feature = db.create_feature_from_template(project.id, input, "user_story")
+                    .expect("Failed to create feature");
+
+                let details = feature.details.expect("Expected details");
+                assert!(details.contains("## User Story"));
+                assert!(details.contains("## Acceptance Criteria"));
+            }
+
+            it "keeps details given in the input" {
+                let project = create_test_project(&db);
+                let input = CreateFeatureInput { id: None,
+                    parent_id: None,
+                    title: "Checkout".to_string(),
+                    details: Some("Written by hand".to_string()),
+                    priority: None,
+                    state: None,
+                };
+
+                let feature = db.create_feature_from_template(project.id, input, "user_story")
+                    .expect("Failed to create feature");
+
+                assert_eq!(feature.details, Some("Written by hand".to_string()));
+            }
+
+            it "rejects an unknown template" {
+                let project = create_test_project(&db);
+                let input = CreateFeatureInput { id: None,
+                    parent_id: None,
+                    title: "Checkout".to_string(),
+                    details: None,
+                    priority: None,
+                    state: None,
+                };
+
+                let err = db.create_feature_from_template(project.id, input, "missing")
+                    .expect_err("Expected validation error");
+                assert!(matches!(
+                    err.downcast_ref::<ManifestError>(),
+                    Some(ManifestError::Validation(_))
+                ));
+            }
+
+            it "uses templates from the project's directories" {
+                let project = create_test_project(&db);
+                let dir = tempfile::tempdir().expect("Failed to create temp dir");
+                let template_dir = dir.path().join(TEMPLATE_DIR);
+                std::fs::create_dir_all(&template_dir).expect("Failed to create template dir");
+                std::fs::write(template_dir.join("bug_fix.md"), "## Our Bug Format\n").unwrap();
+                std::fs::write(template_dir.join("rfc.md"), "## Motivation\n").unwrap();
+                db.add_project_directory(project.id, AddDirectoryInput {
+                    path: dir.path().to_string_lossy().to_string(),
+                    git_remote: None,
+                    is_primary: true,
+                    instructions: None,
+                }).expect("Failed to add directory");
+
+                let templates = db.get_feature_templates(project.id).expect("Query failed");
+                let bug_fix = templates.iter().find(|t| t.name == "bug_fix").unwrap();
+                assert_eq!(bug_fix.content, "## Our Bug Format\n");
+                let rfc = templates.iter().find(|t| t.name == "rfc").unwrap();
+                assert_eq!(rfc.title, "Rfc");
+                assert_eq!(templates.len(), 4);
+            }
         }
 
         describe "get_feature" {
@@ -263,6 +384,7 @@ speculate! {
                     desired_details: None,
                     priority: None,
                     state: None,
+                    expected_updated_at: None,
                 };
 
                 let result = db.update_feature(Uuid::new_v4(), input).expect("Query failed");
@@ -287,6 +409,7 @@ speculate! {
                     desired_details: None,
                     priority: None,
                     state: None,
+                    expected_updated_at: None,
                 }).expect("Query failed").expect("Feature not found");
 
                 assert_eq!(updated.title, "Updated Title");
@@ -311,10 +434,239 @@ speculate! {
                     desired_details: None,
                     priority: None,
                     state: Some(FeatureState::Implemented),
+                    expected_updated_at: None,
                 }).expect("Query failed").expect("Feature not found");
 
                 assert_eq!(updated.state, FeatureState::Implemented);
             }
+
+            it "records state changes in the feature history" {
+                let project = create_test_project(&db);
+                let created = db.create_feature(project.id, CreateFeatureInput { id: None,
+                    parent_id: None,
+                    title: "Feature".to_string(),
+                    details: None,
+                    priority: None,
+                    state: Some(FeatureState::Implemented),
+                }).expect("Failed to create");
+                let update = |state| UpdateFeatureInput {
+                    parent_id: None,
+                    title: None,
+                    details: Some("Revised".to_string()),
+                    desired_details: None,
+                    priority: None,
+                    state,
+                    expected_updated_at: None,
+                };
+
+                db.update_feature(created.id, update(None)).expect("Query failed");
+                db.update_feature(created.id, update(Some(FeatureState::Implemented))).expect("Query failed");
+                assert!(db.get_feature_history(created.id).expect("Query failed").is_empty());
+
+                // Moving backwards is allowed too
+                db.update_feature(created.id, update(Some(FeatureState::Proposed))).expect("Query failed");
+                let history = db.get_feature_history(created.id).expect("Query failed");
+                assert_eq!(history.len(), 1);
+                assert_eq!(history[0].details.summary, "State changed from implemented to proposed");
+                assert!(history[0].session_id.is_none());
+            }
+
+            it "applies update when expected_updated_at matches" {
+                let project = create_test_project(&db);
+                let created = db.create_feature(project.id, CreateFeatureInput { id: None,
+                    parent_id: None,
+                    title: "Feature".to_string(),
+                    details: None,
+                    priority: None,
+                    state: None,
+                }).expect("Failed to create");
+
+                let updated = db.update_feature(created.id, UpdateFeatureInput {
+                    parent_id: None,
+                    title: Some("Renamed".to_string()),
+                    details: None,
+                    desired_details: None,
+                    priority: None,
+                    state: None,
+                    expected_updated_at: Some(created.updated_at),
+                }).expect("Query failed").expect("Feature not found");
+
+                assert_eq!(updated.title, "Renamed");
+                assert!(updated.updated_at > created.updated_at);
+            }
+
+            it "rejects update when feature changed since expected_updated_at" {
+                let project = create_test_project(&db);
+                let created = db.create_feature(project.id, CreateFeatureInput { id: None,
+                    parent_id: None,
+                    title: "Feature".to_string(),
+                    details: None,
+                    priority: None,
+                    state: None,
+                }).expect("Failed to create");
+
+                // Another writer updates first
+                db.update_feature(created.id, UpdateFeatureInput {
+                    parent_id: None,
+                    title: Some("Their Title".to_string()),
+                    details: None,
+                    desired_details: None,
+                    priority: None,
+                    state: None,
+                    expected_updated_at: None,
+                }).expect("Query failed");
+
+                let result = db.update_feature(created.id, UpdateFeatureInput {
+                    parent_id: None,
+                    title: Some("My Title".to_string()),
+                    details: None,
+                    desired_details: None,
+                    priority: None,
+                    state: None,
+                    expected_updated_at: Some(created.updated_at),
+                });
+
+                let err = result.expect_err("Expected conflict");
+                assert!(matches!(
+                    err.downcast_ref::<ManifestError>(),
+                    Some(ManifestError::Conflict(_))
+                ));
+
+                let current = db.get_feature(created.id).expect("Query failed").unwrap();
+                assert_eq!(current.title, "Their Title");
+            }
+
+            it "rejects every update made with the same stale expected_updated_at" {
+                let project = create_test_project(&db);
+                let created = db.create_feature(project.id, CreateFeatureInput { id: None,
+                    parent_id: None,
+                    title: "Feature".to_string(),
+                    details: None,
+                    priority: None,
+                    state: None,
+                }).expect("Failed to create");
+                let rename = |title: &str| UpdateFeatureInput {
+                    parent_id: None,
+                    title: Some(title.to_string()),
+                    details: None,
+                    desired_details: None,
+                    priority: None,
+                    state: None,
+                    expected_updated_at: Some(created.updated_at),
+                };
+
+                // The first writer wins and moves updated_at on
+                db.update_feature(created.id, rename("First"))
+                    .expect("Query failed")
+                    .expect("Feature not found");
+
+                let err = db.update_feature(created.id, rename("Second")).expect_err("Expected conflict");
+                assert!(matches!(
+                    err.downcast_ref::<ManifestError>(),
+                    Some(ManifestError::Conflict(_))
+                ));
+
+                let current = db.get_feature(created.id).expect("Query failed").unwrap();
+                assert_eq!(current.title, "First");
+            }
+
+            it "accepts expected_updated_at for rows stored in older timestamp formats" {
+                let dir = tempfile::tempdir().expect("Failed to create temp dir");
+                let path = dir.path().join("manifest.db");
+                let db = Database::open(path.clone()).expect("Failed to open database");
+                db.migrate().expect("Failed to run migrations");
+                let project = create_test_project(&db);
+
+                for stored in ["2026-01-11 18:51:25", "2026-01-11T18:51:25Z"] {
+                    let created = db.create_feature(project.id, CreateFeatureInput { id: None,
+                        parent_id: None,
+                        title: "Feature".to_string(),
+                        details: None,
+                        priority: None,
+                        state: None,
+                    }).expect("Failed to create");
+                    rusqlite::Connection::open(&path)
+                        .expect("Failed to open database")
+                        .execute(
+                            "UPDATE features SET updated_at = ? WHERE id = ?",
+                            (stored, created.id.to_string()),
+                        )
+                        .expect("Failed to seed timestamp");
+                    let loaded = db.get_feature(created.id).expect("Query failed").unwrap();
+
+                    let updated = db.update_feature(created.id, UpdateFeatureInput {
+                        parent_id: None,
+                        title: Some("Renamed".to_string()),
+                        details: None,
+                        desired_details: None,
+                        priority: None,
+                        state: None,
+                        expected_updated_at: Some(loaded.updated_at),
+                    }).expect("Query failed").expect("Feature not found");
+                    assert_eq!(updated.title, "Renamed");
+                }
+            }
+
+            it "rejects a parent from another project" {
+                let project = create_test_project(&db);
+                let other = create_test_project(&db);
+                let create = |project_id: Uuid, title: &str| db.create_feature(project_id, CreateFeatureInput { id: None,
+                    parent_id: None,
+                    title: title.to_string(),
+                    details: None,
+                    priority: None,
+                    state: None,
+                }).expect("Failed to create");
+                let feature = create(project.id, "Feature");
+                let elsewhere = create(other.id, "Elsewhere");
+
+                let err = db.update_feature(feature.id, move_under(elsewhere.id)).expect_err("Expected validation error");
+                assert!(matches!(
+                    err.downcast_ref::<ManifestError>(),
+                    Some(ManifestError::Validation(_))
+                ));
+                assert!(db.get_feature(feature.id).expect("Query failed").unwrap().parent_id.is_none());
+            }
+
+            it "rejects the feature itself as its parent" {
+                let project = create_test_project(&db);
+                let feature = db.create_feature(project.id, CreateFeatureInput { id: None,
+                    parent_id: None,
+                    title: "Feature".to_string(),
+                    details: None,
+                    priority: None,
+                    state: None,
+                }).expect("Failed to create");
+
+                let err = db.update_feature(feature.id, move_under(feature.id)).expect_err("Expected validation error");
+                assert!(matches!(
+                    err.downcast_ref::<ManifestError>(),
+                    Some(ManifestError::Validation(_))
+                ));
+            }
+
+            it "rejects a descendant as the parent" {
+                let project = create_test_project(&db);
+                let create = |parent_id: Option<Uuid>, title: &str| db.create_feature(project.id, CreateFeatureInput { id: None,
+                    parent_id,
+                    title: title.to_string(),
+                    details: None,
+                    priority: None,
+                    state: None,
+                }).expect("Failed to create");
+                let root = create(None, "Root");
+                let child = create(Some(root.id), "Child");
+                let grandchild = create(Some(child.id), "Grandchild");
+
+                let err = db.update_feature(root.id, move_under(grandchild.id)).expect_err("Expected validation error");
+                assert!(matches!(
+                    err.downcast_ref::<ManifestError>(),
+                    Some(ManifestError::Validation(_))
+                ));
+                let tree = db.get_feature_tree(project.id).expect("Query failed");
+                assert_eq!(tree.len(), 1);
+                assert_eq!(tree[0].feature.id, root.id);
+            }
         }
 
         describe "delete_feature" {
@@ -340,53 +692,804 @@ speculate! {
                 let found = db.get_feature(created.id).expect("Query failed");
                 assert!(found.is_none());
             }
+
+            it "hides the feature and its children from every query" {
+                let project = create_test_project(&db);
+                let feature = |title: &str, parent_id: Option<Uuid>| CreateFeatureInput { id: None,
+                    parent_id,
+                    title: title.to_string(),
+                    details: Some("Reporting".to_string()),
+                    priority: None,
+                    state: None,
+                };
+                let parent = db.create_feature(project.id, feature("Reports", None)).expect("Failed to create");
+                let child = db.create_feature(project.id, feature("Charts", Some(parent.id))).expect("Failed to create");
+                let other = db.create_feature(project.id, feature("Billing", None)).expect("Failed to create");
+                db.set_feature_tags(child.id, &["ui".to_string()]).expect("Failed to tag");
+                db.add_dependency(other.id, child.id).expect("Failed to add dependency");
+
+                assert!(db.delete_feature(parent.id).expect("Query failed"));
+                assert!(!db.delete_feature(child.id).expect("Query failed"));
+
+                let titles = |features: Vec<Feature>| features.into_iter().map(|f| f.title).collect::<Vec<_>>();
+                assert_eq!(titles(db.get_all_features().expect("Query failed")), vec!["Billing"]);
+                assert_eq!(db.get_feature_tree(project.id).expect("Query failed").len(), 1);
+                assert!(db.get_children(parent.id).expect("Query failed").is_empty());
+                assert!(db.get_features_by_tag("ui", None).expect("Query failed").is_empty());
+                assert!(db.get_project_feature_tags(project.id).expect("Query failed").is_empty());
+                assert!(db.get_dependencies(other.id).expect("Query failed").is_empty());
+                assert!(db.get_blocked_feature_ids(project.id).expect("Query failed").is_empty());
+                assert_eq!(db.search("reporting", None, None).expect("Query failed").len(), 1);
+                assert_eq!(db.search_features("Rep", None, None).expect("Query failed").len(), 1);
+            }
         }
 
-        describe "get_feature_diff" {
-            it "returns has_changes false when no desired_details" {
+        describe "restore_feature" {
+            it "returns None for non-existent feature" {
+                assert!(db.restore_feature(Uuid::new_v4()).expect("Query failed").is_none());
+            }
+
+            it "restores the children deleted with it" {
+                let project = create_test_project(&db);
+                let feature = |title: &str, parent_id: Option<Uuid>| CreateFeatureInput { id: None,
+                    parent_id,
+                    title: title.to_string(),
+                    details: None,
+                    priority: None,
+                    state: None,
+                };
+                let parent = db.create_feature(project.id, feature("Reports", None)).expect("Failed to create");
+                let kept = db.create_feature(project.id, feature("Charts", Some(parent.id))).expect("Failed to create");
+                let dropped = db.create_feature(project.id, feature("Exports", Some(parent.id))).expect("Failed to create");
+                db.set_feature_tags(kept.id, &["ui".to_string()]).expect("Failed to tag");
+
+                db.delete_feature(dropped.id).expect("Failed to delete");
+                db.delete_feature(parent.id).expect("Failed to delete");
+                let trash = db.get_deleted_features(project.id).expect("Query failed");
+                assert_eq!(trash.len(), 1);
+                assert_eq!(trash[0].feature.id, parent.id);
+
+                let restored = db.restore_feature(parent.id).expect("Failed to restore").unwrap();
+                assert_eq!(restored.title, "Reports");
+                let tree = db.get_feature_tree(project.id).expect("Query failed");
+                assert_eq!(tree[0].children.len(), 1);
+                assert_eq!(tree[0].children[0].feature.id, kept.id);
+                assert_eq!(db.get_feature_tags(kept.id).expect("Query failed"), vec!["ui"]);
+
+                // The child deleted on its own is still in the trash
+                let trash = db.get_deleted_features(project.id).expect("Query failed");
+                assert_eq!(trash.len(), 1);
+                assert_eq!(trash[0].feature.id, dropped.id);
+            }
+
+            it "rejects a feature whose parent is in the trash" {
+                let project = create_test_project(&db);
+                let parent = db.create_feature(project.id, CreateFeatureInput { id: None,
+                    parent_id: None,
+                    title: "Reports".to_string(),
+                    details: None,
+                    priority: None,
+                    state: None,
+                }).expect("Failed to create");
+                let child = db.create_feature(project.id, CreateFeatureInput { id: None,
+                    parent_id: Some(parent.id),
+                    title: "Charts".to_string(),
+                    details: None,
+                    priority: None,
+                    state: None,
+                }).expect("Failed to create");
+                db.delete_feature(parent.id).expect("Failed to delete");
+
+                let err = db.restore_feature(child.id).unwrap_err();
+                assert!(matches!(err.downcast_ref::<ManifestError>(), Some(ManifestError::InvalidState(_))));
+                assert!(db.get_feature(child.id).expect("Query failed").is_none());
+            }
+        }
+
+        describe "purge_deleted" {
+            it "removes only features deleted before the cutoff" {
                 let project = create_test_project(&db);
                 let feature = db.create_feature(project.id, CreateFeatureInput { id: None,
                     parent_id: None,
-                    title: "Feature".to_string(),
-                    details: Some("Current details".to_string()),
+                    title: "Old".to_string(),
+                    details: None,
+                    priority: None,
+                    state: None,
+                }).expect("Failed to create");
+                db.delete_feature(feature.id).expect("Failed to delete");
+
+                let yesterday = chrono::Utc::now() - chrono::Duration::days(1);
+                assert_eq!(db.purge_deleted(yesterday).expect("Failed to purge"), 0);
+                assert_eq!(db.get_deleted_features(project.id).expect("Query failed").len(), 1);
+
+                let soon = chrono::Utc::now() + chrono::Duration::seconds(1);
+                assert_eq!(db.purge_deleted(soon).expect("Failed to purge"), 1);
+                assert!(db.get_deleted_features(project.id).expect("Query failed").is_empty());
+                assert!(db.restore_feature(feature.id).expect("Query failed").is_none());
+            }
+        }
+
+        describe "duplicate_feature" {
+            it "returns None for non-existent feature" {
+                let result = db.duplicate_feature(Uuid::new_v4()).expect("Query failed");
+                assert!(result.is_none());
+            }
+
+            it "copies content under the same parent as a new proposed feature" {
+                let project = create_test_project(&db);
+                let parent = db.create_feature(project.id, CreateFeatureInput { id: None,
+                    parent_id: None,
+                    title: "Payments".to_string(),
+                    details: None,
+                    priority: None,
+                    state: None,
+                }).expect("Failed to create");
+                let original = db.create_feature(project.id, CreateFeatureInput { id: None,
+                    parent_id: Some(parent.id),
+                    title: "Card Payments".to_string(),
+                    details: Some("Accept cards".to_string()),
+                    priority: Some(3),
+                    state: Some(FeatureState::Implemented),
+                }).expect("Failed to create");
+                db.update_feature(original.id, UpdateFeatureInput {
+                    parent_id: None,
+                    title: None,
+                    details: None,
+                    desired_details: Some("Accept cards and wallets".to_string()),
                     priority: None,
                     state: None,
+                    expected_updated_at: None,
+                }).expect("Failed to update");
+
+                let copy = db.duplicate_feature(original.id)
+                    .expect("Query failed")
+                    .expect("Expected a copy");
+
+                assert_ne!(copy.id, original.id);
+                assert_eq!(copy.parent_id, Some(parent.id));
+                assert_eq!(copy.title, "Card Payments (copy)");
+                assert_eq!(copy.details, Some("Accept cards".to_string()));
+                assert_eq!(copy.priority, 3);
+                assert_eq!(copy.state, FeatureState::Proposed);
+                assert!(copy.desired_details.is_none());
+                assert!(db.get_feature_history(copy.id).expect("Query failed").is_empty());
+                assert_eq!(db.get_children(parent.id).expect("Query failed").len(), 2);
+            }
+        }
+
+        describe "get_feature_subtree" {
+            it "returns None for non-existent feature" {
+                let result = db.get_feature_subtree(Uuid::new_v4(), None).expect("Query failed");
+                assert!(result.is_none());
+            }
+
+            it "returns the feature with its descendants, cut off at max_depth" {
+                let project = create_test_project(&db);
+                let root = db.create_feature(project.id, CreateFeatureInput { id: None,
+                    parent_id: None,
+                    title: "Payments".to_string(),
+                    details: None,
+                    priority: None,
+                    state: None,
+                }).expect("Failed to create");
+                let cards = db.create_feature(project.id, CreateFeatureInput { id: None,
+                    parent_id: Some(root.id),
+                    title: "Cards".to_string(),
+                    details: None,
+                    priority: None,
+                    state: None,
+                }).expect("Failed to create");
+                db.create_feature(project.id, CreateFeatureInput { id: None,
+                    parent_id: Some(cards.id),
+                    title: "3-D Secure".to_string(),
+                    details: None,
+                    priority: None,
+                    state: None,
+                }).expect("Failed to create");
+
+                let subtree = db.get_feature_subtree(cards.id, None)
+                    .expect("Query failed")
+                    .expect("Expected subtree");
+                assert_eq!(subtree.feature.id, cards.id);
+                assert_eq!(subtree.children.len(), 1);
+                assert_eq!(subtree.children[0].feature.title, "3-D Secure");
+
+                let pruned = db.get_feature_subtree(root.id, Some(1))
+                    .expect("Query failed")
+                    .expect("Expected subtree");
+                assert_eq!(pruned.children.len(), 1);
+                assert!(pruned.children[0].children.is_empty());
+            }
+        }
+
+        describe "tags" {
+            it "returns None when tagging a non-existent feature" {
+                let result = db.set_feature_tags(Uuid::new_v4(), &["api".to_string()])
+                    .expect("Query failed");
+                assert!(result.is_none());
+            }
+
+            it "normalizes, dedupes and replaces a feature's tags" {
+                let project = create_test_project(&db);
+                let feature = db.create_feature(project.id, CreateFeatureInput { id: None,
+                    parent_id: None,
+                    title: "Login".to_string(),
+                    details: None,
+                    priority: None,
+                    state: None,
+                }).expect("Failed to create");
+
+                let tags = db.set_feature_tags(feature.id, &[
+                    " MVP ".to_string(),
+                    "backend".to_string(),
+                    "mvp".to_string(),
+                    "".to_string(),
+                ]).expect("Query failed").expect("Feature exists");
+                assert_eq!(tags, vec!["backend".to_string(), "mvp".to_string()]);
+
+                db.set_feature_tags(feature.id, &["auth".to_string()]).expect("Query failed");
+                assert_eq!(db.get_feature_tags(feature.id).expect("Query failed"), vec!["auth".to_string()]);
+                // Tags no feature uses any more are removed from the project.
+                assert_eq!(db.get_project_tags(project.id, None).expect("Query failed"), vec!["auth".to_string()]);
+            }
+
+            it "finds features by tag and completes tag prefixes" {
+                let project = create_test_project(&db);
+                let login = db.create_feature(project.id, CreateFeatureInput { id: None,
+                    parent_id: None,
+                    title: "Login".to_string(),
+                    details: None,
+                    priority: None,
+                    state: None,
+                }).expect("Failed to create");
+                let search = db.create_feature(project.id, CreateFeatureInput { id: None,
+                    parent_id: None,
+                    title: "Search".to_string(),
+                    details: None,
+                    priority: None,
+                    state: None,
+                }).expect("Failed to create");
+                db.set_feature_tags(login.id, &["backend".to_string(), "mvp".to_string()]).expect("Query failed");
+                db.set_feature_tags(search.id, &["beta".to_string()]).expect("Query failed");
+
+                let tagged = db.get_features_by_tag("MVP", Some(project.id)).expect("Query failed");
+                assert_eq!(tagged.len(), 1);
+                assert_eq!(tagged[0].id, login.id);
+
+                let completions = db.get_project_tags(project.id, Some("b")).expect("Query failed");
+                assert_eq!(completions, vec!["backend".to_string(), "beta".to_string()]);
+
+                let by_feature = db.get_project_feature_tags(project.id).expect("Query failed");
+                assert_eq!(by_feature.get(&search.id), Some(&vec!["beta".to_string()]));
+            }
+
+            it "matches features carrying every one of several tags" {
+                let project = create_test_project(&db);
+                let feature = |title: &str| CreateFeatureInput { id: None,
+                    parent_id: None,
+                    title: title.to_string(),
+                    details: None,
+                    priority: None,
+                    state: None,
+                };
+                let login = db.create_feature(project.id, feature("Login")).expect("Failed to create");
+                let signup = db.create_feature(project.id, feature("Signup")).expect("Failed to create");
+                db.set_feature_tags(login.id, &["frontend".to_string(), "p0".to_string()]).expect("Failed to tag");
+                db.set_feature_tags(signup.id, &["frontend".to_string()]).expect("Failed to tag");
+
+                let titles = |tags: &[&str]| {
+                    let tags: Vec<String> = tags.iter().map(|t| t.to_string()).collect();
+                    db.get_features_by_tags(&tags, Some(project.id))
+                        .expect("Query failed")
+                        .into_iter()
+                        .map(|f| f.title)
+                        .collect::<Vec<_>>()
+                };
+                assert_eq!(titles(&["frontend"]), vec!["Login", "Signup"]);
+                assert_eq!(titles(&["Frontend", "p0", "p0"]), vec!["Login"]);
+                assert!(titles(&["frontend", "needs-review"]).is_empty());
+                assert!(titles(&[" "]).is_empty());
+
+                // Removing a tag drops the feature from the match
+                db.set_feature_tags(login.id, &["frontend".to_string()]).expect("Failed to tag");
+                assert!(titles(&["frontend", "p0"]).is_empty());
+            }
+        }
+
+        describe "dependencies" {
+            it "records dependencies in both directions" {
+                let project = create_test_project(&db);
+                let auth = db.create_feature(project.id, CreateFeatureInput { id: None,
+                    parent_id: None,
+                    title: "Auth".to_string(),
+                    details: None,
+                    priority: None,
+                    state: None,
+                }).expect("Failed to create");
+                let billing = db.create_feature(project.id, CreateFeatureInput { id: None,
+                    parent_id: None,
+                    title: "Billing".to_string(),
+                    details: None,
+                    priority: None,
+                    state: None,
+                }).expect("Failed to create");
+
+                db.add_dependency(billing.id, auth.id).expect("Failed to add");
+                // Adding the same dependency again is a no-op
+                db.add_dependency(billing.id, auth.id).expect("Failed to add");
+
+                let blocked_by = db.get_dependencies(billing.id).expect("Query failed");
+                assert_eq!(blocked_by.len(), 1);
+                assert_eq!(blocked_by[0].id, auth.id);
+                let blocks = db.get_dependents(auth.id).expect("Query failed");
+                assert_eq!(blocks.len(), 1);
+                assert_eq!(blocks[0].id, billing.id);
+
+                assert_eq!(db.get_blocked_feature_ids(project.id).expect("Query failed"), vec![billing.id]);
+
+                assert!(db.remove_dependency(billing.id, auth.id).expect("Query failed"));
+                assert!(!db.remove_dependency(billing.id, auth.id).expect("Query failed"));
+                assert!(db.get_dependencies(billing.id).expect("Query failed").is_empty());
+            }
+
+            it "stops counting implemented dependencies as blocking" {
+                let project = create_test_project(&db);
+                let auth = db.create_feature(project.id, CreateFeatureInput { id: None,
+                    parent_id: None,
+                    title: "Auth".to_string(),
+                    details: None,
+                    priority: None,
+                    state: Some(FeatureState::Implemented),
+                }).expect("Failed to create");
+                let billing = db.create_feature(project.id, CreateFeatureInput { id: None,
+                    parent_id: None,
+                    title: "Billing".to_string(),
+                    details: None,
+                    priority: None,
+                    state: None,
+                }).expect("Failed to create");
+
+                db.add_dependency(billing.id, auth.id).expect("Failed to add");
+
+                assert!(db.get_blocked_feature_ids(project.id).expect("Query failed").is_empty());
+            }
+
+            it "rejects self-dependencies and cycles" {
+                let project = create_test_project(&db);
+                let a = db.create_feature(project.id, CreateFeatureInput { id: None,
+                    parent_id: None,
+                    title: "A".to_string(),
+                    details: None,
+                    priority: None,
+                    state: None,
+                }).expect("Failed to create");
+                let b = db.create_feature(project.id, CreateFeatureInput { id: None,
+                    parent_id: None,
+                    title: "B".to_string(),
+                    details: None,
+                    priority: None,
+                    state: None,
+                }).expect("Failed to create");
+                let c = db.create_feature(project.id, CreateFeatureInput { id: None,
+                    parent_id: None,
+                    title: "C".to_string(),
+                    details: None,
+                    priority: None,
+                    state: None,
+                }).expect("Failed to create");
+
+                assert!(db.add_dependency(a.id, a.id).is_err());
+
+                db.add_dependency(a.id, b.id).expect("Failed to add");
+                db.add_dependency(b.id, c.id).expect("Failed to add");
+                let err = db.add_dependency(c.id, a.id).expect_err("Cycle should be rejected");
+                assert!(err.to_string().contains("cycle"));
+                assert!(db.get_dependencies(c.id).expect("Query failed").is_empty());
+            }
+
+            it "rejects dependencies across projects" {
+                let project = create_test_project(&db);
+                let other = create_test_project(&db);
+                let a = db.create_feature(project.id, CreateFeatureInput { id: None,
+                    parent_id: None,
+                    title: "A".to_string(),
+                    details: None,
+                    priority: None,
+                    state: None,
+                }).expect("Failed to create");
+                let b = db.create_feature(other.id, CreateFeatureInput { id: None,
+                    parent_id: None,
+                    title: "B".to_string(),
+                    details: None,
+                    priority: None,
+                    state: None,
+                }).expect("Failed to create");
+
+                assert!(db.add_dependency(a.id, b.id).is_err());
+            }
+
+            it "records a blocks link as the reverse dependency" {
+                let project = create_test_project(&db);
+                let auth = db.create_feature(project.id, CreateFeatureInput { id: None,
+                    parent_id: None,
+                    title: "Auth".to_string(),
+                    details: None,
+                    priority: None,
+                    state: None,
+                }).expect("Failed to create");
+                let billing = db.create_feature(project.id, CreateFeatureInput { id: None,
+                    parent_id: None,
+                    title: "Billing".to_string(),
+                    details: None,
+                    priority: None,
+                    state: None,
+                }).expect("Failed to create");
+
+                db.add_link(auth.id, billing.id, LinkKind::Blocks).expect("Failed to add");
+                // Blocking is the same link seen from the other end
+                db.add_dependency(billing.id, auth.id).expect("Failed to add");
+
+                let blocked_by = db.get_dependencies(billing.id).expect("Query failed");
+                assert_eq!(blocked_by.len(), 1);
+                assert_eq!(blocked_by[0].id, auth.id);
+                assert!(db.get_dependencies(auth.id).expect("Query failed").is_empty());
+                assert!(db.get_related(auth.id).expect("Query failed").is_empty());
+            }
+
+            it "records relates links on both ends without blocking" {
+                let project = create_test_project(&db);
+                let auth = db.create_feature(project.id, CreateFeatureInput { id: None,
+                    parent_id: None,
+                    title: "Auth".to_string(),
+                    details: None,
+                    priority: None,
+                    state: None,
+                }).expect("Failed to create");
+                let billing = db.create_feature(project.id, CreateFeatureInput { id: None,
+                    parent_id: None,
+                    title: "Billing".to_string(),
+                    details: None,
+                    priority: None,
+                    state: None,
+                }).expect("Failed to create");
+
+                db.add_link(billing.id, auth.id, LinkKind::Relates).expect("Failed to add");
+                // Relates has no direction, so the mirrored link is the same one
+                db.add_link(auth.id, billing.id, LinkKind::Relates).expect("Failed to add");
+
+                let related = db.get_related(auth.id).expect("Query failed");
+                assert_eq!(related.len(), 1);
+                assert_eq!(related[0].id, billing.id);
+                assert_eq!(db.get_related(billing.id).expect("Query failed")[0].id, auth.id);
+                assert!(db.get_dependencies(billing.id).expect("Query failed").is_empty());
+                assert!(db.get_dependents(auth.id).expect("Query failed").is_empty());
+                assert!(db.get_blocked_feature_ids(project.id).expect("Query failed").is_empty());
+
+                // Either end can remove it
+                assert!(db.remove_dependency(auth.id, billing.id).expect("Query failed"));
+                assert!(db.get_related(billing.id).expect("Query failed").is_empty());
+            }
+
+            it "only checks cycles through dependency links" {
+                let project = create_test_project(&db);
+                let a = db.create_feature(project.id, CreateFeatureInput { id: None,
+                    parent_id: None,
+                    title: "A".to_string(),
+                    details: None,
+                    priority: None,
+                    state: None,
+                }).expect("Failed to create");
+                let b = db.create_feature(project.id, CreateFeatureInput { id: None,
+                    parent_id: None,
+                    title: "B".to_string(),
+                    details: None,
+                    priority: None,
+                    state: None,
+                }).expect("Failed to create");
+                let c = db.create_feature(project.id, CreateFeatureInput { id: None,
+                    parent_id: None,
+                    title: "C".to_string(),
+                    details: None,
+                    priority: None,
+                    state: None,
+                }).expect("Failed to create");
+
+                db.add_dependency(a.id, b.id).expect("Failed to add");
+                db.add_dependency(b.id, c.id).expect("Failed to add");
+
+                // A relation back to the start is not a cycle
+                db.add_link(c.id, a.id, LinkKind::Relates).expect("Failed to add");
+                assert!(db.remove_dependency(a.id, c.id).expect("Query failed"));
+
+                // C depending on A would be
+                let err = db.add_link(a.id, c.id, LinkKind::Blocks).expect_err("Cycle should be rejected");
+                assert!(err.to_string().contains("cycle"));
+            }
+
+            it "rejects a second kind of link between the same features" {
+                let project = create_test_project(&db);
+                let a = db.create_feature(project.id, CreateFeatureInput { id: None,
+                    parent_id: None,
+                    title: "A".to_string(),
+                    details: None,
+                    priority: None,
+                    state: None,
+                }).expect("Failed to create");
+                let b = db.create_feature(project.id, CreateFeatureInput { id: None,
+                    parent_id: None,
+                    title: "B".to_string(),
+                    details: None,
+                    priority: None,
+                    state: None,
+                }).expect("Failed to create");
+
+                assert!(db.add_link(a.id, a.id, LinkKind::Relates).is_err());
+
+                db.add_dependency(a.id, b.id).expect("Failed to add");
+                let err = db.add_link(b.id, a.id, LinkKind::Relates).expect_err("Second link should be rejected");
+                assert!(err.downcast_ref::<ManifestError>().is_some());
+                assert!(err.to_string().contains("already linked"));
+                assert!(db.add_link(a.id, b.id, LinkKind::Blocks).is_err());
+                assert!(db.get_related(a.id).expect("Query failed").is_empty());
+            }
+        }
+
+        describe "get_feature_diff" {
+            it "returns has_changes false when no desired_details" {
+                let project = create_test_project(&db);
+                let feature = db.create_feature(project.id, CreateFeatureInput { id: None,
+                    parent_id: None,
+                    title: "Feature".to_string(),
+                    details: Some("Current details".to_string()),
+                    priority: None,
+                    state: None,
+                }).expect("Failed to create");
+
+                let diff = db.get_feature_diff(feature.id).expect("Query failed").unwrap();
+                assert!(!diff.has_changes);
+                assert_eq!(diff.current, Some("Current details".to_string()));
+                assert!(diff.desired.is_none());
+            }
+
+            it "returns has_changes true when desired_details differs" {
+                let project = create_test_project(&db);
+                let feature = db.create_feature(project.id, CreateFeatureInput { id: None,
+                    parent_id: None,
+                    title: "Feature".to_string(),
+                    details: Some("Current".to_string()),
+                    priority: None,
+                    state: None,
+                }).expect("Failed to create");
+
+                db.update_feature(feature.id, UpdateFeatureInput {
+                    parent_id: None,
+                    title: None,
+                    details: None,
+                    desired_details: Some("Desired".to_string()),
+                    priority: None,
+                    state: None,
+                    expected_updated_at: None,
+                }).expect("Failed to update");
+
+                let diff = db.get_feature_diff(feature.id).expect("Query failed").unwrap();
+                assert!(diff.has_changes);
+                assert_eq!(diff.current, Some("Current".to_string()));
+                assert_eq!(diff.desired, Some("Desired".to_string()));
+            }
+
+            it "returns None for non-existent feature" {
+                let result = db.get_feature_diff(Uuid::new_v4()).expect("Query failed");
+                assert!(result.is_none());
+            }
+        }
+
+        describe "feature_to_markdown" {
+            it "returns None for non-existent feature" {
+                let result = db.feature_to_markdown(Uuid::new_v4(), true).expect("Query failed");
+                assert!(result.is_none());
+            }
+
+            it "renders title, metadata and details" {
+                let project = create_test_project(&db);
+                let feature = db.create_feature(project.id, CreateFeatureInput { id: None,
+                    parent_id: None,
+                    title: "Login".to_string(),
+                    details: Some("As a user I want to sign in.\n".to_string()),
+                    priority: Some(2),
+                    state: Some(FeatureState::Specified),
+                }).expect("Failed to create");
+                db.set_feature_tags(feature.id, &["auth".to_string()]).expect("Failed to tag");
+
+                let markdown = db.feature_to_markdown(feature.id, true).expect("Query failed").unwrap();
+
+                assert_eq!(
+                    markdown,
+                    "# Login\n\n**State:** specified · **Priority:** 2 · **Tags:** auth\n\nAs a user I want to sign in.\n"
+                );
+            }
+
+            it "lists the latest session's tasks as a checklist when asked" {
+                let project = create_test_project(&db);
+                let feature = db.create_feature(project.id, CreateFeatureInput { id: None,
+                    parent_id: None,
+                    title: "Login".to_string(),
+                    details: None,
+                    priority: None,
+                    state: None,
+                }).expect("Failed to create");
+                let task = |title: &str| CreateTaskInput {
+                    parent_id: None,
+                    title: title.to_string(),
+                    scope: "Scope".to_string(),
+                    agent_type: AgentType::Claude,
+                };
+                let session = db.create_session(CreateSessionInput {
+                    feature_id: feature.id,
+                    goal: "Build the form".to_string(),
+                    tasks: vec![task("Form"), task("Validation")],
+                }).expect("Failed to create");
+                db.update_task(session.tasks[0].id, UpdateTaskInput {
+                    status: Some(TaskStatus::Completed),
+                    worktree_path: None,
+                    branch: None,
+                    log_path: None,
+                }).expect("Failed to update");
+
+                let with_tasks = db.feature_to_markdown(feature.id, true).expect("Query failed").unwrap();
+                let without_tasks = db.feature_to_markdown(feature.id, false).expect("Query failed").unwrap();
+
+                assert!(with_tasks.ends_with("## Tasks\n\nBuild the form\n\n- [x] Form\n- [ ] Validation\n"));
+                assert!(!without_tasks.contains("## Tasks"));
+            }
+        }
+
+        describe "export_project" {
+            it "returns None for non-existent project" {
+                assert!(db.export_project(Uuid::new_v4()).expect("Query failed").is_none());
+                assert!(db.export_project_markdown(Uuid::new_v4()).expect("Query failed").is_none());
+            }
+
+            it "nests features with their tags, dependencies and tasks" {
+                let project = create_test_project(&db);
+                let feature = |title: &str, parent_id: Option<Uuid>| CreateFeatureInput { id: None,
+                    parent_id,
+                    title: title.to_string(),
+                    details: Some(format!("{} details", title)),
+                    priority: None,
+                    state: None,
+                };
+                let auth = db.create_feature(project.id, feature("Auth", None)).expect("Failed to create");
+                let login = db.create_feature(project.id, feature("Login", Some(auth.id))).expect("Failed to create");
+                let billing = db.create_feature(project.id, feature("Billing", None)).expect("Failed to create");
+                db.set_feature_tags(login.id, &["ui".to_string()]).expect("Failed to tag");
+                db.add_dependency(billing.id, login.id).expect("Failed to add dependency");
+                db.create_session(CreateSessionInput {
+                    feature_id: login.id,
+                    goal: "Build the form".to_string(),
+                    tasks: vec![CreateTaskInput {
+                        parent_id: None,
+                        title: "Form".to_string(),
+                        scope: "Scope".to_string(),
+                        agent_type: AgentType::Claude,
+                    }],
                 }).expect("Failed to create");
 
-                let diff = db.get_feature_diff(feature.id).expect("Query failed").unwrap();
-                assert!(!diff.has_changes);
-                assert_eq!(diff.current, Some("Current details".to_string()));
-                assert!(diff.desired.is_none());
+                let export = db.export_project(project.id).expect("Query failed").unwrap();
+                assert_eq!(export.version, EXPORT_VERSION);
+                assert_eq!(export.project.id, project.id);
+                let roots: Vec<&str> = export.features.iter().map(|f| f.feature.title.as_str()).collect();
+                assert_eq!(roots, vec!["Auth", "Billing"]);
+                let exported_login = &export.features[0].children[0];
+                assert_eq!(exported_login.tags, vec!["ui"]);
+                assert_eq!(exported_login.sessions[0].tasks[0].title, "Form");
+                assert_eq!(export.features[1].depends_on, vec![login.id]);
+
+                let markdown = db.export_project_markdown(project.id).expect("Query failed").unwrap();
+                assert!(markdown.starts_with("# Test Project\n\n## Auth\n"));
+                assert!(markdown.contains("\n### Login\n\n**State:** specified · **Priority:** 0 · **Tags:** ui\n\nLogin details\n"));
+                assert!(markdown.contains("\n#### Tasks\n\nBuild the form\n\n- [ ] Form\n"));
+                assert!(markdown.ends_with("\n## Billing\n\n**State:** proposed · **Priority:** 0\n\nBilling details\n"));
             }
+        }
 
-            it "returns has_changes true when desired_details differs" {
+        describe "import_project" {
+            it "round-trips an export, remapping ids already in use" {
                 let project = create_test_project(&db);
-                let feature = db.create_feature(project.id, CreateFeatureInput { id: None,
-                    parent_id: None,
-                    title: "Feature".to_string(),
-                    details: Some("Current".to_string()),
+                let feature = |title: &str, parent_id: Option<Uuid>| CreateFeatureInput { id: None,
+                    parent_id,
+                    title: title.to_string(),
+                    details: None,
                     priority: None,
                     state: None,
+                };
+                let auth = db.create_feature(project.id, feature("Auth", None)).expect("Failed to create");
+                let login = db.create_feature(project.id, feature("Login", Some(auth.id))).expect("Failed to create");
+                let billing = db.create_feature(project.id, feature("Billing", None)).expect("Failed to create");
+                db.set_feature_tags(login.id, &["ui".to_string()]).expect("Failed to tag");
+                db.add_dependency(billing.id, login.id).expect("Failed to add dependency");
+                db.add_link(auth.id, billing.id, LinkKind::Relates).expect("Failed to add link");
+                let session = db.create_session(CreateSessionInput {
+                    feature_id: login.id,
+                    goal: "Build the form".to_string(),
+                    tasks: vec![CreateTaskInput {
+                        parent_id: None,
+                        title: "Form".to_string(),
+                        scope: "Scope".to_string(),
+                        agent_type: AgentType::Claude,
+                    }],
                 }).expect("Failed to create");
+                db.complete_session(session.session.id, CompleteSessionInput {
+                    summary: "Built it".to_string(),
+                    commits: vec![],
+                    feature_state: None,
+                }).expect("Failed to complete");
+                let export = db.export_project(project.id).expect("Query failed").unwrap();
+
+                // Every id is taken in this database, so the copy gets fresh ones
+                let copy = db.import_project(export.clone()).expect("Failed to import");
+                assert_ne!(copy.id, project.id);
+                assert_eq!(copy.name, project.name);
+
+                let imported = db.export_project(copy.id).expect("Query failed").unwrap();
+                let copy_auth = &imported.features[0];
+                let copy_login = &copy_auth.children[0];
+                let copy_billing = &imported.features[1];
+                assert_ne!(copy_login.feature.id, login.id);
+                assert_eq!(copy_login.feature.parent_id, Some(copy_auth.feature.id));
+                assert_eq!(copy_login.feature.project_id, copy.id);
+                assert_eq!(copy_login.tags, vec!["ui"]);
+                assert_eq!(copy_billing.depends_on, vec![copy_login.feature.id]);
+                assert_eq!(copy_billing.related, vec![copy_auth.feature.id]);
+                assert_eq!(db.get_related(copy_auth.feature.id).expect("Query failed").len(), 1);
+                assert_eq!(copy_login.history[0].details.summary, "Built it");
+                assert_eq!(copy_login.history[0].session_id, Some(copy_login.sessions[0].session.id));
+                assert_eq!(copy_login.feature.created_at, login.created_at);
+
+                // A fresh database keeps the original ids
+                let other = Database::open_memory().expect("Failed to create in-memory database");
+                other.migrate().expect("Failed to run migrations");
+                let moved = other.import_project(export).expect("Failed to import");
+                assert_eq!(moved.id, project.id);
+                assert_eq!(other.get_feature(login.id).expect("Query failed").unwrap().parent_id, Some(auth.id));
+            }
 
-                db.update_feature(feature.id, UpdateFeatureInput {
+            it "rolls back when any row fails" {
+                let project = create_test_project(&db);
+                let feature = db.create_feature(project.id, CreateFeatureInput { id: None,
                     parent_id: None,
-                    title: None,
+                    title: "Login".to_string(),
                     details: None,
-                    desired_details: Some("Desired".to_string()),
                     priority: None,
                     state: None,
-                }).expect("Failed to update");
+                }).expect("Failed to create");
+                let task = CreateTaskInput {
+                    parent_id: None,
+                    title: "Form".to_string(),
+                    scope: "Scope".to_string(),
+                    agent_type: AgentType::Claude,
+                };
+                db.create_session(CreateSessionInput {
+                    feature_id: feature.id,
+                    goal: "Build the form".to_string(),
+                    tasks: vec![task],
+                }).expect("Failed to create");
+                let mut export = db.export_project(project.id).expect("Query failed").unwrap();
 
-                let diff = db.get_feature_diff(feature.id).expect("Query failed").unwrap();
-                assert!(diff.has_changes);
-                assert_eq!(diff.current, Some("Current".to_string()));
-                assert_eq!(diff.desired, Some("Desired".to_string()));
+                // The same task twice can't be inserted, after the project and feature were
+                let tasks = &mut export.features[0].sessions[0].tasks;
+                tasks.push(tasks[0].clone());
+
+                assert!(db.import_project(export).is_err());
+                assert_eq!(db.get_all_projects().expect("Query failed").len(), 1);
             }
 
-            it "returns None for non-existent feature" {
-                let result = db.get_feature_diff(Uuid::new_v4()).expect("Query failed");
-                assert!(result.is_none());
+            it "rejects exports from a newer version" {
+                let project = create_test_project(&db);
+                let mut export = db.export_project(project.id).expect("Query failed").unwrap();
+                export.version = EXPORT_VERSION + 1;
+
+                let err = db.import_project(export).unwrap_err();
+                assert!(matches!(err.downcast_ref::<ManifestError>(), Some(ManifestError::Validation(_))));
             }
         }
 
@@ -408,6 +1511,7 @@ speculate! {
                     desired_details: Some("Desired".to_string()),
                     priority: None,
                     state: None,
+                    expected_updated_at: None,
                 }).expect("Failed to update").unwrap();
 
                 assert_eq!(updated.details, Some("Current".to_string()));
@@ -524,6 +1628,27 @@ speculate! {
                 assert_eq!(results[0].title, "Auth Feature");
             }
 
+            it "matches LIKE wildcards literally" {
+                let project = create_test_project(&db);
+                for title in ["snake_case names", "snakeXcase names", "100% coverage"] {
+                    db.create_feature(project.id, CreateFeatureInput { id: None,
+                        parent_id: None,
+                        title: title.to_string(),
+                        details: None,
+                        priority: None,
+                        state: None,
+                    }).expect("Failed to create");
+                }
+
+                let results = db.search_features("snake_case", None, None).expect("Query failed");
+                assert_eq!(results.len(), 1);
+                assert_eq!(results[0].title, "snake_case names");
+
+                let results = db.search_features("0%", None, None).expect("Query failed");
+                assert_eq!(results.len(), 1);
+                assert_eq!(results[0].title, "100% coverage");
+            }
+
             it "respects limit parameter" {
                 let project = create_test_project(&db);
                 for i in 1..=5 {
@@ -577,6 +1702,101 @@ speculate! {
                 // FeatureSummary doesn't have details field - that's the point!
             }
         }
+
+        describe "search" {
+            it "finds partial words ranked by relevance with highlighted snippets" {
+                let project = create_test_project(&db);
+                let feature = |title: &str, details: &str| CreateFeatureInput { id: None,
+                    parent_id: None,
+                    title: title.to_string(),
+                    details: Some(details.to_string()),
+                    priority: None,
+                    state: None,
+                };
+                db.create_feature(project.id, feature("Billing", "Invoices are sent after authentication")).expect("Failed to create");
+                db.create_feature(project.id, feature("User Authentication", "Sign in with a passkey")).expect("Failed to create");
+                db.create_feature(project.id, feature("Exports", "CSV and JSON")).expect("Failed to create");
+
+                let hits = db.search("auth", None, None).expect("Query failed");
+                let titles: Vec<&str> = hits.iter().map(|h| h.feature.title.as_str()).collect();
+                assert_eq!(titles, vec!["User Authentication", "Billing"]);
+                assert_eq!(hits[0].snippet.as_deref(), Some("User **Auth**entication"));
+                assert!(hits[1].snippet.as_deref().unwrap().contains("**auth**entication"));
+
+                // Every word must match, in any order
+                let hits = db.search("invoice thentic", None, None).expect("Query failed");
+                assert_eq!(hits.len(), 1);
+                assert_eq!(hits[0].feature.title, "Billing");
+            }
+
+            it "keeps the index in step with updates and deletes" {
+                let project = create_test_project(&db);
+                let parent = db.create_feature(project.id, CreateFeatureInput { id: None,
+                    parent_id: None,
+                    title: "Reporting".to_string(),
+                    details: None,
+                    priority: None,
+                    state: None,
+                }).expect("Failed to create");
+                let child = db.create_feature(project.id, CreateFeatureInput { id: None,
+                    parent_id: Some(parent.id),
+                    title: "Dashboards".to_string(),
+                    details: None,
+                    priority: None,
+                    state: None,
+                }).expect("Failed to create");
+
+                db.update_feature(child.id, UpdateFeatureInput {
+                    title: Some("Charts".to_string()),
+                    details: Some("Line and bar charts".to_string()),
+                    desired_details: None,
+                    state: None,
+                    parent_id: None,
+                    priority: None,
+                    expected_updated_at: None,
+                }).expect("Failed to update");
+                assert!(db.search("dashboard", None, None).expect("Query failed").is_empty());
+                assert_eq!(db.search("bar chart", None, None).expect("Query failed").len(), 1);
+
+                // The child is deleted along with it
+                db.delete_feature(parent.id).expect("Failed to delete");
+                assert!(db.search("chart", None, None).expect("Query failed").is_empty());
+                assert!(db.search("reporting", None, None).expect("Query failed").is_empty());
+            }
+
+            it "matches query syntax literally and filters by project" {
+                let project = create_test_project(&db);
+                let other = create_test_project(&db);
+                for (project_id, title) in [(project.id, "C++ \"bindings\" OR more"), (other.id, "C++ tooling")] {
+                    db.create_feature(project_id, CreateFeatureInput { id: None,
+                        parent_id: None,
+                        title: title.to_string(),
+                        details: None,
+                        priority: None,
+                        state: None,
+                    }).expect("Failed to create");
+                }
+
+                let hits = db.search("C++ \"bindings\" OR", Some(project.id), None).expect("Query failed");
+                assert_eq!(hits.len(), 1);
+                assert_eq!(db.search("C++", None, None).expect("Query failed").len(), 2);
+            }
+
+            it "falls back to substring search for short queries" {
+                let project = create_test_project(&db);
+                db.create_feature(project.id, CreateFeatureInput { id: None,
+                    parent_id: None,
+                    title: "Go client".to_string(),
+                    details: None,
+                    priority: None,
+                    state: None,
+                }).expect("Failed to create");
+
+                let hits = db.search("go", None, None).expect("Query failed");
+                assert_eq!(hits.len(), 1);
+                assert!(hits[0].snippet.is_none());
+            }
+        }
     }
 
     describe "feature_hierarchy" {
@@ -764,6 +1984,84 @@ speculate! {
             }
         }
 
+        describe "reorder_features" {
+            it "orders siblings as listed" {
+                let project = create_test_project(&db);
+                let parent = db.create_feature(project.id, CreateFeatureInput { id: None,
+                    parent_id: None,
+                    title: "Parent".to_string(),
+                    details: None,
+                    priority: None,
+                    state: None,
+                }).expect("Failed to create");
+                let mut ids = Vec::new();
+                for title in ["Alpha", "Beta", "Gamma"] {
+                    let child = db.create_feature(project.id, CreateFeatureInput { id: None,
+                        parent_id: Some(parent.id),
+                        title: title.to_string(),
+                        details: None,
+                        priority: None,
+                        state: None,
+                    }).expect("Failed to create");
+                    ids.push(child.id);
+                }
+
+                let reordered = db.reorder_features(&[ids[2], ids[0]]).expect("Failed to reorder");
+                assert_eq!(reordered.iter().map(|f| f.priority).collect::<Vec<_>>(), vec![0, 1]);
+                db.reorder_features(&[ids[2], ids[0], ids[1]]).expect("Failed to reorder");
+
+                let titles: Vec<_> = db.get_children(parent.id).expect("Query failed")
+                    .into_iter().map(|f| f.title).collect();
+                assert_eq!(titles, vec!["Gamma", "Alpha", "Beta"]);
+                let tree = db.get_feature_tree(project.id).expect("Query failed");
+                assert_eq!(tree[0].children[0].feature.title, "Gamma");
+            }
+
+            it "puts siblings left out after the listed ones" {
+                let project = create_test_project(&db);
+                let mut ids = Vec::new();
+                for (priority, title) in ["A", "B", "C"].into_iter().enumerate() {
+                    let feature = db.create_feature(project.id, CreateFeatureInput { id: None,
+                        parent_id: None,
+                        title: title.to_string(),
+                        details: None,
+                        priority: Some(priority as i32),
+                        state: None,
+                    }).expect("Failed to create");
+                    ids.push(feature.id);
+                }
+
+                db.reorder_features(&[ids[2], ids[1]]).expect("Failed to reorder");
+
+                let tree = db.get_feature_tree(project.id).expect("Query failed");
+                let order: Vec<_> = tree.iter().map(|n| (n.feature.title.as_str(), n.feature.priority)).collect();
+                assert_eq!(order, vec![("C", 0), ("B", 1), ("A", 2)]);
+            }
+
+            it "rejects features that are not siblings or are listed twice" {
+                let project = create_test_project(&db);
+                let parent = db.create_feature(project.id, CreateFeatureInput { id: None,
+                    parent_id: None,
+                    title: "Parent".to_string(),
+                    details: None,
+                    priority: None,
+                    state: None,
+                }).expect("Failed to create");
+                let child = db.create_feature(project.id, CreateFeatureInput { id: None,
+                    parent_id: Some(parent.id),
+                    title: "Child".to_string(),
+                    details: None,
+                    priority: None,
+                    state: None,
+                }).expect("Failed to create");
+
+                assert!(db.reorder_features(&[parent.id, child.id]).is_err());
+                assert!(db.reorder_features(&[child.id, child.id]).is_err());
+                assert!(db.reorder_features(&[Uuid::new_v4()]).is_err());
+                assert_eq!(db.get_feature(parent.id).unwrap().unwrap().priority, 0);
+            }
+        }
+
         describe "is_leaf" {
             it "returns true for feature with no children" {
                 let project = create_test_project(&db);
@@ -803,6 +2101,52 @@ speculate! {
             }
         }
 
+        describe "get_feature_tree" {
+            it "groups a large project into a tree from one listing" {
+                let project = create_test_project(&db);
+                let input = |id: Uuid, parent_id: Option<Uuid>, title: String| CreateFeatureInput {
+                    id: Some(id),
+                    parent_id,
+                    title,
+                    details: None,
+                    priority: None,
+                    state: None,
+                };
+
+                let mut inputs = Vec::new();
+                for group in 0..50 {
+                    let group_id = Uuid::new_v4();
+                    inputs.push(input(group_id, None, format!("Group {:02}", group)));
+                    for leaf in 0..100 {
+                        inputs.push(input(
+                            Uuid::new_v4(),
+                            Some(group_id),
+                            format!("Feature {:02}-{:03}", group, leaf),
+                        ));
+                    }
+                }
+                db.create_features_bulk(project.id, inputs).expect("Failed to create");
+
+                let (tree, statements) = db.count_statements(|| db.get_feature_tree(project.id));
+                let tree = tree.expect("Query failed");
+
+                // One listing, however many features there are
+                assert_eq!(statements, 1);
+                let count: usize = tree.iter().map(|group| 1 + group.children.len()).sum();
+                assert_eq!(count, 5050);
+                assert_eq!(tree.len(), 50);
+                for (g, group) in tree.iter().enumerate() {
+                    assert_eq!(group.feature.title, format!("Group {:02}", g));
+                    assert_eq!(group.children.len(), 100);
+                    for (l, leaf) in group.children.iter().enumerate() {
+                        assert_eq!(leaf.feature.title, format!("Feature {:02}-{:03}", g, l));
+                        assert_eq!(leaf.feature.parent_id, Some(group.feature.id));
+                        assert!(leaf.children.is_empty());
+                    }
+                }
+            }
+        }
+
         describe "cascade delete" {
             it "deletes children when parent is deleted" {
                 let project = create_test_project(&db);
@@ -959,6 +2303,165 @@ speculate! {
             }
         }
 
+        describe "get_session_summary" {
+            it "returns None for non-existent session" {
+                let result = db.get_session_summary(Uuid::new_v4()).expect("Query failed");
+                assert!(result.is_none());
+            }
+
+            it "counts tasks by status for an active session" {
+                let project = create_test_project(&db);
+                let feature = db.create_feature(project.id, CreateFeatureInput { id: None,
+                    parent_id: None,
+                    title: "Feature".to_string(),
+                    details: None,
+                    priority: None,
+                    state: None,
+                }).expect("Failed to create");
+
+                let task = |title: &str| CreateTaskInput {
+                    parent_id: None,
+                    title: title.to_string(),
+                    scope: "Scope".to_string(),
+                    agent_type: AgentType::Claude,
+                };
+                let session_response = db.create_session(CreateSessionInput {
+                    feature_id: feature.id,
+                    goal: "Goal".to_string(),
+                    tasks: vec![task("Done"), task("Broken"), task("Waiting")],
+                }).expect("Failed to create");
+
+                db.update_task(session_response.tasks[0].id, UpdateTaskInput {
+                    status: Some(TaskStatus::Completed),
+                    worktree_path: None,
+                    branch: None,
+                    log_path: None,
+                }).expect("Failed to update");
+                db.update_task(session_response.tasks[1].id, UpdateTaskInput {
+                    status: Some(TaskStatus::Failed),
+                    worktree_path: None,
+                    branch: None,
+                    log_path: None,
+                }).expect("Failed to update");
+
+                let summary = db.get_session_summary(session_response.session.id)
+                    .expect("Query failed")
+                    .expect("Session not found");
+
+                assert_eq!(summary.tasks.len(), 3);
+                assert_eq!(summary.task_counts, TaskCounts {
+                    total: 3,
+                    pending: 1,
+                    running: 0,
+                    completed: 1,
+                    failed: 1,
+                });
+                assert!(summary.summary.is_none());
+                assert!(summary.commits.is_empty());
+            }
+
+            it "reports summary and deduplicated commits after completion" {
+                let project = create_test_project(&db);
+                let feature = db.create_feature(project.id, CreateFeatureInput { id: None,
+                    parent_id: None,
+                    title: "Feature".to_string(),
+                    details: None,
+                    priority: None,
+                    state: None,
+                }).expect("Failed to create");
+
+                let session_response = db.create_session(CreateSessionInput {
+                    feature_id: feature.id,
+                    goal: "Goal".to_string(),
+                    tasks: vec![],
+                }).expect("Failed to create");
+
+                let commit = |sha: &str| CommitRef {
+                    sha: sha.to_string(),
+                    message: "Commit".to_string(),
+                    author: None,
+                };
+                db.complete_session(session_response.session.id, CompleteSessionInput {
+                    summary: "Shipped it".to_string(),
+                    commits: vec![commit("abc1234"), commit("def5678"), commit("abc1234")],
+                    feature_state: None,
+                }).expect("Failed to complete");
+
+                let summary = db.get_session_summary(session_response.session.id)
+                    .expect("Query failed")
+                    .expect("Session not found");
+
+                assert_eq!(summary.session.status, SessionStatus::Completed);
+                assert_eq!(summary.summary.as_deref(), Some("Shipped it"));
+                let shas: Vec<&str> = summary.commits.iter().map(|c| c.sha.as_str()).collect();
+                assert_eq!(shas, vec!["abc1234", "def5678"]);
+            }
+        }
+
+        describe "get_active_task_counts" {
+            it "counts tasks only for features with an active session" {
+                let project = create_test_project(&db);
+                let feature = |title: &str| db.create_feature(project.id, CreateFeatureInput { id: None,
+                    parent_id: None,
+                    title: title.to_string(),
+                    details: None,
+                    priority: None,
+                    state: None,
+                }).expect("Failed to create");
+                let active = feature("Active");
+                let finished = feature("Finished");
+                let idle = feature("Idle");
+
+                let task = |title: &str| CreateTaskInput {
+                    parent_id: None,
+                    title: title.to_string(),
+                    scope: "Scope".to_string(),
+                    agent_type: AgentType::Claude,
+                };
+                let active_session = db.create_session(CreateSessionInput {
+                    feature_id: active.id,
+                    goal: "Goal".to_string(),
+                    tasks: vec![task("Done"), task("Running"), task("Waiting")],
+                }).expect("Failed to create");
+                db.update_task(active_session.tasks[0].id, UpdateTaskInput {
+                    status: Some(TaskStatus::Completed),
+                    worktree_path: None,
+                    branch: None,
+                    log_path: None,
+                }).expect("Failed to update");
+                db.update_task(active_session.tasks[1].id, UpdateTaskInput {
+                    status: Some(TaskStatus::Running),
+                    worktree_path: None,
+                    branch: None,
+                    log_path: None,
+                }).expect("Failed to update");
+
+                let finished_session = db.create_session(CreateSessionInput {
+                    feature_id: finished.id,
+                    goal: "Goal".to_string(),
+                    tasks: vec![task("Done")],
+                }).expect("Failed to create");
+                db.complete_session(finished_session.session.id, CompleteSessionInput {
+                    summary: "Done".to_string(),
+                    commits: vec![],
+                    feature_state: None,
+                }).expect("Failed to complete");
+
+                let counts = db.get_active_task_counts(project.id).expect("Query failed");
+
+                assert_eq!(counts.len(), 1);
+                assert_eq!(counts.get(&active.id), Some(&TaskCounts {
+                    total: 3,
+                    pending: 1,
+                    running: 1,
+                    completed: 1,
+                    failed: 0,
+                }));
+                assert!(!counts.contains_key(&finished.id));
+                assert!(!counts.contains_key(&idle.id));
+            }
+        }
+
         describe "complete_session" {
             it "returns None for non-existent session" {
                 let result = db.complete_session(Uuid::new_v4(), CompleteSessionInput {
@@ -1091,6 +2594,7 @@ speculate! {
                     desired_details: Some("New desired details".to_string()),
                     priority: None,
                     state: None,
+                    expected_updated_at: None,
                 }).expect("Failed to update");
 
                 let session_response = db.create_session(CreateSessionInput {
@@ -1265,7 +2769,7 @@ speculate! {
         }
 
         describe "cascade delete" {
-            it "deletes history when feature is deleted" {
+            it "deletes history when feature is purged" {
                 let project = create_test_project(&db);
                 let feature = db.create_feature(project.id, CreateFeatureInput { id: None,
                     parent_id: None,
@@ -1287,11 +2791,137 @@ speculate! {
 
                 db.delete_feature(feature.id).expect("Failed to delete");
 
-                // History should be gone (cascade delete)
+                // History is kept in the trash and goes when it is purged
+                assert_eq!(db.get_feature_history(feature.id).expect("Query failed").len(), 1);
+                db.purge_deleted(chrono::Utc::now() + chrono::Duration::seconds(1)).expect("Failed to purge");
                 let history = db.get_feature_history(feature.id).expect("Query failed");
                 assert!(history.is_empty());
             }
         }
     }
 
+    describe "read_cache" {
+        it "serves updated features after writes through the cached handle" {
+            let db = db.with_cache(16);
+            let project = create_test_project(&db);
+            let feature = db.create_feature(project.id, CreateFeatureInput { id: None,
+                parent_id: None,
+                title: "Before".to_string(),
+                details: None,
+                priority: None,
+                state: None,
+            }).expect("Failed to create");
+
+            assert_eq!(db.get_feature(feature.id).expect("Query failed").unwrap().title, "Before");
+            assert_eq!(db.get_feature_tree(project.id).expect("Query failed").len(), 1);
+
+            db.update_feature(feature.id, UpdateFeatureInput {
+                title: Some("After".to_string()),
+                details: None,
+                desired_details: None,
+                state: None,
+                parent_id: None,
+                priority: None,
+                expected_updated_at: None,
+            }).expect("Failed to update");
+            db.create_feature(project.id, CreateFeatureInput { id: None,
+                parent_id: None,
+                title: "Second".to_string(),
+                details: None,
+                priority: None,
+                state: None,
+            }).expect("Failed to create");
+
+            assert_eq!(db.get_feature(feature.id).expect("Query failed").unwrap().title, "After");
+            let tree = db.get_feature_tree(project.id).expect("Query failed");
+            let titles: Vec<&str> = tree.iter().map(|n| n.feature.title.as_str()).collect();
+            assert_eq!(titles, vec!["After", "Second"]);
+
+            db.delete_feature(feature.id).expect("Failed to delete");
+            assert!(db.get_feature(feature.id).expect("Query failed").is_none());
+        }
+    }
+
+    describe "change_listener" {
+        it "reports each committed write" {
+            let events = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+            let db = db.with_change_listener({
+                let events = events.clone();
+                move |event| events.lock().unwrap().push(event.clone())
+            });
+            let project = create_test_project(&db);
+            let feature = db.create_feature(project.id, CreateFeatureInput { id: None,
+                parent_id: None,
+                title: "Watched".to_string(),
+                details: None,
+                priority: None,
+                state: None,
+            }).expect("Failed to create");
+            let session = db.create_session(CreateSessionInput {
+                feature_id: feature.id,
+                goal: "Build it".to_string(),
+                tasks: vec![],
+            }).expect("Failed to create");
+            let task = db.create_task(session.session.id, CreateTaskInput {
+                parent_id: None,
+                title: "Task".to_string(),
+                scope: "All of it".to_string(),
+                agent_type: AgentType::Claude,
+            }).expect("Failed to create");
+            db.update_task(task.id, UpdateTaskInput {
+                status: Some(TaskStatus::Running),
+                worktree_path: None,
+                branch: None,
+                log_path: None,
+            }).expect("Failed to update");
+            // Missing rows change nothing, so report nothing
+            assert!(!db.delete_feature(Uuid::new_v4()).expect("Failed to delete"));
+            db.delete_feature(feature.id).expect("Failed to delete");
+
+            let changed = ChangeEvent::FeatureChanged { project_id: project.id, feature_id: feature.id };
+            let task_changed = ChangeEvent::TaskChanged {
+                project_id: project.id,
+                session_id: session.session.id,
+                task_id: task.id,
+            };
+            assert_eq!(*events.lock().unwrap(), vec![
+                changed.clone(),
+                changed,
+                task_changed.clone(),
+                task_changed,
+                ChangeEvent::FeatureDeleted { project_id: project.id, feature_id: feature.id },
+            ]);
+        }
+
+        it "reports every feature a delete or restore moves" {
+            let events = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+            let db = db.with_change_listener({
+                let events = events.clone();
+                move |event| events.lock().unwrap().push(event.clone())
+            });
+            let project = create_test_project(&db);
+            let create = |parent_id: Option<Uuid>, title: &str| db.create_feature(project.id, CreateFeatureInput { id: None,
+                parent_id,
+                title: title.to_string(),
+                details: None,
+                priority: None,
+                state: None,
+            }).expect("Failed to create");
+            let parent = create(None, "Parent");
+            let child = create(Some(parent.id), "Child");
+            events.lock().unwrap().clear();
+
+            db.delete_feature(parent.id).expect("Failed to delete");
+            let deleted = events.lock().unwrap().drain(..).collect::<Vec<_>>();
+            assert_eq!(deleted.len(), 2);
+            assert!(deleted.contains(&ChangeEvent::FeatureDeleted { project_id: project.id, feature_id: parent.id }));
+            assert!(deleted.contains(&ChangeEvent::FeatureDeleted { project_id: project.id, feature_id: child.id }));
+
+            db.restore_feature(parent.id).expect("Failed to restore");
+            let restored = events.lock().unwrap().drain(..).collect::<Vec<_>>();
+            assert_eq!(restored.len(), 2);
+            assert!(restored.contains(&ChangeEvent::FeatureChanged { project_id: project.id, feature_id: parent.id }));
+            assert!(restored.contains(&ChangeEvent::FeatureChanged { project_id: project.id, feature_id: child.id }));
+        }
+    }
 }
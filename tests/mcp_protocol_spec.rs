@@ -1,7 +1,8 @@
 //! MCP protocol integration tests.
 //!
 //! These tests spawn the actual `mfst mcp` process and communicate via
-//! JSON-RPC over stdio, testing the complete MCP protocol flow.
+//! JSON-RPC over stdio, testing the complete MCP protocol flow. The
+//! `http_transport` tests cover the same server behind `mfst mcp --http`.
 //!
 //! The rmcp library uses line-delimited JSON (each message is one line):
 //! ```
@@ -57,12 +58,19 @@ struct McpTestClient {
 impl McpTestClient {
     /// Spawn a new MCP server process with an isolated test database
     fn spawn() -> Self {
+        Self::spawn_with_args(&[])
+    }
+
+    /// Spawn `mfst mcp` with extra arguments, running in the test's home directory
+    fn spawn_with_args(args: &[&str]) -> Self {
         // Create temp directory for test database
         let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
         let home_dir = temp_dir.path().to_path_buf();
 
         let mut child = Command::new(env!("CARGO_BIN_EXE_mfst"))
             .arg("mcp")
+            .args(args)
+            .current_dir(temp_dir.path())
             .env("XDG_DATA_HOME", temp_dir.path())
             .env("HOME", temp_dir.path()) // For macOS directories crate
             .stdin(Stdio::piped())
@@ -203,11 +211,11 @@ mod protocol {
         let tools = result.get("tools").expect("Expected tools array");
         let tools_array = tools.as_array().expect("Tools should be array");
 
-        // We have 19 tools
+        // We have 28 tools
         assert_eq!(
             tools_array.len(),
-            19,
-            "Expected 19 tools, got {}",
+            28,
+            "Expected 28 tools, got {}",
             tools_array.len()
         );
 
@@ -224,11 +232,20 @@ mod protocol {
         assert!(tool_names.contains(&"create_task"));
         assert!(tool_names.contains(&"breakdown_feature"));
         assert!(tool_names.contains(&"list_session_tasks"));
+        assert!(tool_names.contains(&"get_session_summary"));
         assert!(tool_names.contains(&"complete_session"));
         assert!(tool_names.contains(&"list_features"));
         assert!(tool_names.contains(&"search_features"));
+        assert!(tool_names.contains(&"filter_features_by_tag"));
         assert!(tool_names.contains(&"get_feature"));
         assert!(tool_names.contains(&"get_feature_history"));
+        assert!(tool_names.contains(&"get_feature_subtree"));
+        assert!(tool_names.contains(&"get_feature_tree"));
+        assert!(tool_names.contains(&"get_active_context"));
+        assert!(tool_names.contains(&"get_feature_dependencies"));
+        assert!(tool_names.contains(&"reprioritize_feature"));
+        assert!(tool_names.contains(&"reorder_features"));
+        assert!(tool_names.contains(&"add_feature_dependency"));
         assert!(tool_names.contains(&"get_project_context"));
         assert!(tool_names.contains(&"update_feature_state"));
         assert!(tool_names.contains(&"create_project"));
@@ -372,6 +389,20 @@ mod tool_calls {
         let feature: Value = serde_json::from_str(&feature_text).unwrap();
         let feature_id = feature["id"].as_str().unwrap();
 
+        let prerequisite_text = extract_text_content(&client.call_tool(
+            "create_feature",
+            json!({ "project_id": project_id, "title": "Prerequisite" }),
+        ));
+        let prerequisite: Value = serde_json::from_str(&prerequisite_text).unwrap();
+        let dependency_response = client.call_tool(
+            "add_feature_dependency",
+            json!({
+                "feature_id": feature_id,
+                "depends_on_id": prerequisite["id"].as_str().unwrap()
+            }),
+        );
+        assert!(dependency_response.error.is_none());
+
         // 1. Create session
         let session_text = extract_text_content(&client.call_tool(
             "create_session",
@@ -405,6 +436,10 @@ mod tool_calls {
         let context: Value = serde_json::from_str(&context_text).unwrap();
         assert_eq!(context["task"]["title"].as_str(), Some("Write tests"));
         assert_eq!(context["feature"]["title"].as_str(), Some("Test Feature"));
+        assert_eq!(
+            context["blocked_by"][0]["title"].as_str(),
+            Some("Prerequisite")
+        );
 
         // 4. Start task
         let start_response = client.call_tool("start_task", json!({ "task_id": task_id }));
@@ -480,6 +515,284 @@ mod tool_calls {
         );
     }
 
+    #[test]
+    fn get_feature_subtree_nests_and_caps_descendants() {
+        let mut client = McpTestClient::spawn();
+        client.initialize();
+
+        let project_text = extract_text_content(
+            &client.call_tool("create_project", json!({ "name": "Subtree Test" })),
+        );
+        let project: Value = serde_json::from_str(&project_text).unwrap();
+        let project_id = project["id"].as_str().unwrap();
+
+        let mut create = |title: &str, parent_id: Option<&str>| -> String {
+            let text = extract_text_content(&client.call_tool(
+                "create_feature",
+                json!({ "project_id": project_id, "title": title, "parent_id": parent_id }),
+            ));
+            let feature: Value = serde_json::from_str(&text).unwrap();
+            feature["id"].as_str().unwrap().to_string()
+        };
+        let root = create("Payments", None);
+        let cards = create("Cards", Some(&root));
+        create("Refunds", Some(&root));
+        create("3-D Secure", Some(&cards));
+
+        let text = extract_text_content(
+            &client.call_tool("get_feature_subtree", json!({ "feature_id": root })),
+        );
+        let subtree: Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(subtree["feature"]["title"].as_str(), Some("Payments"));
+        assert_eq!(subtree["feature"]["children"].as_array().unwrap().len(), 2);
+        assert_eq!(subtree["feature_count"].as_u64(), Some(4));
+        assert_eq!(subtree["truncated"].as_bool(), Some(false));
+
+        let text = extract_text_content(&client.call_tool(
+            "get_feature_subtree",
+            json!({ "feature_id": root, "max_depth": 1 }),
+        ));
+        let subtree: Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(subtree["feature_count"].as_u64(), Some(3));
+        assert_eq!(subtree["truncated"].as_bool(), Some(true));
+
+        let text = extract_text_content(&client.call_tool(
+            "get_feature_subtree",
+            json!({ "feature_id": root, "max_features": 2 }),
+        ));
+        let subtree: Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(subtree["feature_count"].as_u64(), Some(2));
+        assert_eq!(subtree["truncated"].as_bool(), Some(true));
+    }
+
+    #[test]
+    fn get_feature_tree_nests_and_filters_by_state() {
+        let mut client = McpTestClient::spawn();
+        client.initialize();
+
+        let project_text = extract_text_content(
+            &client.call_tool("create_project", json!({ "name": "Tree Test" })),
+        );
+        let project: Value = serde_json::from_str(&project_text).unwrap();
+        let project_id = project["id"].as_str().unwrap();
+
+        let mut create = |title: &str, parent_id: Option<&str>| -> String {
+            let text = extract_text_content(&client.call_tool(
+                "create_feature",
+                json!({ "project_id": project_id, "title": title, "parent_id": parent_id }),
+            ));
+            let feature: Value = serde_json::from_str(&text).unwrap();
+            feature["id"].as_str().unwrap().to_string()
+        };
+        let root = create("Payments", None);
+        let cards = create("Cards", Some(&root));
+        create("Refunds", Some(&root));
+        create("Reporting", None);
+        client.call_tool(
+            "update_feature_state",
+            json!({ "feature_id": cards, "state": "implemented" }),
+        );
+
+        let text = extract_text_content(
+            &client.call_tool("get_feature_tree", json!({ "project_id": project_id })),
+        );
+        let tree: Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(tree["features"].as_array().unwrap().len(), 2);
+        assert_eq!(tree["feature_count"].as_u64(), Some(4));
+
+        let text = extract_text_content(&client.call_tool(
+            "get_feature_tree",
+            json!({ "project_id": project_id, "state_filter": "implemented" }),
+        ));
+        let tree: Value = serde_json::from_str(&text).unwrap();
+        let roots = tree["features"].as_array().unwrap();
+        assert_eq!(roots.len(), 1);
+        assert_eq!(roots[0]["title"].as_str(), Some("Payments"));
+        assert_eq!(roots[0]["children"][0]["title"].as_str(), Some("Cards"));
+        assert_eq!(tree["feature_count"].as_u64(), Some(2));
+
+        let response = client.call_tool(
+            "get_feature_tree",
+            json!({ "project_id": "00000000-0000-0000-0000-0000000000ff" }),
+        );
+        assert!(response.error.is_some());
+    }
+
+    #[test]
+    fn features_are_readable_as_resources() {
+        let mut client = McpTestClient::spawn();
+        client.initialize();
+
+        let project_text = extract_text_content(
+            &client.call_tool("create_project", json!({ "name": "Resource Test" })),
+        );
+        let project: Value = serde_json::from_str(&project_text).unwrap();
+        let project_id = project["id"].as_str().unwrap().to_string();
+        let feature_text = extract_text_content(&client.call_tool(
+            "create_feature",
+            json!({ "project_id": project_id, "title": "Exports", "details": "CSV and JSON" }),
+        ));
+        let feature: Value = serde_json::from_str(&feature_text).unwrap();
+        let feature_uri = format!("manifest://feature/{}", feature["id"].as_str().unwrap());
+        let project_uri = format!("manifest://project/{}/features", project_id);
+
+        let listed = client.request("resources/list", None).result.unwrap();
+        assert!(listed["resources"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|r| r["uri"].as_str() == Some(project_uri.as_str())));
+
+        let read = |client: &mut McpTestClient, uri: &str| {
+            let result = client
+                .request("resources/read", Some(json!({ "uri": uri })))
+                .result
+                .expect("Expected result");
+            result["contents"][0]["text"].as_str().unwrap().to_string()
+        };
+        let project_markdown = read(&mut client, &project_uri);
+        assert!(project_markdown.contains(&format!("[Exports]({})", feature_uri)));
+        let feature_markdown = read(&mut client, &feature_uri);
+        assert!(feature_markdown.starts_with("# Exports"));
+        assert!(feature_markdown.contains("CSV and JSON"));
+
+        let missing = client.request(
+            "resources/read",
+            Some(json!({ "uri": "manifest://feature/00000000-0000-0000-0000-0000000000ff" })),
+        );
+        assert!(missing.error.is_some());
+    }
+
+    #[test]
+    fn workflow_prompts_expand_a_feature() {
+        let mut client = McpTestClient::spawn();
+        client.initialize();
+
+        let listed = client.request("prompts/list", None).result.unwrap();
+        let names: Vec<&str> = listed["prompts"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(|p| p["name"].as_str())
+            .collect();
+        assert_eq!(names, ["implement-feature", "review-feature"]);
+
+        let project_text = extract_text_content(
+            &client.call_tool("create_project", json!({ "name": "Prompt Test" })),
+        );
+        let project: Value = serde_json::from_str(&project_text).unwrap();
+        let feature_text = extract_text_content(&client.call_tool(
+            "create_feature",
+            json!({
+                "project_id": project["id"],
+                "title": "Exports",
+                "details": "Users can export CSV."
+            }),
+        ));
+        let feature: Value = serde_json::from_str(&feature_text).unwrap();
+
+        let result = client
+            .request(
+                "prompts/get",
+                Some(json!({
+                    "name": "implement-feature",
+                    "arguments": { "feature_id": feature["id"] }
+                })),
+            )
+            .result
+            .expect("Expected result");
+        let text = result["messages"][0]["content"]["text"].as_str().unwrap();
+        assert!(text.contains("# Exports"));
+        assert!(text.contains("Users can export CSV."));
+        assert!(text.contains("complete_session"));
+
+        let missing = client.request(
+            "prompts/get",
+            Some(json!({ "name": "implement-feature", "arguments": {} })),
+        );
+        assert!(missing.error.is_some());
+    }
+
+    #[test]
+    fn search_features_sends_query_text_unchanged() {
+        let mut client = McpTestClient::spawn();
+        client.initialize();
+
+        let project_text = extract_text_content(
+            &client.call_tool("create_project", json!({ "name": "Search Test" })),
+        );
+        let project: Value = serde_json::from_str(&project_text).unwrap();
+        let project_id = project["id"].as_str().unwrap();
+        for title in ["C++ bindings", "C bindings"] {
+            client.call_tool(
+                "create_feature",
+                json!({ "project_id": project_id, "title": title }),
+            );
+        }
+
+        let text = extract_text_content(&client.call_tool(
+            "search_features",
+            json!({ "query": "C++", "project_id": project_id }),
+        ));
+        let results: Value = serde_json::from_str(&text).unwrap();
+        let features = results["features"].as_array().unwrap();
+        assert_eq!(features.len(), 1);
+        assert_eq!(features[0]["title"].as_str(), Some("C++ bindings"));
+    }
+
+    #[test]
+    fn filter_features_by_tag_requires_every_tag() {
+        let mut client = McpTestClient::spawn();
+        client.initialize();
+
+        let project_text = extract_text_content(
+            &client.call_tool("create_project", json!({ "name": "Tag Test" })),
+        );
+        let project: Value = serde_json::from_str(&project_text).unwrap();
+        let project_id = project["id"].as_str().unwrap();
+
+        // Tags are set through the API, which the MCP server talks to
+        let base_url = std::env::var("MANIFEST_URL")
+            .unwrap_or_else(|_| "http://localhost:17010/api/v1".to_string());
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        for (title, tags) in [
+            ("Login", vec!["frontend", "p0"]),
+            ("Signup", vec!["frontend"]),
+        ] {
+            let feature: Value = serde_json::from_str(&extract_text_content(&client.call_tool(
+                "create_feature",
+                json!({ "project_id": project_id, "title": title }),
+            )))
+            .unwrap();
+            runtime.block_on(async {
+                reqwest::Client::new()
+                    .put(format!(
+                        "{}/features/{}/tags",
+                        base_url,
+                        feature["id"].as_str().unwrap()
+                    ))
+                    .json(&json!({ "tags": tags }))
+                    .send()
+                    .await
+                    .unwrap()
+                    .error_for_status()
+                    .unwrap();
+            });
+        }
+
+        let text = extract_text_content(&client.call_tool(
+            "filter_features_by_tag",
+            json!({ "tags": ["Frontend", "p0"], "project_id": project_id }),
+        ));
+        let results: Value = serde_json::from_str(&text).unwrap();
+        let features = results["features"].as_array().unwrap();
+        assert_eq!(features.len(), 1);
+        assert_eq!(features[0]["title"].as_str(), Some("Login"));
+
+        let response = client.call_tool("filter_features_by_tag", json!({ "tags": [] }));
+        assert!(response.error.is_some());
+    }
+
     /// Helper to extract text content from MCP tool response
     pub fn extract_text_content(response: &JsonRpcResponse) -> String {
         response
@@ -616,6 +929,127 @@ mod active_feature {
     }
 }
 
+mod active_context {
+    use super::tool_calls::extract_text_content;
+    use super::*;
+    use std::fs;
+
+    fn write_context(dir: &std::path::Path, feature_id: &str) {
+        let manifest_dir = dir.join(".manifest");
+        fs::create_dir_all(&manifest_dir).unwrap();
+        let context = json!({
+            "feature_id": feature_id,
+            "title": "Title when selected",
+            "updated_at": "2024-01-15T10:30:00Z"
+        });
+        fs::write(
+            manifest_dir.join("active_context.json"),
+            serde_json::to_string_pretty(&context).unwrap(),
+        )
+        .unwrap();
+    }
+
+    fn active_context(client: &mut McpTestClient, dir: &std::path::Path) -> Value {
+        let response = client.call_tool(
+            "get_active_context",
+            json!({ "directory_path": dir.to_str().unwrap() }),
+        );
+        assert!(response.error.is_none(), "Expected success, got error");
+        serde_json::from_str(&extract_text_content(&response)).expect("Expected JSON")
+    }
+
+    #[test]
+    fn returns_selected_feature_with_current_details() {
+        let mut client = McpTestClient::spawn();
+        client.initialize();
+
+        let text = extract_text_content(
+            &client.call_tool("create_project", json!({ "name": "Context Test" })),
+        );
+        let project: Value = serde_json::from_str(&text).unwrap();
+        let text = extract_text_content(&client.call_tool(
+            "create_feature",
+            json!({
+                "project_id": project["id"],
+                "title": "Checkout",
+                "details": "Current spec"
+            }),
+        ));
+        let feature: Value = serde_json::from_str(&text).unwrap();
+
+        let project_dir = client.home_dir.join("shop");
+        write_context(&project_dir, feature["id"].as_str().unwrap());
+
+        // The agent may be running in a subdirectory of the project
+        let result = active_context(&mut client, &project_dir.join("src"));
+        let active = &result["active_feature"];
+        assert_eq!(active["title"].as_str(), Some("Checkout"));
+        assert_eq!(active["details"].as_str(), Some("Current spec"));
+        assert_eq!(result["selected_at"].as_str(), Some("2024-01-15T10:30:00Z"));
+    }
+
+    #[test]
+    fn returns_null_when_nothing_is_selected() {
+        let mut client = McpTestClient::spawn();
+        client.initialize();
+
+        let dir = client.home_dir.join("unselected");
+        let result = active_context(&mut client, &dir);
+
+        assert!(result["active_feature"].is_null());
+        assert!(result["message"].is_string());
+    }
+
+    #[test]
+    fn watched_context_follows_file_replacement() {
+        let mut client = McpTestClient::spawn_with_args(&["--watch-context"]);
+        client.initialize();
+
+        let call = |client: &mut McpTestClient| -> Value {
+            let response = client.call_tool("get_active_context", json!({}));
+            serde_json::from_str(&extract_text_content(&response)).expect("Expected JSON")
+        };
+        assert!(call(&mut client)["context_file"].is_null());
+
+        // Write the way the app does: to a temp file, then rename over the target
+        let manifest_dir = client.home_dir.join(".manifest");
+        fs::create_dir_all(&manifest_dir).unwrap();
+        let temp = manifest_dir.join("active_context.json.tmp");
+        fs::write(
+            &temp,
+            json!({ "feature_id": "00000000-0000-0000-0000-0000000000ff" }).to_string(),
+        )
+        .unwrap();
+        fs::rename(&temp, manifest_dir.join("active_context.json")).unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(600));
+
+        let result = call(&mut client);
+        assert!(result["context_file"]
+            .as_str()
+            .is_some_and(|f| f.ends_with("active_context.json")));
+        assert!(result["message"]
+            .as_str()
+            .unwrap()
+            .contains("no longer exists"));
+    }
+
+    #[test]
+    fn reports_stale_selection() {
+        let mut client = McpTestClient::spawn();
+        client.initialize();
+
+        let dir = client.home_dir.join("stale");
+        write_context(&dir, "00000000-0000-0000-0000-0000000000ff");
+        let result = active_context(&mut client, &dir);
+
+        assert!(result["active_feature"].is_null());
+        assert!(result["message"]
+            .as_str()
+            .unwrap()
+            .contains("no longer exists"));
+    }
+}
+
 // ============================================================
 // Error Handling Tests
 // ============================================================
@@ -672,4 +1106,165 @@ mod errors {
             }
         );
     }
+
+    /// Call a tool whose arguments fail validation and return the error message.
+    fn rejected_message(client: &mut McpTestClient, tool: &str, arguments: Value) -> String {
+        let response = client.call_tool(tool, arguments);
+        let error = response.error.expect("Expected invalid params error");
+        assert_eq!(error.code, -32602);
+        error.message
+    }
+
+    #[test]
+    fn blank_title_is_rejected() {
+        let mut client = McpTestClient::spawn();
+        client.initialize();
+
+        let message = rejected_message(
+            &mut client,
+            "create_feature",
+            json!({
+                "project_id": "00000000-0000-0000-0000-000000000001",
+                "title": "   "
+            }),
+        );
+
+        assert!(message.contains("title must not be empty"), "{}", message);
+    }
+
+    #[test]
+    fn oversized_details_are_rejected() {
+        let mut client = McpTestClient::spawn();
+        client.initialize();
+
+        let message = rejected_message(
+            &mut client,
+            "update_feature_state",
+            json!({
+                "feature_id": "00000000-0000-0000-0000-000000000001",
+                "details": "x".repeat(64 * 1024 + 1)
+            }),
+        );
+
+        assert!(message.contains("details is too long"), "{}", message);
+    }
+
+    #[test]
+    fn relative_directory_path_is_rejected() {
+        let mut client = McpTestClient::spawn();
+        client.initialize();
+
+        let message = rejected_message(
+            &mut client,
+            "add_project_directory",
+            json!({
+                "project_id": "00000000-0000-0000-0000-000000000001",
+                "path": "../elsewhere"
+            }),
+        );
+
+        assert!(message.contains("absolute path"), "{}", message);
+    }
+
+    #[test]
+    fn malformed_commit_sha_is_rejected() {
+        let mut client = McpTestClient::spawn();
+        client.initialize();
+
+        let message = rejected_message(
+            &mut client,
+            "complete_session",
+            json!({
+                "session_id": "00000000-0000-0000-0000-000000000001",
+                "summary": "Done",
+                "commits": [{ "sha": "not a sha", "message": "Fix" }]
+            }),
+        );
+
+        assert!(message.contains("Invalid commit sha"), "{}", message);
+    }
+
+    #[test]
+    fn out_of_range_limit_is_rejected() {
+        let mut client = McpTestClient::spawn();
+        client.initialize();
+
+        let message = rejected_message(&mut client, "list_features", json!({ "limit": 0 }));
+
+        assert!(message.contains("limit must be between"), "{}", message);
+    }
+}
+
+mod http_transport {
+    use super::*;
+    use std::net::TcpListener;
+    use std::time::Duration;
+
+    /// Kills the spawned server when the test ends, pass or fail.
+    struct KillOnDrop(Child);
+
+    impl Drop for KillOnDrop {
+        fn drop(&mut self) {
+            let _ = self.0.kill();
+        }
+    }
+
+    #[test]
+    fn initialize_over_http_returns_server_info() {
+        let port = TcpListener::bind("127.0.0.1:0")
+            .and_then(|l| l.local_addr())
+            .expect("Failed to find a free port")
+            .port();
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let _server = KillOnDrop(
+            Command::new(env!("CARGO_BIN_EXE_mfst"))
+                .args(["mcp", "--http", "--port", &port.to_string()])
+                .env("XDG_DATA_HOME", temp_dir.path())
+                .env("HOME", temp_dir.path())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .spawn()
+                .expect("Failed to spawn mfst mcp --http"),
+        );
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let body = runtime.block_on(async {
+            let client = reqwest::Client::new();
+            let request = json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "initialize",
+                "params": {
+                    "protocolVersion": "2024-11-05",
+                    "capabilities": {},
+                    "clientInfo": { "name": "test-client", "version": "1.0.0" }
+                }
+            });
+
+            // Retry until the server is accepting connections
+            for _ in 0..50 {
+                let sent = client
+                    .post(format!("http://127.0.0.1:{}/mcp", port))
+                    .header("Accept", "application/json, text/event-stream")
+                    .json(&request)
+                    .send()
+                    .await;
+                if let Ok(response) = sent {
+                    assert!(response.headers().contains_key("mcp-session-id"));
+                    return response.text().await.unwrap();
+                }
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+            panic!("MCP HTTP server did not start");
+        });
+
+        // Responses arrive as SSE events: "data: {json}"
+        let data = body
+            .lines()
+            .find_map(|line| line.strip_prefix("data: "))
+            .expect("Expected an SSE data event");
+        let response: JsonRpcResponse = serde_json::from_str(data).unwrap();
+        let result = response.result.expect("Expected initialize result");
+        assert_eq!(result["serverInfo"]["name"], "manifest");
+    }
 }